@@ -0,0 +1,425 @@
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[cfg(test)]
+mod tests;
+
+type NodeRef = Arc<RwLock<Node>>;
+
+/// Node of a `ConcurrentBTree`. Leaves hold the key-value pairs, while internal nodes only hold separators: `keys[i]`
+/// is the smallest key that can be found under `children[i + 1]`.
+///
+/// ### Constraints
+/// Contains at most `2 * b - 1` keys and, except for the root, at least `b - 1` keys.
+struct Node {
+    keys: Vec<usize>,
+    /// Only used by leaves
+    values: Vec<usize>,
+    /// Only used by internal nodes
+    children: Vec<NodeRef>,
+}
+
+/// Operations that modify the tree, used for checking whether a node is *safe* (the operation can't change the
+/// structure of the tree above the node).
+#[derive(Clone, Copy)]
+enum Operation {
+    Insert,
+    Remove,
+}
+
+impl Node {
+    fn new_leaf(keys: Vec<usize>, values: Vec<usize>) -> Self {
+        Self {
+            keys,
+            values,
+            children: vec![],
+        }
+    }
+
+    fn new_internal(keys: Vec<usize>, children: Vec<NodeRef>) -> Self {
+        Self {
+            keys,
+            values: vec![],
+            children,
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Returns the index of the child whose subtree can contain `key`.
+    fn child_index(&self, key: usize) -> usize {
+        self.keys.partition_point(|k| *k <= key)
+    }
+
+    /// Returns `true` if `operation` can't split or merge the node.
+    fn is_safe(&self, operation: Operation, b: usize, is_root: bool) -> bool {
+        match operation {
+            Operation::Insert => self.keys.len() < 2 * b - 1,
+            // the root can't underflow, but a root with a single separator is removed when its children are merged
+            Operation::Remove if is_root => self.is_leaf() || self.keys.len() > 1,
+            Operation::Remove => self.keys.len() > b - 1,
+        }
+    }
+
+    /// Inserts the pair in a leaf, returning the old value if the key was already in the leaf.
+    fn insert_entry(&mut self, key: usize, value: usize) -> Option<usize> {
+        match self.keys.binary_search(&key) {
+            Ok(index) => Some(std::mem::replace(&mut self.values[index], value)),
+            Err(index) => {
+                self.keys.insert(index, key);
+                self.values.insert(index, value);
+                None
+            }
+        }
+    }
+
+    /// Removes the pair from a leaf, returning the removed value.
+    fn remove_entry(&mut self, key: usize) -> Option<usize> {
+        let index = self.keys.binary_search(&key).ok()?;
+        self.keys.remove(index);
+        Some(self.values.remove(index))
+    }
+
+    /// Splits the node if it has more than `2 * b - 1` keys. Returns the separator and the new right node, which have
+    /// to be inserted in the parent.
+    fn split_if_overflowing(&mut self, b: usize) -> Option<(usize, NodeRef)> {
+        if self.keys.len() < 2 * b {
+            return None;
+        }
+
+        let (separator, right) = if self.is_leaf() {
+            let keys = self.keys.split_off(b);
+            let values = self.values.split_off(b);
+            (keys[0], Node::new_leaf(keys, values))
+        } else {
+            // the middle key moves up to the parent
+            let keys = self.keys.split_off(b + 1);
+            let children = self.children.split_off(b + 1);
+            let separator = self.keys.pop().unwrap();
+            (separator, Node::new_internal(keys, children))
+        };
+
+        Some((separator, Arc::new(RwLock::new(right))))
+    }
+
+    /// Fixes the underflowing child at `index` by borrowing a key from one of its siblings or by merging it with one
+    /// of them. The node is assumed to be write latched (so no other writer can reach the siblings).
+    fn rebalance_child(&mut self, index: usize, child: &mut Node, b: usize) {
+        let use_right = index + 1 < self.children.len();
+        let sibling_index = if use_right { index + 1 } else { index - 1 };
+        let sibling_ref = Arc::clone(&self.children[sibling_index]);
+        let mut sibling = sibling_ref.write().unwrap();
+
+        if sibling.keys.len() > b - 1 {
+            if use_right {
+                self.borrow_from_right(index, child, &mut sibling);
+            } else {
+                self.borrow_from_left(index, &mut sibling, child);
+            }
+        } else if use_right {
+            self.merge_children(index, child, &mut sibling);
+        } else {
+            self.merge_children(index - 1, &mut sibling, child);
+        }
+    }
+
+    /// Moves the first entry of `right` (the child at `index + 1`) to the end of `left` (the child at `index`).
+    fn borrow_from_right(&mut self, index: usize, left: &mut Node, right: &mut Node) {
+        if left.is_leaf() {
+            left.keys.push(right.keys.remove(0));
+            left.values.push(right.values.remove(0));
+            self.keys[index] = right.keys[0];
+        } else {
+            left.keys.push(self.keys[index]);
+            left.children.push(right.children.remove(0));
+            self.keys[index] = right.keys.remove(0);
+        }
+    }
+
+    /// Moves the last entry of `left` (the child at `index - 1`) to the start of `right` (the child at `index`).
+    fn borrow_from_left(&mut self, index: usize, left: &mut Node, right: &mut Node) {
+        if right.is_leaf() {
+            right.keys.insert(0, left.keys.pop().unwrap());
+            right.values.insert(0, left.values.pop().unwrap());
+            self.keys[index - 1] = right.keys[0];
+        } else {
+            right.keys.insert(0, self.keys[index - 1]);
+            right.children.insert(0, left.children.pop().unwrap());
+            self.keys[index - 1] = left.keys.pop().unwrap();
+        }
+    }
+
+    /// Merges `right` (the child at `index + 1`) into `left` (the child at `index`) and removes it from the node.
+    fn merge_children(&mut self, index: usize, left: &mut Node, right: &mut Node) {
+        let separator = self.keys.remove(index);
+        self.children.remove(index + 1);
+
+        if !left.is_leaf() {
+            left.keys.push(separator);
+        }
+        left.keys.append(&mut right.keys);
+        left.values.append(&mut right.values);
+        left.children.append(&mut right.children);
+    }
+}
+
+/// Thread-safe B+ tree that maps a number to a number, with latches on every node.
+///
+/// - Readers descend with read latches, releasing the latch of a node as soon as the latch of the child is acquired.
+/// - Writers first descend optimistically, like readers, and only write latch the leaf. If the leaf has to be split
+///   or merged, they restart from the root with write latches, keeping the latches of the ancestors only until they
+///   reach a node that is safe for the operation (latch crabbing).
+///
+/// Latches are always acquired top-down, and siblings are only latched by the writer holding their parent, so writers
+/// can't deadlock.
+pub struct ConcurrentBTree {
+    /// Latch on the root pointer, which changes when the root is split or when the tree shrinks
+    root: RwLock<NodeRef>,
+    b: usize,
+}
+
+impl ConcurrentBTree {
+    /// # Panics
+    /// Will panic if `b` is smaller than `2`.
+    pub fn new(b: usize) -> Self {
+        assert!(b >= 2, "B-tree nodes need at least 3 keys");
+
+        Self {
+            root: RwLock::new(Arc::new(RwLock::new(Node::new_leaf(vec![], vec![])))),
+            b,
+        }
+    }
+
+    /// Returns the value associated with `key`.
+    pub fn get(&self, key: usize) -> Option<usize> {
+        let root = self.root.read().unwrap();
+        let node = Arc::clone(&root);
+        let guard = node.read().unwrap();
+        drop(root);
+
+        Self::get_in(guard, key)
+    }
+
+    fn get_in(guard: RwLockReadGuard<'_, Node>, key: usize) -> Option<usize> {
+        if guard.is_leaf() {
+            return guard.keys.binary_search(&key).ok().map(|i| guard.values[i]);
+        }
+
+        let child = Arc::clone(&guard.children[guard.child_index(key)]);
+        let child_guard = child.read().unwrap();
+        drop(guard);
+
+        Self::get_in(child_guard, key)
+    }
+
+    /// Inserts the `key` -> `value` pair. Returns the old value if `key` was already in the tree.
+    pub fn insert(&self, key: usize, value: usize) -> Option<usize> {
+        if let Some(old_value) = self
+            .modify_leaf_optimistic(key, Operation::Insert, |leaf| leaf.insert_entry(key, value))
+        {
+            return old_value;
+        }
+
+        // the leaf might need to be split, so the tree is latched from the root
+        let mut root = Some(self.root.write().unwrap());
+        let node = Arc::clone(root.as_ref().unwrap());
+        let mut guard = Some(node.write().unwrap());
+        if guard
+            .as_ref()
+            .unwrap()
+            .is_safe(Operation::Insert, self.b, true)
+        {
+            root = None;
+        }
+
+        let (old_value, split) = {
+            let mut release = || root = None;
+            self.insert_in(&mut guard, &mut release, key, value)
+        };
+
+        if let Some((separator, right)) = split {
+            let root = root
+                .as_mut()
+                .expect("Root pointer is latched when the root splits");
+            **root = Arc::new(RwLock::new(Node::new_internal(
+                vec![separator],
+                vec![Arc::clone(&node), right],
+            )));
+        }
+
+        old_value
+    }
+
+    /// Inserts the pair in the subtree of the node latched by `guard`. Returns the old value and the split of the node,
+    /// if it overflows. `release_ancestors` releases the latches of the ancestors (and of the root pointer).
+    fn insert_in(
+        &self,
+        guard: &mut Option<RwLockWriteGuard<'_, Node>>,
+        release_ancestors: &mut dyn FnMut(),
+        key: usize,
+        value: usize,
+    ) -> (Option<usize>, Option<(usize, NodeRef)>) {
+        let node = guard.as_mut().unwrap();
+        if node.is_leaf() {
+            let old_value = node.insert_entry(key, value);
+            return (old_value, node.split_if_overflowing(self.b));
+        }
+
+        let child = Arc::clone(&node.children[node.child_index(key)]);
+        let mut child_guard = Some(child.write().unwrap());
+        if child_guard
+            .as_ref()
+            .unwrap()
+            .is_safe(Operation::Insert, self.b, false)
+        {
+            *guard = None;
+            release_ancestors();
+        }
+
+        let (old_value, split) = {
+            let mut release = || {
+                *guard = None;
+                release_ancestors();
+            };
+            self.insert_in(&mut child_guard, &mut release, key, value)
+        };
+
+        match split {
+            Some((separator, right)) => {
+                let node = guard
+                    .as_mut()
+                    .expect("Node is latched when its child splits");
+                let index = node.child_index(separator);
+                node.keys.insert(index, separator);
+                node.children.insert(index + 1, right);
+
+                (old_value, node.split_if_overflowing(self.b))
+            }
+            None => (old_value, None),
+        }
+    }
+
+    /// Removes the pair with `key`. Returns the removed pair, or `Err` if `key` is not in the tree.
+    pub fn remove(&self, key: usize) -> Result<(usize, usize), ()> {
+        if let Some(removed) =
+            self.modify_leaf_optimistic(key, Operation::Remove, |leaf| leaf.remove_entry(key))
+        {
+            return removed.map(|value| (key, value)).ok_or(());
+        }
+
+        // the leaf might need to be merged, so the tree is latched from the root
+        let mut root = Some(self.root.write().unwrap());
+        let node = Arc::clone(root.as_ref().unwrap());
+        let mut guard = Some(node.write().unwrap());
+        if guard
+            .as_ref()
+            .unwrap()
+            .is_safe(Operation::Remove, self.b, true)
+        {
+            root = None;
+        }
+
+        let removed = {
+            let mut release = || root = None;
+            self.remove_in(&mut guard, &mut release, key)
+        };
+
+        // the tree shrinks when the root loses its last separator
+        if let Some(node) = guard.as_mut() {
+            if !node.is_leaf() && node.keys.is_empty() {
+                let root = root
+                    .as_mut()
+                    .expect("Root pointer is latched when the root shrinks");
+                **root = node.children.remove(0);
+            }
+        }
+
+        removed.map(|value| (key, value)).ok_or(())
+    }
+
+    /// Removes the pair from the subtree of the node latched by `guard`, rebalancing the underflowing children.
+    /// Returns the removed value. `release_ancestors` releases the latches of the ancestors (and of the root pointer).
+    fn remove_in(
+        &self,
+        guard: &mut Option<RwLockWriteGuard<'_, Node>>,
+        release_ancestors: &mut dyn FnMut(),
+        key: usize,
+    ) -> Option<usize> {
+        let node = guard.as_mut().unwrap();
+        if node.is_leaf() {
+            return node.remove_entry(key);
+        }
+
+        let index = node.child_index(key);
+        let child = Arc::clone(&node.children[index]);
+        let mut child_guard = Some(child.write().unwrap());
+        if child_guard
+            .as_ref()
+            .unwrap()
+            .is_safe(Operation::Remove, self.b, false)
+        {
+            *guard = None;
+            release_ancestors();
+        }
+
+        let removed = {
+            let mut release = || {
+                *guard = None;
+                release_ancestors();
+            };
+            self.remove_in(&mut child_guard, &mut release, key)
+        };
+
+        if let (Some(node), Some(child)) = (guard.as_mut(), child_guard.as_mut()) {
+            if child.keys.len() < self.b - 1 {
+                node.rebalance_child(index, child, self.b);
+            }
+        }
+
+        removed
+    }
+
+    /// Descends to the leaf for `key` with read latches, write latches the leaf and applies `modify` on it. Returns
+    /// `None` (without applying `modify`) if the leaf isn't safe for `operation`.
+    fn modify_leaf_optimistic<F, R>(&self, key: usize, operation: Operation, modify: F) -> Option<R>
+    where
+        F: FnOnce(&mut Node) -> R,
+    {
+        let root = self.root.read().unwrap();
+        let node = Arc::clone(&root);
+
+        self.modify_leaf_in(root, &node, true, key, operation, modify)
+    }
+
+    fn modify_leaf_in<P, F, R>(
+        &self,
+        parent: P,
+        node: &NodeRef,
+        is_root: bool,
+        key: usize,
+        operation: Operation,
+        modify: F,
+    ) -> Option<R>
+    where
+        F: FnOnce(&mut Node) -> R,
+    {
+        let guard = node.read().unwrap();
+        if !guard.is_leaf() {
+            let child = Arc::clone(&guard.children[guard.child_index(key)]);
+            drop(parent);
+            return self.modify_leaf_in(guard, &child, false, key, operation, modify);
+        }
+
+        // the parent is still latched, so the leaf can't be split or merged before it is write latched
+        drop(guard);
+        let mut guard = node.write().unwrap();
+        drop(parent);
+
+        if !guard.is_safe(operation, self.b, is_root) {
+            return None;
+        }
+
+        Some(modify(&mut guard))
+    }
+}
@@ -0,0 +1,229 @@
+use std::{sync::Arc, thread};
+
+use super::*;
+
+/// Checks the constraints of the tree and returns its depth and its pairs, in order.
+fn validate(tree: &ConcurrentBTree) -> (usize, Vec<(usize, usize)>) {
+    let root = Arc::clone(&tree.root.read().unwrap());
+    let mut pairs = vec![];
+    let depth = validate_node(&root, tree.b, true, None, None, &mut pairs);
+
+    assert!(pairs.windows(2).all(|w| w[0].0 < w[1].0));
+    (depth, pairs)
+}
+
+fn validate_node(
+    node: &NodeRef,
+    b: usize,
+    is_root: bool,
+    lower: Option<usize>,
+    upper: Option<usize>,
+    pairs: &mut Vec<(usize, usize)>,
+) -> usize {
+    let node = node.read().unwrap();
+    assert!(node.keys.len() < 2 * b);
+    assert!(is_root || node.keys.len() >= b - 1);
+    assert!(node
+        .keys
+        .iter()
+        .all(|k| lower.is_none_or(|l| *k >= l) && upper.is_none_or(|u| *k < u)));
+
+    if node.is_leaf() {
+        pairs.extend(node.keys.iter().copied().zip(node.values.iter().copied()));
+        return 1;
+    }
+
+    assert!(!node.keys.is_empty());
+    assert_eq!(node.children.len(), node.keys.len() + 1);
+    let depths = node
+        .children
+        .iter()
+        .enumerate()
+        .map(|(i, child)| {
+            let child_lower = if i == 0 {
+                lower
+            } else {
+                Some(node.keys[i - 1])
+            };
+            let child_upper = node.keys.get(i).copied().or(upper);
+            validate_node(child, b, false, child_lower, child_upper, pairs)
+        })
+        .collect::<Vec<_>>();
+    assert!(depths.iter().all(|d| *d == depths[0]));
+
+    depths[0] + 1
+}
+
+#[test]
+fn insert_and_get() {
+    let tree = ConcurrentBTree::new(2);
+
+    assert_eq!(tree.insert(5, 50), None);
+    assert_eq!(tree.insert(1, 10), None);
+    assert_eq!(tree.insert(5, 55), Some(50));
+
+    assert_eq!(tree.get(5), Some(55));
+    assert_eq!(tree.get(1), Some(10));
+    assert_eq!(tree.get(2), None);
+}
+
+#[test]
+fn insert_with_splits() {
+    let tree = ConcurrentBTree::new(2);
+
+    for i in 0..1000 {
+        tree.insert((i * 7919) % 1000, i);
+    }
+
+    let (depth, pairs) = validate(&tree);
+    assert!(depth > 3);
+    assert_eq!(pairs.len(), 1000);
+    for i in 0..1000 {
+        assert_eq!(tree.get((i * 7919) % 1000), Some(i));
+    }
+}
+
+#[test]
+fn remove_with_merges() {
+    let tree = ConcurrentBTree::new(2);
+    for i in 0..1000 {
+        tree.insert(i, i);
+    }
+
+    assert_eq!(tree.remove(500), Ok((500, 500)));
+    assert_eq!(tree.remove(500), Err(()));
+    assert_eq!(tree.get(500), None);
+
+    for i in (0..1000).filter(|i| i % 3 != 0) {
+        tree.remove(i).ok();
+    }
+    let (_, pairs) = validate(&tree);
+    assert_eq!(
+        pairs,
+        (0..1000)
+            .filter(|i| i % 3 == 0)
+            .map(|i| (i, i))
+            .collect::<Vec<_>>()
+    );
+
+    // the tree shrinks back to a single leaf
+    for i in 0..1000 {
+        tree.remove(i).ok();
+    }
+    assert_eq!(validate(&tree), (1, vec![]));
+
+    tree.insert(1, 1);
+    assert_eq!(tree.get(1), Some(1));
+}
+
+#[test]
+fn insert_multi_threaded() {
+    let size = 8;
+
+    let single_thread = ConcurrentBTree::new(3);
+    for t in 0..size {
+        for i in 0..500 {
+            single_thread.insert(i * size + t, i);
+        }
+    }
+
+    let multi_thread = Arc::new(ConcurrentBTree::new(3));
+    let handles = (0..size)
+        .map(|t| {
+            let tree = Arc::clone(&multi_thread);
+            thread::spawn(move || {
+                for i in 0..500 {
+                    tree.insert(i * size + t, i);
+                    assert_eq!(tree.get(i * size + t), Some(i));
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let (_, single_thread_pairs) = validate(&single_thread);
+    let (_, multi_thread_pairs) = validate(&multi_thread);
+    assert_eq!(single_thread_pairs.len(), 4000);
+    assert_eq!(single_thread_pairs, multi_thread_pairs);
+}
+
+#[test]
+fn remove_multi_threaded() {
+    let size = 8;
+    let tree = Arc::new(ConcurrentBTree::new(2));
+    for i in 0..4000 {
+        tree.insert(i, i);
+    }
+
+    // every thread removes the keys of its own class, except for one in four
+    let handles = (0..size)
+        .map(|t| {
+            let tree = Arc::clone(&tree);
+            thread::spawn(move || {
+                for i in (t..4000).step_by(size) {
+                    if i % 4 != 0 {
+                        assert_eq!(tree.remove(i), Ok((i, i)));
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let (_, pairs) = validate(&tree);
+    assert_eq!(
+        pairs,
+        (0..4000).step_by(4).map(|i| (i, i)).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn mixed_operations_multi_threaded() {
+    let size = 8;
+    let tree = Arc::new(ConcurrentBTree::new(2));
+    // keys that are never modified, readers must always find them
+    for i in (0..4000).step_by(2) {
+        tree.insert(i, i);
+    }
+
+    let writers = (0..size / 2)
+        .map(|t| {
+            let tree = Arc::clone(&tree);
+            thread::spawn(move || {
+                for round in 0..3 {
+                    for i in (2 * t + 1..4000).step_by(size) {
+                        tree.insert(i, round);
+                    }
+                    for i in (2 * t + 1..4000).step_by(size) {
+                        assert_eq!(tree.remove(i), Ok((i, round)));
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let readers = (0..size / 2)
+        .map(|_| {
+            let tree = Arc::clone(&tree);
+            thread::spawn(move || {
+                for _ in 0..3 {
+                    for i in (0..4000).step_by(2) {
+                        assert_eq!(tree.get(i), Some(i));
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in writers.into_iter().chain(readers) {
+        handle.join().unwrap();
+    }
+
+    let (_, pairs) = validate(&tree);
+    assert_eq!(
+        pairs,
+        (0..4000).step_by(2).map(|i| (i, i)).collect::<Vec<_>>()
+    );
+}
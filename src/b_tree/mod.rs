@@ -1,2 +1,3 @@
+pub mod concurrent;
 pub mod node;
 pub mod tree;
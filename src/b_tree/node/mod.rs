@@ -1,9 +1,10 @@
 use core::panic;
+use std::fmt::Debug;
 
 #[cfg(test)]
 mod tests;
 
-/// Node of a key-value pair that maps a number to a number.
+/// Node of a B-tree that maps keys of type `K` to values of type `V`.
 ///
 /// ### Constraints
 /// Contains a maximum of `2 * b - 1` elements. This affects the coundaries of the vectors as follows:
@@ -16,24 +17,27 @@ mod tests;
 /// - `value[i]`: the value of the *ith* element in the node
 /// - `edges[i]`: the left child of the *ith* element in the node
 /// - `edges[i + 1]`: the right child of the *ith* element in the node
-/// 
+///
 /// ### I Fucked Up
 /// The node's element min and max counts were all fucked up and I just made a quick fix for them. So the result is that they work, but they don't make much sense
 /// and I'm too lazy to rethink the whole thing so I left them like this :)
 #[derive(Clone)]
-pub struct Node {
-    pub keys: Vec<usize>,
-    pub values: Vec<usize>,
-    pub edges: Vec<Option<Node>>,
+pub struct Node<K, V> {
+    pub keys: Vec<K>,
+    pub values: Vec<V>,
+    pub edges: Vec<Option<Node<K, V>>>,
     pub b: usize,
 }
 
-pub struct NodeSplit {
-    pub median: (usize, usize),
-    pub left: Node,
-    pub right: Node,
+pub struct NodeSplit<K, V> {
+    pub median: (K, V),
+    pub left: Node<K, V>,
+    pub right: Node<K, V>,
 }
 
+/// Left and right siblings of a node.
+type Siblings<'a, K, V> = (Option<&'a Node<K, V>>, Option<&'a Node<K, V>>);
+
 enum KeySearchResult {
     NodeEmpty,
     LessThanAll,
@@ -41,7 +45,7 @@ enum KeySearchResult {
     GreaterThanAll,
 }
 
-impl Node {
+impl<K: Ord + Clone, V: Clone> Node<K, V> {
     pub fn new(b: usize) -> Self {
         Self {
             keys: vec![],
@@ -51,12 +55,23 @@ impl Node {
         }
     }
 
-    // allocates memory for node with elems key value pairs
-    fn new_of_size(b: usize, elems: usize) -> Self {
+    /// Creates a node with the given entries and children. Leaves are created if `edges` is empty.
+    pub fn with_entries(
+        b: usize,
+        keys: Vec<K>,
+        values: Vec<V>,
+        edges: Vec<Option<Node<K, V>>>,
+    ) -> Self {
+        let edges = if edges.is_empty() {
+            vec![None; keys.len() + 1]
+        } else {
+            edges
+        };
+
         Self {
-            keys: vec![0; elems],
-            values: vec![0; elems],
-            edges: vec![None; elems + 1],
+            keys,
+            values,
+            edges,
             b,
         }
     }
@@ -67,7 +82,7 @@ impl Node {
     }
 
     /// Returns `true` if the `node` reference can be found in the children of `self`.
-    pub fn is_parent_of(&self, node: &Node) -> bool {
+    pub fn is_parent_of(&self, node: &Node<K, V>) -> bool {
         self.edges
             .iter()
             .filter(|op| op.is_some())
@@ -76,7 +91,7 @@ impl Node {
     }
 
     /// Returns the parent of `node`, if it can be found in the children of `self` (including itself). Otherwise returns `None`.
-    pub fn find_parent_of(&self, node: &Node) -> Option<&Node> {
+    pub fn find_parent_of(&self, node: &Node<K, V>) -> Option<&Node<K, V>> {
         if self.is_parent_of(node) {
             return Some(self);
         }
@@ -105,7 +120,7 @@ impl Node {
     }
 
     /// Returns a reference to a *leaf* in `self`'s *subtree* (including itself) that is fit for inserting a node with a key of `key`.
-    pub fn find_leaf_for(&self, key: usize) -> &Node {
+    pub fn find_leaf_for(&self, key: &K) -> &Node<K, V> {
         if self.is_leaf() {
             return self;
         }
@@ -128,7 +143,7 @@ impl Node {
     /// Return a new `Node` that represents how `self` looks like after inserting a `key` -> `value` pair that has `None` as left and right children.
     ///
     /// **Note:** This method does not check whether the node is full or not before inserting into it.
-    pub fn push(&self, key: usize, value: usize) -> Node {
+    pub fn push(&self, key: K, value: V) -> Node<K, V> {
         self.push_with_children(key, value, None, None)
     }
 
@@ -137,12 +152,12 @@ impl Node {
     /// **Note:** This method does not check whether the node is full or not before inserting into it.
     pub fn push_with_children(
         &self,
-        key: usize,
-        value: usize,
-        left: Option<Node>,
-        right: Option<Node>,
-    ) -> Node {
-        let search_result = self.search_key(key);
+        key: K,
+        value: V,
+        left: Option<Node<K, V>>,
+        right: Option<Node<K, V>>,
+    ) -> Node<K, V> {
+        let search_result = self.search_key(&key);
         let mut new_node = self.clone();
 
         match search_result {
@@ -180,33 +195,30 @@ impl Node {
     ///
     /// # Panics
     /// Panics if `self` is not full
-    pub fn get_split(&self) -> NodeSplit {
+    pub fn get_split(&self) -> NodeSplit<K, V> {
         if !self.is_full() {
             panic!("Can't split, node is not full");
         }
 
         // find median
         let median = (
-            *self.keys.get(self.b - 1).unwrap(),
-            *self.values.get(self.b - 1).unwrap(),
+            self.keys.get(self.b - 1).unwrap().clone(),
+            self.values.get(self.b - 1).unwrap().clone(),
         );
 
         // find left and right
-        let mut left = Node::new_of_size(self.b, self.b - 1);
-        let mut right = Node::new_of_size(self.b, self.b - 1);
-        for i in 0..self.b - 1 {
-            // left
-            left.keys[i] = self.keys[i];
-            left.values[i] = self.values[i];
-            left.edges[i + 1] = self.edges[i + 1].clone();
-
-            // right
-            right.keys[i] = self.keys[self.b + i];
-            right.values[i] = self.values[self.b + i];
-            right.edges[i + 1] = self.edges[self.b + i + 1].clone();
-        }
-        left.edges[0] = self.edges[0].clone();
-        right.edges[0] = self.edges[self.b].clone();
+        let left = Node {
+            keys: self.keys[..self.b - 1].to_vec(),
+            values: self.values[..self.b - 1].to_vec(),
+            edges: self.edges[..self.b].to_vec(),
+            b: self.b,
+        };
+        let right = Node {
+            keys: self.keys[self.b..2 * self.b - 1].to_vec(),
+            values: self.values[self.b..2 * self.b - 1].to_vec(),
+            edges: self.edges[self.b..2 * self.b].to_vec(),
+            b: self.b,
+        };
 
         NodeSplit {
             median,
@@ -216,17 +228,17 @@ impl Node {
     }
 
     /// Returns a `KeySearchResult` that indicates where `key` should be inserted in `self`.
-    fn search_key(&self, key: usize) -> KeySearchResult {
+    fn search_key(&self, key: &K) -> KeySearchResult {
         if self.edges.is_empty() {
             return KeySearchResult::NodeEmpty;
         }
 
-        if key < *self.keys.get(0).unwrap() {
+        if key < self.keys.get(0).unwrap() {
             return KeySearchResult::LessThanAll;
         }
 
         for (i, self_key) in self.keys.iter().enumerate() {
-            if key < *self_key {
+            if key < self_key {
                 return KeySearchResult::LessThanIndex(i);
             }
         }
@@ -236,7 +248,11 @@ impl Node {
 
     /// Returns a clone of `self` that resembles how it looks like with `to_replace` replaced with `replace_with`.
     /// Just acts as a regular clone if `to_replace` can't be found in `self`'s children (including itself).
-    pub fn clone_with_replaced_node(&self, to_replace: *const Node, replace_with: &Node) -> Node {
+    pub fn clone_with_replaced_node(
+        &self,
+        to_replace: *const Node<K, V>,
+        replace_with: &Node<K, V>,
+    ) -> Node<K, V> {
         if std::ptr::addr_eq(self, to_replace) {
             return replace_with.clone();
         }
@@ -254,7 +270,7 @@ impl Node {
     }
 
     /// Returns clone of `self`, with all edges set to `None`.
-    fn clone_without_edges(&self) -> Node {
+    fn clone_without_edges(&self) -> Node<K, V> {
         let mut new_edges = vec![];
         for _ in self.edges.iter() {
             new_edges.push(None);
@@ -269,13 +285,17 @@ impl Node {
     }
 
     /// Recursively print `self` with all children.
-    pub fn print_node(&self, level: usize) {
+    pub fn print_node(&self, level: usize)
+    where
+        K: Debug,
+        V: Debug,
+    {
         // print entries
         let padding = "\t".repeat(level);
 
         let mut entries = String::from("[ ");
         for i in 0..self.keys.len() {
-            entries.push_str(format!("| {} -> {} | ", self.keys[i], self.values[i]).as_str());
+            entries.push_str(format!("| {:?} -> {:?} | ", self.keys[i], self.values[i]).as_str());
         }
         entries.push_str("]");
         println!("{}{}{}", level, padding, entries);
@@ -307,27 +327,28 @@ impl Node {
     }
 
     /// Returns a reference to the node in the subtree starting from `self` that contains `key`.
-    pub fn find_node_with(&self, key: usize) -> Option<&Node> {
+    pub fn find_node_with(&self, key: &K) -> Option<&Node<K, V>> {
         if self.contains(key) {
             return Some(self);
         }
 
-        for edge in self.edges.iter().flatten() {
-            if let Some(found) = edge.find_node_with(key) {
-                return Some(found);
-            }
-        }
-
-        None
+        let edge = self.keys.partition_point(|k| k < key);
+        self.edges.get(edge)?.as_ref()?.find_node_with(key)
     }
 
     /// Returns `true` if the current node contains `key`.
-    fn contains(&self, key: usize) -> bool {
-        self.keys.iter().any(|k| *k == key)
+    fn contains(&self, key: &K) -> bool {
+        self.index_of(key).is_some()
+    }
+
+    /// Returns a reference to the value of `key` in the subtree starting from `self`.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let node = self.find_node_with(key)?;
+        node.index_of(key).map(|i| &node.values[i])
     }
 
     /// Returns new node with key, value and right edge removed from it. Second entry in the returned tuple is the value that would be removed.
-    pub fn delete_entry(&self, key: usize) -> (Node, Option<usize>) {
+    pub fn delete_entry(&self, key: &K) -> (Node<K, V>, Option<V>) {
         let index = self.index_of(key);
         let mut new_node = self.clone();
 
@@ -344,33 +365,32 @@ impl Node {
     }
 
     /// Returns index of entry with `key`.
-    fn index_of(&self, key: usize) -> Option<usize> {
-        self.keys.iter().position(|k| *k == key)
+    fn index_of(&self, key: &K) -> Option<usize> {
+        self.keys.binary_search(key).ok()
     }
 
     /// Returns a reference to the right child of entry with `key`.
     ///
     /// # Panics
     /// Panics if node with key has no right child, so *should* only be called on non-leaf nodes.
-    pub fn get_right_child(&self, key: usize) -> Option<&Node> {
+    pub fn get_right_child(&self, key: &K) -> Option<&Node<K, V>> {
         self.index_of(key)
             .map(|i| Some(self.edges[i + 1].as_ref().unwrap()))?
     }
 
     /// Returns the largest key in the node. Will return `None` if the node is empty (only possible for root nodes).
-    pub fn smallest_key(&self) -> Option<usize> {
+    pub fn smallest_key(&self) -> Option<K> {
         self.keys.first().cloned()
     }
 
     /// Return new node that has its `key` entry replaced with `new`. Second entry in the tuple is the value what got replaced.
     /// Returns `None` if `key` is not in the node.
-    pub fn replace_entry_with(&self, key: usize, new: (usize, usize)) -> Option<(Node, usize)> {
+    pub fn replace_entry_with(&self, key: &K, new: (K, V)) -> Option<(Node<K, V>, V)> {
         let mut new_node = self.clone();
         let index = new_node.index_of(key)?;
 
-        let old_value = new_node.values[index];
         new_node.keys[index] = new.0;
-        new_node.values[index] = new.1;
+        let old_value = std::mem::replace(&mut new_node.values[index], new.1);
 
         Some((new_node, old_value))
     }
@@ -384,7 +404,7 @@ impl Node {
     ///
     /// # Panics
     /// Panics if `child` can't be found in the children of the node.
-    pub fn get_siblings_of(&self, child: *const Node) -> (Option<&Node>, Option<&Node>) {
+    pub fn get_siblings_of(&self, child: *const Node<K, V>) -> Siblings<'_, K, V> {
         let child_index = self.get_child_index(child);
 
         let left = if child_index == 0 {
@@ -407,7 +427,7 @@ impl Node {
     ///
     /// # Panics
     /// Panics if `child` is not one of node's children.
-    pub fn get_child_index(&self, child: *const Node) -> usize {
+    pub fn get_child_index(&self, child: *const Node<K, V>) -> usize {
         let mut index = None;
         for (i, edge) in self.edges.iter().enumerate() {
             let edge = match edge {
@@ -432,7 +452,7 @@ impl Node {
     ///
     /// # Panics
     /// Panics if `left` and `right` are not siblings in the node's children OR if their order is not correct.
-    pub fn get_rotated_left(&self, left: &Node, right: &Node) -> Node {
+    pub fn get_rotated_left(&self, left: &Node<K, V>, right: &Node<K, V>) -> Node<K, V> {
         let left_index = self.get_child_index(left);
         let right_index = self.get_child_index(right);
 
@@ -446,8 +466,8 @@ impl Node {
             .as_mut()
             .expect("Left rotation child should also exist in cloned node");
 
-        new_left.keys.push(new_node.keys[left_index]);
-        new_left.values.push(new_node.values[left_index]);
+        new_left.keys.push(new_node.keys[left_index].clone());
+        new_left.values.push(new_node.values[left_index].clone());
 
         let new_right = new_node.edges[right_index]
             .as_mut()
@@ -463,7 +483,7 @@ impl Node {
     ///
     /// # Panics
     /// Panics if `left` and `right` are not siblings in the node's children OR if their order is not correct.
-    pub fn get_rotated_right(&self, left: &Node, right: &Node) -> Node {
+    pub fn get_rotated_right(&self, left: &Node<K, V>, right: &Node<K, V>) -> Node<K, V> {
         let left_index = self.get_child_index(left);
         let right_index = self.get_child_index(right);
 
@@ -478,8 +498,10 @@ impl Node {
             .expect("Right rotation child should also exist in cloned node");
 
         // copy separator from parent to start of right
-        new_right.keys.insert(0, new_node.keys[left_index]);
-        new_right.values.insert(0, new_node.values[left_index]);
+        new_right.keys.insert(0, new_node.keys[left_index].clone());
+        new_right
+            .values
+            .insert(0, new_node.values[left_index].clone());
 
         // replace separator in parent with last element in left
         let new_left = new_node.edges[left_index]
@@ -499,7 +521,7 @@ impl Node {
     /// Panics if:
     /// - `left` and `right` are not adjacent children in the node.
     /// - `left` and `right` have more combined elements than `2 * b - 2` (`left + right >= 2 * b - 2`)
-    pub fn get_sandwitched_for(&self, left: &Node, right: &Node) -> Node {
+    pub fn get_sandwitched_for(&self, left: &Node<K, V>, right: &Node<K, V>) -> Node<K, V> {
         let left_index = self.get_child_index(left);
         let right_index = self.get_child_index(right);
 
@@ -532,8 +554,8 @@ impl Node {
             .expect("Sandwitch left node should exist");
 
         // copy separator to end of left
-        left.keys.push(new_node.keys[left_index]);
-        left.values.push(new_node.values[left_index]);
+        left.keys.push(new_node.keys[left_index].clone());
+        left.values.push(new_node.values[left_index].clone());
 
         // move everything from right to left
         for (k, v) in right_elements {
@@ -554,7 +576,7 @@ impl Node {
     }
 
     /// Removes and returns key value pairs from node.
-    fn get_elements(&mut self) -> Vec<(usize, usize)> {
+    fn get_elements(&mut self) -> Vec<(K, V)> {
         let mut keys = vec![];
         let mut values = vec![];
 
@@ -567,7 +589,7 @@ impl Node {
     }
 
     /// Removes and returns edges from node.
-    fn get_edges(&mut self) -> Vec<Option<Node>> {
+    fn get_edges(&mut self) -> Vec<Option<Node<K, V>>> {
         let mut edges = vec![];
 
         while !self.edges.is_empty() {
@@ -1,4 +1,4 @@
-use super::*;
+type Node = super::Node<usize, usize>;
 
 #[test]
 fn is_leaf_if_empty() {
@@ -95,7 +95,7 @@ fn find_leaf_for_node_with_no_children() {
     let mut node = Node::new(2);
     node = node.push(1, 1);
 
-    let insert_in = node.find_leaf_for(2);
+    let insert_in = node.find_leaf_for(&2);
     assert!(std::ptr::eq(&node, insert_in));
 }
 
@@ -113,7 +113,7 @@ fn find_leaf_for_node_with_child() {
     node = node.push_with_children(2, 2, Some(left), Some(right));
 
     // when
-    let insert_in = node.find_leaf_for(1);
+    let insert_in = node.find_leaf_for(&1);
 
     // then
     let left = node.edges[0].as_ref().unwrap();
@@ -134,7 +134,7 @@ fn find_leaf_for_node_with_child_right() {
     node = node.push_with_children(2, 2, Some(left), Some(right));
 
     // when
-    let insert_in = node.find_leaf_for(4);
+    let insert_in = node.find_leaf_for(&4);
 
     // then
     let right = node.edges[1].as_ref().unwrap();
@@ -243,7 +243,7 @@ fn depth_one_layer() {
 fn find_node_with_present() {
     let node = Node::new(2).push(1, 1).push(2, 2);
 
-    let result = node.find_node_with(2);
+    let result = node.find_node_with(&2);
     assert!(result.is_some());
     assert!(std::ptr::addr_eq(&node, result.unwrap()))
 }
@@ -252,7 +252,7 @@ fn find_node_with_present() {
 fn find_node_with_absent() {
     let node = Node::new(2).push(1, 1).push(2, 2);
 
-    let result = node.find_node_with(3);
+    let result = node.find_node_with(&3);
     assert!(result.is_none());
 }
 
@@ -262,7 +262,7 @@ fn find_node_with_present_in_child() {
     let right = Node::new(2).push(3, 3);
     let node = Node::new(2).push_with_children(2, 2, Some(left), Some(right));
 
-    let result = node.find_node_with(3);
+    let result = node.find_node_with(&3);
     let expected = node.edges[1].as_ref().unwrap();
     assert!(result.is_some());
     assert!(std::ptr::addr_eq(expected, result.unwrap()));
@@ -272,14 +272,14 @@ fn find_node_with_present_in_child() {
 fn delete_entry_when_exists() {
     let node = Node::new(2).push(1, 2).push(3, 4);
 
-    let (new_node, removed) = node.delete_entry(3);
+    let (new_node, removed) = node.delete_entry(&3);
 
     assert_eq!(new_node.keys.len(), 1);
     assert_eq!(new_node.values.len(), 1);
     assert_eq!(new_node.edges.len(), 2);
 
-    assert!(new_node.contains(1));
-    assert!(!new_node.contains(3));
+    assert!(new_node.contains(&1));
+    assert!(!new_node.contains(&3));
 
     assert_eq!(removed.unwrap(), 4);
 }
@@ -288,7 +288,7 @@ fn delete_entry_when_exists() {
 fn delete_entry_when_not_exists() {
     let node = Node::new(2).push(1, 2).push(3, 4);
 
-    let (new_node, removed) = node.delete_entry(5);
+    let (new_node, removed) = node.delete_entry(&5);
 
     assert_eq!(new_node.keys.len(), 2);
     assert_eq!(new_node.values.len(), 2);
@@ -303,9 +303,12 @@ fn get_right_child_exists() {
     let right = Node::new(2).push(3, 3);
     let node = Node::new(2).push_with_children(2, 2, Some(left), Some(right));
 
-    let right_child = node.get_right_child(2);
-    
-    assert!(std::ptr::addr_eq(right_child.unwrap(), node.edges[1].as_ref().unwrap()))
+    let right_child = node.get_right_child(&2);
+
+    assert!(std::ptr::addr_eq(
+        right_child.unwrap(),
+        node.edges[1].as_ref().unwrap()
+    ))
 }
 
 #[test]
@@ -314,8 +317,8 @@ fn get_right_child_not_exists() {
     let right = Node::new(2).push(3, 3);
     let node = Node::new(2).push_with_children(2, 2, Some(left), Some(right));
 
-    let right_child = node.get_right_child(99);
-    
+    let right_child = node.get_right_child(&99);
+
     assert!(right_child.is_none());
 }
 
@@ -337,7 +340,9 @@ fn largest_key_empty() {
 fn replace_entry_with_exists() {
     let node = Node::new(3).push(1, 1).push(2, 2).push(3, 3);
 
-    let (new_node, old_value) = node.replace_entry_with(1, (6, 9)).expect("Replace entry should not return None when key is in node");
+    let (new_node, old_value) = node
+        .replace_entry_with(&1, (6, 9))
+        .expect("Replace entry should not return None when key is in node");
 
     assert_eq!(old_value, 1);
     assert_eq!(new_node.keys, vec![6, 2, 3]);
@@ -348,7 +353,7 @@ fn replace_entry_with_exists() {
 fn replace_entry_with_not_exists() {
     let node = Node::new(3).push(1, 1).push(2, 2).push(3, 3);
 
-    let result = node.replace_entry_with(9, (4, 4));
+    let result = node.replace_entry_with(&9, (4, 4));
     assert!(result.is_none());
 }
 
@@ -359,7 +364,7 @@ fn is_deficient() {
 
     node = node.push(2, 2);
     assert!(!node.is_deficient());
-    
+
     node = node.push(3, 3);
     assert!(!node.is_deficient());
 
@@ -387,11 +392,17 @@ fn get_siblings_of_one_sibling() {
     let left = node.edges[0].as_ref().unwrap();
     let result = node.get_siblings_of(left);
     assert!(result.0.is_none());
-    assert!(std::ptr::addr_eq(result.1.unwrap(), node.edges[1].as_ref().unwrap()));
+    assert!(std::ptr::addr_eq(
+        result.1.unwrap(),
+        node.edges[1].as_ref().unwrap()
+    ));
 
     let right = node.edges[1].as_ref().unwrap();
     let result = node.get_siblings_of(right);
-    assert!(std::ptr::addr_eq(result.0.unwrap(), node.edges[0].as_ref().unwrap()));
+    assert!(std::ptr::addr_eq(
+        result.0.unwrap(),
+        node.edges[0].as_ref().unwrap()
+    ));
     assert!(result.1.is_none());
 }
 
@@ -403,13 +414,23 @@ fn get_siblings_of_two_siblings() {
     let right = Node::new(2).push(5, 5);
     node = node.push_with_children(4, 4, Some(mid), Some(right));
 
-    let children: Vec<usize> = node.edges.iter().map(|e| e.as_ref().unwrap().keys[0]).collect();
+    let children: Vec<usize> = node
+        .edges
+        .iter()
+        .map(|e| e.as_ref().unwrap().keys[0])
+        .collect();
     assert_eq!(children, vec![1, 3, 5]);
 
     let mid = node.edges[1].as_ref().unwrap();
     let result = node.get_siblings_of(mid);
-    assert!(std::ptr::eq(result.0.unwrap(), node.edges[0].as_ref().unwrap()));
-    assert!(std::ptr::eq(result.1.unwrap(), node.edges[2].as_ref().unwrap()));
+    assert!(std::ptr::eq(
+        result.0.unwrap(),
+        node.edges[0].as_ref().unwrap()
+    ));
+    assert!(std::ptr::eq(
+        result.1.unwrap(),
+        node.edges[2].as_ref().unwrap()
+    ));
 }
 
 #[test]
@@ -502,7 +523,10 @@ fn get_sandwitched_for_larger() {
     assert_eq!(sandwitched.edges.len(), 2);
     assert_eq!(sandwitched.keys, vec![2]);
     assert_eq!(sandwitched.edges[0].as_ref().unwrap().keys, vec![1]);
-    assert_eq!(sandwitched.edges[1].as_ref().unwrap().keys, vec![3, 4, 5, 6]);
+    assert_eq!(
+        sandwitched.edges[1].as_ref().unwrap().keys,
+        vec![3, 4, 5, 6]
+    );
 }
 
 #[test]
@@ -517,4 +541,4 @@ fn get_sandwitched_for_more_elements_than_max() {
     let left = node.edges[1].as_ref().unwrap();
     let right = node.edges[2].as_ref().unwrap();
     let sandwitched = node.get_sandwitched_for(left, right);
-}
\ No newline at end of file
+}
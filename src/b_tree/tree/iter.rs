@@ -0,0 +1,141 @@
+use std::ops::Bound;
+
+use crate::b_tree::node::Node;
+
+/// Double-ended in-order iterator over the entries of a `BTree` with keys in a range.
+///
+/// Each end keeps the path from the root to its current position, as `(node, index)` pairs:
+/// - front: `index` is the entry of the node returned next, after the subtree of `edges[index]`
+/// - back: `index - 1` is the entry of the node returned next, after the subtree of `edges[index]`
+pub struct Iter<'a, K, V> {
+    front: Vec<(&'a Node<K, V>, usize)>,
+    back: Vec<(&'a Node<K, V>, usize)>,
+    lower: Bound<K>,
+    upper: Bound<K>,
+    /// Last keys returned by each end, used for detecting when the ends meet (keys aren't unique, so entries are
+    /// compared by address)
+    front_last: Option<&'a K>,
+    back_last: Option<&'a K>,
+}
+
+impl<'a, K: Ord, V> Iter<'a, K, V> {
+    pub(super) fn new(root: &'a Node<K, V>, lower: Bound<K>, upper: Bound<K>) -> Self {
+        let mut iter = Self {
+            front: vec![],
+            back: vec![],
+            lower,
+            upper,
+            front_last: None,
+            back_last: None,
+        };
+
+        // descend to the first entry above the lower bound
+        let mut node = Some(root);
+        while let Some(current) = node {
+            let index = current
+                .keys
+                .partition_point(|key| !iter.is_above_lower(key));
+            iter.front.push((current, index));
+            node = Self::edge(current, index);
+        }
+
+        // descend to the last entry below the upper bound
+        let mut node = Some(root);
+        while let Some(current) = node {
+            let index = current.keys.partition_point(|key| iter.is_below_upper(key));
+            iter.back.push((current, index));
+            node = Self::edge(current, index);
+        }
+
+        iter
+    }
+
+    fn edge(node: &'a Node<K, V>, index: usize) -> Option<&'a Node<K, V>> {
+        node.edges.get(index).and_then(Option::as_ref)
+    }
+
+    fn is_above_lower(&self, key: &K) -> bool {
+        match &self.lower {
+            Bound::Included(lower) => key >= lower,
+            Bound::Excluded(lower) => key > lower,
+            Bound::Unbounded => true,
+        }
+    }
+
+    fn is_below_upper(&self, key: &K) -> bool {
+        match &self.upper {
+            Bound::Included(upper) => key <= upper,
+            Bound::Excluded(upper) => key < upper,
+            Bound::Unbounded => true,
+        }
+    }
+
+    /// Stops both ends of the iterator.
+    fn finish(&mut self) {
+        self.front.clear();
+        self.back.clear();
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // pop the nodes whose entries were all returned
+        while let Some((node, index)) = self.front.last().copied() {
+            if index < node.keys.len() {
+                break;
+            }
+            self.front.pop();
+        }
+        let (node, index) = self.front.pop()?;
+
+        let key = &node.keys[index];
+        if !self.is_below_upper(key) || self.back_last.is_some_and(|last| std::ptr::eq(last, key)) {
+            self.finish();
+            return None;
+        }
+
+        // the next entry is the leftmost one in the right subtree of the current entry
+        self.front.push((node, index + 1));
+        let mut child = Self::edge(node, index + 1);
+        while let Some(current) = child {
+            self.front.push((current, 0));
+            child = Self::edge(current, 0);
+        }
+
+        self.front_last = Some(key);
+        Some((key, &node.values[index]))
+    }
+}
+
+impl<'a, K: Ord, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // pop the nodes whose entries were all returned
+        while let Some((_, index)) = self.back.last().copied() {
+            if index > 0 {
+                break;
+            }
+            self.back.pop();
+        }
+        let (node, index) = self.back.pop()?;
+
+        let key = &node.keys[index - 1];
+        if !self.is_above_lower(key) || self.front_last.is_some_and(|last| std::ptr::eq(last, key))
+        {
+            self.finish();
+            return None;
+        }
+
+        // the next entry is the rightmost one in the left subtree of the current entry
+        self.back.push((node, index - 1));
+        let mut child = Self::edge(node, index - 1);
+        while let Some(current) = child {
+            self.back.push((current, current.keys.len()));
+            child = Self::edge(current, current.keys.len());
+        }
+
+        self.back_last = Some(key);
+        Some((key, &node.values[index - 1]))
+    }
+}
@@ -1,30 +1,147 @@
+use std::{fmt::Debug, ops::RangeBounds};
+
 use super::node::{Node, NodeSplit};
 
+mod iter;
 #[cfg(test)]
 mod tests;
 
-pub struct BTree {
-    root: Node,
+pub use iter::Iter;
+
+/// In-memory ordered map, which can hold multiple entries with the same key.
+pub struct BTree<K, V> {
+    root: Node<K, V>,
     b: usize,
+    len: usize,
 }
 
-enum NodeReplace {
-    Node(Node, *const Node), // what node you need to replace with what value
-    Root(Node),              // root needs to be replaced with value
+/// Nodes of a level built by `BTree::from_sorted`, with the keys and values of the separators between them.
+type Level<K, V> = (Vec<Node<K, V>>, Vec<K>, Vec<V>);
+
+enum NodeReplace<K, V> {
+    Node(Node<K, V>, *const Node<K, V>), // what node you need to replace with what value
+    Root(Node<K, V>),                    // root needs to be replaced with value
 }
 
-impl BTree {
+impl<K: Ord + Clone, V: Clone> BTree<K, V> {
     pub fn new(b: usize) -> Self {
         Self {
             root: Node::new(b),
             b,
+            len: 0,
+        }
+    }
+
+    /// Builds a tree from `entries`, bottom-up, without going through the inserts.
+    ///
+    /// # Errors
+    /// Will return `Err` if the keys of `entries` are not sorted.
+    pub fn from_sorted(b: usize, entries: impl IntoIterator<Item = (K, V)>) -> Result<Self, ()> {
+        let (mut keys, mut values): (Vec<K>, Vec<V>) = entries.into_iter().unzip();
+        if keys.windows(2).any(|w| w[0] > w[1]) {
+            return Err(());
+        }
+
+        let len = keys.len();
+        if len == 0 {
+            return Ok(Self::new(b));
+        }
+
+        // build the tree level by level, the separators of a level being the entries of the level above
+        let mut edges = vec![];
+        loop {
+            let (nodes, separator_keys, separator_values) =
+                Self::build_level(b, keys, values, edges);
+            if nodes.len() == 1 {
+                return Ok(Self {
+                    root: nodes.into_iter().next().unwrap(),
+                    b,
+                    len,
+                });
+            }
+
+            keys = separator_keys;
+            values = separator_values;
+            edges = nodes.into_iter().map(Some).collect();
         }
     }
 
-    /// Insert `key` -> `value` pair into tree.
-    pub fn insert(&mut self, key: usize, value: usize) {
+    /// Splits the entries of a level in as few nodes as possible and returns the nodes and the separators between
+    /// them. `edges` are the children of the entries, or empty for leaves.
+    ///
+    /// Nodes hold at most `2 * b - 2` entries (a node with `2 * b - 1` entries is full) and the entries are spread
+    /// evenly, so every node also holds at least `b - 1` entries.
+    fn build_level(
+        b: usize,
+        keys: Vec<K>,
+        values: Vec<V>,
+        edges: Vec<Option<Node<K, V>>>,
+    ) -> Level<K, V> {
+        let count = keys.len();
+        let node_count = (count + 1).div_ceil(2 * b - 1);
+        let node_entries = count + 1 - node_count;
+
+        let mut keys = keys.into_iter();
+        let mut values = values.into_iter();
+        let mut edges = edges.into_iter();
+        let (mut nodes, mut separator_keys, mut separator_values) = (vec![], vec![], vec![]);
+        for i in 0..node_count {
+            let size = node_entries / node_count + usize::from(i < node_entries % node_count);
+            let node_edges = if edges.len() == 0 {
+                vec![]
+            } else {
+                edges.by_ref().take(size + 1).collect()
+            };
+            nodes.push(Node::with_entries(
+                b,
+                keys.by_ref().take(size).collect(),
+                values.by_ref().take(size).collect(),
+                node_edges,
+            ));
+
+            if i + 1 < node_count {
+                separator_keys.push(keys.next().unwrap());
+                separator_values.push(values.next().unwrap());
+            }
+        }
+
+        (nodes, separator_keys, separator_values)
+    }
+
+    /// Returns the number of entries in the tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a reference to the value of `key` (of one of them, if the key is not unique).
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.root.get(key)
+    }
+
+    /// Returns a double-ended iterator over the entries with keys in `range`, in ascending order of the keys.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Iter<'_, K, V> {
+        Iter::new(
+            &self.root,
+            range.start_bound().cloned(),
+            range.end_bound().cloned(),
+        )
+    }
+
+    /// Returns a double-ended iterator over the entries of the tree, in ascending order of the keys.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.range(..)
+    }
+
+    /// Insert `key` -> `value` pair into tree. Keys don't have to be unique.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.len += 1;
+
         // find leaf to insert into
-        let node_to_insert = self.root.find_leaf_for(key);
+        let node_to_insert = self.root.find_leaf_for(&key);
         // insert inside it
         let new_node_to_insert = node_to_insert.push(key, value);
         if !new_node_to_insert.is_full() {
@@ -37,7 +154,7 @@ impl BTree {
         let split_node = new_node_to_insert.get_split();
 
         // insert median in parent (potentiallt split again, again, ...)
-        let node_replace = self.insert_split_in_parent(&node_to_insert, split_node);
+        let node_replace = self.insert_split_in_parent(node_to_insert, split_node);
         self.root = self.get_root_after_replace(node_replace);
     }
 
@@ -48,7 +165,11 @@ impl BTree {
     /// # Details
     /// It builds a new node subtree that represents correct retulst post insertion into parent. The return value
     /// represents information about what needs to be replaced into `self` with the newly formed subtree.
-    fn insert_split_in_parent(&self, current: &Node, split: NodeSplit) -> NodeReplace {
+    fn insert_split_in_parent(
+        &self,
+        current: &Node<K, V>,
+        split: NodeSplit<K, V>,
+    ) -> NodeReplace<K, V> {
         // find parent of node
         let parent = self.find_parent(current);
 
@@ -90,7 +211,7 @@ impl BTree {
     ///
     /// # Panics
     /// Panics if `node` is not in tree.
-    fn find_parent(&self, node: &Node) -> Option<&Node> {
+    fn find_parent(&self, node: &Node<K, V>) -> Option<&Node<K, V>> {
         if std::ptr::addr_eq(&self.root, node) {
             return None;
         }
@@ -104,7 +225,7 @@ impl BTree {
     }
 
     /// Return new root of tree, after the `replace` has been applied in `self`.
-    fn get_root_after_replace(&self, replace: NodeReplace) -> Node {
+    fn get_root_after_replace(&self, replace: NodeReplace<K, V>) -> Node<K, V> {
         match replace {
             NodeReplace::Node(node, to_replace) => {
                 self.root.clone_with_replaced_node(to_replace, &node)
@@ -113,12 +234,16 @@ impl BTree {
         }
     }
 
-    pub fn print_tree(&self) {
+    pub fn print_tree(&self)
+    where
+        K: Debug,
+        V: Debug,
+    {
         self.root.print_node(0);
     }
 
     /// Remove element with `key` from `self`. Returns `(key, value)` that was removed if node exists in tree, or `Err` otherwise.
-    pub fn remove(&mut self, key: usize) -> Result<(usize, usize), ()> {
+    pub fn remove(&mut self, key: &K) -> Result<(K, V), ()> {
         let node_with_key = self.root.find_node_with(key);
 
         if node_with_key.is_none() {
@@ -128,11 +253,14 @@ impl BTree {
         let found = node_with_key.unwrap();
         if found.is_leaf() {
             let (new_leaf, deleted_value) = found.delete_entry(key);
-            let replace = self.rebalance_node(&found, new_leaf);
+            let replace = self.rebalance_node(found, new_leaf);
 
             self.root = self.get_root_after_replace(replace);
+            self.len -= 1;
 
-            return deleted_value.map(|v| Ok((key, v))).unwrap_or(Err(()));
+            return deleted_value
+                .map(|v| Ok((key.clone(), v)))
+                .unwrap_or(Err(()));
         } else {
             // unwrap is fine, because found is the node that contains the key
             let right_child = found.get_right_child(key).unwrap();
@@ -144,7 +272,7 @@ impl BTree {
             let largest_key_right = largest_key_right.unwrap();
 
             // unwrap is fine, because on recursive calls it's not possible to have element not exist in tree
-            let replace_with = self.remove(largest_key_right).unwrap();
+            let replace_with = self.remove(&largest_key_right).unwrap();
 
             // this is pretty bad, but couldn't think of a way to please the borrow checker
             let found = self.root.find_node_with(key).unwrap();
@@ -154,7 +282,7 @@ impl BTree {
 
             self.root = self.get_root_after_replace(NodeReplace::Node(found_replaced, found));
 
-            return Ok((key, replaced_value));
+            return Ok((key.clone(), replaced_value));
         }
     }
 
@@ -164,7 +292,11 @@ impl BTree {
     ///
     /// # Asserts
     /// Asserts that `start_node` is a valid pointer to a node in the tree.
-    fn rebalance_node(&self, start_node: &Node, node_replacement: Node) -> NodeReplace {
+    fn rebalance_node(
+        &self,
+        start_node: &Node<K, V>,
+        node_replacement: Node<K, V>,
+    ) -> NodeReplace<K, V> {
         if !node_replacement.is_deficient() {
             return NodeReplace::Node(node_replacement, start_node);
         }
@@ -178,7 +310,10 @@ impl BTree {
         let start_node_index = parent.get_child_index(start_node);
 
         let mut new_parent = parent.clone();
-        let _ = std::mem::replace(&mut new_parent.edges[start_node_index], Some(node_replacement));
+        let _ = std::mem::replace(
+            &mut new_parent.edges[start_node_index],
+            Some(node_replacement),
+        );
         let node_replacement = new_parent.edges[start_node_index].as_ref().unwrap();
 
         let (left_sibling, right_sibling) = new_parent.get_siblings_of(node_replacement);
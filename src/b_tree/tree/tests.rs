@@ -1,4 +1,8 @@
-use super::*;
+use std::ops::Bound;
+
+use crate::b_tree::node::Node;
+
+type BTree = super::BTree<usize, usize>;
 
 #[test]
 fn insert_in_empty() {
@@ -29,7 +33,6 @@ fn insert_with_one_split() {
 
     assert_eq!(2, tree.root.depth());
     assert_eq!(2, tree.root.keys[0]);
-
 }
 
 #[test]
@@ -47,5 +50,141 @@ fn insert_with_two_splits() {
     tree.insert(3, 3);
     assert_eq!(3, tree.root.depth());
     assert_eq!(3, tree.root.keys[0]);
+}
+
+#[test]
+fn get_and_len() {
+    let mut tree = BTree::new(2);
+    assert!(tree.is_empty());
+
+    for i in 0..100 {
+        tree.insert((i * 37) % 100, i);
+    }
+
+    assert_eq!(tree.len(), 100);
+    assert_eq!(tree.get(&74), Some(&2));
+    assert_eq!(tree.get(&100), None);
+
+    tree.remove(&74).unwrap();
+    assert_eq!(tree.len(), 99);
+    assert_eq!(tree.get(&74), None);
+}
+
+#[test]
+fn iter_double_ended() {
+    let mut tree = BTree::new(2);
+    for i in 0..100 {
+        tree.insert((i * 37) % 100, i);
+    }
+
+    let keys = tree.iter().map(|(k, _)| *k).collect::<Vec<_>>();
+    assert_eq!(keys, (0..100).collect::<Vec<_>>());
+
+    let keys = tree.iter().rev().map(|(k, _)| *k).collect::<Vec<_>>();
+    assert_eq!(keys, (0..100).rev().collect::<Vec<_>>());
+
+    // both ends meet in the middle
+    let mut iter = tree.iter();
+    let mut keys = vec![];
+    while let (Some((front, _)), Some((back, _))) = (iter.next(), iter.next_back()) {
+        keys.push(*front);
+        keys.push(*back);
+    }
+    keys.sort();
+    assert_eq!(keys, (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn range() {
+    let mut tree = BTree::new(3);
+    for i in (0..200).rev() {
+        tree.insert(i, i * 2);
+    }
 
-}
\ No newline at end of file
+    fn keys<'a>(iter: impl Iterator<Item = (&'a usize, &'a usize)>) -> Vec<usize> {
+        iter.map(|(k, _)| *k).collect()
+    }
+
+    assert_eq!(keys(tree.range(50..55)), vec![50, 51, 52, 53, 54]);
+    assert_eq!(keys(tree.range(197..)), vec![197, 198, 199]);
+    assert_eq!(keys(tree.range(..=2)), vec![0, 1, 2]);
+    assert_eq!(keys(tree.range(300..)), vec![]);
+    assert_eq!(keys(tree.range(10..=13).rev()), vec![13, 12, 11, 10]);
+    assert_eq!(
+        tree.range((Bound::Excluded(98), Bound::Excluded(100)))
+            .collect::<Vec<_>>(),
+        vec![(&99, &198)]
+    );
+}
+
+#[test]
+fn duplicate_keys() {
+    let mut tree = BTree::new(2);
+    for i in 0..30 {
+        tree.insert(i % 3, i);
+    }
+
+    assert_eq!(tree.len(), 30);
+    assert_eq!(tree.range(1..2).count(), 10);
+    assert!(tree.range(1..2).all(|(k, v)| *k == 1 && v % 3 == 1));
+    assert_eq!(tree.range(1..=1).rev().count(), 10);
+}
+
+#[test]
+fn from_sorted() {
+    for count in [0, 1, 2, 3, 4, 5, 6, 7, 20, 100, 1000] {
+        let tree = BTree::from_sorted(2, (0..count).map(|i| (i, i))).unwrap();
+
+        assert_eq!(tree.len(), count);
+        assert_eq!(
+            tree.iter().map(|(k, _)| *k).collect::<Vec<_>>(),
+            (0..count).collect::<Vec<_>>()
+        );
+        assert_valid(&tree.root, true);
+    }
+
+    assert!(BTree::from_sorted(2, vec![(2, 2), (1, 1)]).is_err());
+
+    // the bulk-loaded tree can be modified like any other tree
+    let mut tree = BTree::from_sorted(2, (0..100).map(|i| (i * 2, i))).unwrap();
+    tree.insert(51, 51);
+    tree.remove(&50).unwrap();
+    assert_eq!(
+        tree.range(48..=52).map(|(k, _)| *k).collect::<Vec<_>>(),
+        vec![48, 51, 52]
+    );
+}
+
+#[test]
+fn generic_types() {
+    let mut tree = super::BTree::<String, Vec<u8>>::new(2);
+    for word in ["delta", "alpha", "charlie", "bravo"] {
+        tree.insert(word.to_string(), word.as_bytes().to_vec());
+    }
+
+    assert_eq!(
+        tree.get(&String::from("charlie")),
+        Some(&b"charlie".to_vec())
+    );
+    assert_eq!(
+        tree.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>(),
+        vec!["alpha", "bravo", "charlie", "delta"]
+    );
+}
+
+/// Asserts that the entry counts of all the nodes are in bounds and that all the leaves are at the same depth.
+fn assert_valid(node: &Node<usize, usize>, is_root: bool) -> usize {
+    assert!(!node.is_full());
+    assert!(is_root || !node.is_deficient());
+    if node.is_leaf() {
+        return 1;
+    }
+
+    let depths = node
+        .edges
+        .iter()
+        .map(|edge| assert_valid(edge.as_ref().unwrap(), false))
+        .collect::<Vec<_>>();
+    assert!(depths.iter().all(|d| *d == depths[0]));
+    depths[0] + 1
+}
@@ -0,0 +1,138 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+};
+
+use crate::table::tuple::{Tuple, RID};
+
+use super::info::IndexInfo;
+
+/// Change made to a table while one of its indexes is built concurrently. The changes are applied by the build, in
+/// the order they were made, over the rows read by its scan before the index becomes valid.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexChange {
+    Insert(Tuple, RID),
+    Delete(Tuple, RID),
+}
+
+/// Phase of a concurrent index build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexBuildPhase {
+    /// Reading the rows of the table, without locking it
+    Scanning,
+    /// Inserting the rows read by the scan
+    Building,
+    /// Applying the changes made to the table during the build
+    CatchingUp,
+    /// The index is valid and used by queries
+    Done,
+    /// The build was aborted (the index is unique and the table has duplicate keys) and the index removed
+    Failed,
+}
+
+/// Progress of a concurrent index build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexBuildProgress {
+    pub phase: IndexBuildPhase,
+    /// Rows read by the scan that were inserted in the index
+    pub rows_done: usize,
+    /// Rows read by the scan, 0 until the scan is done
+    pub rows_total: usize,
+    /// Changes made to the table during the build that were applied to the index
+    pub changes_applied: usize,
+    /// Changes made to the table during the build that weren't applied to the index yet
+    pub changes_pending: usize,
+}
+
+/// State of a concurrent index build, shared by the build and the writers of the table.
+pub struct IndexBuild {
+    phase: Mutex<IndexBuildPhase>,
+    rows_done: AtomicUsize,
+    rows_total: AtomicUsize,
+    changes_applied: AtomicUsize,
+    pending: Mutex<Vec<IndexChange>>,
+}
+
+impl IndexBuild {
+    pub(super) fn new() -> Self {
+        Self {
+            phase: Mutex::new(IndexBuildPhase::Scanning),
+            rows_done: AtomicUsize::new(0),
+            rows_total: AtomicUsize::new(0),
+            changes_applied: AtomicUsize::new(0),
+            pending: Mutex::new(vec![]),
+        }
+    }
+
+    /// Records a change made to the table, which the build applies before the index becomes valid.
+    pub fn record(&self, change: IndexChange) {
+        self.pending.lock().unwrap().push(change);
+    }
+
+    pub fn progress(&self) -> IndexBuildProgress {
+        IndexBuildProgress {
+            phase: *self.phase.lock().unwrap(),
+            rows_done: self.rows_done.load(Ordering::SeqCst),
+            rows_total: self.rows_total.load(Ordering::SeqCst),
+            changes_applied: self.changes_applied.load(Ordering::SeqCst),
+            changes_pending: self.pending.lock().unwrap().len(),
+        }
+    }
+
+    pub(super) fn set_phase(&self, phase: IndexBuildPhase) {
+        *self.phase.lock().unwrap() = phase;
+    }
+
+    pub(super) fn set_rows_total(&self, count: usize) {
+        self.rows_total.store(count, Ordering::SeqCst);
+    }
+
+    pub(super) fn add_rows_done(&self, count: usize) {
+        self.rows_done.fetch_add(count, Ordering::SeqCst);
+    }
+
+    /// Removes the recorded changes and returns them, in the order they were made.
+    pub(super) fn take_pending(&self) -> Vec<IndexChange> {
+        let changes = std::mem::take(&mut *self.pending.lock().unwrap());
+        self.changes_applied
+            .fetch_add(changes.len(), Ordering::SeqCst);
+
+        changes
+    }
+}
+
+/// Result of a concurrent index build: the info of the valid index, or `Err` if the build failed.
+pub type IndexBuildResult = Result<Arc<Mutex<IndexInfo>>, ()>;
+
+/// Handle of an index built in the background, returned by `Catalog::create_index_concurrently`.
+pub struct IndexBuildHandle {
+    build: Arc<IndexBuild>,
+    thread: JoinHandle<IndexBuildResult>,
+}
+
+impl IndexBuildHandle {
+    pub(super) fn new(build: Arc<IndexBuild>, thread: JoinHandle<IndexBuildResult>) -> Self {
+        Self { build, thread }
+    }
+
+    pub fn progress(&self) -> IndexBuildProgress {
+        self.build.progress()
+    }
+
+    /// Returns `true` if the build is done (successfully or not).
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Waits for the build to finish and returns the info of the index, which is valid at this point.
+    ///
+    /// # Errors
+    /// Will return `Err` if the index is unique and the table had duplicate keys. The index is removed from the catalog
+    /// in this case.
+    pub fn wait(self) -> IndexBuildResult {
+        self.thread.join().expect("Index build panicked")
+    }
+}
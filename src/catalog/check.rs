@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use crate::{
+    index::Index,
+    table::{
+        schema::Schema,
+        tuple::{Tuple, RID},
+    },
+};
+
+/// Inconsistency found by `Catalog::check_table` in an index of the table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexProblem {
+    /// A live row that belongs in the index has no entry
+    MissingEntry { index_name: String, rid: RID },
+    /// An entry points to a row that doesn't exist, is deleted or doesn't belong in the index (partial indexes)
+    DanglingEntry { index_name: String, rid: RID },
+    /// The entries of a row don't have the keys of the row (e.g. the index wasn't updated with the row)
+    WrongKey { index_name: String, rid: RID },
+    /// A row has more entries than keys (more than one entry, except in full-text indexes)
+    DuplicateEntry { index_name: String, rid: RID },
+    /// An invariant of the data structure backing the index is violated (e.g. incoherent hash directory depths)
+    Structure { index_name: String, message: String },
+}
+
+impl IndexProblem {
+    pub fn index_name(&self) -> &str {
+        match self {
+            IndexProblem::MissingEntry { index_name, .. }
+            | IndexProblem::DanglingEntry { index_name, .. }
+            | IndexProblem::WrongKey { index_name, .. }
+            | IndexProblem::DuplicateEntry { index_name, .. }
+            | IndexProblem::Structure { index_name, .. } => index_name,
+        }
+    }
+}
+
+/// Result of `Catalog::check_table`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TableCheckReport {
+    /// Problems found in the indexes of the table, before any repair
+    pub problems: Vec<IndexProblem>,
+    /// Names of the indexes rebuilt because they had problems (only when repairing)
+    pub rebuilt_indexes: Vec<String>,
+}
+
+impl TableCheckReport {
+    /// Returns `true` if no problem was found.
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// Returns the names of the indexes with problems, sorted and without duplicates.
+    pub fn broken_indexes(&self) -> Vec<&str> {
+        let mut names = self
+            .problems
+            .iter()
+            .map(IndexProblem::index_name)
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+
+        names
+    }
+}
+
+/// Cross-validates the index with the live rows of its table and checks the invariants of its data structure. Every
+/// live row for which `Index::has_tuple` is true must have exactly one entry for each of its keys (see `Index::keys`)
+/// and every entry must point to such a row.
+pub(super) fn check_index(
+    index_name: &str,
+    index: &Index,
+    rows: &[(Tuple, RID)],
+    table_schema: &Schema,
+) -> Vec<IndexProblem> {
+    let mut problems = index
+        .check_integrity()
+        .into_iter()
+        .map(|message| IndexProblem::Structure {
+            index_name: index_name.to_string(),
+            message,
+        })
+        .collect::<Vec<_>>();
+
+    let mut entries: HashMap<RID, Vec<Tuple>> = HashMap::new();
+    for (key, rid) in index.entries() {
+        entries.entry(rid).or_default().push(key);
+    }
+
+    for (tuple, rid) in rows {
+        if !index.has_tuple(tuple, table_schema) {
+            continue;
+        }
+
+        let rid = rid.clone();
+        let expected_keys = index.keys(tuple, table_schema);
+        let keys = entries.remove(&rid).unwrap_or_default();
+        if keys.is_empty() {
+            if !expected_keys.is_empty() {
+                problems.push(IndexProblem::MissingEntry {
+                    index_name: index_name.to_string(),
+                    rid,
+                });
+            }
+            continue;
+        }
+
+        if keys.len() > expected_keys.len() {
+            problems.push(IndexProblem::DuplicateEntry {
+                index_name: index_name.to_string(),
+                rid: rid.clone(),
+            });
+        }
+        if keys.iter().any(|key| !expected_keys.contains(key))
+            || expected_keys.iter().any(|key| !keys.contains(key))
+        {
+            problems.push(IndexProblem::WrongKey {
+                index_name: index_name.to_string(),
+                rid,
+            });
+        }
+    }
+
+    // the entries left don't belong to any row of the index
+    let mut dangling = entries.into_keys().collect::<Vec<_>>();
+    dangling.sort_by_key(RID::get);
+    problems.extend(dangling.into_iter().map(|rid| IndexProblem::DanglingEntry {
+        index_name: index_name.to_string(),
+        rid,
+    }));
+
+    problems
+}
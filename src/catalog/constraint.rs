@@ -0,0 +1,251 @@
+use crate::{
+    exec::expression::{bind::bind_expression, Expression},
+    parser::ast::general::{
+        ColumnConstraint, ColumnDef, ForeignKeyDef, References,
+        ReferentialAction as AstReferentialAction,
+    },
+    table::schema::Schema,
+};
+
+use super::{Catalog, OID};
+
+/// Constraints of a table that involve more than a single value. `NOT NULL` and `DEFAULT` are stored in the columns of
+/// the schema instead.
+#[derive(Clone)]
+pub enum TableConstraint {
+    /// Values of the columns (indexes in the schema) are unique and not null. Backed by a unique index.
+    PrimaryKey(Vec<usize>),
+    /// Values of the columns (indexes in the schema) are unique, unless one of them is null. Backed by a unique index.
+    Unique(Vec<usize>),
+    /// Rows for which `expr` evaluates to false are rejected (null passes the check).
+    Check { name: String, expr: Expression },
+    /// Values of the columns reference a row of another table, unless one of them is null.
+    ForeignKey(ForeignKey),
+}
+
+/// Reference from the columns of a (child) table to the primary key or a unique key of a parent table.
+#[derive(Clone)]
+pub struct ForeignKey {
+    pub name: String,
+    /// Referencing columns (indexes in the schema of the child table)
+    pub columns: Vec<usize>,
+    pub parent_oid: OID,
+    pub parent_name: String,
+    /// Referenced columns (indexes in the schema of the parent table), always backed by a unique index
+    pub parent_columns: Vec<usize>,
+    pub on_delete: ReferentialAction,
+    pub on_update: ReferentialAction,
+}
+
+/// What happens to the referencing rows when the referenced row is deleted or its key is updated.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReferentialAction {
+    /// The parent row can't be changed while it's referenced (also used for `NO ACTION`, as checks can't be deferred).
+    Restrict,
+    /// Referencing rows are deleted (or their keys are updated) along with the parent row.
+    Cascade,
+    /// Referencing columns are set to null.
+    SetNull,
+}
+
+impl ReferentialAction {
+    pub fn from_ast(action: &AstReferentialAction) -> Self {
+        match action {
+            AstReferentialAction::NoAction | AstReferentialAction::Restrict => {
+                ReferentialAction::Restrict
+            }
+            AstReferentialAction::Cascade => ReferentialAction::Cascade,
+            AstReferentialAction::SetNull => ReferentialAction::SetNull,
+        }
+    }
+}
+
+impl ForeignKey {
+    /// Resolves the `columns` of the table with `table_name` and `schema` referencing a parent table in the catalog.
+    ///
+    /// # Errors
+    /// Will return `Err` if a column or the parent table doesn't exist, if the types of the columns don't match or if
+    /// the referenced columns don't have a primary key or unique constraint. Foreign keys referencing their own table
+    /// are not supported, since the table doesn't exist yet when they are resolved.
+    pub fn resolve(
+        table_name: &str,
+        columns: &[String],
+        references: &References,
+        schema: &Schema,
+        catalog: &Catalog,
+    ) -> Result<Self, String> {
+        if references.table_name == table_name {
+            return Err(format!(
+                "Foreign key on ({}) can't reference its own table {}",
+                columns.join(", "),
+                table_name
+            ));
+        }
+
+        let parent = catalog
+            .get_table_by_name(&references.table_name)
+            .ok_or(format!("Unknown table {}", references.table_name))?;
+        let parent = parent.lock().unwrap();
+
+        if columns.len() != references.columns.len() {
+            return Err(format!(
+                "Foreign key on ({}) references {} columns of table {}",
+                columns.join(", "),
+                references.columns.len(),
+                parent.name
+            ));
+        }
+
+        let col_index = |schema: &Schema, table: &str, name: &String| {
+            schema
+                .get_col_index(name)
+                .ok_or(format!("Unknown column {} in table {}", name, table))
+        };
+        let child_columns = columns
+            .iter()
+            .map(|c| col_index(schema, table_name, c))
+            .collect::<Result<Vec<_>, _>>()?;
+        let parent_columns = references
+            .columns
+            .iter()
+            .map(|c| col_index(&parent.schema, &parent.name, c))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (child, parent_col) in child_columns.iter().zip(parent_columns.iter()) {
+            let child = schema.get_column(*child);
+            let parent_col = parent.schema.get_column(*parent_col);
+            if child.col_type() != parent_col.col_type() {
+                return Err(format!(
+                    "Column {} of type {} can't reference column {} of type {}",
+                    child.name(),
+                    child.col_type().to_string(),
+                    parent_col.name(),
+                    parent_col.col_type().to_string()
+                ));
+            }
+        }
+
+        let (parent_oid, parent_name) = (parent.oid, parent.name.clone());
+        drop(parent);
+
+        let has_unique_index = catalog.get_table_indexes(&parent_name).iter().any(|i| {
+            let index_info = i.lock().unwrap();
+            let meta = index_info.index.meta();
+            index_info.is_valid() && meta.is_unique() && meta.is_on_columns(&parent_columns)
+        });
+        if !has_unique_index {
+            return Err(format!(
+                "There is no unique constraint on columns ({}) of table {}",
+                references.columns.join(", "),
+                parent_name
+            ));
+        }
+
+        Ok(Self {
+            name: format!("{}_{}_fkey", table_name, columns.join("_")),
+            columns: child_columns,
+            parent_oid,
+            parent_name,
+            parent_columns,
+            on_delete: ReferentialAction::from_ast(&references.on_delete),
+            on_update: ReferentialAction::from_ast(&references.on_update),
+        })
+    }
+}
+
+impl TableConstraint {
+    /// Collects the table constraints from the column definitions and foreign keys of a `CREATE TABLE` statement.
+    /// Tables referenced by foreign keys are looked up in `catalog`.
+    ///
+    /// # Errors
+    /// Will return `Err` if there are multiple primary keys, if a `CHECK` expression can't be bound to `schema` or if a
+    /// foreign key can't be resolved.
+    pub fn from_column_defs(
+        table_name: &str,
+        defs: &[ColumnDef],
+        foreign_keys: &[ForeignKeyDef],
+        schema: &Schema,
+        catalog: &Catalog,
+    ) -> Result<Vec<Self>, String> {
+        let mut constraints = vec![];
+        for (col_index, def) in defs.iter().enumerate() {
+            for constraint in def.constraints.iter() {
+                match constraint {
+                    ColumnConstraint::PrimaryKey => {
+                        if constraints
+                            .iter()
+                            .any(|c| matches!(c, TableConstraint::PrimaryKey(_)))
+                        {
+                            return Err(format!(
+                                "Multiple primary keys for table {} are not allowed",
+                                table_name
+                            ));
+                        }
+
+                        constraints.push(TableConstraint::PrimaryKey(vec![col_index]));
+                    }
+                    ColumnConstraint::Unique => {
+                        constraints.push(TableConstraint::Unique(vec![col_index]))
+                    }
+                    ColumnConstraint::Check(expression) => {
+                        let expr = bind_expression(expression, schema)
+                            .map_err(|e| format!("Invalid check for column {}: {}", def.name, e))?;
+
+                        constraints.push(TableConstraint::Check {
+                            name: format!("{}_{}_check", table_name, def.name),
+                            expr,
+                        });
+                    }
+                    ColumnConstraint::References(references) => {
+                        constraints.push(TableConstraint::ForeignKey(ForeignKey::resolve(
+                            table_name,
+                            std::slice::from_ref(&def.name),
+                            references,
+                            schema,
+                            catalog,
+                        )?));
+                    }
+                    ColumnConstraint::NotNull | ColumnConstraint::Default(_) => {}
+                }
+            }
+        }
+
+        for foreign_key in foreign_keys.iter() {
+            constraints.push(TableConstraint::ForeignKey(ForeignKey::resolve(
+                table_name,
+                &foreign_key.columns,
+                &foreign_key.references,
+                schema,
+                catalog,
+            )?));
+        }
+
+        Ok(constraints)
+    }
+
+    /// Returns the name of the unique index backing the constraint, `None` for checks and foreign keys.
+    pub fn index_name(&self, table_name: &str, schema: &Schema) -> Option<String> {
+        match self {
+            TableConstraint::PrimaryKey(_) => Some(format!("{}_pkey", table_name)),
+            TableConstraint::Unique(columns) => {
+                let columns = columns
+                    .iter()
+                    .map(|i| schema.get_column(*i).name().to_string())
+                    .collect::<Vec<_>>();
+
+                Some(format!("{}_{}_key", table_name, columns.join("_")))
+            }
+            TableConstraint::Check { .. } | TableConstraint::ForeignKey(_) => None,
+        }
+    }
+
+    /// Returns the indexes of the columns of the backing unique index, `None` for checks and foreign keys.
+    pub fn columns(&self) -> Option<&[usize]> {
+        match self {
+            TableConstraint::PrimaryKey(columns) | TableConstraint::Unique(columns) => {
+                Some(columns)
+            }
+            TableConstraint::Check { .. } | TableConstraint::ForeignKey(_) => None,
+        }
+    }
+}
@@ -1,20 +1,80 @@
+use std::sync::Arc;
+
 use crate::{
+    catalog::constraint::{ForeignKey, TableConstraint},
     index::Index,
-    table::{schema::Schema, TableHeap},
+    table::{
+        schema::Schema,
+        tuple::{Tuple, RID},
+        TableHeap,
+    },
 };
 
-use super::OID;
+use super::{
+    build::{IndexBuild, IndexChange},
+    OID,
+};
 
 pub struct TableInfo {
     pub name: String,
     pub oid: OID,
     pub schema: Schema,
     pub table: TableHeap,
+    /// Primary key, unique, check and foreign key constraints of the table
+    pub constraints: Vec<TableConstraint>,
+}
+
+impl TableInfo {
+    /// Returns the foreign keys of the table, referencing other (parent) tables.
+    pub fn foreign_keys(&self) -> Vec<ForeignKey> {
+        self.constraints
+            .iter()
+            .filter_map(|c| match c {
+                TableConstraint::ForeignKey(foreign_key) => Some(foreign_key.clone()),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 pub struct IndexInfo {
     pub name: String,
     pub oid: OID,
     pub index: Index,
+    /// Maximum size of a key (hash indexes store every key with the size of its actual data)
     pub key_size: usize,
+    /// State of the concurrent build of the index, `None` once the index is valid (complete and usable by queries).
+    /// While the index is built, the changes to the table are recorded in the build instead of being applied.
+    pub build: Option<Arc<IndexBuild>>,
+}
+
+impl IndexInfo {
+    /// Returns `true` if the index is complete, so it can be used by queries.
+    pub fn is_valid(&self) -> bool {
+        self.build.is_none()
+    }
+
+    /// Inserts the entry of `tuple` in the index, or records the insertion if the index is being built.
+    ///
+    /// # Errors
+    /// Will return `Err` if the entry can't be inserted in the index (see `Index::insert`).
+    pub fn insert_entry(&self, tuple: &Tuple, tuple_schema: &Schema, rid: RID) -> Result<(), ()> {
+        match &self.build {
+            Some(build) => {
+                build.record(IndexChange::Insert(tuple.clone(), rid));
+                Ok(())
+            }
+            None => self.index.insert(tuple, tuple_schema, rid),
+        }
+    }
+
+    /// Deletes the entry of `tuple` from the index, or records the deletion if the index is being built.
+    pub fn delete_entry(&self, tuple: &Tuple, tuple_schema: &Schema, rid: &RID) {
+        match &self.build {
+            Some(build) => build.record(IndexChange::Delete(tuple.clone(), rid.clone())),
+            None => {
+                self.index.delete_entry(tuple, tuple_schema, rid);
+            }
+        }
+    }
 }
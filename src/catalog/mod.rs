@@ -6,14 +6,26 @@ use std::{
     },
 };
 
+use build::{IndexBuild, IndexBuildHandle, IndexBuildPhase, IndexChange};
+use check::TableCheckReport;
+use constraint::{ForeignKey, TableConstraint};
 use info::{IndexInfo, TableInfo};
 
 use crate::{
     disk::buffer_pool_manager::BufferPoolManager,
-    index::{Index, IndexMeta},
-    table::{schema::Schema, TableHeap},
+    exec::expression::Expression,
+    index::{Index, IndexMeta, IndexType},
+    parser::ast::CreateTableStatement,
+    table::{
+        schema::{Column, Schema},
+        tuple::{Tuple, RID},
+        TableHeap, TableHeapIterator,
+    },
 };
 
+pub mod build;
+pub mod check;
+pub mod constraint;
 pub mod info;
 #[cfg(test)]
 mod tests;
@@ -26,6 +38,13 @@ type TableNamesMapping = Mutex<HashMap<String, OID>>;
 type IndexesMapping = Mutex<HashMap<OID, Arc<Mutex<IndexInfo>>>>;
 type IndexNamesMapping = Mutex<HashMap<String, HashMap<String, OID>>>;
 
+type ReferencesMapping = Mutex<HashMap<OID, Vec<(OID, ForeignKey)>>>;
+
+/// Number of rows inserted in an index built concurrently each time its lock is taken
+const INDEX_BUILD_BATCH_SIZE: usize = 256;
+/// Number of pending changes under which an index built concurrently applies the last ones with the table locked
+const INDEX_BUILD_CATCH_UP_THRESHOLD: usize = 64;
+
 pub struct Catalog {
     bpm: Arc<BufferPoolManager>,
     next_oid: AtomicU32,
@@ -37,6 +56,8 @@ pub struct Catalog {
     indexes: IndexesMapping,
     /// table name -> index name -> oid
     index_names: IndexNamesMapping,
+    /// parent table oid -> (child table oid, foreign key referencing the parent)
+    references: ReferencesMapping,
 }
 
 impl Catalog {
@@ -48,6 +69,7 @@ impl Catalog {
             table_names: Mutex::new(HashMap::new()),
             indexes: Mutex::new(HashMap::new()),
             index_names: Mutex::new(HashMap::new()),
+            references: Mutex::new(HashMap::new()),
         }
     }
 
@@ -56,17 +78,64 @@ impl Catalog {
     /// # Errors
     /// Will return `Err` if a table with the same name already exists.
     pub fn create_table(&self, name: &str, schema: Schema) -> Result<Arc<Mutex<TableInfo>>, ()> {
+        self.create_table_with_constraints(name, schema, vec![])
+    }
+
+    /// Create a new table with `constraints` in the catalog and return the table info. Primary key and unique
+    /// constraints automatically create a unique index on their columns, foreign keys are registered on their parent
+    /// tables.
+    ///
+    /// # Errors
+    /// Will return `Err` if a table with the same name already exists **or** if an index for a constraint can't be created
+    /// (e.g. two constraints need an index with the same name). Nothing is added to the catalog in that case.
+    pub fn create_table_with_constraints(
+        &self,
+        name: &str,
+        schema: Schema,
+        constraints: Vec<TableConstraint>,
+    ) -> Result<Arc<Mutex<TableInfo>>, ()> {
         if let Some(_) = self.table_names.lock().unwrap().get(name) {
             return Err(());
         }
 
+        // validate the indexes backing the constraints before registering anything
+        let mut index_metas: Vec<(IndexMeta, usize)> = vec![];
+        for constraint in constraints.iter() {
+            let (Some(index_name), Some(key_attrs)) =
+                (constraint.index_name(name, &schema), constraint.columns())
+            else {
+                continue;
+            };
+
+            let key_schema = Schema::new(
+                key_attrs
+                    .iter()
+                    .map(|i| {
+                        let column = schema.get_column(*i);
+                        Column::new_named(column.name().to_string(), column.col_type().clone())
+                    })
+                    .collect(),
+            );
+            let key_size = key_schema.get_tuple_len();
+
+            let index_meta = IndexMeta::new(key_schema, index_name, key_attrs.to_vec()).unique();
+            let is_duplicate = index_metas
+                .iter()
+                .any(|(meta, _)| meta.index_name() == index_meta.index_name());
+            if is_duplicate || !index_meta.is_supported_by(&schema) {
+                return Err(());
+            }
+            index_metas.push((index_meta, key_size));
+        }
+
         let oid = self.next_oid.fetch_add(1, Ordering::SeqCst);
         let heap = TableHeap::new(self.bpm.clone());
         let table_info = TableInfo {
             name: name.to_string(),
             oid,
-            schema,
+            schema: schema.clone(),
             table: heap,
+            constraints: constraints.clone(),
         };
 
         self.table_names
@@ -79,10 +148,70 @@ impl Catalog {
             .unwrap()
             .insert(name.to_string(), HashMap::new());
 
-        let mut tables = self.tables.lock().unwrap();
-        tables.insert(oid, Arc::new(Mutex::new(table_info)));
+        let table_info = Arc::new(Mutex::new(table_info));
+        self.tables.lock().unwrap().insert(oid, table_info.clone());
+
+        // create the indexes backing the constraints, the table is empty so only a concurrent change of the catalog
+        // can make this fail
+        for (index_meta, key_size) in index_metas {
+            if self
+                .create_index_with_meta(index_meta, name, schema.clone(), key_size)
+                .is_err()
+            {
+                self.unregister_table(name, oid);
+                return Err(());
+            }
+        }
+
+        // register foreign keys on the parent tables, once the table can't fail to be created anymore
+        let mut references = self.references.lock().unwrap();
+        for constraint in constraints.iter() {
+            if let TableConstraint::ForeignKey(foreign_key) = constraint {
+                references
+                    .entry(foreign_key.parent_oid)
+                    .or_default()
+                    .push((oid, foreign_key.clone()));
+            }
+        }
+        drop(references);
+
+        Ok(table_info)
+    }
+
+    /// Creates the table defined by a `CREATE TABLE` statement, along with the constraints of its columns and its
+    /// foreign keys.
+    ///
+    /// # Errors
+    /// Will return `Err` with a description of the problem if a column or constraint is invalid, or if the table can't
+    /// be created.
+    pub fn create_table_from_statement(
+        &self,
+        statement: &CreateTableStatement,
+    ) -> Result<Arc<Mutex<TableInfo>>, String> {
+        let name = &statement.table_name;
+        let schema = Schema::from_column_defs(&statement.columns)?;
+        let constraints = TableConstraint::from_column_defs(
+            name,
+            &statement.columns,
+            &statement.foreign_keys,
+            &schema,
+            self,
+        )?;
+
+        self.create_table_with_constraints(name, schema, constraints)
+            .map_err(|_| format!("Couldn't create table {}", name))
+    }
 
-        Ok(tables.get(&oid).unwrap().clone())
+    /// Removes the table with `name` and `oid` from the catalog, along with its indexes.
+    fn unregister_table(&self, name: &str, oid: OID) {
+        if let Some(index_oids) = self.index_names.lock().unwrap().remove(name) {
+            let mut indexes = self.indexes.lock().unwrap();
+            for index_oid in index_oids.values() {
+                indexes.remove(index_oid);
+            }
+        }
+        self.tables.lock().unwrap().remove(&oid);
+        self.table_names.lock().unwrap().remove(name);
     }
 
     /// Get a table by name.
@@ -98,6 +227,16 @@ impl Catalog {
         self.tables.lock().unwrap().get(&oid).cloned()
     }
 
+    /// Get the foreign keys referencing the table with `oid`, along with the oids of the tables they belong to.
+    pub fn get_referencing_foreign_keys(&self, oid: OID) -> Vec<(OID, ForeignKey)> {
+        self.references
+            .lock()
+            .unwrap()
+            .get(&oid)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Create a new index in the catalog and return the index info.
     ///
     /// # Errors
@@ -111,6 +250,29 @@ impl Catalog {
         key_attrs: Vec<usize>,
         key_size: usize,
     ) -> Result<Arc<Mutex<IndexInfo>>, ()> {
+        let index_meta = IndexMeta::new(key_schema, index_name.to_string(), key_attrs);
+        self.create_index_with_meta(index_meta, table_name, table_schema, key_size)
+    }
+
+    /// Creates the index described by `index_meta` (e.g. a unique or a B+ tree index) on the table, filling it with the
+    /// tuples of the table.
+    ///
+    /// # Errors
+    /// Will return `Err` if a table with `table_name` does not exist, if an index with the same name already exists,
+    /// if the index can't be built on the columns (see `IndexMeta::is_supported_by`) **or** if the index is unique and
+    /// the table has rows with duplicate keys.
+    pub fn create_index_with_meta(
+        &self,
+        index_meta: IndexMeta,
+        table_name: &str,
+        table_schema: Schema,
+        key_size: usize,
+    ) -> Result<Arc<Mutex<IndexInfo>>, ()> {
+        let index_name = index_meta.index_name().to_string();
+        if !index_meta.is_supported_by(&table_schema) {
+            return Err(());
+        }
+
         // check if table exists
         let table_oid = if let Some(oid) = self.table_names.lock().unwrap().get(table_name) {
             oid.clone()
@@ -121,20 +283,23 @@ impl Catalog {
         // check if index exists
         let mut index_names = self.index_names.lock().unwrap();
         let names_map = index_names.get_mut(table_name).unwrap();
-        if names_map.contains_key(index_name) {
+        if names_map.contains_key(&index_name) {
             return Err(());
         }
 
         // create index
-        let index_meta = IndexMeta::new(key_schema, index_name.to_string(), key_attrs);
         let index = Index::new(index_meta, self.bpm.clone());
 
         // add all tuples in table to index
         let table = self.get_table_by_oid(table_oid).unwrap();
         let table = table.lock().unwrap();
 
-        let tuples = table.table.iter();
+        let tuples = table.table.iter().filter(|(meta, _, _)| !meta.is_deleted);
         for (_, tuple, rid) in tuples {
+            if index.is_duplicate(&tuple, &table_schema, Some(&rid)) {
+                return Err(());
+            }
+
             index
                 .insert(&tuple, &table_schema, rid)
                 .expect("Can't create index. Too many tuples!");
@@ -143,18 +308,306 @@ impl Catalog {
         // update catalog metadata
         let oid = self.next_oid.fetch_add(1, Ordering::SeqCst);
         let index_info = Arc::new(Mutex::new(IndexInfo {
-            name: index_name.to_string(),
+            name: index_name.clone(),
             oid,
             index,
             key_size,
+            build: None,
         }));
 
         self.indexes.lock().unwrap().insert(oid, index_info.clone());
-        names_map.insert(index_name.to_string(), oid);
+        names_map.insert(index_name, oid);
 
         Ok(index_info)
     }
 
+    /// Creates the index described by `index_meta` on the table without blocking writes to the table while the index is
+    /// filled. The index is registered in the catalog as invalid (ignored by queries, see `IndexInfo::is_valid`), so the
+    /// writers of the table record their changes in it from then on, and built by a background thread:
+    /// 1. the rows of the table are read without locking it, so the scan can see some of the changes made meanwhile
+    /// 2. the rows read are inserted in batches, with only the index locked
+    /// 3. the changes recorded since the index was registered are replayed over the rows read
+    /// 4. the table is locked, the last changes applied and the index marked as valid
+    ///
+    /// The returned handle reports the progress of the build and waits for its end. If the index is unique and the table
+    /// has duplicate keys, the build fails and the index is removed from the catalog.
+    ///
+    /// # Errors
+    /// Will return `Err` if a table with `table_name` does not exist, if an index with the same name already exists **or**
+    /// if the index can't be built on the columns (see `IndexMeta::is_supported_by`).
+    pub fn create_index_concurrently(
+        self: &Arc<Self>,
+        index_meta: IndexMeta,
+        table_name: &str,
+        table_schema: Schema,
+        key_size: usize,
+    ) -> Result<IndexBuildHandle, ()> {
+        let index_name = index_meta.index_name().to_string();
+        if !index_meta.is_supported_by(&table_schema) {
+            return Err(());
+        }
+
+        // check if table exists
+        let table_oid = if let Some(oid) = self.table_names.lock().unwrap().get(table_name) {
+            *oid
+        } else {
+            return Err(());
+        };
+
+        // check if index exists
+        let mut index_names = self.index_names.lock().unwrap();
+        let names_map = index_names.get_mut(table_name).unwrap();
+        if names_map.contains_key(&index_name) {
+            return Err(());
+        }
+
+        let build = Arc::new(IndexBuild::new());
+        let oid = self.next_oid.fetch_add(1, Ordering::SeqCst);
+        let index_info = Arc::new(Mutex::new(IndexInfo {
+            name: index_name.clone(),
+            oid,
+            index: Index::new(index_meta, self.bpm.clone()),
+            key_size,
+            build: Some(build.clone()),
+        }));
+
+        self.indexes.lock().unwrap().insert(oid, index_info.clone());
+        names_map.insert(index_name.clone(), oid);
+        drop(index_names);
+
+        // the writers holding the table lock might have missed the new index, so the scan starts once they are done.
+        // Every change made after that is recorded
+        let rows = self
+            .get_table_by_oid(table_oid)
+            .unwrap()
+            .lock()
+            .unwrap()
+            .table
+            .iter();
+
+        let catalog = self.clone();
+        let table_name = table_name.to_string();
+        let thread_build = build.clone();
+        let thread = std::thread::spawn(move || {
+            let result =
+                catalog.build_index(table_oid, &index_info, &thread_build, rows, &table_schema);
+            if result.is_err() {
+                catalog.indexes.lock().unwrap().remove(&oid);
+                if let Some(names_map) = catalog.index_names.lock().unwrap().get_mut(&table_name) {
+                    names_map.remove(&index_name);
+                }
+                thread_build.set_phase(IndexBuildPhase::Failed);
+                return Err(());
+            }
+
+            thread_build.set_phase(IndexBuildPhase::Done);
+            Ok(index_info)
+        });
+
+        Ok(IndexBuildHandle::new(build, thread))
+    }
+
+    /// Fills the index with the `rows` of the table and the changes recorded by `build`, then marks it as valid.
+    ///
+    /// # Errors
+    /// Will return `Err` if the index is unique and a key is duplicated.
+    fn build_index(
+        &self,
+        table_oid: OID,
+        index_info: &Arc<Mutex<IndexInfo>>,
+        build: &IndexBuild,
+        rows: TableHeapIterator,
+        table_schema: &Schema,
+    ) -> Result<(), ()> {
+        let rows = rows
+            .filter(|(meta, _, _)| !meta.is_deleted)
+            .map(|(_, tuple, rid)| (tuple, rid))
+            .collect::<Vec<_>>();
+        build.set_rows_total(rows.len());
+        build.set_phase(IndexBuildPhase::Building);
+
+        // inserts of keys used by another row when they were applied. The other row may be a version that a later
+        // change replaces, so they are only checked once all the changes are applied
+        let mut deferred = vec![];
+        for batch in rows.chunks(INDEX_BUILD_BATCH_SIZE) {
+            let index_info = index_info.lock().unwrap();
+            for (tuple, rid) in batch {
+                Self::apply_index_change(
+                    &index_info.index,
+                    IndexChange::Insert(tuple.clone(), rid.clone()),
+                    table_schema,
+                    &mut deferred,
+                );
+            }
+            build.add_rows_done(batch.len());
+        }
+
+        build.set_phase(IndexBuildPhase::CatchingUp);
+        while build.progress().changes_pending > INDEX_BUILD_CATCH_UP_THRESHOLD {
+            for change in build.take_pending() {
+                let index_info = index_info.lock().unwrap();
+                Self::apply_index_change(&index_info.index, change, table_schema, &mut deferred);
+            }
+        }
+
+        // no change can be recorded while the table is locked
+        let table = self.get_table_by_oid(table_oid).unwrap();
+        let _table = table.lock().unwrap();
+        let mut index_info = index_info.lock().unwrap();
+        for change in build.take_pending() {
+            Self::apply_index_change(&index_info.index, change, table_schema, &mut deferred);
+        }
+
+        // the index has the entries of the rows of the table now, so the keys still used by another row are duplicated
+        let mut duplicates = vec![];
+        for (tuple, rid) in deferred {
+            Self::apply_index_change(
+                &index_info.index,
+                IndexChange::Insert(tuple, rid),
+                table_schema,
+                &mut duplicates,
+            );
+        }
+        if !duplicates.is_empty() {
+            return Err(());
+        }
+        index_info.build = None;
+
+        Ok(())
+    }
+
+    /// Applies a change of the table to an index being built. A change can be applied to a row that already has it,
+    /// since the scan of the build can see the changes recorded during the build, so entries already in the index
+    /// aren't inserted again. Inserts of keys already used by another row are added to `deferred` instead.
+    fn apply_index_change(
+        index: &Index,
+        change: IndexChange,
+        table_schema: &Schema,
+        deferred: &mut Vec<(Tuple, RID)>,
+    ) {
+        match change {
+            IndexChange::Insert(tuple, rid) => {
+                let is_indexed = index
+                    .keys(&tuple, table_schema)
+                    .into_iter()
+                    .all(|key| index.scan(key).contains(&rid));
+                if !index.has_tuple(&tuple, table_schema) || is_indexed {
+                    return;
+                }
+                if index.is_duplicate(&tuple, table_schema, Some(&rid)) {
+                    deferred.push((tuple, rid));
+                    return;
+                }
+
+                index
+                    .insert(&tuple, table_schema, rid)
+                    .expect("Can't create index. Too many tuples!");
+            }
+            IndexChange::Delete(tuple, rid) => {
+                deferred.retain(|(t, r)| (t, r) != (&tuple, &rid));
+                index.delete_entry(&tuple, table_schema, &rid);
+            }
+        }
+    }
+
+    /// Checks the consistency of the indexes of the table with its rows (see `check::IndexProblem`) and the invariants
+    /// of their data structures, amcheck-style. Indexes being built concurrently are skipped.
+    ///
+    /// With `repair`, every index with a problem is rebuilt from the rows of the table. The pages of the broken data
+    /// structure are left as they are, since they can't be trusted. A unique index whose rows have duplicate keys can't
+    /// be rebuilt and is left as it is. The skip index of the table, if any, is rebuilt too, dropping the values of the
+    /// rows deleted or replaced since it was built.
+    ///
+    /// # Errors
+    /// Will return `Err` if a table with `table_name` does not exist.
+    pub fn check_table(&self, table_name: &str, repair: bool) -> Result<TableCheckReport, ()> {
+        let table = self.get_table_by_name(table_name).ok_or(())?;
+        let table = table.lock().unwrap();
+        let rows = table
+            .table
+            .iter()
+            .filter(|(meta, _, _)| !meta.is_deleted)
+            .map(|(_, tuple, rid)| (tuple, rid))
+            .collect::<Vec<_>>();
+
+        let mut report = TableCheckReport::default();
+        for index_info in self.get_table_indexes(table_name) {
+            let mut index_info = index_info.lock().unwrap();
+            if !index_info.is_valid() {
+                continue;
+            }
+
+            let problems =
+                check::check_index(&index_info.name, &index_info.index, &rows, &table.schema);
+            if problems.is_empty() {
+                continue;
+            }
+            report.problems.extend(problems);
+
+            if repair {
+                if let Ok(index) =
+                    self.rebuild_index(index_info.index.meta().clone(), &rows, &table.schema)
+                {
+                    index_info.index = index;
+                    report.rebuilt_indexes.push(index_info.name.clone());
+                }
+            }
+        }
+        if repair {
+            table.table.rebuild_skip_index();
+        }
+
+        Ok(report)
+    }
+
+    /// Creates a skip index on the `columns` of the table (see `SkipIndex`), replacing the one it had, so sequential
+    /// scans filtering the columns with constants can skip the pages that can't have matching rows.
+    ///
+    /// # Errors
+    /// Will return `Err` if the table doesn't exist or if one of the columns is out of bounds.
+    pub fn create_skip_index(&self, table_name: &str, columns: Vec<usize>) -> Result<(), ()> {
+        let table = self.get_table_by_name(table_name).ok_or(())?;
+        let mut table = table.lock().unwrap();
+        if columns
+            .iter()
+            .any(|&col_index| col_index >= table.schema.get_cols_count())
+        {
+            return Err(());
+        }
+
+        let schema = table.schema.clone();
+        table.table.create_skip_index(schema, columns);
+
+        Ok(())
+    }
+
+    /// Creates an index described by `index_meta` with the `rows` of its table.
+    ///
+    /// # Errors
+    /// Will return `Err` if the index is unique and the rows have duplicate keys.
+    fn rebuild_index(
+        &self,
+        index_meta: IndexMeta,
+        rows: &[(Tuple, RID)],
+        table_schema: &Schema,
+    ) -> Result<Index, ()> {
+        let index = Index::new(index_meta, self.bpm.clone());
+        let mut duplicates = vec![];
+        for (tuple, rid) in rows {
+            Self::apply_index_change(
+                &index,
+                IndexChange::Insert(tuple.clone(), rid.clone()),
+                table_schema,
+                &mut duplicates,
+            );
+        }
+        if !duplicates.is_empty() {
+            return Err(());
+        }
+
+        Ok(index)
+    }
+
     /// Get an index by name.
     pub fn get_index_by_name(
         &self,
@@ -197,6 +650,65 @@ impl Catalog {
         table_name: &str,
         col_index: usize,
     ) -> Option<Arc<Mutex<IndexInfo>>> {
+        self.find_table_index_by_column(table_name, col_index, |_| true)
+    }
+
+    /// Get an index for table with `table_name` that is made for the column with the index `col_index` and keeps its
+    /// keys ordered, so it can be used for range scans. Will return `None` if there is no such index.
+    pub fn get_table_ordered_index_by_column(
+        &self,
+        table_name: &str,
+        col_index: usize,
+    ) -> Option<Arc<Mutex<IndexInfo>>> {
+        self.find_table_index_by_column(table_name, col_index, |meta| {
+            meta.index_type() == IndexType::BPlusTree
+        })
+    }
+
+    /// Get a valid index for table with `table_name` whose key is `expr` (a column or an expression of the columns, like
+    /// `LOWER(name)`) and that has all the rows matching the `conditions` (combined with `AND`), so it can be used for
+    /// looking them up by `expr`. Partial indexes qualify if their predicate is part of the `conditions`. Will return
+    /// `None` if there is no such index.
+    pub fn get_table_index_by_expression(
+        &self,
+        table_name: &str,
+        expr: &Expression,
+        conditions: &[&Expression],
+    ) -> Option<Arc<Mutex<IndexInfo>>> {
+        self.get_table_indexes(table_name)
+            .into_iter()
+            .find(|index| {
+                let index_guard = index.lock().unwrap();
+                index_guard.is_valid() && index_guard.index.meta().matches(expr, conditions)
+            })
+    }
+
+    /// Get a full-text index on a column of a table.
+    pub fn get_table_full_text_index(
+        &self,
+        table_name: &str,
+        col_index: usize,
+    ) -> Option<Arc<Mutex<IndexInfo>>> {
+        self.get_table_indexes(table_name)
+            .into_iter()
+            .find(|index| {
+                let index_guard = index.lock().unwrap();
+                let meta = index_guard.index.meta();
+                index_guard.is_valid()
+                    && meta.index_type() == IndexType::FullText
+                    && meta.key_attrs() == [col_index]
+            })
+    }
+
+    fn find_table_index_by_column<F>(
+        &self,
+        table_name: &str,
+        col_index: usize,
+        predicate: F,
+    ) -> Option<Arc<Mutex<IndexInfo>>>
+    where
+        F: Fn(&IndexMeta) -> bool,
+    {
         // search for index
         let indexes = self.get_table_indexes(table_name);
         if indexes.is_empty() {
@@ -205,9 +717,12 @@ impl Catalog {
 
         for index in indexes.into_iter() {
             let index_guard = index.lock().unwrap();
+            if !index_guard.is_valid() {
+                continue;
+            }
 
             let meta = index_guard.index.meta();
-            if meta.key_attrs().len() == 1 && meta.key_attrs()[0] == col_index {
+            if meta.is_on_columns(&[col_index]) && predicate(meta) {
                 drop(index_guard);
                 return Some(index);
             }
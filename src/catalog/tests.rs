@@ -1,17 +1,43 @@
-use std::{collections::HashMap, env::temp_dir, fs::remove_file, sync::Arc};
+use std::{
+    collections::HashMap,
+    env::temp_dir,
+    fs::remove_file,
+    sync::{Arc, Mutex},
+};
 
 use crate::{
     disk::buffer_pool_manager::BufferPoolManager,
+    exec::{
+        executor::{
+            insert::InsertExecutor,
+            util::{
+                delete_from_table_and_indexes, insert_tuple_in_table_and_indexes,
+                update_tuple_in_table_and_indexes,
+            },
+            values::ValuesExecutor,
+            Execute, Executor, ExecutorContext,
+        },
+        plan::{insert::InsertPlanNode, values::ValuesPlanNode, PlanNode},
+    },
+    index::{IndexMeta, IndexType},
+    parser::{parse_sql, SqlStatement},
     table::{
         self,
         page::TupleMeta,
         schema::{ColumnType, Schema},
+        skip_index::{ColumnPredicate, ComparisonType},
         tuple::Tuple,
     },
     test_utils,
 };
 
-use super::Catalog;
+use super::{
+    build::IndexBuildPhase,
+    check::IndexProblem,
+    constraint::{ReferentialAction, TableConstraint},
+    info::TableInfo,
+    Catalog,
+};
 
 #[test]
 fn create_table_and_use() {
@@ -162,3 +188,712 @@ pub fn get_index_by_column() {
     // cleanup
     remove_file(db_path).expect("Couldn't remove test DB file");
 }
+
+#[test]
+fn create_table_with_constraints() {
+    // init
+    let db_path = temp_dir().join("catalog_create_table_with_constraints.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = BufferPoolManager::new(db_file_path.clone(), 2, 2);
+    let catalog = Catalog::new(Arc::new(bpm));
+
+    // test
+    let schema = Schema::new(vec![
+        table::schema::Column::new_named("id".to_string(), ColumnType::Integer).not_null(),
+        table::schema::Column::new_named("email".to_string(), ColumnType::Varchar(50)),
+    ]);
+    let constraints = vec![
+        TableConstraint::PrimaryKey(vec![0]),
+        TableConstraint::Unique(vec![1]),
+    ];
+    let table_info = catalog
+        .create_table_with_constraints("users", schema, constraints)
+        .unwrap();
+    assert_eq!(table_info.lock().unwrap().constraints.len(), 2);
+
+    // assert
+    for name in ["users_pkey", "users_email_key"] {
+        let index_info = catalog.get_index_by_name(name, "users").unwrap();
+        assert!(index_info.lock().unwrap().index.meta().is_unique());
+    }
+    assert_eq!(catalog.get_table_indexes("users").len(), 2);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn create_table_from_statement() {
+    // init
+    let db_path = temp_dir().join("catalog_create_table_from_statement.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path.clone(), 10, 2));
+    let catalog = Arc::new(Catalog::new(bpm.clone()));
+    let context = ExecutorContext::new(catalog.clone(), bpm);
+    let create_table = |sql| match parse_sql(sql).unwrap() {
+        SqlStatement::CreateTable(statement) => catalog.create_table_from_statement(&statement),
+        _ => panic!("Expected a CREATE TABLE statement"),
+    };
+
+    // test
+    let table_info = create_table(
+        "CREATE TABLE items (id INTEGER PRIMARY KEY, stock INTEGER CHECK (stock >= 0))",
+    )
+    .unwrap();
+    let (table_oid, schema) = {
+        let table_info = table_info.lock().unwrap();
+        (table_info.oid, table_info.schema.clone())
+    };
+    assert_eq!(table_info.lock().unwrap().constraints.len(), 2);
+    assert!(catalog.get_index_by_name("items_pkey", "items").is_some());
+
+    let insert = |rows: Vec<(i32, i32)>| {
+        let plan = ValuesPlanNode {
+            output_schema: schema.clone(),
+            values: rows
+                .into_iter()
+                .map(|(id, stock)| vec![test_utils::const_int(id), test_utils::const_int(stock)])
+                .collect(),
+        };
+        let insert_plan = InsertPlanNode::new(
+            table_oid,
+            "items".to_string(),
+            PlanNode::Values(plan.clone()),
+        );
+        let values = ValuesExecutor { plan, cursor: 0 };
+        let mut executor =
+            InsertExecutor::new(context.clone(), insert_plan, Executor::Values(values));
+
+        executor.init();
+        match executor.next() {
+            Some(_) => Ok(()),
+            None => Err(executor.error().unwrap().to_string()),
+        }
+    };
+    assert_eq!(insert(vec![(1, 10), (2, 0)]), Ok(()));
+    assert_eq!(
+        insert(vec![(3, -1)]),
+        Err(
+            "Can't insert tuple: CHECK constraint \"items_stock_check\" violated by row { 3 , -1 }"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        insert(vec![(1, 5)]),
+        Err("Can't insert tuple: UNIQUE constraint \"items_pkey\" violated: key { 1 } already exists in table \"items\"".to_string())
+    );
+
+    // invalid constraints don't create the table
+    assert_eq!(
+        create_table("CREATE TABLE orders (item_id INTEGER REFERENCES missing (id))").err(),
+        Some("Unknown table missing".to_string())
+    );
+    assert!(catalog.get_table_by_name("orders").is_none());
+
+    // constraints whose indexes can't be created don't leave a half created table
+    assert_eq!(
+        create_table(
+            "CREATE TABLE tags (item_id INTEGER REFERENCES items (id), name VARCHAR UNIQUE UNIQUE)"
+        )
+        .err(),
+        Some("Couldn't create table tags".to_string())
+    );
+    assert!(catalog.get_table_by_name("tags").is_none());
+    assert!(catalog.get_table_indexes("tags").is_empty());
+    assert!(catalog.get_referencing_foreign_keys(table_oid).is_empty());
+    assert!(create_table(
+        "CREATE TABLE tags (item_id INTEGER REFERENCES items (id), name VARCHAR UNIQUE)"
+    )
+    .is_ok());
+    assert_eq!(catalog.get_referencing_foreign_keys(table_oid).len(), 1);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn resolve_foreign_keys() {
+    // init
+    let db_path = temp_dir().join("catalog_resolve_foreign_keys.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path.clone(), 10, 2));
+    let catalog = Catalog::new(bpm);
+    let create_table = |sql| match parse_sql(sql).unwrap() {
+        SqlStatement::CreateTable(statement) => catalog.create_table_from_statement(&statement),
+        _ => panic!("Expected a CREATE TABLE statement"),
+    };
+    let foreign_keys = |table_info: &Arc<Mutex<TableInfo>>| {
+        table_info
+            .lock()
+            .unwrap()
+            .constraints
+            .iter()
+            .filter_map(|c| match c {
+                TableConstraint::ForeignKey(fk) => Some(fk.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // test
+    let users = create_table(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, email VARCHAR UNIQUE, age INTEGER)",
+    )
+    .unwrap();
+    let users_oid = users.lock().unwrap().oid;
+
+    // column constraint
+    let orders = create_table(
+        "CREATE TABLE orders (id INTEGER, user_id INTEGER REFERENCES users (id) ON DELETE CASCADE)",
+    )
+    .unwrap();
+    let orders_oid = orders.lock().unwrap().oid;
+    let fks = foreign_keys(&orders);
+    assert_eq!(fks.len(), 1);
+    assert_eq!(fks[0].name, "orders_user_id_fkey");
+    assert_eq!(fks[0].columns, vec![1]);
+    assert_eq!(fks[0].parent_oid, users_oid);
+    assert_eq!(fks[0].parent_name, "users");
+    assert_eq!(fks[0].parent_columns, vec![0]);
+    assert_eq!(fks[0].on_delete, ReferentialAction::Cascade);
+    assert_eq!(fks[0].on_update, ReferentialAction::Restrict);
+
+    // table constraint referencing a unique key
+    let reviews = create_table(
+        "CREATE TABLE reviews (stars INTEGER, author VARCHAR, FOREIGN KEY (author) REFERENCES users (email))",
+    )
+    .unwrap();
+    let reviews_oid = reviews.lock().unwrap().oid;
+    let fks = foreign_keys(&reviews);
+    assert_eq!(fks.len(), 1);
+    assert_eq!(fks[0].name, "reviews_author_fkey");
+    assert_eq!(fks[0].columns, vec![1]);
+    assert_eq!(fks[0].parent_columns, vec![1]);
+
+    let references = catalog.get_referencing_foreign_keys(users_oid);
+    assert_eq!(
+        references
+            .iter()
+            .map(|(oid, fk)| (*oid, fk.name.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (orders_oid, "orders_user_id_fkey"),
+            (reviews_oid, "reviews_author_fkey")
+        ]
+    );
+
+    // invalid foreign keys
+    for (sql, error) in [
+        (
+            "CREATE TABLE t1 (a INTEGER, FOREIGN KEY (b) REFERENCES users (id))",
+            "Unknown column b in table t1",
+        ),
+        (
+            "CREATE TABLE t2 (a INTEGER REFERENCES users (missing))",
+            "Unknown column missing in table users",
+        ),
+        (
+            "CREATE TABLE t3 (a VARCHAR REFERENCES users (id))",
+            "Column a of type VARCHAR(255) can't reference column id of type INTEGER",
+        ),
+        (
+            "CREATE TABLE t4 (a INTEGER REFERENCES users (age))",
+            "There is no unique constraint on columns (age) of table users",
+        ),
+        (
+            "CREATE TABLE t5 (a INTEGER, b INTEGER, FOREIGN KEY (a, b) REFERENCES users (id))",
+            "Foreign key on (a, b) references 1 columns of table users",
+        ),
+        (
+            "CREATE TABLE t6 (id INTEGER PRIMARY KEY, parent INTEGER REFERENCES t6 (id))",
+            "Foreign key on (parent) can't reference its own table t6",
+        ),
+    ] {
+        assert_eq!(create_table(sql).err(), Some(error.to_string()));
+    }
+    for name in ["t1", "t2", "t3", "t4", "t5", "t6"] {
+        assert!(catalog.get_table_by_name(name).is_none());
+    }
+    assert_eq!(catalog.get_referencing_foreign_keys(users_oid).len(), 2);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn create_index_concurrently_with_writes() {
+    // init
+    let db_path = temp_dir().join("catalog_create_index_concurrently_with_writes.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = BufferPoolManager::new(db_file_path.clone(), 50, 2);
+    let catalog = Arc::new(Catalog::new(Arc::new(bpm)));
+
+    let table_schema = Schema::with_types(vec![ColumnType::Integer, ColumnType::Integer]);
+    let table_info = catalog.create_table("items", table_schema.clone()).unwrap();
+    let mut rids = vec![];
+    {
+        let table_guard = table_info.lock().unwrap();
+        for i in 0..1000 {
+            let meta = TupleMeta {
+                ts: 0,
+                is_deleted: false,
+            };
+            let values = vec![test_utils::int_value(i), test_utils::int_value(i)];
+            let tuple = Tuple::new(values, &table_schema);
+            rids.push(table_guard.table.insert_tuple(meta, tuple).unwrap());
+        }
+    }
+
+    // test
+    let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let index_meta = IndexMeta::new(key_schema.clone(), "items_id".to_string(), vec![0]);
+    let handle = catalog
+        .create_index_concurrently(index_meta, "items", table_schema.clone(), 4)
+        .unwrap();
+
+    // the rows are inserted without the table lock, but the build can't finish while the table is locked, so the writes
+    // below are recorded
+    {
+        let mut table_guard = table_info.lock().unwrap();
+        while handle.progress().phase != IndexBuildPhase::CatchingUp {}
+        assert_eq!(handle.progress().rows_done, 1000);
+
+        let index_infos = catalog.get_table_indexes("items");
+        let index_guards = index_infos
+            .iter()
+            .map(|i| i.lock().unwrap())
+            .collect::<Vec<_>>();
+        assert!(!index_guards[0].is_valid());
+
+        for i in 1000..1100 {
+            let values = vec![test_utils::int_value(i), test_utils::int_value(i)];
+            let tuple = Tuple::new(values, &table_schema);
+            insert_tuple_in_table_and_indexes(&mut table_guard, &index_guards, tuple).unwrap();
+        }
+        for rid in rids[..50].iter() {
+            delete_from_table_and_indexes(&table_guard, &index_guards, rid);
+        }
+        let old_tuple = table_guard.table.get_tuple(&rids[100]).unwrap().1;
+        let values = vec![test_utils::int_value(5000), test_utils::int_value(100)];
+        let new_tuple = Tuple::new(values, &table_schema);
+        update_tuple_in_table_and_indexes(
+            &mut table_guard,
+            &index_guards,
+            &rids[100],
+            &old_tuple,
+            new_tuple,
+        )
+        .unwrap();
+
+        // invalid indexes aren't used by queries
+        drop(index_guards);
+        assert!(catalog.get_table_index_by_column("items", 0).is_none());
+    }
+
+    let index_info = handle.wait().unwrap();
+
+    // assert
+    let index_guard = index_info.lock().unwrap();
+    assert!(index_guard.is_valid());
+
+    let table_guard = table_info.lock().unwrap();
+    let mut rows = 0;
+    for (meta, tuple, rid) in table_guard.table.iter() {
+        if meta.is_deleted {
+            continue;
+        }
+        let key = Tuple::new(vec![tuple.get_value(&table_schema, 0)], &key_schema);
+        assert_eq!(index_guard.index.scan(key), vec![rid]);
+        rows += 1;
+    }
+    assert_eq!(rows, 1050);
+    for i in [0, 49, 100] {
+        let key = Tuple::new(vec![test_utils::int_value(i)], &key_schema);
+        assert!(index_guard.index.scan(key).is_empty());
+    }
+    drop(index_guard);
+    drop(table_guard);
+
+    let result = catalog.get_table_index_by_column("items", 0).unwrap();
+    assert!(Arc::ptr_eq(&result, &index_info));
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn create_index_concurrently_progress() {
+    // init
+    let db_path = temp_dir().join("catalog_create_index_concurrently_progress.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = BufferPoolManager::new(db_file_path.clone(), 50, 2);
+    let catalog = Arc::new(Catalog::new(Arc::new(bpm)));
+
+    let table_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let table_info = catalog.create_table("items", table_schema.clone()).unwrap();
+    {
+        let table_guard = table_info.lock().unwrap();
+        for i in 0..600 {
+            let meta = TupleMeta {
+                ts: 0,
+                is_deleted: false,
+            };
+            let tuple = Tuple::new(vec![test_utils::int_value(i)], &table_schema);
+            table_guard.table.insert_tuple(meta, tuple).unwrap();
+        }
+    }
+
+    // test
+    let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let index_meta = IndexMeta::new(key_schema.clone(), "items_id".to_string(), vec![0]);
+    let handle = catalog
+        .create_index_concurrently(index_meta, "items", table_schema.clone(), 4)
+        .unwrap();
+
+    // the name is taken while the index is built
+    let index_meta = IndexMeta::new(key_schema, "items_id".to_string(), vec![0]);
+    assert!(catalog
+        .create_index_concurrently(index_meta, "items", table_schema, 4)
+        .is_err());
+
+    let mut last_rows_done = 0;
+    while !handle.is_finished() {
+        let progress = handle.progress();
+        assert!(progress.rows_done >= last_rows_done);
+        assert!(progress.rows_done <= progress.rows_total);
+        last_rows_done = progress.rows_done;
+    }
+
+    // assert
+    let progress = handle.progress();
+    assert_eq!(progress.phase, IndexBuildPhase::Done);
+    assert_eq!(progress.rows_total, 600);
+    assert_eq!(progress.rows_done, 600);
+    assert_eq!(progress.changes_pending, 0);
+    assert!(handle.wait().is_ok());
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn create_unique_index_concurrently_with_moved_keys() {
+    // init
+    let db_path = temp_dir().join("catalog_create_unique_index_concurrently_with_moved_keys.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = BufferPoolManager::new(db_file_path.clone(), 50, 2);
+    let catalog = Arc::new(Catalog::new(Arc::new(bpm)));
+
+    let table_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let table_info = catalog.create_table("items", table_schema.clone()).unwrap();
+    let mut rids = vec![];
+    {
+        let table_guard = table_info.lock().unwrap();
+        for i in 0..2000 {
+            let meta = TupleMeta {
+                ts: 0,
+                is_deleted: false,
+            };
+            let tuple = Tuple::new(vec![test_utils::int_value(i)], &table_schema);
+            rids.push(table_guard.table.insert_tuple(meta, tuple).unwrap());
+        }
+    }
+
+    // test
+    let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let index_meta = IndexMeta::new(key_schema.clone(), "items_id".to_string(), vec![0]).unique();
+    let handle = catalog
+        .create_index_concurrently(index_meta, "items", table_schema.clone(), 4)
+        .unwrap();
+
+    // while the table is scanned, the keys are moved to other rows, so the scan can read a key twice (before and after
+    // it is moved) without the table having duplicates
+    for (i, rid) in rids.iter().enumerate() {
+        let mut table_guard = table_info.lock().unwrap();
+        let index_infos = catalog.get_table_indexes("items");
+        let index_guards = index_infos
+            .iter()
+            .map(|i| i.lock().unwrap())
+            .collect::<Vec<_>>();
+
+        let old_tuple = Tuple::new(vec![test_utils::int_value(i as i32)], &table_schema);
+        let new_tuple = Tuple::new(vec![test_utils::int_value(i as i32 + 2000)], &table_schema);
+        update_tuple_in_table_and_indexes(
+            &mut table_guard,
+            &index_guards,
+            rid,
+            &old_tuple,
+            new_tuple,
+        )
+        .unwrap();
+        let tuple = Tuple::new(vec![test_utils::int_value(i as i32)], &table_schema);
+        insert_tuple_in_table_and_indexes(&mut table_guard, &index_guards, tuple).unwrap();
+    }
+
+    // assert
+    let index_info = handle.wait().unwrap();
+    let index_guard = index_info.lock().unwrap();
+    let table_guard = table_info.lock().unwrap();
+    let mut rows = 0;
+    for (meta, tuple, rid) in table_guard.table.iter() {
+        if meta.is_deleted {
+            continue;
+        }
+        let key = Tuple::new(vec![tuple.get_value(&table_schema, 0)], &key_schema);
+        assert_eq!(index_guard.index.scan(key), vec![rid]);
+        rows += 1;
+    }
+    assert_eq!(rows, 4000);
+    assert_eq!(index_guard.index.entries().len(), 4000);
+    drop(index_guard);
+    drop(table_guard);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn create_unique_index_concurrently_with_duplicates() {
+    // init
+    let db_path = temp_dir().join("catalog_create_unique_index_concurrently_with_duplicates.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = BufferPoolManager::new(db_file_path.clone(), 50, 2);
+    let catalog = Arc::new(Catalog::new(Arc::new(bpm)));
+
+    let table_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let table_info = catalog.create_table("items", table_schema.clone()).unwrap();
+    {
+        let table_guard = table_info.lock().unwrap();
+        for i in [1, 2, 1] {
+            let meta = TupleMeta {
+                ts: 0,
+                is_deleted: false,
+            };
+            let tuple = Tuple::new(vec![test_utils::int_value(i)], &table_schema);
+            table_guard.table.insert_tuple(meta, tuple).unwrap();
+        }
+    }
+
+    // test
+    let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let index_meta = IndexMeta::new(key_schema, "items_id".to_string(), vec![0]).unique();
+    let handle = catalog
+        .create_index_concurrently(index_meta, "items", table_schema.clone(), 4)
+        .unwrap();
+
+    // the scan reads both rows, but the duplicate is only reported once the changes made during the build are applied
+    while !handle.is_finished() {}
+
+    // assert
+    assert_eq!(handle.progress().phase, IndexBuildPhase::Failed);
+    assert!(handle.wait().is_err());
+    assert!(catalog.get_index_by_name("items_id", "items").is_none());
+    assert!(catalog.get_table_indexes("items").is_empty());
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn create_unique_index_with_deleted_rows() {
+    // init
+    let db_path = temp_dir().join("catalog_create_unique_index_with_deleted_rows.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = BufferPoolManager::new(db_file_path.clone(), 50, 2);
+    let catalog = Catalog::new(Arc::new(bpm));
+
+    let table_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let table_info = catalog.create_table("items", table_schema.clone()).unwrap();
+    let tuple = |value| Tuple::new(vec![test_utils::int_value(value)], &table_schema);
+    {
+        let mut table_guard = table_info.lock().unwrap();
+        let rid = insert_tuple_in_table_and_indexes(&mut table_guard, &vec![], tuple(1)).unwrap();
+        insert_tuple_in_table_and_indexes(&mut table_guard, &vec![], tuple(2)).unwrap();
+        delete_from_table_and_indexes(&table_guard, &vec![], &rid);
+        insert_tuple_in_table_and_indexes(&mut table_guard, &vec![], tuple(1)).unwrap();
+    }
+
+    // test
+    // the deleted row has the same key as a live one, but isn't indexed
+    let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let index_meta = IndexMeta::new(key_schema, "items_id".to_string(), vec![0]).unique();
+    catalog
+        .create_index_with_meta(index_meta, "items", table_schema.clone(), 4)
+        .unwrap();
+    assert!(catalog.check_table("items", false).unwrap().is_ok());
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn check_and_repair_table() {
+    // init
+    let db_path = temp_dir().join("catalog_check_and_repair_table.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = BufferPoolManager::new(db_file_path.clone(), 50, 2);
+    let catalog = Catalog::new(Arc::new(bpm));
+
+    let table_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let table_info = catalog.create_table("items", table_schema.clone()).unwrap();
+    catalog
+        .create_index(
+            "items_hash",
+            "items",
+            table_schema.clone(),
+            key_schema.clone(),
+            vec![0],
+            4,
+        )
+        .unwrap();
+    let index_meta = IndexMeta::new(key_schema, "items_tree".to_string(), vec![0])
+        .with_type(IndexType::BPlusTree);
+    catalog
+        .create_index_with_meta(index_meta, "items", table_schema.clone(), 4)
+        .unwrap();
+
+    let tuple = |value| Tuple::new(vec![test_utils::int_value(value)], &table_schema);
+    let mut rids = vec![];
+    {
+        let mut table_guard = table_info.lock().unwrap();
+        let index_infos = catalog.get_table_indexes("items");
+        let index_guards = index_infos
+            .iter()
+            .map(|i| i.lock().unwrap())
+            .collect::<Vec<_>>();
+        for i in 0..100 {
+            rids.push(
+                insert_tuple_in_table_and_indexes(&mut table_guard, &index_guards, tuple(i))
+                    .unwrap(),
+            );
+        }
+    }
+
+    // test
+    assert!(catalog.check_table("items", false).unwrap().is_ok());
+    assert!(catalog.check_table("missing", false).is_err());
+
+    // corrupt the table behind the back of the indexes
+    let meta = TupleMeta {
+        ts: 0,
+        is_deleted: false,
+    };
+    let unindexed_rid = {
+        let table_guard = table_info.lock().unwrap();
+        table_guard.table.update_tuple_meta(
+            TupleMeta {
+                ts: 0,
+                is_deleted: true,
+            },
+            &rids[1],
+        );
+        table_guard.table.insert_tuple(meta, tuple(100)).unwrap()
+    };
+    let hash_index = catalog.get_index_by_name("items_hash", "items").unwrap();
+    hash_index
+        .lock()
+        .unwrap()
+        .index
+        .insert(&tuple(42), &table_schema, rids[2].clone())
+        .unwrap();
+
+    let report = catalog.check_table("items", false).unwrap();
+    let mut expected = vec![
+        IndexProblem::MissingEntry {
+            index_name: "items_hash".to_string(),
+            rid: unindexed_rid.clone(),
+        },
+        IndexProblem::DuplicateEntry {
+            index_name: "items_hash".to_string(),
+            rid: rids[2].clone(),
+        },
+        IndexProblem::WrongKey {
+            index_name: "items_hash".to_string(),
+            rid: rids[2].clone(),
+        },
+        IndexProblem::DanglingEntry {
+            index_name: "items_hash".to_string(),
+            rid: rids[1].clone(),
+        },
+        IndexProblem::MissingEntry {
+            index_name: "items_tree".to_string(),
+            rid: unindexed_rid,
+        },
+        IndexProblem::DanglingEntry {
+            index_name: "items_tree".to_string(),
+            rid: rids[1].clone(),
+        },
+    ];
+    let sort_key = |p: &IndexProblem| format!("{p:?}");
+    let mut problems = report.problems.clone();
+    problems.sort_by_key(sort_key);
+    expected.sort_by_key(sort_key);
+    assert_eq!(problems, expected);
+    assert_eq!(report.broken_indexes(), vec!["items_hash", "items_tree"]);
+    assert!(report.rebuilt_indexes.is_empty());
+
+    // repair
+    let report = catalog.check_table("items", true).unwrap();
+    assert_eq!(report.problems.len(), 6);
+    let mut rebuilt_indexes = report.rebuilt_indexes;
+    rebuilt_indexes.sort();
+    assert_eq!(rebuilt_indexes, vec!["items_hash", "items_tree"]);
+    assert!(catalog.check_table("items", false).unwrap().is_ok());
+
+    let hash_index = catalog.get_index_by_name("items_hash", "items").unwrap();
+    let hash_index = &hash_index.lock().unwrap().index;
+    assert_eq!(hash_index.scan(tuple(1)), vec![]);
+    assert_eq!(hash_index.scan(tuple(2)), vec![rids[2].clone()]);
+    assert_eq!(hash_index.scan(tuple(42)), vec![rids[42].clone()]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn repair_rebuilds_skip_index() {
+    // init
+    let db_path = temp_dir().join("catalog_repair_rebuilds_skip_index.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = BufferPoolManager::new(db_file_path.clone(), 50, 2);
+    let catalog = Catalog::new(Arc::new(bpm));
+
+    let table_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let table_info = catalog.create_table("items", table_schema.clone()).unwrap();
+    catalog.create_skip_index("items", vec![0]).unwrap();
+
+    let meta = |is_deleted| TupleMeta { ts: 0, is_deleted };
+    let table_guard = table_info.lock().unwrap();
+    let rids = (0..10)
+        .map(|i| {
+            let tuple = Tuple::new(vec![test_utils::int_value(i)], &table_schema);
+            table_guard.table.insert_tuple(meta(false), tuple).unwrap()
+        })
+        .collect::<Vec<_>>();
+    table_guard.table.update_tuple_meta(meta(true), &rids[9]);
+    drop(table_guard);
+
+    // test
+    let rows_read = || {
+        let predicates = vec![ColumnPredicate {
+            col_index: 0,
+            typ: ComparisonType::GT,
+            value: test_utils::int_value(8),
+        }];
+        table_info
+            .lock()
+            .unwrap()
+            .table
+            .iter_skipping(&predicates)
+            .count()
+    };
+
+    // the deleted row is still summarized, so its page is read
+    assert_eq!(rows_read(), 10);
+    assert!(catalog.check_table("items", true).unwrap().is_ok());
+    assert_eq!(rows_read(), 0);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
@@ -2,3 +2,7 @@ pub const DB_PAGE_SIZE: u32 = 4096;
 pub const DB_DEFAULT_PAGES_AMOUNT: usize = 16;
 pub const DB_EHT_HEADER_MAX_DEPTH: usize = 9;
 pub const DB_EHT_DIRECTORY_MAX_DEPTH: usize = 9;
+/// Bytes of rows a hash join keeps in its in-memory hash table before spilling partitions to disk.
+pub const DB_HASH_JOIN_MEMORY_BUDGET: usize = 1024 * 1024;
+/// Bytes of rows a sort keeps in memory before spilling sorted runs to disk.
+pub const DB_SORT_MEMORY_BUDGET: usize = 1024 * 1024;
@@ -6,7 +6,8 @@ use std::{
     },
 };
 
-use page::{Page, PageReadGuard, PageWriteGuard};
+use page::Page;
+pub use page::{PageReadGuard, PageWriteGuard};
 
 use crate::disk::disk_scheduler::{DiskRequest, DiskRequestType, DiskResponse};
 
@@ -26,6 +27,8 @@ pub trait DiskRead {
 
 pub trait DiskWrite {
     fn write(&mut self, data: Vec<u8>);
+    /// Returns the data of the page so it can be modified in place. The page is marked as dirty.
+    fn data_mut(&mut self) -> &mut [u8];
 }
 
 pub struct Frame {
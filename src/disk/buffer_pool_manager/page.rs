@@ -88,4 +88,9 @@ impl<'a> DiskWrite for PageWriteGuard<'a> {
         self.page.as_mut().unwrap().data = data;
         self.frame.is_dirty.store(true, Ordering::SeqCst);
     }
+
+    fn data_mut(&mut self) -> &mut [u8] {
+        self.frame.is_dirty.store(true, Ordering::SeqCst);
+        &mut self.page.as_mut().unwrap().data
+    }
 }
@@ -10,7 +10,8 @@ use crate::{
 };
 
 use super::{
-    util::{delete_from_table_and_indexes, int_tuple},
+    referential::{apply_referential_actions, check_restricted_references},
+    util::{delete_from_table_and_indexes, get_live_tuple, int_tuple},
     Execute, Executor, ExecutorContext,
 };
 
@@ -20,25 +21,60 @@ pub struct DeleteExecutor {
     pub child: Box<Executor>,
     /// Whether the executor has already deleted the tuples or not.
     deleted: bool,
+    /// Needed for running the referential actions of foreign keys on other tables and undoing the deletes.
+    context: ExecutorContext,
+    /// Length of the undo log when the executor started.
+    savepoint: usize,
+    /// Why the delete failed, if it did.
+    error: Option<String>,
 }
 
 impl DeleteExecutor {
     pub fn new(context: ExecutorContext, plan: DeletePlanNode, child: Executor) -> Self {
         Self {
             plan,
-            catalog: context.catalog,
+            catalog: context.catalog.clone(),
             child: Box::new(child),
             deleted: false,
+            context,
+            savepoint: 0,
+            error: None,
         }
     }
+
+    /// Returns why the delete failed, `None` if it didn't.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Undoes the changes made by the executor (and the referential actions it ran) and records the error.
+    fn fail(&mut self, error: String) -> Option<(Tuple, RID)> {
+        self.context
+            .undo_log
+            .lock()
+            .unwrap()
+            .rollback_to(&self.catalog, self.savepoint);
+        self.error = Some(format!("Can't delete tuple: {}", error));
+        self.deleted = true;
+        None
+    }
 }
 
 impl Execute for DeleteExecutor {
     fn init(&mut self) {
         self.child.init();
         self.deleted = false;
+        self.savepoint = self.context.undo_log.lock().unwrap().savepoint();
+        self.error = None;
     }
 
+    /// Deletes the tuples returned by the child and returns the number of deleted tuples. Rows of other tables
+    /// referencing the deleted tuples are handled according to the `ON DELETE` actions of their foreign keys. Tuples
+    /// deleted after the child returned them are skipped.
+    ///
+    /// # Errors
+    /// Returns `None` without deleting anything if a deleted tuple is still referenced through a foreign key with the
+    /// `RESTRICT` action. The tuples already deleted are restored and the violation is returned by `error`.
     fn next(&mut self) -> Option<(Tuple, RID)> {
         if self.deleted {
             return None;
@@ -53,13 +89,55 @@ impl Execute for DeleteExecutor {
                 .catalog
                 .get_table_with_indexes(self.plan.table_oid, &self.plan.table_name);
 
-            let table_info = table_info.lock().unwrap();
-            let index_infos = index_infos
-                .iter()
-                .map(|i| i.lock().unwrap())
-                .collect::<Vec<_>>();
+            let (schema, mut current_tuple) = {
+                let table_info = table_info.lock().unwrap();
+                (table_info.schema.clone(), get_live_tuple(&table_info, &rid))
+            };
+
+            // references are checked without holding any locks, since they need to scan other tables. The tuple is
+            // read again once the table is locked, and checked again if it was changed in the meantime
+            let tuple = loop {
+                // the tuple was deleted after the child returned it
+                let Some(tuple) = current_tuple else {
+                    break None;
+                };
+                if let Err(e) = check_restricted_references(
+                    &self.context,
+                    self.plan.table_oid,
+                    &schema,
+                    &tuple,
+                    None,
+                ) {
+                    return self.fail(e);
+                }
+
+                let table_info = table_info.lock().unwrap();
+                current_tuple = get_live_tuple(&table_info, &rid);
+                if current_tuple.as_ref() != Some(&tuple) {
+                    continue;
+                }
+                let index_infos = index_infos
+                    .iter()
+                    .map(|i| i.lock().unwrap())
+                    .collect::<Vec<_>>();
+
+                delete_from_table_and_indexes(&table_info, &index_infos, &rid);
+                break Some(tuple);
+            };
+            let Some(tuple) = tuple else {
+                continue;
+            };
+            self.context.undo_log.lock().unwrap().record_delete(
+                self.plan.table_oid,
+                rid,
+                tuple.clone(),
+            );
 
-            delete_from_table_and_indexes(&table_info, &index_infos, &rid);
+            if let Err(e) =
+                apply_referential_actions(&self.context, self.plan.table_oid, &schema, &tuple, None)
+            {
+                return self.fail(e);
+            }
 
             deleted_tuples += 1;
         }
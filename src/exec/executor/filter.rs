@@ -6,7 +6,7 @@ use crate::{
         plan::{filter::FilterNode, AbstractPlanNode},
     },
     table::{
-        schema::Schema,
+        schema::{ColumnType, Schema},
         tuple::{Tuple, RID},
         value::ColumnValue,
     },
@@ -29,12 +29,11 @@ impl Execute for FilterExecutor {
             let (tuple, rid) = self.child.next()?;
             let schema = self.child.output_schema();
 
-            if let ColumnValue::Boolean(val) = self.plan.predicate.evaluate(&tuple, schema) {
-                if val.value {
-                    return Some((tuple, rid));
-                }
-            } else {
-                panic!("Filter predicate did not evaluate to a boolean value");
+            match self.plan.predicate.evaluate(&tuple, schema) {
+                ColumnValue::Boolean(val) if val.value => return Some((tuple, rid)),
+                // false or unknown (null) don't match
+                ColumnValue::Boolean(_) | ColumnValue::Null(ColumnType::Boolean) => {}
+                _ => panic!("Filter predicate did not evaluate to a boolean value"),
             }
         }
     }
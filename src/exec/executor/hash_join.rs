@@ -0,0 +1,663 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    mem,
+    sync::Arc,
+};
+
+use crate::{
+    disk::buffer_pool_manager::BufferPoolManager,
+    exec::{
+        expression::{value::JoinSide, Evaluate, Expression},
+        plan::{hash_join::HashJoinPlanNode, AbstractPlanNode},
+    },
+    table::{
+        schema::Schema,
+        tuple::{Tuple, RID},
+    },
+};
+
+use super::{
+    nested_loop_join::{is_match, join_rows},
+    spill::{SpillFile, SpillReader},
+    Execute, Executor, ExecutorContext,
+};
+
+/// Number of partitions the rows of a side are split into when they are spilled.
+const PARTITION_FANOUT: usize = 8;
+/// Times a partition that still doesn't fit in memory is split again. Partitions can still be too large after that
+/// (e.g. when most rows have the same key), they are then joined in memory anyway.
+const MAX_PARTITION_DEPTH: usize = 3;
+
+/// Returns the join key of `tuple`, i.e. the serialized values of `key_exprs`. Returns `None` if one of the values is
+/// null, as such keys don't match any row.
+fn join_key(key_exprs: &[Expression], tuple: &Tuple, schema: &Schema) -> Option<Vec<u8>> {
+    let mut key = vec![];
+    for expr in key_exprs {
+        let value = expr.evaluate(tuple, schema);
+        if value.is_null() {
+            return None;
+        }
+        key.extend(value.serialize());
+    }
+
+    Some(key)
+}
+
+/// Returns the partition of a row with `key` when partitioning at `depth`, so partitions split again don't keep all
+/// their rows together. Rows with null keys go to the first partition.
+fn partition_of(key: Option<&[u8]>, depth: usize) -> usize {
+    let Some(key) = key else {
+        return 0;
+    };
+
+    let mut hasher = DefaultHasher::new();
+    depth.hash(&mut hasher);
+    key.hash(&mut hasher);
+    (hasher.finish() % PARTITION_FANOUT as u64) as usize
+}
+
+fn new_partitions() -> Vec<SpillFile> {
+    (0..PARTITION_FANOUT)
+        .map(|_| SpillFile::default())
+        .collect()
+}
+
+fn other_side(side: JoinSide) -> JoinSide {
+    match side {
+        JoinSide::Left => JoinSide::Right,
+        JoinSide::Right => JoinSide::Left,
+    }
+}
+
+/// Partitions of the build and probe rows with the same keys, which are joined together.
+struct PartitionPair {
+    build: SpillFile,
+    probe: SpillFile,
+    /// Times the rows were partitioned
+    depth: usize,
+}
+
+/// Build rows kept in memory, indexed by their join key.
+#[derive(Default)]
+struct HashTable {
+    /// Rows, with whether they matched a probe row
+    rows: Vec<(Tuple, bool)>,
+    /// Indexes in `rows` of the rows with each key. Rows with null keys aren't in it.
+    keys: HashMap<Vec<u8>, Vec<usize>>,
+    /// Bytes of the rows
+    size: usize,
+}
+
+impl HashTable {
+    fn insert(&mut self, key: Option<Vec<u8>>, tuple: Tuple) {
+        if let Some(key) = key {
+            self.keys.entry(key).or_default().push(self.rows.len());
+        }
+        self.size += tuple.size();
+        self.rows.push((tuple, false));
+    }
+}
+
+/// Source of the probe rows joined with the hash table.
+enum ProbeInput {
+    /// The probe child, when the build rows fit in memory
+    Child,
+    /// A spilled partition
+    Partition(SpillReader),
+}
+
+/// Hash join of two children (see `HashJoinPlanNode`). The build child is read when the join is initialized, and so is
+/// the probe child if the build rows have to be spilled.
+pub struct HashJoinExecutor {
+    pub plan: HashJoinPlanNode,
+    pub left: Box<Executor>,
+    pub right: Box<Executor>,
+    bpm: Arc<BufferPoolManager>,
+    table: HashTable,
+    probe_input: ProbeInput,
+    /// Probe row being joined, with the indexes of the build rows with its key that weren't compared yet and whether it
+    /// matched one of them
+    current_probe: Option<(Tuple, Vec<usize>, bool)>,
+    /// Index of the next build row to return without a match, once all the probe rows were joined with the hash table
+    /// (`None` before that)
+    unmatched_build: Option<usize>,
+    /// Spilled partitions that weren't joined yet
+    partitions: Vec<PartitionPair>,
+    /// Number of pairs of partitions written to disk since the join was initialized
+    spilled_partitions: usize,
+}
+
+impl HashJoinExecutor {
+    pub fn new(
+        context: ExecutorContext,
+        plan: HashJoinPlanNode,
+        left: Executor,
+        right: Executor,
+    ) -> Self {
+        Self {
+            plan,
+            left: Box::new(left),
+            right: Box::new(right),
+            bpm: context.bpm,
+            table: HashTable::default(),
+            probe_input: ProbeInput::Child,
+            current_probe: None,
+            unmatched_build: None,
+            partitions: vec![],
+            spilled_partitions: 0,
+        }
+    }
+
+    fn build_side(&self) -> JoinSide {
+        self.plan.build_side
+    }
+
+    fn probe_side(&self) -> JoinSide {
+        other_side(self.plan.build_side)
+    }
+
+    fn child_mut(&mut self, side: JoinSide) -> &mut Executor {
+        match side {
+            JoinSide::Left => &mut self.left,
+            JoinSide::Right => &mut self.right,
+        }
+    }
+
+    /// Returns the join key of a row of the child on `side`.
+    fn key(&self, side: JoinSide, tuple: &Tuple) -> Option<Vec<u8>> {
+        match side {
+            JoinSide::Left => join_key(&self.plan.left_key_exprs, tuple, self.left.output_schema()),
+            JoinSide::Right => join_key(
+                &self.plan.right_key_exprs,
+                tuple,
+                self.right.output_schema(),
+            ),
+        }
+    }
+
+    /// Returns `true` if the rows of `side` without a match are returned.
+    fn keeps(&self, side: JoinSide) -> bool {
+        match side {
+            JoinSide::Left => self.plan.join_type.keeps_left(),
+            JoinSide::Right => self.plan.join_type.keeps_right(),
+        }
+    }
+
+    fn is_match(&self, probe: &Tuple, build: &Tuple) -> bool {
+        let (left, right) = match self.build_side() {
+            JoinSide::Left => (build, probe),
+            JoinSide::Right => (probe, build),
+        };
+
+        is_match(
+            &self.plan.predicate,
+            left,
+            self.left.output_schema(),
+            right,
+            self.right.output_schema(),
+        )
+    }
+
+    fn join_rows(&self, probe: Option<&Tuple>, build: Option<&Tuple>) -> Tuple {
+        let (left, right) = match self.build_side() {
+            JoinSide::Left => (build, probe),
+            JoinSide::Right => (probe, build),
+        };
+
+        join_rows(
+            left,
+            self.left.output_schema(),
+            right,
+            self.right.output_schema(),
+            &self.plan.output_schema,
+        )
+    }
+
+    /// Writes a row of the child on `side` to its partition.
+    fn spill(&self, partitions: &mut [SpillFile], side: JoinSide, tuple: &Tuple, depth: usize) {
+        let key = self.key(side, tuple);
+        partitions[partition_of(key.as_deref(), depth)].push(&self.bpm, tuple);
+    }
+
+    /// Splits the rows of a spilled partition of the child on `side` in smaller partitions, freeing its pages.
+    fn repartition(&self, side: JoinSide, partition: SpillFile, depth: usize) -> Vec<SpillFile> {
+        let mut partitions = new_partitions();
+        let mut reader = partition.reader();
+        while let Some(tuple) = reader.next(&self.bpm) {
+            self.spill(&mut partitions, side, &tuple, depth);
+        }
+        reader.delete(&self.bpm);
+
+        partitions
+    }
+
+    /// Adds the pairs of `build` and `probe` partitions that have rows to the partitions to join.
+    fn push_partitions(&mut self, build: Vec<SpillFile>, probe: Vec<SpillFile>, depth: usize) {
+        // the first partitions are joined first
+        for (build, probe) in build.into_iter().zip(probe).rev() {
+            if build.is_empty() && probe.is_empty() {
+                continue;
+            }
+            self.spilled_partitions += 1;
+            self.partitions.push(PartitionPair {
+                build,
+                probe,
+                depth,
+            });
+        }
+    }
+
+    /// Loads the build rows of the next spilled partition in the hash table, splitting it again if it doesn't fit in
+    /// memory. Returns `false` if all the partitions were joined.
+    fn load_next_partition(&mut self) -> bool {
+        self.finish_probe_input();
+
+        while let Some(pair) = self.partitions.pop() {
+            if pair.build.size() > self.plan.memory_budget && pair.depth < MAX_PARTITION_DEPTH {
+                let depth = pair.depth + 1;
+                let build = self.repartition(self.build_side(), pair.build, depth);
+                let probe = self.repartition(self.probe_side(), pair.probe, depth);
+                self.push_partitions(build, probe, depth);
+                continue;
+            }
+
+            let mut table = HashTable::default();
+            let mut build = pair.build.reader();
+            while let Some(tuple) = build.next(&self.bpm) {
+                table.insert(self.key(self.build_side(), &tuple), tuple);
+            }
+            build.delete(&self.bpm);
+
+            self.table = table;
+            self.unmatched_build = None;
+            self.probe_input = ProbeInput::Partition(pair.probe.reader());
+            return true;
+        }
+
+        false
+    }
+
+    /// Returns the next probe row to join with the hash table.
+    fn next_probe_row(&mut self) -> Option<Tuple> {
+        match &mut self.probe_input {
+            ProbeInput::Child => {
+                let probe_side = self.probe_side();
+                self.child_mut(probe_side).next().map(|(tuple, _)| tuple)
+            }
+            ProbeInput::Partition(reader) => reader.next(&self.bpm),
+        }
+    }
+
+    /// Frees the pages of the partition the probe rows were read from, if any.
+    fn finish_probe_input(&mut self) {
+        if let ProbeInput::Partition(reader) =
+            mem::replace(&mut self.probe_input, ProbeInput::Child)
+        {
+            reader.delete(&self.bpm);
+        }
+    }
+
+    /// Drops the rows of the join and frees all the spilled partitions.
+    fn clear(&mut self) {
+        self.finish_probe_input();
+        for pair in mem::take(&mut self.partitions) {
+            pair.build.delete(&self.bpm);
+            pair.probe.delete(&self.bpm);
+        }
+
+        self.table = HashTable::default();
+        self.current_probe = None;
+        self.unmatched_build = None;
+        self.spilled_partitions = 0;
+    }
+}
+
+impl Execute for HashJoinExecutor {
+    fn init(&mut self) {
+        self.clear();
+        self.left.init();
+        self.right.init();
+
+        // build the hash table, until it doesn't fit in memory anymore
+        let build_side = self.build_side();
+        let mut build_partitions: Option<Vec<SpillFile>> = None;
+        while let Some((tuple, _)) = self.child_mut(build_side).next() {
+            if let Some(partitions) = build_partitions.as_mut() {
+                self.spill(partitions, build_side, &tuple, 0);
+                continue;
+            }
+
+            self.table.insert(self.key(build_side, &tuple), tuple);
+            if self.table.size > self.plan.memory_budget {
+                let mut partitions = new_partitions();
+                for (tuple, _) in mem::take(&mut self.table).rows {
+                    self.spill(&mut partitions, build_side, &tuple, 0);
+                }
+                build_partitions = Some(partitions);
+            }
+        }
+        let Some(build_partitions) = build_partitions else {
+            return;
+        };
+
+        // the rows of the probe child are partitioned the same way, then the partitions are joined one by one
+        let probe_side = self.probe_side();
+        let mut probe_partitions = new_partitions();
+        while let Some((tuple, _)) = self.child_mut(probe_side).next() {
+            self.spill(&mut probe_partitions, probe_side, &tuple, 0);
+        }
+        self.push_partitions(build_partitions, probe_partitions, 0);
+        // the (empty) hash table is done, so the first partition is loaded by `next`
+        self.unmatched_build = Some(0);
+    }
+
+    fn next(&mut self) -> Option<(Tuple, RID)> {
+        loop {
+            if let Some((probe, mut candidates, matched)) = self.current_probe.take() {
+                while let Some(i) = candidates.pop() {
+                    let build = &self.table.rows[i].0;
+                    if self.is_match(&probe, build) {
+                        let row = self.join_rows(Some(&probe), Some(build));
+                        self.table.rows[i].1 = true;
+                        self.current_probe = Some((probe, candidates, true));
+                        return Some((row, RID::invalid()));
+                    }
+                }
+
+                // all the build rows with the key of the probe row were compared with it
+                if !matched && self.keeps(self.probe_side()) {
+                    return Some((self.join_rows(Some(&probe), None), RID::invalid()));
+                }
+                continue;
+            }
+
+            if let Some(next) = self.unmatched_build {
+                if self.keeps(self.build_side()) {
+                    let unmatched = self.table.rows[next..]
+                        .iter()
+                        .position(|(_, matched)| !matched);
+                    if let Some(offset) = unmatched {
+                        self.unmatched_build = Some(next + offset + 1);
+                        let build = &self.table.rows[next + offset].0;
+                        return Some((self.join_rows(None, Some(build)), RID::invalid()));
+                    }
+                }
+
+                // all the rows of the hash table were joined
+                self.unmatched_build = Some(self.table.rows.len());
+                if !self.load_next_partition() {
+                    return None;
+                }
+                continue;
+            }
+
+            match self.next_probe_row() {
+                Some(probe) => {
+                    let candidates = self
+                        .key(self.probe_side(), &probe)
+                        .and_then(|key| self.table.keys.get(&key))
+                        // candidates are compared from the end, so matches are returned in the order of the build rows
+                        .map(|rows| rows.iter().rev().copied().collect())
+                        .unwrap_or_default();
+                    self.current_probe = Some((probe, candidates, false));
+                }
+                None => self.unmatched_build = Some(0),
+            }
+        }
+    }
+
+    fn output_schema(&self) -> &Schema {
+        self.plan.get_output_schema()
+    }
+
+    fn to_string(&self, indent_level: usize) -> String {
+        let keys = |exprs: &[Expression]| {
+            exprs
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        let self_string = format!(
+            "HashJoin | Schema: {} | Type: {} | Keys: [ {} ] = [ {} ] | Build: {:?} | Predicate: {}",
+            self.output_schema().to_string(),
+            self.plan.join_type,
+            keys(&self.plan.left_key_exprs),
+            keys(&self.plan.right_key_exprs),
+            self.plan.build_side,
+            self.plan.predicate.to_string()
+        );
+
+        let tabs = "\t".repeat(indent_level + 1);
+        format!(
+            "{}\n{}-> {}\n{}-> {}",
+            self_string,
+            tabs,
+            self.left.to_string(indent_level + 1),
+            tabs,
+            self.right.to_string(indent_level + 1)
+        )
+    }
+}
+
+impl Drop for HashJoinExecutor {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::remove_file};
+
+    use crate::{
+        exec::{
+            executor::{seq_scan::SeqScanExecutor, Execute, Executor, ExecutorContext},
+            expression::{
+                boolean::{BooleanExpression, BooleanType},
+                value::JoinSide,
+            },
+            plan::{
+                hash_join::HashJoinPlanNode, nested_loop_join::JoinType, seq_scan::SeqScanPlanNode,
+                PlanNode,
+            },
+        },
+        sample_code::util::{create_join_tables, create_table_with_values},
+        table::{
+            schema::{ColumnType, Schema},
+            value::ColumnValue,
+        },
+        test_utils::{column_with, int_value, right_column_with},
+    };
+
+    use super::HashJoinExecutor;
+
+    fn seq_scan(context: &ExecutorContext, table_name: &str) -> (PlanNode, Executor) {
+        let table = context.catalog.get_table_by_name(table_name).unwrap();
+        let table = table.lock().unwrap();
+        let plan = SeqScanPlanNode {
+            output_schema: table.schema.clone(),
+            table_oid: table.oid,
+            table_name: table_name.to_string(),
+            filter_expr: None,
+        };
+
+        (
+            PlanNode::SeqScan(plan.clone()),
+            Executor::SeqScan(SeqScanExecutor::new(context.clone(), plan)),
+        )
+    }
+
+    /// Returns a hash join of `left_table.#0 = right_table.#0`.
+    fn hash_join(
+        context: &ExecutorContext,
+        left_table: &str,
+        right_table: &str,
+        join_type: JoinType,
+        build_side: JoinSide,
+        memory_budget: usize,
+    ) -> HashJoinExecutor {
+        let (left_plan, left) = seq_scan(context, left_table);
+        let (right_plan, right) = seq_scan(context, right_table);
+        let predicate = BooleanExpression {
+            left: Box::new(column_with(0, ColumnType::Integer)),
+            right: Box::new(right_column_with(0, ColumnType::Integer)),
+            typ: BooleanType::EQ,
+        };
+        let plan = HashJoinPlanNode::new(
+            join_type,
+            predicate,
+            left_plan,
+            right_plan,
+            vec![column_with(0, ColumnType::Integer)],
+            vec![column_with(0, ColumnType::Integer)],
+        )
+        .with_build_side(build_side)
+        .with_memory_budget(memory_budget);
+
+        HashJoinExecutor::new(context.clone(), plan, left, right)
+    }
+
+    /// Returns the sorted rows of the join as `(users.id, orders.user_id, orders.amount)`, `None` for nulls.
+    fn sorted_rows(executor: &mut HashJoinExecutor) -> Vec<[Option<i32>; 3]> {
+        executor.init();
+        let schema = executor.output_schema().clone();
+        let mut rows = vec![];
+        while let Some((tuple, _)) = executor.next() {
+            rows.push([0, 1, 2].map(|i| match tuple.get_value(&schema, i) {
+                ColumnValue::Integer(value) => Some(value.value),
+                value => {
+                    assert!(value.is_null());
+                    None
+                }
+            }));
+        }
+        rows.sort();
+
+        rows
+    }
+
+    #[test]
+    fn hash_join_types() {
+        // init
+        let db_path = temp_dir().join("hash_join_hash_join_types.db");
+        let context = create_join_tables(db_path.to_str().unwrap().to_string());
+
+        // test
+        let user_without_orders = [Some(2), None, None];
+        let order_without_user = [None, Some(4), Some(40)];
+        let matches = vec![
+            [Some(1), Some(1), Some(10)],
+            [Some(1), Some(1), Some(11)],
+            [Some(3), Some(3), Some(30)],
+        ];
+        let expected = [
+            (JoinType::Inner, matches.clone()),
+            (
+                JoinType::Left,
+                vec![matches[0], matches[1], user_without_orders, matches[2]],
+            ),
+            (
+                JoinType::Right,
+                vec![order_without_user, matches[0], matches[1], matches[2]],
+            ),
+            (
+                JoinType::Outer,
+                vec![
+                    order_without_user,
+                    matches[0],
+                    matches[1],
+                    user_without_orders,
+                    matches[2],
+                ],
+            ),
+        ];
+
+        for (join_type, rows) in expected {
+            for build_side in [JoinSide::Left, JoinSide::Right] {
+                // in memory
+                let mut executor =
+                    hash_join(&context, "users", "orders", join_type, build_side, 1024);
+                assert_eq!(sorted_rows(&mut executor), rows);
+                assert_eq!(executor.spilled_partitions, 0);
+
+                // every row is over the budget, so the partitions are split until the maximum depth
+                let mut executor = hash_join(&context, "users", "orders", join_type, build_side, 1);
+                assert_eq!(sorted_rows(&mut executor), rows);
+                assert!(executor.spilled_partitions > 0);
+            }
+        }
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn hash_join_spill() {
+        // init
+        let db_path = temp_dir().join("hash_join_hash_join_spill.db");
+        let values = (0..1000).collect::<Vec<i32>>();
+        let (context, _, _, table_name) =
+            create_table_with_values(db_path.to_str().unwrap().to_string(), &values);
+
+        // test
+        let mut executor = hash_join(
+            &context,
+            &table_name,
+            &table_name,
+            JoinType::Inner,
+            JoinSide::Right,
+            1024,
+        );
+        executor.init();
+        let schema = executor.output_schema().clone();
+        let mut ids = vec![];
+        while let Some((tuple, _)) = executor.next() {
+            let left_id = tuple.get_value(&schema, 0);
+            assert_eq!(left_id, tuple.get_value(&schema, 3));
+            ids.push(left_id);
+        }
+        ids.sort_by(|a, b| a.compare(b).unwrap());
+        assert_eq!(
+            ids,
+            values.iter().map(|&v| int_value(v)).collect::<Vec<_>>()
+        );
+        assert!(executor.spilled_partitions > 0);
+
+        // all the partitions were joined
+        assert!(executor.partitions.is_empty());
+
+        // cleanup
+        drop(executor);
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    #[should_panic]
+    fn hash_join_key_types() {
+        let seq_scan = |typ: ColumnType| {
+            PlanNode::SeqScan(SeqScanPlanNode {
+                output_schema: Schema::with_types(vec![typ]),
+                table_oid: 0,
+                table_name: "t".to_string(),
+                filter_expr: None,
+            })
+        };
+        let predicate = BooleanExpression {
+            left: Box::new(column_with(0, ColumnType::Integer)),
+            right: Box::new(right_column_with(0, ColumnType::BigInt)),
+            typ: BooleanType::EQ,
+        };
+
+        // the serialized values of the keys would never be equal
+        let _ = HashJoinPlanNode::new(
+            JoinType::Inner,
+            predicate,
+            seq_scan(ColumnType::Integer),
+            seq_scan(ColumnType::BigInt),
+            vec![column_with(0, ColumnType::Integer)],
+            vec![column_with(0, ColumnType::BigInt)],
+        );
+    }
+}
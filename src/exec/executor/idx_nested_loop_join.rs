@@ -0,0 +1,355 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    catalog::info::{IndexInfo, TableInfo},
+    exec::{
+        expression::Evaluate,
+        plan::{idx_nested_loop_join::IdxNestedLoopJoinPlanNode, AbstractPlanNode},
+    },
+    index::IndexType,
+    table::{
+        schema::Schema,
+        tuple::{Tuple, RID},
+    },
+};
+
+use super::{
+    nested_loop_join::{is_match, join_rows},
+    Execute, Executor, ExecutorContext,
+};
+
+/// Nested loop join that looks up the key of every left (outer) row in an index of the right (inner) table.
+pub struct IdxNestedLoopJoinExecutor {
+    pub plan: IdxNestedLoopJoinPlanNode,
+    pub left: Box<Executor>,
+    pub index: Arc<Mutex<IndexInfo>>,
+    pub table: Arc<Mutex<TableInfo>>,
+    /// Left row being joined, with the rows of the table found in the index for it that weren't compared yet
+    current_left: Option<(Tuple, Vec<RID>)>,
+    /// Whether the current left row matched a row of the table
+    left_matched: bool,
+    /// Rows of the table that matched a left row, for right and full outer joins
+    matched_rids: HashSet<RID>,
+    /// Rows of the table that didn't match any left row, read once the left child is exhausted (`None` before that)
+    unmatched_right: Option<Vec<(Tuple, RID)>>,
+}
+
+impl IdxNestedLoopJoinExecutor {
+    /// Creates a new `IdxNestedLoopJoinExecutor`.
+    ///
+    /// # Panics
+    /// Will panic if the table doesn't exist, if it doesn't have a usable index with the name of the plan or if the index
+    /// is a full-text index (its keys aren't the values of the rows).
+    pub fn new(context: ExecutorContext, plan: IdxNestedLoopJoinPlanNode, left: Executor) -> Self {
+        let index = context
+            .catalog
+            .get_index_by_name(&plan.index_name, &plan.table_name)
+            .expect("No index with given name on the table");
+        let index_guard = index.lock().unwrap();
+        assert!(index_guard.is_valid(), "The index is still being built");
+        assert!(
+            index_guard.index.meta().index_type() != IndexType::FullText,
+            "Index nested loop joins can't use full-text indexes"
+        );
+        drop(index_guard);
+
+        Self {
+            index,
+            table: context
+                .catalog
+                .get_table_by_oid(plan.table_oid)
+                .expect("No table with given OID"),
+            plan,
+            left: Box::new(left),
+            current_left: None,
+            left_matched: false,
+            matched_rids: HashSet::new(),
+            unmatched_right: None,
+        }
+    }
+
+    /// Returns the RIDs of the rows of the table with the key of the `left` row. Keys with nulls don't match any row.
+    fn lookup(&self, left: &Tuple) -> Vec<RID> {
+        let left_schema = self.left.output_schema();
+        let values = self
+            .plan
+            .key_exprs
+            .iter()
+            .map(|expr| expr.evaluate(left, left_schema))
+            .collect::<Vec<_>>();
+        if values.iter().any(|value| value.is_null()) {
+            return vec![];
+        }
+
+        let index = self.index.lock().unwrap();
+        let key = Tuple::new(values, index.index.meta().key_schema());
+        let mut rids = index.index.scan(key);
+        // rows are compared in reverse, so they are returned in the order of the index
+        rids.reverse();
+
+        rids
+    }
+
+    /// Returns the next row of the table that didn't match any left row, for right and full outer joins.
+    fn next_unmatched_right(&mut self) -> Option<(Tuple, RID)> {
+        if !self.plan.join_type.keeps_right() {
+            return None;
+        }
+
+        if self.unmatched_right.is_none() {
+            let table = self.table.lock().unwrap();
+            let mut rows = table
+                .table
+                .iter()
+                .filter(|(meta, _, rid)| !meta.is_deleted && !self.matched_rids.contains(rid))
+                .map(|(_, tuple, rid)| (tuple, rid))
+                .collect::<Vec<_>>();
+            // rows are taken from the end
+            rows.reverse();
+            self.unmatched_right = Some(rows);
+        }
+
+        let (right, _) = self.unmatched_right.as_mut().unwrap().pop()?;
+        let table = self.table.lock().unwrap();
+        let row = join_rows(
+            None,
+            self.left.output_schema(),
+            Some(&right),
+            &table.schema,
+            &self.plan.output_schema,
+        );
+
+        Some((row, RID::invalid()))
+    }
+}
+
+impl Execute for IdxNestedLoopJoinExecutor {
+    fn init(&mut self) {
+        self.left.init();
+        self.current_left = None;
+        self.left_matched = false;
+        self.matched_rids.clear();
+        self.unmatched_right = None;
+    }
+
+    fn next(&mut self) -> Option<(Tuple, RID)> {
+        loop {
+            let Some((left, rids)) = self.current_left.as_mut() else {
+                let (left, _) = match self.left.next() {
+                    Some(row) => row,
+                    None => return self.next_unmatched_right(),
+                };
+                let rids = self.lookup(&left);
+                self.current_left = Some((left, rids));
+                self.left_matched = false;
+                continue;
+            };
+
+            let left_schema = self.left.output_schema();
+            while let Some(rid) = rids.pop() {
+                let table = self.table.lock().unwrap();
+                let Some((meta, right)) = table.table.get_tuple(&rid) else {
+                    continue;
+                };
+                if meta.is_deleted
+                    || !is_match(
+                        &self.plan.predicate,
+                        left,
+                        left_schema,
+                        &right,
+                        &table.schema,
+                    )
+                {
+                    continue;
+                }
+
+                self.left_matched = true;
+                self.matched_rids.insert(rid);
+                let row = join_rows(
+                    Some(left),
+                    left_schema,
+                    Some(&right),
+                    &table.schema,
+                    &self.plan.output_schema,
+                );
+                return Some((row, RID::invalid()));
+            }
+
+            // all the rows found in the index were compared with the left row
+            let (left, _) = self.current_left.take().unwrap();
+            if !self.left_matched && self.plan.join_type.keeps_left() {
+                let table = self.table.lock().unwrap();
+                let row = join_rows(
+                    Some(&left),
+                    self.left.output_schema(),
+                    None,
+                    &table.schema,
+                    &self.plan.output_schema,
+                );
+                return Some((row, RID::invalid()));
+            }
+        }
+    }
+
+    fn output_schema(&self) -> &Schema {
+        self.plan.get_output_schema()
+    }
+
+    fn to_string(&self, indent_level: usize) -> String {
+        let table = self.table.lock().unwrap();
+        let table_name = table.name.clone();
+        let table_oid = table.oid;
+        drop(table);
+
+        let index = self.index.lock().unwrap();
+        let index_name = index.name.clone();
+        let index_oid = index.oid;
+        drop(index);
+
+        let self_string = format!(
+            "IdxNestedLoopJoin | Schema: {} | Type: {} | Table: {}({}) | Index: {}({}) - [ {} ] | Predicate: {}",
+            self.output_schema().to_string(),
+            self.plan.join_type,
+            table_name,
+            table_oid,
+            index_name,
+            index_oid,
+            self.plan
+                .key_exprs
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<String>>()
+                .join(", "),
+            self.plan.predicate.to_string()
+        );
+
+        let tabs = "\t".repeat(indent_level + 1);
+        format!(
+            "{}\n{}-> {}",
+            self_string,
+            tabs,
+            self.left.to_string(indent_level + 1)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::remove_file};
+
+    use crate::{
+        exec::{
+            executor::{seq_scan::SeqScanExecutor, Execute, Executor, ExecutorContext},
+            expression::boolean::{BooleanExpression, BooleanType},
+            plan::{
+                idx_nested_loop_join::IdxNestedLoopJoinPlanNode, nested_loop_join::JoinType,
+                seq_scan::SeqScanPlanNode, PlanNode,
+            },
+        },
+        index::{IndexMeta, IndexType},
+        sample_code::util::create_join_tables,
+        table::{
+            schema::{ColumnType, Schema},
+            value::ColumnValue,
+        },
+        test_utils::{column_with, right_column_with},
+    };
+
+    use super::IdxNestedLoopJoinExecutor;
+
+    /// Returns the rows of the join as `(users.id, orders.user_id, orders.amount)`, `None` for nulls.
+    fn join(context: &ExecutorContext, join_type: JoinType) -> Vec<[Option<i32>; 3]> {
+        let users = context.catalog.get_table_by_name("users").unwrap();
+        let users = users.lock().unwrap();
+        let scan_plan = SeqScanPlanNode {
+            output_schema: users.schema.clone(),
+            table_oid: users.oid,
+            table_name: users.name.clone(),
+            filter_expr: None,
+        };
+        drop(users);
+        let scan = Executor::SeqScan(SeqScanExecutor::new(context.clone(), scan_plan.clone()));
+
+        // users.id = orders.user_id
+        let predicate = BooleanExpression {
+            left: Box::new(column_with(0, ColumnType::Integer)),
+            right: Box::new(right_column_with(0, ColumnType::Integer)),
+            typ: BooleanType::EQ,
+        };
+        let orders = context.catalog.get_table_by_name("orders").unwrap();
+        let plan = IdxNestedLoopJoinPlanNode::new(
+            join_type,
+            predicate,
+            PlanNode::SeqScan(scan_plan),
+            &orders.lock().unwrap(),
+            "orders_user_id".to_string(),
+            vec![column_with(0, ColumnType::Integer)],
+        );
+        let mut executor = IdxNestedLoopJoinExecutor::new(context.clone(), plan, scan);
+
+        executor.init();
+        let schema = executor.output_schema().clone();
+        let mut rows = vec![];
+        while let Some((tuple, _)) = executor.next() {
+            rows.push([0, 1, 2].map(|i| match tuple.get_value(&schema, i) {
+                ColumnValue::Integer(value) => Some(value.value),
+                value => {
+                    assert!(value.is_null());
+                    None
+                }
+            }));
+        }
+        rows
+    }
+
+    #[test]
+    fn idx_nested_loop_join() {
+        // init
+        let db_path = temp_dir().join("idx_nested_loop_join_idx_nested_loop_join.db");
+        let context = create_join_tables(db_path.to_str().unwrap().to_string());
+        let orders = context.catalog.get_table_by_name("orders").unwrap();
+        let orders_schema = orders.lock().unwrap().schema.clone();
+        let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+        let meta = IndexMeta::new(key_schema.clone(), "orders_user_id".to_string(), vec![0])
+            .with_type(IndexType::BPlusTree);
+        context
+            .catalog
+            .create_index_with_meta(meta, "orders", orders_schema, key_schema.get_tuple_len())
+            .unwrap();
+
+        // test
+        let matches = vec![
+            [Some(1), Some(1), Some(10)],
+            [Some(1), Some(1), Some(11)],
+            [Some(3), Some(3), Some(30)],
+        ];
+        let user_without_orders = [Some(2), None, None];
+        let order_without_user = [None, Some(4), Some(40)];
+
+        assert_eq!(join(&context, JoinType::Inner), matches);
+        assert_eq!(
+            join(&context, JoinType::Left),
+            vec![matches[0], matches[1], user_without_orders, matches[2]]
+        );
+        assert_eq!(
+            join(&context, JoinType::Right),
+            [matches.clone(), vec![order_without_user]].concat()
+        );
+        assert_eq!(
+            join(&context, JoinType::Outer),
+            vec![
+                matches[0],
+                matches[1],
+                user_without_orders,
+                matches[2],
+                order_without_user
+            ]
+        );
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+}
@@ -0,0 +1,477 @@
+use std::{
+    ops::Bound,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    catalog::info::{IndexInfo, TableInfo},
+    exec::{
+        executor::{Execute, ExecutorContext},
+        expression::{Evaluate, Expression},
+        plan::{
+            idx_range_scan::{IdxRangeScanPlanNode, KeyBound},
+            AbstractPlanNode,
+        },
+    },
+    index::ScanDirection,
+    table::{
+        schema::{ColumnType, Schema},
+        tuple::{Tuple, RID},
+    },
+};
+
+pub struct IdxRangeScanExecutor {
+    pub plan: IdxRangeScanPlanNode,
+    pub index: Arc<Mutex<IndexInfo>>,
+    pub table: Arc<Mutex<TableInfo>>,
+    /// Matching rows, with the rows built from the index keys for index-only scans (`None` if they are read from the
+    /// table)
+    results: Vec<(Option<Tuple>, RID)>,
+    current: usize,
+}
+
+impl IdxRangeScanExecutor {
+    /// Creates a new `IdxRangeScanExecutor`.
+    ///
+    /// # Panics
+    /// Will panic if the table doesn't have an ordered (B+ tree) index on the scanned column.
+    pub fn new(context: ExecutorContext, plan: IdxRangeScanPlanNode) -> Self {
+        Self {
+            index: context
+                .catalog
+                .get_table_ordered_index_by_column(&plan.table_name, plan.col_index)
+                .expect("No ordered index on the scanned column of the table"),
+            table: context
+                .catalog
+                .get_table_by_oid(plan.table_oid)
+                .expect("No table with given OID"),
+            plan,
+            results: vec![],
+            current: 0,
+        }
+    }
+
+    /// Evaluates a bound of the range to a key of the index. Returns `None` if the bound is null, as no value can be
+    /// compared with it.
+    fn evaluate_bound(bound: &KeyBound, key_schema: &Schema) -> Option<Bound<Tuple>> {
+        // bounds don't depend on specific tuples
+        let evaluate = |expression: &Expression| {
+            let value = expression.evaluate(
+                &Tuple::empty(),
+                &Schema::with_types(vec![ColumnType::Integer]),
+            );
+            (!value.is_null()).then(|| Tuple::new(vec![value], key_schema))
+        };
+
+        Some(match bound {
+            Bound::Included(expression) => Bound::Included(evaluate(expression)?),
+            Bound::Excluded(expression) => Bound::Excluded(evaluate(expression)?),
+            Bound::Unbounded => Bound::Unbounded,
+        })
+    }
+
+    /// Returns `true` if the index key has all the columns read by the parent nodes, so the table isn't read.
+    fn is_index_only(&self) -> bool {
+        self.plan
+            .columns
+            .as_ref()
+            .is_some_and(|columns| self.index.lock().unwrap().index.meta().covers(columns))
+    }
+
+    fn bounds_to_string(&self) -> String {
+        let lower = match &self.plan.lower {
+            Bound::Included(expression) => format!("[{}", expression.to_string()),
+            Bound::Excluded(expression) => format!("({}", expression.to_string()),
+            Bound::Unbounded => String::from("(-inf"),
+        };
+        let upper = match &self.plan.upper {
+            Bound::Included(expression) => format!("{}]", expression.to_string()),
+            Bound::Excluded(expression) => format!("{})", expression.to_string()),
+            Bound::Unbounded => String::from("+inf)"),
+        };
+
+        format!("{}, {}", lower, upper)
+    }
+}
+
+impl Execute for IdxRangeScanExecutor {
+    fn init(&mut self) {
+        let index_only = self.is_index_only();
+        let index = self.index.lock().unwrap();
+        let key_schema = index.index.meta().key_schema();
+
+        self.current = 0;
+        let (Some(lower), Some(upper)) = (
+            Self::evaluate_bound(&self.plan.lower, key_schema),
+            Self::evaluate_bound(&self.plan.upper, key_schema),
+        ) else {
+            self.results = vec![];
+            return;
+        };
+
+        self.results = index
+            .index
+            .range_scan_entries(lower, upper, self.plan.direction)
+            .expect("Range scans need an ordered index")
+            .into_iter()
+            // nulls are ordered before all the other keys, but comparisons with them are never true
+            .filter(|(key, _)| !key.get_value(key_schema, 0).is_null())
+            .map(|(key, rid)| {
+                let row =
+                    index_only.then(|| index.index.row_from_key(&key, &self.plan.output_schema));
+                (row, rid)
+            })
+            .collect();
+    }
+
+    fn next(&mut self) -> Option<(Tuple, RID)> {
+        if self.current >= self.results.len() {
+            return None;
+        }
+
+        let (row, current_rid) = self.results[self.current].clone();
+        let current_tuple = match row {
+            Some(row) => row,
+            None => {
+                let (_, tuple) = self
+                    .table
+                    .lock()
+                    .unwrap()
+                    .table
+                    .get_tuple(&current_rid)
+                    .expect("Invalid RID from index");
+                tuple
+            }
+        };
+
+        self.current += 1;
+        Some((current_tuple, current_rid))
+    }
+
+    fn output_schema(&self) -> &Schema {
+        self.plan.get_output_schema()
+    }
+
+    fn to_string(&self, _indent_level: usize) -> String {
+        let table = self.table.lock().unwrap();
+        let table_name = table.name.clone();
+        let table_oid = table.oid;
+        drop(table);
+
+        let index = self.index.lock().unwrap();
+        let index_name = index.name.clone();
+        let index_oid = index.oid;
+        drop(index);
+
+        format!(
+            "IdxRangeScan{} | Schema: {} | Table: {}({}) | Index: {}({}) - {} {}",
+            if self.is_index_only() {
+                " (index only)"
+            } else {
+                ""
+            },
+            self.output_schema().to_string(),
+            table_name,
+            table_oid,
+            index_name,
+            index_oid,
+            self.bounds_to_string(),
+            match self.plan.direction {
+                ScanDirection::Forward => "ASC",
+                ScanDirection::Backward => "DESC",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::remove_file, ops::Bound};
+
+    use crate::{
+        exec::{
+            executor::Execute,
+            expression::{
+                boolean::{BooleanExpression, BooleanType},
+                Expression,
+            },
+            plan::idx_range_scan::{IdxRangeScanPlanNode, KeyBound},
+        },
+        index::ScanDirection,
+        sample_code::{
+            executors::{idx_range_scan_executor, TableConstructorType},
+            util::create_table_with_values,
+        },
+        table::{
+            page::TupleMeta,
+            schema::{ColumnType, Schema},
+            tuple::Tuple,
+            value::{BigIntValue, ColumnValue},
+        },
+        test_utils::{bool_value, column_with, const_decimal, const_int, decimal_value, int_value},
+    };
+
+    fn comparison(typ: BooleanType, value: i32) -> BooleanExpression {
+        BooleanExpression {
+            left: Box::new(column_with(0, ColumnType::Integer)),
+            right: Box::new(const_int(value)),
+            typ,
+        }
+    }
+
+    fn between(start: i32, end: i32) -> BooleanExpression {
+        BooleanExpression {
+            left: Box::new(Expression::Boolean(comparison(BooleanType::GE, start))),
+            right: Box::new(Expression::Boolean(comparison(BooleanType::LE, end))),
+            typ: BooleanType::And,
+        }
+    }
+
+    #[test]
+    fn idx_range_scan() {
+        // init
+        let db_path = temp_dir().join("idx_range_scan_idx_range_scan.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+
+        // test
+        let run = |filter_expr: Option<BooleanExpression>, direction: ScanDirection| {
+            let (mut executor, table_context) = idx_range_scan_executor(
+                TableConstructorType::WithTable(db_file_path.clone()),
+                filter_expr.as_ref(),
+                direction,
+            );
+            let schema = table_context.1;
+
+            executor.init();
+            let mut values = vec![];
+            while let Some((tuple, _)) = executor.next() {
+                values.push(tuple.get_value(&schema, 0));
+            }
+
+            remove_file(&db_path).expect("Couldn't remove test DB file");
+            values
+        };
+        let ints = |values: Vec<i32>| values.into_iter().map(int_value).collect::<Vec<_>>();
+
+        // table values are [5, 3, 8, 1, 3, 9]
+        assert_eq!(
+            run(Some(between(3, 8)), ScanDirection::Forward),
+            ints(vec![3, 3, 5, 8])
+        );
+        assert_eq!(
+            run(Some(between(3, 8)), ScanDirection::Backward),
+            ints(vec![8, 5, 3, 3])
+        );
+        assert_eq!(
+            run(Some(comparison(BooleanType::LT, 5)), ScanDirection::Forward),
+            ints(vec![1, 3, 3])
+        );
+        assert_eq!(
+            run(
+                Some(comparison(BooleanType::GT, 5)),
+                ScanDirection::Backward
+            ),
+            ints(vec![9, 8])
+        );
+        assert_eq!(
+            run(Some(comparison(BooleanType::EQ, 3)), ScanDirection::Forward),
+            ints(vec![3, 3])
+        );
+        // open range, used for ORDER BY
+        assert_eq!(
+            run(None, ScanDirection::Forward),
+            ints(vec![1, 3, 3, 5, 8, 9])
+        );
+        assert_eq!(
+            run(None, ScanDirection::Backward),
+            ints(vec![9, 8, 5, 3, 3, 1])
+        );
+    }
+
+    #[test]
+    fn idx_range_scan_index_only() {
+        // init
+        let db_path = temp_dir().join("idx_range_scan_idx_range_scan_index_only.db");
+        let (mut executor, table_context) = idx_range_scan_executor(
+            TableConstructorType::WithTable(db_path.to_str().unwrap().to_string()),
+            Some(&between(3, 8)),
+            ScanDirection::Forward,
+        );
+        executor.plan.columns = Some(vec![0]);
+        let schema = table_context.1;
+
+        // test
+        executor.init();
+        let mut rows = vec![];
+        while let Some((tuple, rid)) = executor.next() {
+            rows.push((
+                tuple.get_value(&schema, 0),
+                tuple.get_value(&schema, 1),
+                rid,
+            ));
+        }
+
+        assert_eq!(
+            rows.iter()
+                .map(|(key, _, _)| key.clone())
+                .collect::<Vec<_>>(),
+            vec![int_value(3), int_value(3), int_value(5), int_value(8)]
+        );
+        // only the key is read, but the rows can still be found in the table
+        assert!(rows.iter().all(|(_, value, _)| value.is_null()));
+        let table = table_context
+            .0
+            .catalog
+            .get_table_by_oid(table_context.2)
+            .unwrap();
+        let table = table.lock().unwrap();
+        for (key, _, rid) in rows {
+            let (_, tuple) = table.table.get_tuple(&rid).unwrap();
+            assert_eq!(tuple.get_value(&schema, 0), key);
+        }
+        drop(table);
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn unsupported_filters() {
+        let from_filter = |filter_expr: &BooleanExpression| {
+            IdxRangeScanPlanNode::from_filter(
+                Schema::with_types(vec![ColumnType::Integer]),
+                0,
+                String::from("t"),
+                filter_expr,
+                ScanDirection::Forward,
+            )
+        };
+
+        assert!(from_filter(&comparison(BooleanType::NE, 1)).is_err());
+        // two lower bounds
+        let filter_expr = BooleanExpression {
+            left: Box::new(Expression::Boolean(comparison(BooleanType::GE, 1))),
+            right: Box::new(Expression::Boolean(comparison(BooleanType::GT, 2))),
+            typ: BooleanType::And,
+        };
+        assert!(from_filter(&filter_expr).is_err());
+        // bounds on different columns
+        let filter_expr = BooleanExpression {
+            left: Box::new(Expression::Boolean(comparison(BooleanType::GE, 1))),
+            right: Box::new(Expression::Boolean(BooleanExpression {
+                left: Box::new(column_with(1, ColumnType::Integer)),
+                right: Box::new(const_int(2)),
+                typ: BooleanType::LE,
+            })),
+            typ: BooleanType::And,
+        };
+        assert!(from_filter(&filter_expr).is_err());
+    }
+
+    #[test]
+    fn null_keys() {
+        // init
+        let db_path = temp_dir().join("idx_range_scan_null_keys.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+
+        // test
+        let run = |filter_expr: BooleanExpression| {
+            let (executor_context, schema, table_oid, table_name) =
+                create_table_with_values(db_file_path.clone(), &[5, 3, 8, 1]);
+            let table = executor_context
+                .catalog
+                .get_table_by_oid(table_oid)
+                .unwrap();
+            for _ in 0..2 {
+                let row = vec![
+                    ColumnValue::Null(ColumnType::Integer),
+                    bool_value(true),
+                    decimal_value(1.0),
+                ];
+                table.lock().unwrap().table.insert_tuple(
+                    TupleMeta {
+                        ts: 0,
+                        is_deleted: false,
+                    },
+                    Tuple::new(row, &schema),
+                );
+            }
+
+            let (mut executor, _) = idx_range_scan_executor(
+                TableConstructorType::WithoutTable((
+                    executor_context,
+                    schema.clone(),
+                    table_oid,
+                    table_name,
+                )),
+                Some(&filter_expr),
+                ScanDirection::Forward,
+            );
+            executor.init();
+            let mut values = vec![];
+            while let Some((tuple, _)) = executor.next() {
+                values.push(tuple.get_value(&schema, 0));
+            }
+
+            remove_file(&db_path).expect("Couldn't remove test DB file");
+            values
+        };
+
+        // nulls are ordered before all the other keys, but they are never less than them
+        assert_eq!(
+            run(comparison(BooleanType::LT, 5)),
+            vec![int_value(1), int_value(3)]
+        );
+        assert_eq!(
+            run(comparison(BooleanType::LE, 5)),
+            vec![int_value(1), int_value(3), int_value(5)]
+        );
+    }
+
+    #[test]
+    fn cast_bounds() {
+        let from_filter = |typ: ColumnType, filter_expr: &BooleanExpression| {
+            IdxRangeScanPlanNode::from_filter(
+                Schema::with_types(vec![typ]),
+                0,
+                String::from("t"),
+                filter_expr,
+                ScanDirection::Forward,
+            )
+        };
+        let bound_value = |bound: &KeyBound| match bound {
+            Bound::Included(expression) | Bound::Excluded(expression) => {
+                match expression.as_ref() {
+                    Expression::Constant(constant) => Some(constant.value.clone()),
+                    _ => None,
+                }
+            }
+            Bound::Unbounded => None,
+        };
+
+        // an INTEGER constant compared with a BIGINT column
+        let plan = from_filter(ColumnType::BigInt, &between(3, 8)).unwrap();
+        assert_eq!(
+            bound_value(&plan.lower),
+            Some(ColumnValue::BigInt(BigIntValue { value: 3 }))
+        );
+        assert_eq!(
+            bound_value(&plan.upper),
+            Some(ColumnValue::BigInt(BigIntValue { value: 8 }))
+        );
+
+        // constants that can't be represented exactly by the column type
+        let filter_expr = BooleanExpression {
+            left: Box::new(column_with(0, ColumnType::TinyInt)),
+            right: Box::new(const_int(300)),
+            typ: BooleanType::LT,
+        };
+        assert!(from_filter(ColumnType::TinyInt, &filter_expr).is_err());
+        let filter_expr = BooleanExpression {
+            left: Box::new(column_with(0, ColumnType::Integer)),
+            right: Box::new(const_decimal(1.5)),
+            typ: BooleanType::GE,
+        };
+        assert!(from_filter(ColumnType::Integer, &filter_expr).is_err());
+    }
+}
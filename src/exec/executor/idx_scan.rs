@@ -10,6 +10,7 @@ use crate::{
     table::{
         schema::{ColumnType, Schema},
         tuple::{Tuple, RID},
+        value::{BooleanValue, ColumnValue},
     },
 };
 
@@ -17,42 +18,85 @@ pub struct IdxScanExecutor {
     pub plan: IdxScanPlanNode,
     pub index: Arc<Mutex<IndexInfo>>,
     pub table: Arc<Mutex<TableInfo>>,
-    results: Vec<RID>,
+    /// Value of the key looked up in the index
+    key_value: Box<Expression>,
+    /// Filter of the plan, checked on the rows found in the index if it has conditions other than the key lookup
+    /// (`None` otherwise)
+    other_conditions: Option<Expression>,
+    /// Matching rows, with the rows built from the index keys for index-only scans (`None` if they are read from the
+    /// table)
+    results: Vec<(Option<Tuple>, RID)>,
     current: usize,
 }
 
 impl IdxScanExecutor {
     /// Creates a new `IdxScanExecutor`.
     ///
+    /// The filter must have an `EQ` condition whose left operand is a column or an expression of the columns (e.g.
+    /// `LOWER(name)`) and whose right operand doesn't depend on the row, possibly combined with other conditions with
+    /// `AND`. It's looked up in an index whose key is the left operand: a regular index, an expression index or a
+    /// partial index whose predicate is part of the filter. The other conditions are checked on the rows found.
+    ///
     /// # Panics
-    /// Will panic in case the expression passed is **not** an `EQ` that has its left operand be a column
-    /// expression.
+    /// Will panic if no condition of the filter can be looked up in an index of the table.
     pub fn new(context: ExecutorContext, plan: IdxScanPlanNode) -> Self {
-        assert_eq!(plan.filter_expr.typ, BooleanType::EQ);
-        let col_index = match plan.filter_expr.left.as_ref() {
-            Expression::ColumnValue(col_val_expression) => col_val_expression.col_index,
-            _ => panic!("Left operand must be a column value expression"),
-        };
+        let filter = Expression::Boolean(plan.filter_expr.clone());
+        let conditions = filter.conjuncts();
+        let (index, key_value) = conditions
+            .iter()
+            .find_map(|condition| {
+                let Expression::Boolean(condition) = condition else {
+                    return None;
+                };
+                if condition.typ != BooleanType::EQ || !condition.right.columns().is_empty() {
+                    return None;
+                }
+
+                context
+                    .catalog
+                    .get_table_index_by_expression(&plan.table_name, &condition.left, &conditions)
+                    .map(|index| (index, condition.right.clone()))
+            })
+            .expect("No index matching expression for table");
+        let other_conditions = (conditions.len() > 1).then(|| filter.clone());
 
         Self {
-            index: context
-                .catalog
-                .get_table_index_by_column(&plan.table_name, col_index)
-                .expect("No index matching expression for table"),
+            index,
             table: context
                 .catalog
                 .get_table_by_oid(plan.table_oid)
                 .expect("No table with given OID"),
             plan,
+            key_value,
+            other_conditions,
             results: vec![],
             current: 0,
         }
     }
+
+    /// Returns `true` if the index key has all the columns read by the parent nodes and by the filter, so the table
+    /// isn't read.
+    fn is_index_only(&self) -> bool {
+        self.plan.columns.as_ref().is_some_and(|columns| {
+            let filter_columns = [
+                self.plan.filter_expr.left.columns(),
+                self.plan.filter_expr.right.columns(),
+            ]
+            .concat();
+
+            self.index
+                .lock()
+                .unwrap()
+                .index
+                .meta()
+                .covers(&[columns.clone(), filter_columns].concat())
+        })
+    }
 }
 
 impl Execute for IdxScanExecutor {
     fn init(&mut self) {
-        let right_val = self.plan.filter_expr.right.evaluate(
+        let right_val = self.key_value.evaluate(
             &Tuple::empty(),
             &Schema::with_types(vec![ColumnType::Integer]),
         ); // evaluate should be an expression that doesn't depend on specific tuple
@@ -62,26 +106,49 @@ impl Execute for IdxScanExecutor {
             &Schema::with_types(vec![right_val.typ()]),
         );
 
-        self.results = self.index.lock().unwrap().index.scan(key);
+        let index_only = self.is_index_only();
+        let index = self.index.lock().unwrap();
+        self.results = index
+            .index
+            .scan_entries(key)
+            .into_iter()
+            .map(|(key, rid)| {
+                let row =
+                    index_only.then(|| index.index.row_from_key(&key, &self.plan.output_schema));
+                (row, rid)
+            })
+            .collect();
         self.current = 0;
     }
 
     fn next(&mut self) -> Option<(Tuple, RID)> {
-        if self.current >= self.results.len() {
-            return None;
-        }
+        while self.current < self.results.len() {
+            let (row, current_rid) = self.results[self.current].clone();
+            let current_tuple = match row {
+                Some(row) => row,
+                None => {
+                    let (_, tuple) = self
+                        .table
+                        .lock()
+                        .unwrap()
+                        .table
+                        .get_tuple(&current_rid)
+                        .expect("Invalid RID from index");
+                    tuple
+                }
+            };
+            self.current += 1;
 
-        let current_rid = self.results[self.current].clone();
-        let (_, current_tuple) = self
-            .table
-            .lock()
-            .unwrap()
-            .table
-            .get_tuple(&current_rid)
-            .expect("Invalid RID from index");
+            let matches = self.other_conditions.as_ref().is_none_or(|filter| {
+                filter.evaluate(&current_tuple, &self.plan.output_schema)
+                    == ColumnValue::Boolean(BooleanValue { value: true })
+            });
+            if matches {
+                return Some((current_tuple, current_rid));
+            }
+        }
 
-        self.current += 1;
-        Some((current_tuple, current_rid))
+        None
     }
 
     fn output_schema(&self) -> &Schema {
@@ -100,7 +167,12 @@ impl Execute for IdxScanExecutor {
         drop(index);
 
         format!(
-            "IdxScan | Schema: {} | Table: {}({}) | Index: {}({}) - {}",
+            "IdxScan{} | Schema: {} | Table: {}({}) | Index: {}({}) - {}",
+            if self.is_index_only() {
+                " (index only)"
+            } else {
+                ""
+            },
             self.output_schema().to_string(),
             table_name,
             table_oid,
@@ -113,19 +185,37 @@ impl Execute for IdxScanExecutor {
 
 #[cfg(test)]
 mod tests {
-    use std::{env::temp_dir, fs::remove_file};
+    use std::{env::temp_dir, fs::remove_file, sync::Arc};
 
     use crate::{
+        catalog::Catalog,
+        disk::buffer_pool_manager::BufferPoolManager,
         exec::{
-            executor::{Execute, Executor},
-            plan::PlanNode,
+            executor::{
+                util::insert_tuple_in_table_and_indexes, Execute, Executor, ExecutorContext,
+            },
+            expression::{
+                boolean::{BooleanExpression, BooleanType},
+                constant::ConstantExpression,
+                string_function::{StringFunction, StringFunctionExpression},
+                Expression,
+            },
+            plan::{idx_scan::IdxScanPlanNode, PlanNode},
         },
+        index::IndexMeta,
         sample_code::executors::{
             idx_scan_executor, projection_executor, seq_scan_executor, TableConstructorType,
         },
-        test_utils::int_value,
+        table::{
+            schema::{ColumnType, Schema},
+            tuple::Tuple,
+            value::{ColumnValue, VarcharValue},
+        },
+        test_utils::{bool_value, column_with, const_bool, const_int, int_value},
     };
 
+    use super::IdxScanExecutor;
+
     #[test]
     fn idx_scan() {
         // init
@@ -167,4 +257,214 @@ mod tests {
         // cleanup
         remove_file(db_path).expect("Couldn't remove test DB file");
     }
+
+    #[test]
+    fn idx_scan_index_only() {
+        // init
+        let db_path = temp_dir().join("idx_scan_idx_scan_index_only.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+
+        // test
+        let run = |columns: Vec<usize>| {
+            let (mut idx_scan, table_context) =
+                idx_scan_executor(TableConstructorType::WithTable(db_file_path.clone()));
+            idx_scan.plan.columns = Some(columns);
+            let schema = table_context.1;
+
+            idx_scan.init();
+            let description = idx_scan.to_string(0);
+            let mut rows = vec![];
+            while let Some((tuple, _)) = idx_scan.next() {
+                rows.push(
+                    (0..3)
+                        .map(|i| tuple.get_value(&schema, i))
+                        .collect::<Vec<_>>(),
+                );
+            }
+
+            remove_file(&db_path).expect("Couldn't remove test DB file");
+            (description, rows)
+        };
+
+        // the key has the only column read, so the rows are built from the keys
+        let (description, rows) = run(vec![0]);
+        assert!(description.starts_with("IdxScan (index only)"));
+        assert_eq!(rows.len(), 3);
+        for row in rows {
+            assert_eq!(
+                row,
+                vec![
+                    int_value(2),
+                    ColumnValue::Null(ColumnType::Boolean),
+                    ColumnValue::Null(ColumnType::Decimal)
+                ]
+            );
+        }
+
+        // other columns are read from the table
+        let (description, rows) = run(vec![0, 2]);
+        assert!(description.starts_with("IdxScan |"));
+        assert_eq!(rows.len(), 3);
+        assert!(rows
+            .iter()
+            .all(|row| row[0] == int_value(2) && !row[2].is_null()));
+    }
+
+    #[test]
+    fn idx_scan_expression_and_partial_indexes() {
+        // init
+        let db_path = temp_dir().join("idx_scan_idx_scan_expression_and_partial_indexes.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 10, 2));
+        bpm.new_page(); // this is needed as table heaps assume page with PID 0 is not used
+        let catalog = Arc::new(Catalog::new(bpm.clone()));
+        let context = ExecutorContext::new(catalog.clone(), bpm);
+
+        let schema = Schema::with_types(vec![
+            ColumnType::Integer,
+            ColumnType::Varchar(10),
+            ColumnType::Boolean,
+        ]);
+        let table_oid = catalog
+            .create_table("t", schema.clone())
+            .unwrap()
+            .lock()
+            .unwrap()
+            .oid;
+
+        let name = || column_with(1, ColumnType::Varchar(10));
+        let lower_name = || {
+            Expression::StringFunction(StringFunctionExpression {
+                function: StringFunction::Lower,
+                expr: Box::new(name()),
+            })
+        };
+        let varchar = |value: &str| {
+            ColumnValue::Varchar(VarcharValue {
+                value: value.to_string(),
+                length: 10,
+            })
+        };
+        let condition = |left: Expression, right: Expression, typ: BooleanType| BooleanExpression {
+            left: Box::new(left),
+            right: Box::new(right),
+            typ,
+        };
+        let and = |left: BooleanExpression, right: BooleanExpression| {
+            condition(
+                Expression::Boolean(left),
+                Expression::Boolean(right),
+                BooleanType::And,
+            )
+        };
+        let is_active = || {
+            condition(
+                column_with(2, ColumnType::Boolean),
+                const_bool(true),
+                BooleanType::EQ,
+            )
+        };
+        let has_id = |id| {
+            condition(
+                column_with(0, ColumnType::Integer),
+                const_int(id),
+                BooleanType::EQ,
+            )
+        };
+
+        // (LOWER(name)) and (id) WHERE active = true
+        let name_schema = Schema::with_types(vec![ColumnType::Varchar(10)]);
+        let meta = IndexMeta::with_expressions(
+            name_schema.clone(),
+            "t_lower_name".to_string(),
+            vec![lower_name()],
+        );
+        catalog
+            .create_index_with_meta(meta, "t", schema.clone(), name_schema.get_tuple_len())
+            .unwrap();
+        let id_schema = Schema::with_types(vec![ColumnType::Integer]);
+        let meta = IndexMeta::new(id_schema.clone(), "t_active_id".to_string(), vec![0])
+            .with_predicate(Expression::Boolean(is_active()));
+        catalog
+            .create_index_with_meta(meta, "t", schema.clone(), id_schema.get_tuple_len())
+            .unwrap();
+
+        {
+            let table_info = catalog.get_table_by_oid(table_oid).unwrap();
+            let mut table_info = table_info.lock().unwrap();
+            let index_infos = catalog.get_table_indexes("t");
+            let index_infos = index_infos
+                .iter()
+                .map(|i| i.lock().unwrap())
+                .collect::<Vec<_>>();
+            for (id, name, active) in [(1, "Alice", true), (2, "BOB", false), (3, "bob", true)] {
+                let tuple = Tuple::new(
+                    vec![int_value(id), varchar(name), bool_value(active)],
+                    &schema,
+                );
+                insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, tuple).unwrap();
+            }
+        }
+
+        // test
+        let scan = |filter_expr: BooleanExpression| {
+            let plan = IdxScanPlanNode {
+                output_schema: schema.clone(),
+                table_oid,
+                table_name: "t".to_string(),
+                filter_expr,
+                columns: None,
+            };
+            let mut idx_scan = IdxScanExecutor::new(context.clone(), plan);
+
+            idx_scan.init();
+            let mut ids = vec![];
+            while let Some((tuple, _)) = idx_scan.next() {
+                ids.push(tuple.get_value(&schema, 0));
+            }
+
+            let index_name = idx_scan.index.lock().unwrap().name.clone();
+            (index_name, ids)
+        };
+
+        // the key is looked up by the expression
+        let bob = Expression::Constant(ConstantExpression {
+            value: varchar("bob"),
+        });
+        assert_eq!(
+            scan(condition(lower_name(), bob.clone(), BooleanType::EQ)),
+            ("t_lower_name".to_string(), vec![int_value(2), int_value(3)])
+        );
+
+        // the partial index is used if the filter has its predicate
+        assert_eq!(
+            scan(and(has_id(3), is_active())),
+            ("t_active_id".to_string(), vec![int_value(3)])
+        );
+        assert_eq!(
+            scan(and(is_active(), has_id(2))),
+            ("t_active_id".to_string(), vec![])
+        );
+        assert!(catalog
+            .get_table_index_by_expression("t", &column_with(0, ColumnType::Integer), &[])
+            .is_none());
+
+        // the other conditions are checked on the rows found
+        assert_eq!(
+            scan(and(
+                condition(lower_name(), bob, BooleanType::EQ),
+                condition(
+                    name(),
+                    Expression::Constant(ConstantExpression {
+                        value: varchar("BOB")
+                    }),
+                    BooleanType::EQ
+                ),
+            )),
+            ("t_lower_name".to_string(), vec![int_value(2)])
+        );
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
 }
@@ -12,7 +12,8 @@ use crate::{
 };
 
 use super::{
-    util::{insert_tuple_in_table_and_indexes, int_tuple},
+    referential::check_foreign_keys,
+    util::{apply_defaults, check_constraints, insert_tuple_in_table_and_indexes, int_tuple},
     Execute, Executor, ExecutorContext,
 };
 
@@ -22,23 +23,51 @@ pub struct InsertExecutor {
     pub child: Box<Executor>,
     /// Whether the executor has already inserted the tuples or not.
     inserted: bool,
+    /// Needed for undoing the inserted tuples when the statement fails.
+    context: ExecutorContext,
+    /// Length of the undo log when the executor started.
+    savepoint: usize,
+    /// Why the insert failed, if it did.
+    error: Option<String>,
 }
 
 impl InsertExecutor {
     pub fn new(context: ExecutorContext, plan: InsertPlanNode, child: Executor) -> Self {
         Self {
             plan,
-            catalog: context.catalog,
+            catalog: context.catalog.clone(),
             child: Box::new(child),
             inserted: false,
+            context,
+            savepoint: 0,
+            error: None,
         }
     }
+
+    /// Returns why the insert failed, `None` if it didn't.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Undoes the tuples inserted by the executor and records the error.
+    fn fail(&mut self, error: String) -> Option<(Tuple, RID)> {
+        self.context
+            .undo_log
+            .lock()
+            .unwrap()
+            .rollback_to(&self.catalog, self.savepoint);
+        self.error = Some(format!("Can't insert tuple: {}", error));
+        self.inserted = true;
+        None
+    }
 }
 
 impl Execute for InsertExecutor {
     fn init(&mut self) {
         self.child.init();
         self.inserted = false;
+        self.savepoint = self.context.undo_log.lock().unwrap().savepoint();
+        self.error = None;
     }
 
     /// Inserts tuples into the table and returns the number of inserted tuples.
@@ -46,6 +75,10 @@ impl Execute for InsertExecutor {
     /// # Assumption
     /// This executor assumes that the input tuple have the same schema as the table where we are inserting. This should
     /// be granted by the user of this executor (i.e. the planner).
+    ///
+    /// # Errors
+    /// Returns `None` without inserting anything if a tuple violates a constraint of the table (nulls are replaced with
+    /// column defaults first). The tuples already inserted are removed and the violation is returned by `error`.
     fn next(&mut self) -> Option<(Tuple, RID)> {
         if self.inserted {
             return None;
@@ -57,13 +90,38 @@ impl Execute for InsertExecutor {
                 .catalog
                 .get_table_with_indexes(self.plan.table_oid, &self.plan.table_name);
 
-            let mut table_info = table_info.lock().unwrap();
-            let index_infos = index_infos
-                .iter()
-                .map(|i| i.lock().unwrap())
-                .collect::<Vec<_>>();
+            // foreign keys are checked before locking the table and its indexes, since they lock the parent indexes
+            let (tuple, foreign_keys, schema) = {
+                let table_info = table_info.lock().unwrap();
+                let tuple = apply_defaults(tuple, &table_info.schema);
+                (tuple, table_info.foreign_keys(), table_info.schema.clone())
+            };
+            if let Err(e) = check_foreign_keys(&self.catalog, &foreign_keys, &schema, &tuple) {
+                return self.fail(e);
+            }
+
+            // the locks are released at the end of the block, before undoing anything
+            let result = {
+                let mut table_info = table_info.lock().unwrap();
+                let index_infos = index_infos
+                    .iter()
+                    .map(|i| i.lock().unwrap())
+                    .collect::<Vec<_>>();
+
+                check_constraints(&table_info, &index_infos, &tuple, None).and_then(|_| {
+                    insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, tuple)
+                })
+            };
 
-            insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, tuple);
+            match result {
+                Ok(rid) => self
+                    .context
+                    .undo_log
+                    .lock()
+                    .unwrap()
+                    .record_insert(self.plan.table_oid, rid),
+                Err(e) => return self.fail(e),
+            }
 
             inserted_tuples += 1;
         }
@@ -103,12 +161,14 @@ mod tests {
             executor::{Execute, Executor},
             plan::PlanNode,
         },
+        index::IndexMeta,
         sample_code::executors::{insert_executor, values_executor},
         table::{
             schema::{ColumnType, Schema},
             tuple::Tuple,
             value::{ColumnValue, DecimalValue},
         },
+        test_utils::int_value,
     };
 
     #[test]
@@ -182,4 +242,66 @@ mod tests {
         // cleanup
         remove_file(db_path).expect("Couldn't remove test DB file");
     }
+
+    #[test]
+    fn insert_executor_undoes_failed_statement() {
+        // init
+        let db_path = temp_dir().join("insert_insert_executor_undoes_failed_statement.db");
+        let (first_values_executor, values_schema) = values_executor(vec![4, 5, 3, 6]);
+        let (mut insert_executor, insert_schema, catalog) = insert_executor(
+            db_path.to_str().unwrap().to_string(),
+            PlanNode::Values(first_values_executor.plan.clone()),
+            Executor::Values(first_values_executor),
+        );
+        let table_name = insert_executor.plan.table_name.clone();
+        let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+        let meta = IndexMeta::new(key_schema.clone(), "first_col".to_string(), vec![0]).unique();
+        let index_info = catalog
+            .create_index_with_meta(
+                meta,
+                &table_name,
+                values_schema.clone(),
+                key_schema.get_tuple_len(),
+            )
+            .unwrap();
+
+        // test
+        insert_executor.init();
+        assert_eq!(insert_executor.next(), None);
+        assert_eq!(
+            insert_executor.error(),
+            Some("Can't insert tuple: UNIQUE constraint \"first_col\" violated: key { 3 } already exists in table \"test_table\"")
+        );
+
+        // rows inserted before the violation are gone from the table and the index
+        let table_info = catalog.get_table_by_name(&table_name).unwrap();
+        let table_info = table_info.lock().unwrap();
+        let values = table_info
+            .table
+            .sequencial_dump()
+            .into_iter()
+            .filter(|(meta, _)| !meta.is_deleted)
+            .map(|(_, tuple)| tuple.get_value(&values_schema, 0))
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![int_value(1), int_value(2), int_value(3)]);
+        drop(table_info);
+
+        let index = index_info.lock().unwrap();
+        assert!(index
+            .index
+            .scan(Tuple::new(vec![int_value(4)], &key_schema))
+            .is_empty());
+        drop(index);
+
+        // the table can still be written
+        let (second_values_executor, _) = values_executor(vec![4]);
+        *insert_executor.child = Executor::Values(second_values_executor);
+        insert_executor.init();
+        let (tuple, _) = insert_executor.next().unwrap();
+        assert_eq!(tuple.get_value(&insert_schema, 0), int_value(1));
+        assert_eq!(insert_executor.error(), None);
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
 }
@@ -1,18 +1,24 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use delete::DeleteExecutor;
 use filter::FilterExecutor;
 use insert::InsertExecutor;
 use projection::ProjectionExecutor;
 use seq_scan::SeqScanExecutor;
+use undo::UndoLog;
 use update::UpdateExecutor;
 use values::ValuesExecutor;
 
 use crate::{
     catalog::Catalog,
     disk::buffer_pool_manager::BufferPoolManager,
-    exec::executor::idx_scan::IdxScanExecutor,
+    exec::executor::{
+        hash_join::HashJoinExecutor, idx_nested_loop_join::IdxNestedLoopJoinExecutor,
+        idx_range_scan::IdxRangeScanExecutor, idx_scan::IdxScanExecutor,
+        nested_loop_join::NestedLoopJoinExecutor, sort::SortExecutor, text_scan::TextScanExecutor,
+    },
     table::{
+        datetime,
         schema::Schema,
         tuple::{Tuple, RID},
     },
@@ -23,10 +29,19 @@ pub mod tests;
 
 pub mod delete;
 pub mod filter;
+pub mod hash_join;
+pub mod idx_nested_loop_join;
+pub mod idx_range_scan;
 pub mod idx_scan;
 pub mod insert;
+pub mod nested_loop_join;
 pub mod projection;
+pub mod referential;
 pub mod seq_scan;
+pub mod sort;
+mod spill;
+pub mod text_scan;
+pub mod undo;
 pub mod update;
 pub mod util;
 pub mod values;
@@ -35,6 +50,22 @@ pub mod values;
 pub struct ExecutorContext {
     pub catalog: Arc<Catalog>,
     pub bpm: Arc<BufferPoolManager>,
+    /// Time at which the statement started, returned by `NOW()` for the whole statement
+    pub statement_timestamp: i64,
+    /// Changes made by the statement, undone when one of its executors fails
+    pub undo_log: Arc<Mutex<UndoLog>>,
+}
+
+impl ExecutorContext {
+    /// Creates the context for executing a new statement.
+    pub fn new(catalog: Arc<Catalog>, bpm: Arc<BufferPoolManager>) -> Self {
+        Self {
+            catalog,
+            bpm,
+            statement_timestamp: datetime::now(),
+            undo_log: Arc::new(Mutex::new(UndoLog::default())),
+        }
+    }
 }
 
 pub trait Execute {
@@ -53,6 +84,25 @@ pub enum Executor {
     Delete(DeleteExecutor),
     Update(UpdateExecutor),
     IdxScan(IdxScanExecutor),
+    IdxRangeScan(IdxRangeScanExecutor),
+    TextScan(TextScanExecutor),
+    NestedLoopJoin(NestedLoopJoinExecutor),
+    IdxNestedLoopJoin(IdxNestedLoopJoinExecutor),
+    HashJoin(HashJoinExecutor),
+    Sort(SortExecutor),
+}
+
+impl Executor {
+    /// Returns why the executor stopped without producing its result, `None` if it didn't fail. Only the executors
+    /// that write to tables can fail, after undoing the changes they made.
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Executor::Insert(executor) => executor.error(),
+            Executor::Delete(executor) => executor.error(),
+            Executor::Update(executor) => executor.error(),
+            _ => None,
+        }
+    }
 }
 
 impl Execute for Executor {
@@ -66,6 +116,12 @@ impl Execute for Executor {
             Executor::Delete(executor) => executor.init(),
             Executor::Update(executor) => executor.init(),
             Executor::IdxScan(executor) => executor.init(),
+            Executor::IdxRangeScan(executor) => executor.init(),
+            Executor::TextScan(executor) => executor.init(),
+            Executor::NestedLoopJoin(executor) => executor.init(),
+            Executor::IdxNestedLoopJoin(executor) => executor.init(),
+            Executor::HashJoin(executor) => executor.init(),
+            Executor::Sort(executor) => executor.init(),
         }
     }
 
@@ -79,6 +135,12 @@ impl Execute for Executor {
             Executor::Delete(executor) => executor.next(),
             Executor::Update(executor) => executor.next(),
             Executor::IdxScan(executor) => executor.next(),
+            Executor::IdxRangeScan(executor) => executor.next(),
+            Executor::TextScan(executor) => executor.next(),
+            Executor::NestedLoopJoin(executor) => executor.next(),
+            Executor::IdxNestedLoopJoin(executor) => executor.next(),
+            Executor::HashJoin(executor) => executor.next(),
+            Executor::Sort(executor) => executor.next(),
         }
     }
 
@@ -92,6 +154,12 @@ impl Execute for Executor {
             Executor::Delete(executor) => executor.output_schema(),
             Executor::Update(executor) => executor.output_schema(),
             Executor::IdxScan(executor) => executor.output_schema(),
+            Executor::IdxRangeScan(executor) => executor.output_schema(),
+            Executor::TextScan(executor) => executor.output_schema(),
+            Executor::NestedLoopJoin(executor) => executor.output_schema(),
+            Executor::IdxNestedLoopJoin(executor) => executor.output_schema(),
+            Executor::HashJoin(executor) => executor.output_schema(),
+            Executor::Sort(executor) => executor.output_schema(),
         }
     }
 
@@ -105,6 +173,12 @@ impl Execute for Executor {
             Executor::Delete(executor) => executor.to_string(indent_level),
             Executor::Update(executor) => executor.to_string(indent_level),
             Executor::IdxScan(executor) => executor.to_string(indent_level),
+            Executor::IdxRangeScan(executor) => executor.to_string(indent_level),
+            Executor::TextScan(executor) => executor.to_string(indent_level),
+            Executor::NestedLoopJoin(executor) => executor.to_string(indent_level),
+            Executor::IdxNestedLoopJoin(executor) => executor.to_string(indent_level),
+            Executor::HashJoin(executor) => executor.to_string(indent_level),
+            Executor::Sort(executor) => executor.to_string(indent_level),
         }
     }
 }
@@ -0,0 +1,305 @@
+use crate::{
+    exec::{
+        expression::{boolean::BooleanExpression, Evaluate},
+        plan::{nested_loop_join::NestedLoopJoinPlanNode, AbstractPlanNode},
+    },
+    table::{
+        schema::{ColumnType, Schema},
+        tuple::{Tuple, RID},
+        value::ColumnValue,
+    },
+};
+
+use super::{Execute, Executor};
+
+/// Returns `true` if the join `predicate` is true for the pair of rows.
+///
+/// # Panics
+/// Will panic if the predicate doesn't evaluate to a boolean.
+pub(super) fn is_match(
+    predicate: &BooleanExpression,
+    left: &Tuple,
+    left_schema: &Schema,
+    right: &Tuple,
+    right_schema: &Schema,
+) -> bool {
+    match predicate.evaluate_join(left, left_schema, right, right_schema) {
+        ColumnValue::Boolean(value) => value.value,
+        // unknown doesn't match
+        ColumnValue::Null(ColumnType::Boolean) => false,
+        _ => panic!("Join predicate did not evaluate to a boolean value"),
+    }
+}
+
+/// Builds a row of a join from the values of the `left` and `right` rows, padding a missing side with `NULL`s.
+pub(super) fn join_rows(
+    left: Option<&Tuple>,
+    left_schema: &Schema,
+    right: Option<&Tuple>,
+    right_schema: &Schema,
+    output_schema: &Schema,
+) -> Tuple {
+    let values_of = |tuple: Option<&Tuple>, schema: &Schema| {
+        (0..schema.get_cols_count())
+            .map(|i| match tuple {
+                Some(tuple) => tuple.get_value(schema, i),
+                None => ColumnValue::Null(schema.get_col_type(i)),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut values = values_of(left, left_schema);
+    values.extend(values_of(right, right_schema));
+    Tuple::new(values, output_schema)
+}
+
+/// Nested loop join of two children. The rows of the right child are read once, when the join is initialized, and kept
+/// in memory; each row of the left child is then compared with all of them.
+pub struct NestedLoopJoinExecutor {
+    pub plan: NestedLoopJoinPlanNode,
+    pub left: Box<Executor>,
+    pub right: Box<Executor>,
+    /// Rows of the right child, with whether they matched a left row
+    right_rows: Vec<(Tuple, bool)>,
+    /// Left row being joined, with whether it matched a right row
+    current_left: Option<(Tuple, bool)>,
+    /// Index of the next right row to compare with the current left row, or to return without a match once the left
+    /// child is exhausted
+    current_right: usize,
+    left_exhausted: bool,
+}
+
+impl NestedLoopJoinExecutor {
+    pub fn new(plan: NestedLoopJoinPlanNode, left: Executor, right: Executor) -> Self {
+        Self {
+            plan,
+            left: Box::new(left),
+            right: Box::new(right),
+            right_rows: vec![],
+            current_left: None,
+            current_right: 0,
+            left_exhausted: false,
+        }
+    }
+
+    /// Returns the next right row that didn't match any left row, for right and full outer joins.
+    fn next_unmatched_right(&mut self) -> Option<(Tuple, RID)> {
+        if !self.plan.join_type.keeps_right() {
+            return None;
+        }
+
+        while self.current_right < self.right_rows.len() {
+            let (right, matched) = &self.right_rows[self.current_right];
+            self.current_right += 1;
+
+            if !matched {
+                let row = join_rows(
+                    None,
+                    self.left.output_schema(),
+                    Some(right),
+                    self.right.output_schema(),
+                    &self.plan.output_schema,
+                );
+                return Some((row, RID::invalid()));
+            }
+        }
+
+        None
+    }
+}
+
+impl Execute for NestedLoopJoinExecutor {
+    fn init(&mut self) {
+        self.left.init();
+        self.right.init();
+
+        self.right_rows.clear();
+        while let Some((tuple, _)) = self.right.next() {
+            self.right_rows.push((tuple, false));
+        }
+        self.current_left = None;
+        self.current_right = 0;
+        self.left_exhausted = false;
+    }
+
+    fn next(&mut self) -> Option<(Tuple, RID)> {
+        loop {
+            if self.left_exhausted {
+                return self.next_unmatched_right();
+            }
+
+            let Some((left, left_matched)) = self.current_left.as_mut() else {
+                match self.left.next() {
+                    Some((tuple, _)) => self.current_left = Some((tuple, false)),
+                    None => {
+                        self.left_exhausted = true;
+                        self.current_right = 0;
+                    }
+                }
+                continue;
+            };
+
+            let left_schema = self.left.output_schema();
+            let right_schema = self.right.output_schema();
+            while self.current_right < self.right_rows.len() {
+                let (right, right_matched) = &mut self.right_rows[self.current_right];
+                self.current_right += 1;
+
+                if is_match(&self.plan.predicate, left, left_schema, right, right_schema) {
+                    *left_matched = true;
+                    *right_matched = true;
+                    let row = join_rows(
+                        Some(left),
+                        left_schema,
+                        Some(right),
+                        right_schema,
+                        &self.plan.output_schema,
+                    );
+                    return Some((row, RID::invalid()));
+                }
+            }
+
+            // all the right rows were compared with the left row
+            let (left, left_matched) = self.current_left.take().unwrap();
+            self.current_right = 0;
+            if !left_matched && self.plan.join_type.keeps_left() {
+                let row = join_rows(
+                    Some(&left),
+                    self.left.output_schema(),
+                    None,
+                    self.right.output_schema(),
+                    &self.plan.output_schema,
+                );
+                return Some((row, RID::invalid()));
+            }
+        }
+    }
+
+    fn output_schema(&self) -> &Schema {
+        self.plan.get_output_schema()
+    }
+
+    fn to_string(&self, indent_level: usize) -> String {
+        let self_string = format!(
+            "NestedLoopJoin | Schema: {} | Type: {} | Predicate: {}",
+            self.output_schema().to_string(),
+            self.plan.join_type,
+            self.plan.predicate.to_string()
+        );
+
+        let tabs = "\t".repeat(indent_level + 1);
+        format!(
+            "{}\n{}-> {}\n{}-> {}",
+            self_string,
+            tabs,
+            self.left.to_string(indent_level + 1),
+            tabs,
+            self.right.to_string(indent_level + 1)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::remove_file};
+
+    use crate::{
+        exec::{
+            executor::{seq_scan::SeqScanExecutor, Execute, Executor, ExecutorContext},
+            expression::boolean::{BooleanExpression, BooleanType},
+            plan::{
+                nested_loop_join::{JoinType, NestedLoopJoinPlanNode},
+                seq_scan::SeqScanPlanNode,
+                PlanNode,
+            },
+        },
+        sample_code::util::create_join_tables,
+        table::{schema::ColumnType, value::ColumnValue},
+        test_utils::{column_with, right_column_with},
+    };
+
+    use super::NestedLoopJoinExecutor;
+
+    fn seq_scan(context: &ExecutorContext, table_name: &str) -> (PlanNode, Executor) {
+        let table = context.catalog.get_table_by_name(table_name).unwrap();
+        let table = table.lock().unwrap();
+        let plan = SeqScanPlanNode {
+            output_schema: table.schema.clone(),
+            table_oid: table.oid,
+            table_name: table_name.to_string(),
+            filter_expr: None,
+        };
+
+        (
+            PlanNode::SeqScan(plan.clone()),
+            Executor::SeqScan(SeqScanExecutor::new(context.clone(), plan)),
+        )
+    }
+
+    /// Returns the rows of the join as `(users.id, orders.user_id, orders.amount)`, `None` for nulls.
+    fn join(context: &ExecutorContext, join_type: JoinType) -> Vec<[Option<i32>; 3]> {
+        let (users_plan, users) = seq_scan(context, "users");
+        let (orders_plan, orders) = seq_scan(context, "orders");
+        // users.id = orders.user_id
+        let predicate = BooleanExpression {
+            left: Box::new(column_with(0, ColumnType::Integer)),
+            right: Box::new(right_column_with(0, ColumnType::Integer)),
+            typ: BooleanType::EQ,
+        };
+        let plan = NestedLoopJoinPlanNode::new(join_type, predicate, users_plan, orders_plan);
+        let mut executor = NestedLoopJoinExecutor::new(plan, users, orders);
+
+        executor.init();
+        let schema = executor.output_schema().clone();
+        let mut rows = vec![];
+        while let Some((tuple, _)) = executor.next() {
+            rows.push([0, 1, 2].map(|i| match tuple.get_value(&schema, i) {
+                ColumnValue::Integer(value) => Some(value.value),
+                value => {
+                    assert!(value.is_null());
+                    None
+                }
+            }));
+        }
+        rows
+    }
+
+    #[test]
+    fn nested_loop_join() {
+        // init
+        let db_path = temp_dir().join("nested_loop_join_nested_loop_join.db");
+        let context = create_join_tables(db_path.to_str().unwrap().to_string());
+
+        // test
+        let matches = vec![
+            [Some(1), Some(1), Some(10)],
+            [Some(1), Some(1), Some(11)],
+            [Some(3), Some(3), Some(30)],
+        ];
+        let user_without_orders = [Some(2), None, None];
+        let order_without_user = [None, Some(4), Some(40)];
+
+        assert_eq!(join(&context, JoinType::Inner), matches);
+        assert_eq!(
+            join(&context, JoinType::Left),
+            vec![matches[0], matches[1], user_without_orders, matches[2]]
+        );
+        assert_eq!(
+            join(&context, JoinType::Right),
+            [matches.clone(), vec![order_without_user]].concat()
+        );
+        assert_eq!(
+            join(&context, JoinType::Outer),
+            vec![
+                matches[0],
+                matches[1],
+                user_without_orders,
+                matches[2],
+                order_without_user
+            ]
+        );
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+}
@@ -0,0 +1,634 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    catalog::{
+        constraint::{ForeignKey, ReferentialAction},
+        info::IndexInfo,
+        Catalog, OID,
+    },
+    exec::{
+        expression::{
+            boolean::{BooleanExpression, BooleanType},
+            constant::ConstantExpression,
+            value::{ColumnValueExpression, JoinSide},
+            Expression,
+        },
+        plan::{
+            delete::DeletePlanNode, seq_scan::SeqScanPlanNode, update::UpdatePlanNode, PlanNode,
+        },
+    },
+    table::{schema::Schema, tuple::Tuple, value::ColumnValue},
+};
+
+use super::{
+    delete::DeleteExecutor, seq_scan::SeqScanExecutor, update::UpdateExecutor, Execute, Executor,
+    ExecutorContext,
+};
+
+/// Checks that `foreign_keys` of `tuple` (to be written in a table with `schema`) reference existing rows of their
+/// parent tables. Keys containing nulls don't reference anything and always pass.
+///
+/// The indexes of the parent tables are locked, so this must be called before locking the table being written and its
+/// indexes (the catalog locks tables before their indexes).
+///
+/// # Errors
+/// Will return `Err` with a description of the first violated foreign key.
+pub fn check_foreign_keys(
+    catalog: &Catalog,
+    foreign_keys: &[ForeignKey],
+    schema: &Schema,
+    tuple: &Tuple,
+) -> Result<(), String> {
+    for foreign_key in foreign_keys.iter() {
+        let Some(values) = key_values(tuple, schema, &foreign_key.columns) else {
+            continue;
+        };
+
+        let index_info = parent_index(catalog, foreign_key);
+        let index_info = index_info.lock().unwrap();
+        let key_schema = index_info.index.meta().key_schema();
+        let key = Tuple::new(values, key_schema);
+        let key_string = key.to_string(key_schema);
+
+        if index_info.index.scan(key).is_empty() {
+            return Err(format!(
+                "FOREIGN KEY constraint \"{}\" violated: key {} is not present in table \"{}\"",
+                foreign_key.name, key_string, foreign_key.parent_name
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that no rows reference `old_tuple` of the table with `table_oid` through foreign keys with the `RESTRICT`
+/// action, before the tuple is deleted (`new_tuple` is `None`) or its key is updated to the one of `new_tuple`.
+///
+/// # Errors
+/// Will return `Err` with a description of the first violated foreign key.
+pub fn check_restricted_references(
+    context: &ExecutorContext,
+    table_oid: OID,
+    schema: &Schema,
+    old_tuple: &Tuple,
+    new_tuple: Option<&Tuple>,
+) -> Result<(), String> {
+    for (child_oid, foreign_key) in context.catalog.get_referencing_foreign_keys(table_oid) {
+        let Some((action, old_key)) = changed_key(&foreign_key, schema, old_tuple, new_tuple)
+        else {
+            continue;
+        };
+        if *action != ReferentialAction::Restrict {
+            continue;
+        }
+
+        let (plan, child_name) = referencing_rows_plan(context, child_oid, &foreign_key, &old_key);
+        let mut scan = SeqScanExecutor::new(context.clone(), plan);
+        scan.init();
+
+        if scan.next().is_some() {
+            let key_string = old_key
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(" , ");
+
+            return Err(format!(
+                "FOREIGN KEY constraint \"{}\" violated: key {{ {} }} is still referenced from table \"{}\"",
+                foreign_key.name, key_string, child_name
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies the `CASCADE` and `SET NULL` actions of the foreign keys referencing `old_tuple` of the table with
+/// `table_oid`, after it was deleted (`new_tuple` is `None`) or updated to `new_tuple`. The referencing rows are
+/// changed by running delete and update executors on their tables.
+///
+/// # Errors
+/// Will return `Err` with the error of the executor if changing the referencing rows violates constraints of their
+/// tables. The changes of that executor are already undone, the ones of the caller are left to it.
+pub fn apply_referential_actions(
+    context: &ExecutorContext,
+    table_oid: OID,
+    schema: &Schema,
+    old_tuple: &Tuple,
+    new_tuple: Option<&Tuple>,
+) -> Result<(), String> {
+    for (child_oid, foreign_key) in context.catalog.get_referencing_foreign_keys(table_oid) {
+        let Some((action, old_key)) = changed_key(&foreign_key, schema, old_tuple, new_tuple)
+        else {
+            continue;
+        };
+
+        let (plan, child_name) = referencing_rows_plan(context, child_oid, &foreign_key, &old_key);
+        let scan = SeqScanExecutor::new(context.clone(), plan.clone());
+
+        let mut executor = match (action, new_tuple) {
+            (ReferentialAction::Restrict, _) => continue,
+            (ReferentialAction::Cascade, None) => {
+                let plan = DeletePlanNode::new(child_oid, child_name, PlanNode::SeqScan(plan));
+                Executor::Delete(DeleteExecutor::new(
+                    context.clone(),
+                    plan,
+                    Executor::SeqScan(scan),
+                ))
+            }
+            (ReferentialAction::Cascade, Some(new_tuple)) => {
+                let new_key = foreign_key
+                    .parent_columns
+                    .iter()
+                    .map(|c| new_tuple.get_value(schema, *c))
+                    .collect::<Vec<_>>();
+                let expressions = set_key_expressions(&plan.output_schema, &foreign_key, new_key);
+                let plan = UpdatePlanNode::new(
+                    child_oid,
+                    child_name,
+                    expressions,
+                    PlanNode::SeqScan(plan),
+                );
+                Executor::Update(UpdateExecutor::new(
+                    context.clone(),
+                    plan,
+                    Executor::SeqScan(scan),
+                ))
+            }
+            (ReferentialAction::SetNull, _) => {
+                let nulls = foreign_key
+                    .columns
+                    .iter()
+                    .map(|c| ColumnValue::Null(plan.output_schema.get_col_type(*c)))
+                    .collect::<Vec<_>>();
+                let expressions = set_key_expressions(&plan.output_schema, &foreign_key, nulls);
+                let plan = UpdatePlanNode::new(
+                    child_oid,
+                    child_name,
+                    expressions,
+                    PlanNode::SeqScan(plan),
+                );
+                Executor::Update(UpdateExecutor::new(
+                    context.clone(),
+                    plan,
+                    Executor::SeqScan(scan),
+                ))
+            }
+        };
+
+        executor.init();
+        if executor.next().is_none() {
+            return Err(executor.error().unwrap_or_default().to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the action to apply and the referenced key of `old_tuple`, `None` if the key contains nulls or is not
+/// changed by the update to `new_tuple`.
+fn changed_key<'a>(
+    foreign_key: &'a ForeignKey,
+    schema: &Schema,
+    old_tuple: &Tuple,
+    new_tuple: Option<&Tuple>,
+) -> Option<(&'a ReferentialAction, Vec<ColumnValue>)> {
+    let old_key = key_values(old_tuple, schema, &foreign_key.parent_columns)?;
+
+    match new_tuple {
+        None => Some((&foreign_key.on_delete, old_key)),
+        Some(new_tuple) => {
+            let unchanged = foreign_key
+                .parent_columns
+                .iter()
+                .zip(old_key.iter())
+                .all(|(c, old)| new_tuple.get_value(schema, *c) == *old);
+
+            (!unchanged).then_some((&foreign_key.on_update, old_key))
+        }
+    }
+}
+
+/// Returns the values of `columns` of `tuple`, `None` if any of them is null.
+fn key_values(tuple: &Tuple, schema: &Schema, columns: &[usize]) -> Option<Vec<ColumnValue>> {
+    let values = columns
+        .iter()
+        .map(|c| tuple.get_value(schema, *c))
+        .collect::<Vec<_>>();
+
+    (!values.iter().any(|v| v.is_null())).then_some(values)
+}
+
+/// Returns the unique index on the columns referenced by `foreign_key`.
+///
+/// # Panics
+/// Will panic if the index doesn't exist (it's checked when the foreign key is created).
+fn parent_index(catalog: &Catalog, foreign_key: &ForeignKey) -> Arc<Mutex<IndexInfo>> {
+    catalog
+        .get_table_indexes(&foreign_key.parent_name)
+        .into_iter()
+        .find(|i| {
+            let index_info = i.lock().unwrap();
+            let meta = index_info.index.meta();
+            index_info.is_valid()
+                && meta.is_unique()
+                && meta.is_on_columns(&foreign_key.parent_columns)
+        })
+        .unwrap_or_else(|| panic!("Missing unique index for foreign key {}", foreign_key.name))
+}
+
+/// Returns the plan scanning the rows of the table with `child_oid` that reference `key` through `foreign_key`, along
+/// with the name of the table.
+fn referencing_rows_plan(
+    context: &ExecutorContext,
+    child_oid: OID,
+    foreign_key: &ForeignKey,
+    key: &[ColumnValue],
+) -> (SeqScanPlanNode, String) {
+    let child = context
+        .catalog
+        .get_table_by_oid(child_oid)
+        .expect("Foreign key of non-existing table");
+    let child = child.lock().unwrap();
+
+    let mut conditions = foreign_key
+        .columns
+        .iter()
+        .zip(key.iter())
+        .map(|(c, value)| BooleanExpression {
+            left: Box::new(Expression::ColumnValue(ColumnValueExpression {
+                join_side: JoinSide::Left,
+                col_index: *c,
+                return_type: child.schema.get_column(*c).clone(),
+            })),
+            right: Box::new(Expression::Constant(ConstantExpression {
+                value: value.clone(),
+            })),
+            typ: BooleanType::EQ,
+        });
+    let first = conditions.next().expect("Foreign key without columns");
+    let filter_expr = conditions.fold(first, |acc, condition| BooleanExpression {
+        left: Box::new(Expression::Boolean(acc)),
+        right: Box::new(Expression::Boolean(condition)),
+        typ: BooleanType::And,
+    });
+
+    let plan = SeqScanPlanNode {
+        output_schema: child.schema.clone(),
+        table_oid: child_oid,
+        table_name: child.name.clone(),
+        filter_expr: Some(filter_expr),
+    };
+
+    (plan, child.name.clone())
+}
+
+/// Returns the update expressions that set the columns of `foreign_key` to `values` and keep the other columns.
+fn set_key_expressions(
+    schema: &Schema,
+    foreign_key: &ForeignKey,
+    values: Vec<ColumnValue>,
+) -> Vec<Expression> {
+    (0..schema.get_cols_count())
+        .map(|i| match foreign_key.columns.iter().position(|c| *c == i) {
+            Some(j) => Expression::Constant(ConstantExpression {
+                value: values[j].clone(),
+            }),
+            None => Expression::ColumnValue(ColumnValueExpression {
+                join_side: JoinSide::Left,
+                col_index: i,
+                return_type: schema.get_column(i).clone(),
+            }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::remove_file, sync::Arc};
+
+    use crate::{
+        catalog::{
+            constraint::{ForeignKey, ReferentialAction, TableConstraint},
+            Catalog, OID,
+        },
+        disk::buffer_pool_manager::BufferPoolManager,
+        exec::{
+            executor::{
+                delete::DeleteExecutor, seq_scan::SeqScanExecutor, update::UpdateExecutor,
+                util::insert_tuple_in_table_and_indexes, Execute, Executor, ExecutorContext,
+            },
+            expression::boolean::{BooleanExpression, BooleanType},
+            plan::{
+                delete::DeletePlanNode, seq_scan::SeqScanPlanNode, update::UpdatePlanNode, PlanNode,
+            },
+        },
+        table::{
+            schema::{Column, ColumnType, Schema},
+            tuple::Tuple,
+            value::ColumnValue,
+        },
+        test_utils::{column_with, const_int, int_value},
+    };
+
+    use super::{check_foreign_keys, check_restricted_references};
+
+    /// Creates the tables `users (id INTEGER PRIMARY KEY)` with ids 1 and 2 and
+    /// `orders (id INTEGER, user_id INTEGER REFERENCES users (id))` with orders 10 and 11 of user 1 and 12 of user 2.
+    fn create_tables(
+        db_file: String,
+        on_delete: ReferentialAction,
+        on_update: ReferentialAction,
+    ) -> (ExecutorContext, OID, OID) {
+        let bpm = Arc::new(BufferPoolManager::new(db_file, 10, 2));
+        bpm.new_page(); // this is needed as table heaps assume page with PID 0 is not used
+        let catalog = Arc::new(Catalog::new(bpm.clone()));
+        let context = ExecutorContext::new(catalog.clone(), bpm);
+
+        let users_schema = Schema::new(vec![Column::new_named(
+            "id".to_string(),
+            ColumnType::Integer,
+        )
+        .not_null()]);
+        let users = catalog
+            .create_table_with_constraints(
+                "users",
+                users_schema,
+                vec![TableConstraint::PrimaryKey(vec![0])],
+            )
+            .unwrap();
+        let users_oid = users.lock().unwrap().oid;
+
+        let orders_schema = Schema::new(vec![
+            Column::new_named("id".to_string(), ColumnType::Integer),
+            Column::new_named("user_id".to_string(), ColumnType::Integer),
+        ]);
+        let foreign_key = ForeignKey {
+            name: "orders_user_id_fkey".to_string(),
+            columns: vec![1],
+            parent_oid: users_oid,
+            parent_name: "users".to_string(),
+            parent_columns: vec![0],
+            on_delete,
+            on_update,
+        };
+        let orders = catalog
+            .create_table_with_constraints(
+                "orders",
+                orders_schema,
+                vec![TableConstraint::ForeignKey(foreign_key)],
+            )
+            .unwrap();
+        let orders_oid = orders.lock().unwrap().oid;
+
+        insert(&context, users_oid, vec![int_value(1)]);
+        insert(&context, users_oid, vec![int_value(2)]);
+        insert(&context, orders_oid, vec![int_value(10), int_value(1)]);
+        insert(&context, orders_oid, vec![int_value(11), int_value(1)]);
+        insert(&context, orders_oid, vec![int_value(12), int_value(2)]);
+
+        (context, users_oid, orders_oid)
+    }
+
+    fn insert(context: &ExecutorContext, table_oid: OID, values: Vec<ColumnValue>) {
+        let table_info = context.catalog.get_table_by_oid(table_oid).unwrap();
+        let mut table_info = table_info.lock().unwrap();
+        let index_infos = context.catalog.get_table_indexes(&table_info.name);
+        let index_infos = index_infos
+            .iter()
+            .map(|i| i.lock().unwrap())
+            .collect::<Vec<_>>();
+
+        let tuple = Tuple::new(values, &table_info.schema);
+        insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, tuple).unwrap();
+    }
+
+    /// Returns the plan scanning the users with `id`.
+    fn user_scan(context: &ExecutorContext, users_oid: OID, id: i32) -> SeqScanPlanNode {
+        SeqScanPlanNode {
+            output_schema: context
+                .catalog
+                .get_table_by_oid(users_oid)
+                .unwrap()
+                .lock()
+                .unwrap()
+                .schema
+                .clone(),
+            table_oid: users_oid,
+            table_name: "users".to_string(),
+            filter_expr: Some(BooleanExpression {
+                left: Box::new(column_with(0, ColumnType::Integer)),
+                right: Box::new(const_int(id)),
+                typ: BooleanType::EQ,
+            }),
+        }
+    }
+
+    /// Updates the id of the user with `id` to `new_id`.
+    fn update_user(context: &ExecutorContext, users_oid: OID, id: i32, new_id: i32) {
+        let scan_plan = user_scan(context, users_oid, id);
+        let scan = SeqScanExecutor::new(context.clone(), scan_plan.clone());
+        let plan = UpdatePlanNode::new(
+            users_oid,
+            "users".to_string(),
+            vec![const_int(new_id)],
+            PlanNode::SeqScan(scan_plan),
+        );
+
+        let mut executor = UpdateExecutor::new(context.clone(), plan, Executor::SeqScan(scan));
+        executor.init();
+        executor.next();
+    }
+
+    /// Returns the `(id, user_id)` of all orders.
+    fn orders(context: &ExecutorContext, orders_oid: OID) -> Vec<(ColumnValue, ColumnValue)> {
+        let schema = context
+            .catalog
+            .get_table_by_oid(orders_oid)
+            .unwrap()
+            .lock()
+            .unwrap()
+            .schema
+            .clone();
+        let plan = SeqScanPlanNode {
+            output_schema: schema.clone(),
+            table_oid: orders_oid,
+            table_name: "orders".to_string(),
+            filter_expr: None,
+        };
+
+        let mut executor = SeqScanExecutor::new(context.clone(), plan);
+        executor.init();
+        let mut orders = vec![];
+        while let Some((tuple, _)) = executor.next() {
+            orders.push((tuple.get_value(&schema, 0), tuple.get_value(&schema, 1)));
+        }
+
+        orders
+    }
+
+    #[test]
+    fn foreign_key_references_parent() {
+        // init
+        let db_path = temp_dir().join("referential_foreign_key_references_parent.db");
+        let (context, _, orders_oid) = create_tables(
+            db_path.to_str().unwrap().to_string(),
+            ReferentialAction::Restrict,
+            ReferentialAction::Restrict,
+        );
+        let (foreign_keys, schema) = {
+            let table_info = context.catalog.get_table_by_oid(orders_oid).unwrap();
+            let table_info = table_info.lock().unwrap();
+            (table_info.foreign_keys(), table_info.schema.clone())
+        };
+
+        // test
+        let tuple = Tuple::new(vec![int_value(13), int_value(2)], &schema);
+        assert_eq!(
+            check_foreign_keys(&context.catalog, &foreign_keys, &schema, &tuple),
+            Ok(())
+        );
+
+        let tuple = Tuple::new(
+            vec![int_value(13), ColumnValue::Null(ColumnType::Integer)],
+            &schema,
+        );
+        assert_eq!(
+            check_foreign_keys(&context.catalog, &foreign_keys, &schema, &tuple),
+            Ok(())
+        );
+
+        let tuple = Tuple::new(vec![int_value(13), int_value(3)], &schema);
+        assert_eq!(
+            check_foreign_keys(&context.catalog, &foreign_keys, &schema, &tuple),
+            Err(
+                "FOREIGN KEY constraint \"orders_user_id_fkey\" violated: key { 3 } is not present in table \"users\""
+                    .to_string()
+            )
+        );
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn foreign_key_restrict() {
+        // init
+        let db_path = temp_dir().join("referential_foreign_key_restrict.db");
+        let (context, users_oid, _) = create_tables(
+            db_path.to_str().unwrap().to_string(),
+            ReferentialAction::Restrict,
+            ReferentialAction::Restrict,
+        );
+        let schema = Schema::new(vec![Column::new_named(
+            "id".to_string(),
+            ColumnType::Integer,
+        )]);
+
+        // test
+        let user = Tuple::new(vec![int_value(1)], &schema);
+        assert_eq!(
+            check_restricted_references(&context, users_oid, &schema, &user, None),
+            Err(
+                "FOREIGN KEY constraint \"orders_user_id_fkey\" violated: key { 1 } is still referenced from table \"orders\""
+                    .to_string()
+            )
+        );
+
+        // updates that keep the key are fine
+        assert_eq!(
+            check_restricted_references(&context, users_oid, &schema, &user, Some(&user)),
+            Ok(())
+        );
+
+        // unreferenced users can be deleted
+        let user = Tuple::new(vec![int_value(3)], &schema);
+        assert_eq!(
+            check_restricted_references(&context, users_oid, &schema, &user, None),
+            Ok(())
+        );
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn foreign_key_on_delete_cascade() {
+        // init
+        let db_path = temp_dir().join("referential_foreign_key_on_delete_cascade.db");
+        let (context, users_oid, orders_oid) = create_tables(
+            db_path.to_str().unwrap().to_string(),
+            ReferentialAction::Cascade,
+            ReferentialAction::Restrict,
+        );
+
+        // test
+        let scan_plan = user_scan(&context, users_oid, 1);
+        let scan = SeqScanExecutor::new(context.clone(), scan_plan.clone());
+        let plan =
+            DeletePlanNode::new(users_oid, "users".to_string(), PlanNode::SeqScan(scan_plan));
+        let mut executor = DeleteExecutor::new(context.clone(), plan, Executor::SeqScan(scan));
+        executor.init();
+        executor.next();
+
+        assert_eq!(
+            orders(&context, orders_oid),
+            vec![(int_value(12), int_value(2))]
+        );
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn foreign_key_on_update_cascade() {
+        // init
+        let db_path = temp_dir().join("referential_foreign_key_on_update_cascade.db");
+        let (context, users_oid, orders_oid) = create_tables(
+            db_path.to_str().unwrap().to_string(),
+            ReferentialAction::Restrict,
+            ReferentialAction::Cascade,
+        );
+
+        // test
+        update_user(&context, users_oid, 1, 5);
+
+        assert_eq!(
+            orders(&context, orders_oid),
+            vec![
+                (int_value(10), int_value(5)),
+                (int_value(11), int_value(5)),
+                (int_value(12), int_value(2)),
+            ]
+        );
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn foreign_key_on_update_set_null() {
+        // init
+        let db_path = temp_dir().join("referential_foreign_key_on_update_set_null.db");
+        let (context, users_oid, orders_oid) = create_tables(
+            db_path.to_str().unwrap().to_string(),
+            ReferentialAction::Restrict,
+            ReferentialAction::SetNull,
+        );
+
+        // test
+        update_user(&context, users_oid, 2, 7);
+
+        assert_eq!(
+            orders(&context, orders_oid),
+            vec![
+                (int_value(10), int_value(1)),
+                (int_value(11), int_value(1)),
+                (int_value(12), ColumnValue::Null(ColumnType::Integer)),
+            ]
+        );
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+}
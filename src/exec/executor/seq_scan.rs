@@ -2,11 +2,16 @@ use std::sync::{Arc, Mutex};
 
 use crate::{
     catalog::info::TableInfo,
-    exec::{expression::Evaluate, plan::seq_scan::SeqScanPlanNode},
+    exec::{
+        expression::{boolean::BooleanType, Evaluate, Expression},
+        plan::seq_scan::SeqScanPlanNode,
+    },
     table::{
-        schema::Schema,
+        schema::{ColumnType, Schema},
+        skip_index::{ColumnPredicate, ComparisonType},
         tuple::{Tuple, RID},
         value::ColumnValue,
+        TableHeapIterator,
     },
 };
 
@@ -15,9 +20,8 @@ use super::{Execute, ExecutorContext};
 pub struct SeqScanExecutor {
     pub plan: SeqScanPlanNode,
     pub table_info: Arc<Mutex<TableInfo>>,
-    /// When `None`, it means that there are no more tuples in the table.
-    current_rid: Option<RID>,
-    is_first: bool,
+    /// When `None`, it means that the executor was not initialized yet.
+    cursor: Option<TableHeapIterator>,
 }
 
 impl SeqScanExecutor {
@@ -30,70 +34,94 @@ impl SeqScanExecutor {
         Self {
             plan,
             table_info,
-            current_rid: None,
-            is_first: true,
+            cursor: None,
         }
     }
+
+    /// Returns the comparisons of columns with constants that the rows matching the filter satisfy, used to skip the
+    /// pages of the table that can't have such rows (if it has a skip index).
+    fn skip_predicates(&self) -> Vec<ColumnPredicate> {
+        let Some(filter_expr) = &self.plan.filter_expr else {
+            return vec![];
+        };
+
+        let filter_expr = Expression::Boolean(filter_expr.clone());
+        filter_expr
+            .conjuncts()
+            .into_iter()
+            .filter_map(|conjunct| {
+                let Expression::Boolean(comparison) = conjunct else {
+                    return None;
+                };
+                let (col_index, value, flipped) =
+                    match (comparison.left.as_ref(), comparison.right.as_ref()) {
+                        (Expression::ColumnValue(column), Expression::Constant(constant)) => {
+                            (column.col_index, constant.value.clone(), false)
+                        }
+                        (Expression::Constant(constant), Expression::ColumnValue(column)) => {
+                            (column.col_index, constant.value.clone(), true)
+                        }
+                        _ => return None,
+                    };
+                let typ = match (&comparison.typ, flipped) {
+                    (BooleanType::EQ, _) => ComparisonType::EQ,
+                    (BooleanType::GT, false) | (BooleanType::LT, true) => ComparisonType::GT,
+                    (BooleanType::GE, false) | (BooleanType::LE, true) => ComparisonType::GE,
+                    (BooleanType::LT, false) | (BooleanType::GT, true) => ComparisonType::LT,
+                    (BooleanType::LE, false) | (BooleanType::GE, true) => ComparisonType::LE,
+                    _ => return None,
+                };
+
+                Some(ColumnPredicate {
+                    col_index,
+                    typ,
+                    value,
+                })
+            })
+            .collect()
+    }
 }
 
 impl Execute for SeqScanExecutor {
     fn init(&mut self) {
-        let first = self.table_info.lock().unwrap().table.first_tuple();
-
-        self.current_rid = match first {
-            Some((meta, _, rid)) => {
-                if meta.is_deleted {
-                    self.next();
-                }
-                Some(rid)
-            }
-            None => None,
-        }
+        let predicates = self.skip_predicates();
+        self.cursor = Some(
+            self.table_info
+                .lock()
+                .unwrap()
+                .table
+                .iter_skipping(&predicates),
+        );
     }
 
     fn next(&mut self) -> Option<(Tuple, RID)> {
-        let table_heap = self.table_info.lock().unwrap();
-        let current_rid = self.current_rid.clone()?;
-
-        // get the next tuple (or first tuple, if this is the first call)
-        let tuple = if self.is_first {
-            self.is_first = false;
-            let first = table_heap.table.get_tuple(&current_rid).unwrap();
-            Some((first.0, first.1, current_rid.clone()))
-        } else {
-            table_heap.table.tuple_after(&current_rid)
-        };
-
-        if let Some((next_meta, next_tuple, next_rid)) = tuple {
-            self.current_rid = Some(next_rid.clone());
+        let cursor = self.cursor.as_mut()?;
 
+        for (meta, tuple, rid) in cursor.by_ref() {
             // filter out deleted tuples
-            if next_meta.is_deleted {
-                drop(table_heap);
-                return self.next();
+            if meta.is_deleted {
+                continue;
             }
 
             // filter out tuples that don't match the predicate
             if let Some(predicate) = &self.plan.filter_expr {
-                let filter_result = if let ColumnValue::Boolean(value) =
-                    predicate.evaluate(&next_tuple, &table_heap.schema)
-                {
-                    value.value
-                } else {
-                    panic!("Filter predicate did not evaluate to a boolean value");
+                let table_info = self.table_info.lock().unwrap();
+                let filter_result = match predicate.evaluate(&tuple, &table_info.schema) {
+                    ColumnValue::Boolean(value) => value.value,
+                    // unknown doesn't match
+                    ColumnValue::Null(ColumnType::Boolean) => false,
+                    _ => panic!("Filter predicate did not evaluate to a boolean value"),
                 };
 
                 if !filter_result {
-                    drop(table_heap);
-                    return self.next();
+                    continue;
                 }
             }
 
-            Some((next_tuple, next_rid))
-        } else {
-            self.current_rid = None;
-            None
+            return Some((tuple, rid));
         }
+
+        None
     }
 
     fn output_schema(&self) -> &Schema {
@@ -124,8 +152,13 @@ mod tests {
         disk::buffer_pool_manager::BufferPoolManager,
         exec::{
             executor::{Execute, ExecutorContext},
+            expression::{
+                boolean::{BooleanExpression, BooleanType},
+                Expression,
+            },
             plan::seq_scan::SeqScanPlanNode,
         },
+        sample_code::util::create_table_with_values,
         table::{
             page::TupleMeta,
             schema::{ColumnType, Schema},
@@ -133,6 +166,7 @@ mod tests {
             value::{BooleanValue, ColumnValue, DecimalValue, IntegerValue},
             TableHeap,
         },
+        test_utils::{column_with, const_int, int_value},
     };
 
     use super::SeqScanExecutor;
@@ -194,10 +228,7 @@ mod tests {
 
         // init executor context
         let catalog = Arc::new(Catalog::new(bpm.clone()));
-        let executor_context = ExecutorContext {
-            catalog: catalog.clone(),
-            bpm: bpm.clone(),
-        };
+        let executor_context = ExecutorContext::new(catalog.clone(), bpm.clone());
 
         // create a table
         bpm.new_page(); // this is needed as table heaps assume page with PID 0 is not used
@@ -315,4 +346,77 @@ mod tests {
         // cleanup
         remove_file(db_path).expect("Couldn't remove test DB file");
     }
+
+    #[test]
+    fn scan_with_skip_index() {
+        // init
+        let db_path = temp_dir().join("seq_scan_scan_with_skip_index.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let values = (0..2000).collect::<Vec<_>>();
+        let (executor_context, schema, table_oid, table_name) =
+            create_table_with_values(db_file_path, &values);
+
+        // test
+        let scan = |filter_expr: BooleanExpression| {
+            let plan = SeqScanPlanNode {
+                output_schema: schema.clone(),
+                table_oid,
+                table_name: table_name.clone(),
+                filter_expr: Some(filter_expr),
+            };
+            let mut executor = SeqScanExecutor::new(executor_context.clone(), plan);
+
+            executor.init();
+            let mut values = vec![];
+            while let Some((tuple, _)) = executor.next() {
+                values.push(tuple.get_value(&schema, 0));
+            }
+            values
+        };
+        let comparison =
+            |left: Expression, typ: BooleanType, right: Expression| BooleanExpression {
+                left: Box::new(left),
+                right: Box::new(right),
+                typ,
+            };
+        let id = || column_with(0, ColumnType::Integer);
+        // 1500 <= #0 AND #0 < 1503, with the constant on both sides
+        let range = comparison(
+            Expression::Boolean(comparison(const_int(1500), BooleanType::LE, id())),
+            BooleanType::And,
+            Expression::Boolean(comparison(id(), BooleanType::LT, const_int(1503))),
+        );
+        let ints = |values: Vec<i32>| values.into_iter().map(int_value).collect::<Vec<_>>();
+
+        assert!(executor_context
+            .catalog
+            .create_skip_index(&table_name, vec![3])
+            .is_err());
+        executor_context
+            .catalog
+            .create_skip_index(&table_name, vec![0])
+            .unwrap();
+
+        assert_eq!(scan(range), ints(vec![1500, 1501, 1502]));
+        assert_eq!(
+            scan(comparison(id(), BooleanType::EQ, const_int(42))),
+            ints(vec![42])
+        );
+        assert_eq!(
+            scan(comparison(id(), BooleanType::GT, const_int(1997))),
+            ints(vec![1998, 1999])
+        );
+        assert_eq!(
+            scan(comparison(id(), BooleanType::EQ, const_int(5000))),
+            ints(vec![])
+        );
+        // filters that can't use the skip index
+        assert_eq!(
+            scan(comparison(id(), BooleanType::NE, const_int(0))).len(),
+            1999
+        );
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
 }
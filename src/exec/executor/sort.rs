@@ -0,0 +1,444 @@
+use std::{cmp::Ordering, mem, sync::Arc, vec::IntoIter};
+
+use crate::{
+    disk::buffer_pool_manager::BufferPoolManager,
+    exec::{
+        expression::{Evaluate, Expression},
+        plan::{
+            sort::{SortOrder, SortPlanNode},
+            AbstractPlanNode,
+        },
+    },
+    table::{
+        schema::Schema,
+        tuple::{Tuple, RID},
+        value::ColumnValue,
+    },
+};
+
+use super::{
+    spill::{SpillFile, SpillReader},
+    Execute, Executor, ExecutorContext,
+};
+
+/// Maximum number of runs merged at once. When there are more, groups of runs are first merged into longer runs.
+const MERGE_FAN_IN: usize = 16;
+
+/// Row being sorted, with the values of its keys.
+type SortRow = (Vec<ColumnValue>, Tuple);
+
+fn sort_row(order_by: &[(Expression, SortOrder)], tuple: Tuple, schema: &Schema) -> SortRow {
+    let key = order_by
+        .iter()
+        .map(|(expr, _)| expr.evaluate(&tuple, schema))
+        .collect();
+
+    (key, tuple)
+}
+
+/// Compares the keys of two rows, nulls being greater than all the other values.
+///
+/// # Panics
+/// Will panic if values of a key have different types.
+fn compare_keys(
+    order_by: &[(Expression, SortOrder)],
+    left: &[ColumnValue],
+    right: &[ColumnValue],
+) -> Ordering {
+    for ((_, order), (left, right)) in order_by.iter().zip(left.iter().zip(right)) {
+        let ordering = match (left.is_null(), right.is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => left
+                .compare(right)
+                .expect("Sort key values of different types"),
+        };
+        let ordering = match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// k-way merge of sorted runs. Rows with equal keys are returned in the order of their runs.
+struct Merge {
+    bpm: Arc<BufferPoolManager>,
+    order_by: Vec<(Expression, SortOrder)>,
+    schema: Schema,
+    /// Readers of the runs, with the next row of each of them
+    runs: Vec<(SpillReader, Option<SortRow>)>,
+}
+
+impl Merge {
+    fn new(
+        bpm: Arc<BufferPoolManager>,
+        order_by: Vec<(Expression, SortOrder)>,
+        schema: Schema,
+        runs: Vec<SpillFile>,
+    ) -> Self {
+        let runs = runs
+            .into_iter()
+            .map(|run| {
+                let mut reader = run.reader();
+                let head = reader
+                    .next(&bpm)
+                    .map(|tuple| sort_row(&order_by, tuple, &schema));
+                (reader, head)
+            })
+            .collect();
+
+        Self {
+            bpm,
+            order_by,
+            schema,
+            runs,
+        }
+    }
+
+    fn next(&mut self) -> Option<Tuple> {
+        let mut min: Option<(usize, &[ColumnValue])> = None;
+        for (i, (_, head)) in self.runs.iter().enumerate() {
+            let Some((key, _)) = head else {
+                continue;
+            };
+            // only a smaller key replaces the minimum, so ties are taken from the first run
+            if min.is_none_or(|(_, min_key)| {
+                compare_keys(&self.order_by, key, min_key) == Ordering::Less
+            }) {
+                min = Some((i, key));
+            }
+        }
+
+        let (i, _) = min?;
+        let (reader, head) = &mut self.runs[i];
+        let next = reader
+            .next(&self.bpm)
+            .map(|tuple| sort_row(&self.order_by, tuple, &self.schema));
+        let (_, tuple) = mem::replace(head, next).unwrap();
+
+        Some(tuple)
+    }
+
+    /// Frees the pages of the runs.
+    fn delete(self) {
+        for (reader, _) in self.runs {
+            reader.delete(&self.bpm);
+        }
+    }
+}
+
+/// Sorts the rows of the child (see `SortPlanNode`). The child is read when the sort is initialized. The rows are
+/// returned without RIDs.
+pub struct SortExecutor {
+    pub plan: SortPlanNode,
+    pub child: Box<Executor>,
+    bpm: Arc<BufferPoolManager>,
+    /// Sorted rows, when all of them fit in memory
+    rows: IntoIter<Tuple>,
+    /// Merge of the sorted runs, when the rows were spilled
+    merge: Option<Merge>,
+    /// Number of sorted runs written to disk since the sort was initialized
+    spilled_runs: usize,
+}
+
+impl SortExecutor {
+    pub fn new(context: ExecutorContext, plan: SortPlanNode, child: Executor) -> Self {
+        Self {
+            plan,
+            child: Box::new(child),
+            bpm: context.bpm,
+            rows: vec![].into_iter(),
+            merge: None,
+            spilled_runs: 0,
+        }
+    }
+
+    /// Sorts `rows`, keeping the order of rows with equal keys.
+    fn sort(&self, mut rows: Vec<SortRow>) -> Vec<Tuple> {
+        rows.sort_by(|(left, _), (right, _)| compare_keys(&self.plan.order_by, left, right));
+        rows.into_iter().map(|(_, tuple)| tuple).collect()
+    }
+
+    /// Sorts `rows` and writes them to disk.
+    fn write_run(&mut self, rows: Vec<SortRow>) -> SpillFile {
+        let mut run = SpillFile::default();
+        for tuple in self.sort(rows) {
+            run.push(&self.bpm, &tuple);
+        }
+        self.spilled_runs += 1;
+
+        run
+    }
+
+    fn merge(&self, runs: Vec<SpillFile>) -> Merge {
+        Merge::new(
+            self.bpm.clone(),
+            self.plan.order_by.clone(),
+            self.child.output_schema().clone(),
+            runs,
+        )
+    }
+
+    /// Drops the sorted rows and frees all the runs.
+    fn clear(&mut self) {
+        self.rows = vec![].into_iter();
+        if let Some(merge) = self.merge.take() {
+            merge.delete();
+        }
+        self.spilled_runs = 0;
+    }
+}
+
+impl Execute for SortExecutor {
+    fn init(&mut self) {
+        self.clear();
+        self.child.init();
+
+        // sort the rows in memory, writing them to a run whenever they don't fit anymore
+        let schema = self.child.output_schema().clone();
+        let mut rows = vec![];
+        let mut size = 0;
+        let mut runs = vec![];
+        while let Some((tuple, _)) = self.child.next() {
+            size += tuple.size();
+            rows.push(sort_row(&self.plan.order_by, tuple, &schema));
+            if size > self.plan.memory_budget {
+                let run = self.write_run(mem::take(&mut rows));
+                runs.push(run);
+                size = 0;
+            }
+        }
+
+        if runs.is_empty() {
+            self.rows = self.sort(rows).into_iter();
+            return;
+        }
+        if !rows.is_empty() {
+            let run = self.write_run(rows);
+            runs.push(run);
+        }
+
+        // merge consecutive runs (so the sort stays stable) until they can all be merged at once
+        while runs.len() > MERGE_FAN_IN {
+            let mut merged_runs = vec![];
+            while !runs.is_empty() {
+                let group = runs.drain(..runs.len().min(MERGE_FAN_IN)).collect();
+                let mut merge = self.merge(group);
+                let mut run = SpillFile::default();
+                while let Some(tuple) = merge.next() {
+                    run.push(&self.bpm, &tuple);
+                }
+                merge.delete();
+
+                merged_runs.push(run);
+                self.spilled_runs += 1;
+            }
+            runs = merged_runs;
+        }
+        self.merge = Some(self.merge(runs));
+    }
+
+    fn next(&mut self) -> Option<(Tuple, RID)> {
+        let tuple = match self.merge.as_mut() {
+            Some(merge) => merge.next(),
+            None => self.rows.next(),
+        }?;
+
+        Some((tuple, RID::invalid()))
+    }
+
+    fn output_schema(&self) -> &Schema {
+        self.plan.get_output_schema()
+    }
+
+    fn to_string(&self, indent_level: usize) -> String {
+        let self_string = format!(
+            "Sort | Schema: {} | Order By: [ {} ]",
+            self.output_schema().to_string(),
+            self.plan
+                .order_by
+                .iter()
+                .map(|(expr, order)| format!("{} {}", expr.to_string(), order))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+
+        let tabs = "\t".repeat(indent_level + 1);
+        format!(
+            "{}\n{}-> {}",
+            self_string,
+            tabs,
+            self.child.to_string(indent_level + 1)
+        )
+    }
+}
+
+impl Drop for SortExecutor {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::remove_file};
+
+    use crate::{
+        exec::{
+            executor::{seq_scan::SeqScanExecutor, values::ValuesExecutor, Execute, Executor},
+            expression::{constant::ConstantExpression, Expression},
+            plan::{
+                seq_scan::SeqScanPlanNode,
+                sort::{SortOrder, SortPlanNode},
+                values::ValuesPlanNode,
+                PlanNode,
+            },
+        },
+        sample_code::util::{create_table, create_table_with_values},
+        table::{
+            schema::{ColumnType, Schema},
+            value::ColumnValue,
+        },
+        test_utils::{column_with, const_int, int_value},
+    };
+
+    use super::SortExecutor;
+
+    fn rows(executor: &mut SortExecutor) -> Vec<Vec<ColumnValue>> {
+        executor.init();
+        let schema = executor.output_schema().clone();
+        let mut rows = vec![];
+        while let Some((tuple, _)) = executor.next() {
+            rows.push(
+                (0..schema.get_cols_count())
+                    .map(|i| tuple.get_value(&schema, i))
+                    .collect(),
+            );
+        }
+
+        rows
+    }
+
+    #[test]
+    fn sort_keys() {
+        // init
+        let db_path = temp_dir().join("sort_sort_keys.db");
+        let (context, _, _, _) = create_table(db_path.to_str().unwrap().to_string());
+
+        let null = || {
+            Expression::Constant(ConstantExpression {
+                value: ColumnValue::Null(ColumnType::Integer),
+            })
+        };
+        let values_plan = ValuesPlanNode {
+            output_schema: Schema::with_types(vec![ColumnType::Integer, ColumnType::Integer]),
+            values: vec![
+                vec![const_int(1), const_int(10)],
+                vec![const_int(2), null()],
+                vec![null(), const_int(30)],
+                vec![const_int(1), const_int(20)],
+                vec![const_int(2), const_int(40)],
+            ],
+        };
+        let child = || {
+            Executor::Values(ValuesExecutor {
+                plan: values_plan.clone(),
+                cursor: 0,
+            })
+        };
+        let null_value = ColumnValue::Null(ColumnType::Integer);
+
+        // test
+        // #0 ASC, #1 DESC
+        let plan = SortPlanNode::new(
+            vec![
+                (column_with(0, ColumnType::Integer), SortOrder::Asc),
+                (column_with(1, ColumnType::Integer), SortOrder::Desc),
+            ],
+            PlanNode::Values(values_plan.clone()),
+        );
+        let mut executor = SortExecutor::new(context.clone(), plan.clone(), child());
+        assert_eq!(
+            rows(&mut executor),
+            vec![
+                vec![int_value(1), int_value(20)],
+                vec![int_value(1), int_value(10)],
+                vec![int_value(2), null_value.clone()],
+                vec![int_value(2), int_value(40)],
+                vec![null_value.clone(), int_value(30)],
+            ]
+        );
+        assert_eq!(executor.spilled_runs, 0);
+
+        // every row is spilled in its own run, which are merged
+        let plan = plan.with_memory_budget(1);
+        let mut spilled = SortExecutor::new(context.clone(), plan, child());
+        assert_eq!(rows(&mut spilled), rows(&mut executor));
+        assert_eq!(spilled.spilled_runs, 5);
+
+        // rows with equal keys keep the order of the child, also when merging runs
+        let plan = SortPlanNode::new(
+            vec![(const_int(0), SortOrder::Desc)],
+            PlanNode::Values(values_plan.clone()),
+        )
+        .with_memory_budget(1);
+        let mut executor = SortExecutor::new(context.clone(), plan, child());
+        assert_eq!(
+            rows(&mut executor),
+            vec![
+                vec![int_value(1), int_value(10)],
+                vec![int_value(2), null_value.clone()],
+                vec![null_value.clone(), int_value(30)],
+                vec![int_value(1), int_value(20)],
+                vec![int_value(2), int_value(40)],
+            ]
+        );
+
+        // cleanup
+        drop(executor);
+        drop(spilled);
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn external_merge_sort() {
+        // init
+        let db_path = temp_dir().join("sort_external_merge_sort.db");
+        let values = (0..2000).map(|i| (i * 7919) % 2000).collect::<Vec<i32>>();
+        let (context, schema, table_oid, table_name) =
+            create_table_with_values(db_path.to_str().unwrap().to_string(), &values);
+        let scan_plan = SeqScanPlanNode {
+            output_schema: schema.clone(),
+            table_oid,
+            table_name,
+            filter_expr: None,
+        };
+        let scan = Executor::SeqScan(SeqScanExecutor::new(context.clone(), scan_plan.clone()));
+
+        // test
+        // runs of about 20 rows, so more runs than can be merged at once
+        let plan = SortPlanNode::new(
+            vec![(column_with(0, ColumnType::Integer), SortOrder::Desc)],
+            PlanNode::SeqScan(scan_plan),
+        )
+        .with_memory_budget(256);
+        let mut executor = SortExecutor::new(context.clone(), plan, scan);
+        let ids = rows(&mut executor)
+            .into_iter()
+            .map(|row| row[0].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, (0..2000).rev().map(int_value).collect::<Vec<_>>());
+        assert!(executor.spilled_runs > 2000 / 20);
+
+        // cleanup
+        drop(executor);
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+}
@@ -0,0 +1,116 @@
+use std::vec::IntoIter;
+
+use crate::{
+    disk::{
+        buffer_pool_manager::{BufferPoolManager, DiskRead, DiskWrite},
+        disk_manager::PageID,
+    },
+    table::{
+        page::{TablePageMut, TablePageRef, TupleMeta},
+        tuple::{Tuple, RID},
+    },
+};
+
+/// Rows spilled by an executor to temporary pages of the buffer pool when they don't fit in memory, stored like the
+/// tuples of a table heap. The pages are only freed by `delete`.
+#[derive(Default)]
+pub(super) struct SpillFile {
+    pages: Vec<PageID>,
+    /// Bytes of the rows
+    size: usize,
+}
+
+impl SpillFile {
+    /// Appends `tuple` to the rows of the file.
+    ///
+    /// # Panics
+    /// Will panic if the tuple doesn't fit in a page.
+    pub(super) fn push(&mut self, bpm: &BufferPoolManager, tuple: &Tuple) {
+        let meta = TupleMeta {
+            ts: 0,
+            is_deleted: false,
+        };
+        self.size += tuple.size();
+
+        if let Some(&page_id) = self.pages.last() {
+            let mut page = bpm.get_write_page(page_id);
+            if TablePageMut::new(page.data_mut())
+                .insert_tuple(meta.clone(), tuple)
+                .is_some()
+            {
+                return;
+            }
+        }
+
+        let page_id = bpm.new_page();
+        let mut page = bpm.get_write_page(page_id);
+        TablePageMut::init(page.data_mut())
+            .insert_tuple(meta, tuple)
+            .expect("Row is too large to be spilled");
+        self.pages.push(page_id);
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    pub(super) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns a reader of the rows of the file, in the order they were pushed.
+    pub(super) fn reader(self) -> SpillReader {
+        SpillReader {
+            file: self,
+            next_page: 0,
+            rows: vec![].into_iter(),
+        }
+    }
+
+    /// Frees the pages of the file.
+    pub(super) fn delete(self, bpm: &BufferPoolManager) {
+        for page_id in self.pages {
+            bpm.delete_page(page_id);
+        }
+    }
+
+    /// Returns the rows stored in the `i`-th page of the file.
+    fn read_page(&self, bpm: &BufferPoolManager, i: usize) -> Vec<Tuple> {
+        let page_id = self.pages[i];
+        let guard = bpm.get_read_page(page_id);
+        let page = TablePageRef::new(guard.read());
+
+        (0..page.num_tuples())
+            .map(|slot| page.get_tuple(&RID::new(page_id, slot)).unwrap().1)
+            .collect()
+    }
+}
+
+/// Reads the rows of a `SpillFile` one page at a time.
+pub(super) struct SpillReader {
+    file: SpillFile,
+    next_page: usize,
+    /// Rows of the last page read that weren't returned yet
+    rows: IntoIter<Tuple>,
+}
+
+impl SpillReader {
+    pub(super) fn next(&mut self, bpm: &BufferPoolManager) -> Option<Tuple> {
+        loop {
+            if let Some(tuple) = self.rows.next() {
+                return Some(tuple);
+            }
+            if self.next_page == self.file.pages.len() {
+                return None;
+            }
+
+            self.rows = self.file.read_page(bpm, self.next_page).into_iter();
+            self.next_page += 1;
+        }
+    }
+
+    /// Frees the pages of the file being read.
+    pub(super) fn delete(self, bpm: &BufferPoolManager) {
+        self.file.delete(bpm);
+    }
+}
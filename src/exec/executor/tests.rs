@@ -13,6 +13,7 @@ use crate::{
         },
     },
     table::{
+        numeric::RoundingMode,
         schema::{Column, ColumnType, Schema},
         value::{ColumnValue, IntegerValue},
     }, test_utils::const_int,
@@ -97,6 +98,7 @@ fn get_projection_executor(
             value: ColumnValue::Integer(IntegerValue { value: 2 }),
         })),
         typ: ArithmeticType::Multiply,
+        rounding: RoundingMode::HalfUp,
     })];
 
     let projection_plan = ProjectionPlanNode {
@@ -158,6 +160,7 @@ fn get_filter_executor(child_pln: PlanNode, child_exec: Executor) -> (FilterExec
                 value: ColumnValue::Integer(IntegerValue { value: 2 }),
             })),
             typ: ArithmeticType::Mod,
+            rounding: RoundingMode::HalfUp,
         })),
         right: Box::new(Expression::Constant(ConstantExpression {
             value: ColumnValue::Integer(IntegerValue { value: 0 }),
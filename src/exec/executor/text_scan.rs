@@ -0,0 +1,269 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    catalog::info::{IndexInfo, TableInfo},
+    exec::{
+        executor::{Execute, ExecutorContext},
+        plan::{text_scan::TextScanPlanNode, AbstractPlanNode},
+    },
+    table::{
+        schema::Schema,
+        tuple::{Tuple, RID},
+    },
+};
+
+pub struct TextScanExecutor {
+    pub plan: TextScanPlanNode,
+    pub index: Arc<Mutex<IndexInfo>>,
+    pub table: Arc<Mutex<TableInfo>>,
+    /// RIDs of the rows matching the query
+    rids: Vec<RID>,
+    current: usize,
+}
+
+impl TextScanExecutor {
+    /// Creates a new `TextScanExecutor`.
+    ///
+    /// # Panics
+    /// Will panic if the table doesn't have a full-text index on the scanned column.
+    pub fn new(context: ExecutorContext, plan: TextScanPlanNode) -> Self {
+        Self {
+            index: context
+                .catalog
+                .get_table_full_text_index(&plan.table_name, plan.col_index)
+                .expect("No full-text index on the scanned column of the table"),
+            table: context
+                .catalog
+                .get_table_by_oid(plan.table_oid)
+                .expect("No table with given OID"),
+            plan,
+            rids: vec![],
+            current: 0,
+        }
+    }
+}
+
+impl Execute for TextScanExecutor {
+    fn init(&mut self) {
+        self.rids = self
+            .index
+            .lock()
+            .unwrap()
+            .index
+            .search(&self.plan.query)
+            .expect("Text scans need a full-text index");
+        self.current = 0;
+    }
+
+    fn next(&mut self) -> Option<(Tuple, RID)> {
+        if self.current >= self.rids.len() {
+            return None;
+        }
+
+        let current_rid = self.rids[self.current].clone();
+        let (_, current_tuple) = self
+            .table
+            .lock()
+            .unwrap()
+            .table
+            .get_tuple(&current_rid)
+            .expect("Invalid RID from index");
+
+        self.current += 1;
+        Some((current_tuple, current_rid))
+    }
+
+    fn output_schema(&self) -> &Schema {
+        self.plan.get_output_schema()
+    }
+
+    fn to_string(&self, _indent_level: usize) -> String {
+        let table = self.table.lock().unwrap();
+        let table_name = table.name.clone();
+        let table_oid = table.oid;
+        drop(table);
+
+        let index = self.index.lock().unwrap();
+        let index_name = index.name.clone();
+        let index_oid = index.oid;
+        drop(index);
+
+        format!(
+            "TextScan | Schema: {} | Table: {}({}) | Index: {}({}) - #{} MATCH '{}'",
+            self.output_schema().to_string(),
+            table_name,
+            table_oid,
+            index_name,
+            index_oid,
+            self.plan.col_index,
+            self.plan.query
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::remove_file, sync::Arc};
+
+    use crate::{
+        catalog::Catalog,
+        disk::buffer_pool_manager::BufferPoolManager,
+        exec::{
+            executor::{
+                util::{
+                    delete_from_table_and_indexes, insert_tuple_in_table_and_indexes,
+                    update_tuple_in_table_and_indexes,
+                },
+                Execute, ExecutorContext,
+            },
+            expression::{text_match::MatchExpression, Expression},
+            plan::text_scan::TextScanPlanNode,
+        },
+        index::{full_text::TextQuery, IndexMeta, IndexType},
+        table::{
+            schema::{ColumnType, Schema},
+            tuple::Tuple,
+            value::{ColumnValue, VarcharValue},
+        },
+        test_utils::{column_with, const_varchar, int_value},
+    };
+
+    fn row(id: i32, body: &str, schema: &Schema) -> Tuple {
+        let body = ColumnValue::Varchar(VarcharValue {
+            value: body.to_string(),
+            length: 64,
+        });
+
+        Tuple::new(vec![int_value(id), body], schema)
+    }
+
+    #[test]
+    fn text_scan() {
+        // init
+        let db_path = temp_dir().join("text_scan_text_scan.db");
+        let bpm = Arc::new(BufferPoolManager::new(
+            db_path.to_str().unwrap().to_string(),
+            100,
+            2,
+        ));
+        let catalog = Arc::new(Catalog::new(bpm.clone()));
+        let context = ExecutorContext::new(catalog.clone(), bpm.clone());
+        bpm.new_page();
+
+        let schema = Schema::with_types(vec![ColumnType::Integer, ColumnType::Varchar(64)]);
+        let table = catalog.create_table("posts", schema.clone()).unwrap();
+        let table_oid = table.lock().unwrap().oid;
+        let key_schema = Schema::with_types(vec![ColumnType::Varchar(64)]);
+        let index_meta = IndexMeta::new(key_schema.clone(), "posts_body".to_string(), vec![1])
+            .with_type(IndexType::FullText);
+        catalog
+            .create_index_with_meta(
+                index_meta,
+                "posts",
+                schema.clone(),
+                key_schema.get_tuple_len(),
+            )
+            .unwrap();
+
+        let indexes = catalog.get_table_indexes("posts");
+        let mut table_info = table.lock().unwrap();
+        let index_infos = indexes
+            .iter()
+            .map(|index| index.lock().unwrap())
+            .collect::<Vec<_>>();
+        let mut rids = vec![];
+        for (id, body) in [
+            (1, "Rust database engine"),
+            (2, "A database in C"),
+            (3, "Rust web servers"),
+            (4, "Cooking pasta"),
+        ] {
+            rids.push(
+                insert_tuple_in_table_and_indexes(
+                    &mut table_info,
+                    &index_infos,
+                    row(id, body, &schema),
+                )
+                .unwrap(),
+            );
+        }
+        drop(index_infos);
+        drop(table_info);
+
+        // test
+        let search = |query: &str| {
+            let filter_expr = Expression::Match(MatchExpression {
+                expr: Box::new(column_with(1, ColumnType::Varchar(64))),
+                query: TextQuery::parse(query).unwrap(),
+            });
+            let plan = TextScanPlanNode::from_filter(
+                schema.clone(),
+                table_oid,
+                "posts".to_string(),
+                &filter_expr,
+            )
+            .unwrap();
+            let mut executor = super::TextScanExecutor::new(context.clone(), plan);
+
+            executor.init();
+            let mut ids = vec![];
+            while let Some((tuple, _)) = executor.next() {
+                ids.push(tuple.get_value(&schema, 0));
+            }
+            ids
+        };
+        let ints = |values: Vec<i32>| values.into_iter().map(int_value).collect::<Vec<_>>();
+
+        assert_eq!(search("database"), ints(vec![1, 2]));
+        assert_eq!(search("RUST AND database"), ints(vec![1]));
+        assert_eq!(search("rust database OR pasta"), ints(vec![1, 4]));
+        assert_eq!(search("web OR c"), ints(vec![2, 3]));
+        assert_eq!(search("python"), ints(vec![]));
+
+        // the index follows updates and deletes of the rows
+        let mut table_info = table.lock().unwrap();
+        let index_infos = indexes
+            .iter()
+            .map(|index| index.lock().unwrap())
+            .collect::<Vec<_>>();
+        let old_tuple = row(4, "Cooking pasta", &schema);
+        update_tuple_in_table_and_indexes(
+            &mut table_info,
+            &index_infos,
+            &rids[3],
+            &old_tuple,
+            row(4, "Cooking with Rust", &schema),
+        )
+        .unwrap();
+        delete_from_table_and_indexes(&table_info, &index_infos, &rids[0]);
+        drop(index_infos);
+        drop(table_info);
+
+        assert_eq!(search("rust"), ints(vec![3, 4]));
+        assert_eq!(search("pasta"), ints(vec![]));
+        assert_eq!(search("database"), ints(vec![2]));
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn unsupported_filters() {
+        let from_filter = |filter_expr: &Expression| {
+            TextScanPlanNode::from_filter(
+                Schema::with_types(vec![ColumnType::Varchar(8)]),
+                0,
+                String::from("t"),
+                filter_expr,
+            )
+        };
+
+        assert!(from_filter(&column_with(0, ColumnType::Boolean)).is_err());
+        // MATCH on an expression that isn't a column
+        let filter_expr = Expression::Match(MatchExpression {
+            expr: Box::new(const_varchar("rust".to_string())),
+            query: TextQuery::parse("rust").unwrap(),
+        });
+        assert!(from_filter(&filter_expr).is_err());
+    }
+}
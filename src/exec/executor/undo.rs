@@ -0,0 +1,288 @@
+use crate::{
+    catalog::{Catalog, OID},
+    table::{
+        page::TupleMeta,
+        tuple::{Tuple, RID},
+    },
+};
+
+use super::util::{delete_from_table_and_indexes, insert_tuple_in_table_and_indexes};
+
+/// Change made to a table by a statement, along with what is needed to undo it.
+#[derive(Debug, Clone)]
+enum UndoEntry {
+    /// The tuple with `rid` was inserted.
+    Insert { table_oid: OID, rid: RID },
+    /// `tuple` with `rid` was marked as deleted. Its data may be reclaimed before the rollback.
+    Delete {
+        table_oid: OID,
+        rid: RID,
+        tuple: Tuple,
+    },
+    /// The tuple with `rid` was replaced in place, it was `old_tuple` before.
+    Update {
+        table_oid: OID,
+        rid: RID,
+        old_tuple: Tuple,
+    },
+}
+
+impl UndoEntry {
+    fn table_oid(&self) -> OID {
+        match self {
+            UndoEntry::Insert { table_oid, .. }
+            | UndoEntry::Delete { table_oid, .. }
+            | UndoEntry::Update { table_oid, .. } => *table_oid,
+        }
+    }
+
+    fn rid_mut(&mut self) -> &mut RID {
+        match self {
+            UndoEntry::Insert { rid, .. }
+            | UndoEntry::Delete { rid, .. }
+            | UndoEntry::Update { rid, .. } => rid,
+        }
+    }
+}
+
+/// Changes made by the executors of a statement (including the ones run for the actions of foreign keys), so they
+/// can be undone when the statement fails halfway.
+#[derive(Debug, Default)]
+pub struct UndoLog {
+    entries: Vec<UndoEntry>,
+}
+
+impl UndoLog {
+    /// Returns the number of recorded changes, which can be used as a savepoint for `rollback_to`.
+    pub fn savepoint(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn record_insert(&mut self, table_oid: OID, rid: RID) {
+        self.entries.push(UndoEntry::Insert { table_oid, rid });
+    }
+
+    pub fn record_delete(&mut self, table_oid: OID, rid: RID, tuple: Tuple) {
+        self.entries.push(UndoEntry::Delete {
+            table_oid,
+            rid,
+            tuple,
+        });
+    }
+
+    /// Records the update of `old_tuple` with `old_rid`, which is now stored at `new_rid`. Updates that moved the
+    /// tuple are recorded as a delete followed by an insert.
+    pub fn record_update(&mut self, table_oid: OID, old_rid: RID, new_rid: RID, old_tuple: Tuple) {
+        if old_rid == new_rid {
+            self.entries.push(UndoEntry::Update {
+                table_oid,
+                rid: old_rid,
+                old_tuple,
+            });
+        } else {
+            self.record_delete(table_oid, old_rid, old_tuple);
+            self.record_insert(table_oid, new_rid);
+        }
+    }
+
+    /// Undoes the changes recorded after `savepoint`, most recent first, updating the indexes of the tables as well.
+    ///
+    /// # Panics
+    /// Will panic if a table changed by the statement no longer exists.
+    pub fn rollback_to(&mut self, catalog: &Catalog, savepoint: usize) {
+        while self.entries.len() > savepoint {
+            let entry = self.entries.pop().unwrap();
+            let table_info = catalog
+                .get_table_by_oid(entry.table_oid())
+                .expect("Table changed by the statement doesn't exist");
+            let table_name = table_info.lock().unwrap().name.clone();
+            let index_infos = catalog.get_table_indexes(&table_name);
+            let mut table_info = table_info.lock().unwrap();
+            let index_infos = index_infos
+                .iter()
+                .map(|i| i.lock().unwrap())
+                .collect::<Vec<_>>();
+
+            let meta = TupleMeta {
+                ts: 0,
+                is_deleted: false,
+            };
+            match entry {
+                UndoEntry::Insert { rid, .. } => {
+                    delete_from_table_and_indexes(&table_info, &index_infos, &rid);
+                }
+                UndoEntry::Delete {
+                    table_oid,
+                    rid,
+                    tuple,
+                } => {
+                    // the page may have been compacted since, reclaiming the data of the tuple
+                    if table_info.table.get_tuple(&rid).is_none() {
+                        let new_rid =
+                            insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, tuple)
+                                .unwrap();
+                        self.relocate(savepoint, table_oid, &rid, &new_rid);
+                        continue;
+                    }
+
+                    table_info.table.update_tuple_meta(meta, &rid);
+                    for index_info in index_infos.iter() {
+                        index_info
+                            .insert_entry(&tuple, &table_info.schema, rid.clone())
+                            .unwrap();
+                    }
+                }
+                UndoEntry::Update {
+                    table_oid,
+                    rid,
+                    old_tuple,
+                } => {
+                    let (_, tuple) = table_info
+                        .table
+                        .get_tuple(&rid)
+                        .expect("Updated tuple doesn't exist");
+                    for index_info in index_infos.iter() {
+                        index_info.delete_entry(&tuple, &table_info.schema, &rid);
+                    }
+
+                    // the slot shrinks to the size of the new tuple, so the old one may not fit in it anymore
+                    let old_rid =
+                        match table_info
+                            .table
+                            .update_tuple_in_place(meta, old_tuple.clone(), &rid)
+                        {
+                            Ok(()) => rid.clone(),
+                            Err(()) => {
+                                delete_from_table_and_indexes(&table_info, &vec![], &rid);
+                                let new_rid = insert_tuple_in_table_and_indexes(
+                                    &mut table_info,
+                                    &vec![],
+                                    old_tuple.clone(),
+                                )
+                                .unwrap();
+                                self.relocate(savepoint, table_oid, &rid, &new_rid);
+                                new_rid
+                            }
+                        };
+                    for index_info in index_infos.iter() {
+                        index_info
+                            .insert_entry(&old_tuple, &table_info.schema, old_rid.clone())
+                            .unwrap();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Makes the changes recorded after `savepoint` that refer to the tuple at `old_rid` of the table with `table_oid`
+    /// refer to `new_rid`, after the tuple was moved by the rollback.
+    fn relocate(&mut self, savepoint: usize, table_oid: OID, old_rid: &RID, new_rid: &RID) {
+        for entry in self.entries[savepoint..].iter_mut() {
+            if entry.table_oid() == table_oid && entry.rid_mut() == old_rid {
+                *entry.rid_mut() = new_rid.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::remove_file, sync::Arc};
+
+    use crate::{
+        catalog::{constraint::TableConstraint, Catalog},
+        disk::buffer_pool_manager::BufferPoolManager,
+        exec::executor::util::{delete_from_table_and_indexes, insert_tuple_in_table_and_indexes},
+        table::{
+            schema::{Column, ColumnType, Schema},
+            tuple::Tuple,
+            value::{ColumnValue, VarcharValue},
+        },
+        test_utils::int_value,
+    };
+
+    use super::UndoLog;
+
+    #[test]
+    fn rollback_delete_of_reclaimed_tuple() {
+        // init
+        let db_path = temp_dir().join("undo_rollback_delete_of_reclaimed_tuple.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 10, 2));
+        let catalog = Catalog::new(bpm);
+        let schema = Schema::new(vec![
+            Column::new_named("id".to_string(), ColumnType::Integer),
+            Column::new_named("data".to_string(), ColumnType::Varchar(3000)),
+        ]);
+        let table_info = catalog
+            .create_table_with_constraints(
+                "rows",
+                schema.clone(),
+                vec![TableConstraint::PrimaryKey(vec![0])],
+            )
+            .unwrap();
+        let table_oid = table_info.lock().unwrap().oid;
+        let index_info = catalog.get_index_by_name("rows_pkey", "rows").unwrap();
+        let row = |id: i32| {
+            Tuple::new(
+                vec![
+                    int_value(id),
+                    ColumnValue::Varchar(VarcharValue {
+                        value: id.to_string().repeat(3000),
+                        length: 3000,
+                    }),
+                ],
+                &schema,
+            )
+        };
+
+        let rid = {
+            let mut table_info = table_info.lock().unwrap();
+            let index_infos = vec![index_info.lock().unwrap()];
+            insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, row(1)).unwrap()
+        };
+
+        // test
+        let mut undo_log = UndoLog::default();
+        {
+            let mut table_info = table_info.lock().unwrap();
+            let index_infos = vec![index_info.lock().unwrap()];
+
+            // the second row only fits in the page by reclaiming the space of the first one
+            let tuple = delete_from_table_and_indexes(&table_info, &index_infos, &rid);
+            undo_log.record_delete(table_oid, rid.clone(), tuple);
+            let new_rid =
+                insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, row(2)).unwrap();
+            undo_log.record_insert(table_oid, new_rid.clone());
+            assert_eq!(new_rid.page_id, rid.page_id);
+            assert_eq!(table_info.table.get_tuple(&rid), None);
+        }
+
+        undo_log.rollback_to(&catalog, 0);
+        let table_info = table_info.lock().unwrap();
+        let rows = table_info
+            .table
+            .iter()
+            .filter(|(meta, _, _)| !meta.is_deleted)
+            .map(|(_, tuple, rid)| (tuple, rid))
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].0, row(1));
+
+        let index_info = index_info.lock().unwrap();
+        let key_schema = index_info.index.meta().key_schema();
+        let rids = index_info
+            .index
+            .scan(Tuple::new(vec![int_value(1)], key_schema));
+        assert_eq!(rids, vec![rows[0].1.clone()]);
+        assert!(index_info
+            .index
+            .scan(Tuple::new(vec![int_value(2)], key_schema))
+            .is_empty());
+
+        // cleanup
+        drop(index_info);
+        drop(table_info);
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+}
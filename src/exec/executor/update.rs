@@ -1,7 +1,7 @@
-use std::sync::{Arc, MutexGuard};
+use std::sync::Arc;
 
 use crate::{
-    catalog::{info::TableInfo, Catalog},
+    catalog::Catalog,
     exec::{
         expression::Evaluate,
         plan::{update::UpdatePlanNode, AbstractPlanNode},
@@ -13,7 +13,8 @@ use crate::{
 };
 
 use super::{
-    util::{delete_from_table_and_indexes, insert_tuple_in_table_and_indexes, int_tuple},
+    referential::{apply_referential_actions, check_foreign_keys, check_restricted_references},
+    util::{check_constraints, get_live_tuple, int_tuple, update_tuple_in_table_and_indexes},
     Execute, Executor, ExecutorContext,
 };
 
@@ -22,22 +23,48 @@ pub struct UpdateExecutor {
     pub catalog: Arc<Catalog>,
     pub child: Box<Executor>,
     updated: bool,
-    /// Used for keeping track of what tuples were already updated (deleted + inserted). It contains the new RIDs of
-    /// all tuples that were already processed
+    /// Used for keeping track of what tuples were already updated (in place or deleted + inserted). It contains the new
+    /// RIDs of all tuples that were already processed
     rids_processed: Vec<RID>,
+    /// Needed for running the referential actions of foreign keys on other tables and undoing the updates.
+    context: ExecutorContext,
+    /// Length of the undo log when the executor started.
+    savepoint: usize,
+    /// Why the update failed, if it did.
+    error: Option<String>,
 }
 
 impl UpdateExecutor {
     pub fn new(context: ExecutorContext, plan: UpdatePlanNode, child: Executor) -> Self {
         Self {
             plan,
-            catalog: context.catalog,
+            catalog: context.catalog.clone(),
             child: Box::new(child),
             updated: false,
             rids_processed: vec![],
+            context,
+            savepoint: 0,
+            error: None,
         }
     }
 
+    /// Returns why the update failed, `None` if it didn't.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Undoes the changes made by the executor (and the referential actions it ran) and records the error.
+    fn fail(&mut self, error: String) -> Option<(Tuple, RID)> {
+        self.context
+            .undo_log
+            .lock()
+            .unwrap()
+            .rollback_to(&self.catalog, self.savepoint);
+        self.error = Some(format!("Can't update tuple: {}", error));
+        self.updated = true;
+        None
+    }
+
     /// # Panics
     /// Will panic if expressions don't properly match table schema.
     fn validate(&self) {
@@ -61,15 +88,17 @@ impl UpdateExecutor {
         }
     }
 
-    fn get_updated_tuple(&self, tuple: &Tuple, table_info: &MutexGuard<'_, TableInfo>) -> Tuple {
+    /// # Errors
+    /// Will return `Err` if one of the expressions can't be evaluated (e.g. on numeric overflow).
+    fn get_updated_tuple(&self, tuple: &Tuple, schema: &Schema) -> Result<Tuple, String> {
         let values = self
             .plan
             .expressions
             .iter()
-            .map(|e| e.evaluate(&tuple, &table_info.schema))
-            .collect::<Vec<_>>();
+            .map(|e| e.try_evaluate(&tuple, schema))
+            .collect::<Result<Vec<_>, _>>()?;
 
-        Tuple::new(values, &table_info.schema)
+        Ok(Tuple::new(values, schema))
     }
 }
 
@@ -80,8 +109,18 @@ impl Execute for UpdateExecutor {
         self.child.init();
         self.updated = false;
         self.rids_processed = vec![];
+        self.savepoint = self.context.undo_log.lock().unwrap().savepoint();
+        self.error = None;
     }
 
+    /// Updates the tuples returned by the child and returns the number of updated tuples. Rows of other tables
+    /// referencing updated keys are handled according to the `ON UPDATE` actions of their foreign keys. Tuples deleted
+    /// after the child returned them are skipped.
+    ///
+    /// # Errors
+    /// Returns `None` without updating anything if an updated tuple can't be computed, if it violates a constraint of
+    /// the table or if an updated key is still referenced through a foreign key with the `RESTRICT` action. The tuples
+    /// already updated are restored and the error is returned by `error`.
     fn next(&mut self) -> Option<(Tuple, RID)> {
         if self.updated {
             return None;
@@ -97,17 +136,88 @@ impl Execute for UpdateExecutor {
                 .catalog
                 .get_table_with_indexes(self.plan.table_oid, &self.plan.table_name);
 
-            let mut table_info = table_info.lock().unwrap();
-            let index_infos = index_infos
-                .iter()
-                .map(|i| i.lock().unwrap())
-                .collect::<Vec<_>>();
+            let (schema, foreign_keys, mut old_tuple) = {
+                let table_info = table_info.lock().unwrap();
+                (
+                    table_info.schema.clone(),
+                    table_info.foreign_keys(),
+                    get_live_tuple(&table_info, &rid),
+                )
+            };
+
+            // references and foreign keys are checked without holding any locks, since they use other tables. The
+            // tuple is read again once the table is locked, and checked again if it was changed in the meantime
+            let result = loop {
+                // the tuple was deleted after the child returned it
+                let Some(tuple) = old_tuple else {
+                    break None;
+                };
+                let new_tuple = match self.get_updated_tuple(&tuple, &schema) {
+                    Ok(new_tuple) => new_tuple,
+                    Err(e) => return self.fail(e),
+                };
+                if let Err(e) = check_restricted_references(
+                    &self.context,
+                    self.plan.table_oid,
+                    &schema,
+                    &tuple,
+                    Some(&new_tuple),
+                ) {
+                    return self.fail(e);
+                }
+                if let Err(e) =
+                    check_foreign_keys(&self.catalog, &foreign_keys, &schema, &new_tuple)
+                {
+                    return self.fail(e);
+                }
+
+                // the locks are released at the end of the iteration, before running the referential actions or
+                // undoing
+                let mut table_info = table_info.lock().unwrap();
+                let current_tuple = get_live_tuple(&table_info, &rid);
+                if current_tuple.as_ref() != Some(&tuple) {
+                    old_tuple = current_tuple;
+                    continue;
+                }
+                let index_infos = index_infos
+                    .iter()
+                    .map(|i| i.lock().unwrap())
+                    .collect::<Vec<_>>();
+
+                // update is done in place when possible, otherwise by deleting old tuple and inserting new tuple
+                let result = check_constraints(&table_info, &index_infos, &new_tuple, Some(&rid))
+                    .and_then(|_| {
+                        update_tuple_in_table_and_indexes(
+                            &mut table_info,
+                            &index_infos,
+                            &rid,
+                            &tuple,
+                            new_tuple.clone(),
+                        )
+                    });
+                break Some(result.map(|new_rid| (new_rid, tuple, new_tuple)));
+            };
+            let (new_rid, old_tuple, new_tuple) = match result {
+                Some(Ok(updated)) => updated,
+                Some(Err(e)) => return self.fail(e),
+                None => continue,
+            };
+            self.context.undo_log.lock().unwrap().record_update(
+                self.plan.table_oid,
+                rid,
+                new_rid.clone(),
+                old_tuple.clone(),
+            );
 
-            // update is done by deleting old tuple and inserting new tuple with update values of old tuple
-            let old_tuple = delete_from_table_and_indexes(&table_info, &index_infos, &rid);
-            let new_tuple = self.get_updated_tuple(&old_tuple, &table_info);
-            let new_rid =
-                insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, new_tuple);
+            if let Err(e) = apply_referential_actions(
+                &self.context,
+                self.plan.table_oid,
+                &schema,
+                &old_tuple,
+                Some(&new_tuple),
+            ) {
+                return self.fail(e);
+            }
 
             updated_tuples += 1;
             self.rids_processed.push(new_rid);
@@ -154,12 +264,13 @@ mod tests {
             executor::{Execute, Executor},
             plan::PlanNode,
         },
+        index::IndexMeta,
         sample_code::executors::{seq_scan_executor, update_executor, TableConstructorType},
         table::{
             schema::{ColumnType, Schema},
             tuple::Tuple,
         },
-        test_utils::int_value,
+        test_utils::{bool_value, int_value},
     };
 
     #[test]
@@ -247,4 +358,141 @@ mod tests {
         // cleanup
         remove_file(db_path).expect("Couldn't remove test DB file");
     }
+
+    #[test]
+    fn update_executor_in_place() {
+        // init
+        let db_path = temp_dir().join("update_update_executor_in_place.db");
+        let (scan_executor, table_context) = seq_scan_executor(TableConstructorType::WithTable(
+            db_path.to_str().unwrap().to_string(),
+        ));
+        let tuples_schema = table_context.1.clone();
+
+        let (mut update_executor, _) = update_executor(
+            PlanNode::SeqScan(scan_executor.plan.clone()),
+            Executor::SeqScan(scan_executor),
+            TableConstructorType::WithoutTable(table_context.clone()),
+        );
+
+        // index on a column that the update doesn't change
+        let key_schema = Schema::with_types(vec![ColumnType::Boolean]);
+        let index_info = table_context
+            .0
+            .catalog
+            .create_index(
+                "second_col",
+                "test_table",
+                tuples_schema.clone(),
+                key_schema.clone(),
+                vec![1],
+                key_schema.get_tuple_len(),
+            )
+            .unwrap();
+
+        let (mut tmp_scan_executor, _) =
+            seq_scan_executor(TableConstructorType::WithoutTable(table_context.clone()));
+        tmp_scan_executor.init();
+        let mut rids_before = vec![];
+        while let Some((_, rid)) = tmp_scan_executor.next() {
+            rids_before.push(rid);
+        }
+
+        let tmp_index_info = index_info.lock().unwrap();
+        let index_rids_before = tmp_index_info
+            .index
+            .scan(Tuple::new(vec![bool_value(true)], &key_schema));
+        drop(tmp_index_info);
+
+        // update
+        update_executor.init();
+        update_executor.next().unwrap();
+
+        // tuples keep their RIDs
+        let (mut tmp_scan_executor, _) =
+            seq_scan_executor(TableConstructorType::WithoutTable(table_context.clone()));
+        tmp_scan_executor.init();
+        let mut rids_after = vec![];
+        while let Some((tuple, rid)) = tmp_scan_executor.next() {
+            assert_eq!(tuple.get_value(&tuples_schema, 0), int_value(12));
+            rids_after.push(rid);
+        }
+        assert_eq!(rids_before, rids_after);
+
+        // index entries are left untouched
+        let tmp_index_info = index_info.lock().unwrap();
+        let index_rids_after = tmp_index_info
+            .index
+            .scan(Tuple::new(vec![bool_value(true)], &key_schema));
+        assert_eq!(index_rids_before.len(), 2);
+        assert_eq!(index_rids_before, index_rids_after);
+        drop(tmp_index_info);
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn update_executor_undoes_failed_statement() {
+        // init
+        let db_path = temp_dir().join("update_update_executor_undoes_failed_statement.db");
+        let (scan_executor, table_context) = seq_scan_executor(TableConstructorType::WithTable(
+            db_path.to_str().unwrap().to_string(),
+        ));
+        let tuples_schema = table_context.1.clone();
+
+        let (mut update_executor, _) = update_executor(
+            PlanNode::SeqScan(scan_executor.plan.clone()),
+            Executor::SeqScan(scan_executor),
+            TableConstructorType::WithoutTable(table_context.clone()),
+        );
+
+        // every row is updated to the same key, so the second one violates the index
+        let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+        let meta = IndexMeta::new(key_schema.clone(), "first_col".to_string(), vec![0]).unique();
+        let index_info = table_context
+            .0
+            .catalog
+            .create_index_with_meta(
+                meta,
+                "test_table",
+                tuples_schema.clone(),
+                key_schema.get_tuple_len(),
+            )
+            .unwrap();
+
+        // test
+        update_executor.init();
+        assert_eq!(update_executor.next(), None);
+        assert_eq!(
+            update_executor.error(),
+            Some("Can't update tuple: UNIQUE constraint \"first_col\" violated: key { 12 } already exists in table \"test_table\"")
+        );
+
+        // the first row is restored in the table and the index
+        let (mut tmp_scan_executor, _) =
+            seq_scan_executor(TableConstructorType::WithoutTable(table_context.clone()));
+        tmp_scan_executor.init();
+        let mut values = vec![];
+        while let Some((tuple, _)) = tmp_scan_executor.next() {
+            values.push(tuple.get_value(&tuples_schema, 0));
+        }
+        assert_eq!(values, vec![int_value(1), int_value(2), int_value(3)]);
+
+        let index = index_info.lock().unwrap();
+        assert!(index
+            .index
+            .scan(Tuple::new(vec![int_value(12)], &key_schema))
+            .is_empty());
+        assert_eq!(
+            index
+                .index
+                .scan(Tuple::new(vec![int_value(1)], &key_schema))
+                .len(),
+            1
+        );
+        drop(index);
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
 }
@@ -1,7 +1,10 @@
 use std::sync::MutexGuard;
 
 use crate::{
-    catalog::info::{IndexInfo, TableInfo},
+    catalog::{
+        constraint::TableConstraint,
+        info::{IndexInfo, TableInfo},
+    },
     table::{
         page::TupleMeta,
         schema::{ColumnType, Schema},
@@ -10,6 +13,15 @@ use crate::{
     },
 };
 
+/// Returns the tuple with RID=`rid` of table with `table_info`, `None` if it was deleted.
+pub fn get_live_tuple(table_info: &MutexGuard<'_, TableInfo>, rid: &RID) -> Option<Tuple> {
+    table_info
+        .table
+        .get_tuple(rid)
+        .filter(|(meta, _)| !meta.is_deleted)
+        .map(|(_, tuple)| tuple)
+}
+
 /// Delete tuple with RID=`rid` from table with `table_info` and all indexes in `index_infos`.
 pub fn delete_from_table_and_indexes(
     table_info: &MutexGuard<'_, TableInfo>,
@@ -24,18 +36,24 @@ pub fn delete_from_table_and_indexes(
     table_info.table.update_tuple_meta(meta, rid);
 
     for index_info in index_infos.iter() {
-        index_info.index.delete(&tuple, &table_info.schema);
+        index_info.delete_entry(&tuple, &table_info.schema, rid);
     }
 
     tuple
 }
 
 /// Insert `tuple` in table with `table_info` and indexes in `index_infos`.
+///
+/// # Errors
+/// Will return `Err` with a description of the violation, without inserting anything, if the key of the tuple is
+/// already in one of the unique indexes.
 pub fn insert_tuple_in_table_and_indexes(
     table_info: &mut MutexGuard<'_, TableInfo>,
     index_infos: &Vec<MutexGuard<'_, IndexInfo>>,
     tuple: Tuple,
-) -> RID {
+) -> Result<RID, String> {
+    check_unique_keys(table_info, index_infos, &tuple, None)?;
+
     let new_rid = table_info
         .table
         .insert_tuple(
@@ -49,12 +67,146 @@ pub fn insert_tuple_in_table_and_indexes(
 
     for index_info in index_infos.iter() {
         index_info
-            .index
-            .insert(&tuple, &table_info.schema, new_rid.clone())
+            .insert_entry(&tuple, &table_info.schema, new_rid.clone())
             .unwrap();
     }
 
-    new_rid
+    Ok(new_rid)
+}
+
+/// Replace tuple with RID=`rid` in table with `table_info` with `new_tuple`, returning the RID of the new tuple.
+///
+/// If the new tuple fits in the space of the old one it is updated in place (keeping its RID) and only the indexes
+/// whose key changed are updated. Otherwise, the old tuple is deleted and the new one inserted, updating all indexes.
+///
+/// # Errors
+/// Will return `Err` with a description of the violation, without updating anything, if the new key of the tuple is
+/// used by another row in one of the unique indexes.
+pub fn update_tuple_in_table_and_indexes(
+    table_info: &mut MutexGuard<'_, TableInfo>,
+    index_infos: &Vec<MutexGuard<'_, IndexInfo>>,
+    rid: &RID,
+    old_tuple: &Tuple,
+    new_tuple: Tuple,
+) -> Result<RID, String> {
+    check_unique_keys(table_info, index_infos, &new_tuple, Some(rid))?;
+
+    let meta = TupleMeta {
+        ts: 0,
+        is_deleted: false,
+    };
+    if table_info
+        .table
+        .update_tuple_in_place(meta, new_tuple.clone(), rid)
+        .is_err()
+    {
+        delete_from_table_and_indexes(table_info, index_infos, rid);
+        return insert_tuple_in_table_and_indexes(table_info, index_infos, new_tuple);
+    }
+
+    for index_info in index_infos.iter() {
+        let index_meta = index_info.index.meta();
+        let key_changed = index_meta.columns().iter().any(|&attr| {
+            old_tuple.get_value(&table_info.schema, attr)
+                != new_tuple.get_value(&table_info.schema, attr)
+        });
+
+        if key_changed {
+            index_info.delete_entry(old_tuple, &table_info.schema, rid);
+            index_info
+                .insert_entry(&new_tuple, &table_info.schema, rid.clone())
+                .unwrap();
+        }
+    }
+
+    Ok(rid.clone())
+}
+
+/// Replaces the nulls in `tuple` with the default values of their columns (if they have one).
+pub fn apply_defaults(tuple: Tuple, schema: &Schema) -> Tuple {
+    let values = (0..schema.get_cols_count())
+        .map(|i| match (tuple.get_value(schema, i), schema.get_column(i).default()) {
+            (value, Some(default)) if value.is_null() => default.clone(),
+            (value, _) => value,
+        })
+        .collect();
+
+    Tuple::new(values, schema)
+}
+
+/// Checks that `tuple` satisfies the `NOT NULL`, `CHECK` and unique constraints of the table with `table_info`. For
+/// updates, `rid` is the RID of the tuple being replaced, which doesn't count as a duplicate.
+///
+/// # Errors
+/// Will return `Err` with a description of the first violated constraint, or of the error of a `CHECK` expression.
+pub fn check_constraints(
+    table_info: &MutexGuard<'_, TableInfo>,
+    index_infos: &Vec<MutexGuard<'_, IndexInfo>>,
+    tuple: &Tuple,
+    rid: Option<&RID>,
+) -> Result<(), String> {
+    let schema = &table_info.schema;
+
+    for i in 0..schema.get_cols_count() {
+        let column = schema.get_column(i);
+        if !column.is_nullable() && tuple.get_value(schema, i).is_null() {
+            return Err(format!(
+                "NOT NULL constraint violated: column \"{}\" of table \"{}\" can't be NULL",
+                column.name(),
+                table_info.name
+            ));
+        }
+    }
+
+    for constraint in table_info.constraints.iter() {
+        if let TableConstraint::Check { name, expr } = constraint {
+            // unknown passes the check
+            if let ColumnValue::Boolean(value) = expr.try_evaluate(tuple, schema)? {
+                if !value.value {
+                    return Err(format!(
+                        "CHECK constraint \"{}\" violated by row {}",
+                        name,
+                        tuple.to_string(schema)
+                    ));
+                }
+            }
+        }
+    }
+
+    check_unique_keys(table_info, index_infos, tuple, rid)
+}
+
+/// Checks that the key of `tuple` isn't used by another row in any of the unique indexes of the table. For updates,
+/// `rid` is the RID of the tuple being replaced, which doesn't count as a duplicate. Indexes that are still being built
+/// are skipped: their build checks the keys of the changes when applying them (and fails on duplicates).
+///
+/// # Errors
+/// Will return `Err` with a description of the first duplicate key.
+pub fn check_unique_keys(
+    table_info: &MutexGuard<'_, TableInfo>,
+    index_infos: &Vec<MutexGuard<'_, IndexInfo>>,
+    tuple: &Tuple,
+    rid: Option<&RID>,
+) -> Result<(), String> {
+    for index_info in index_infos.iter() {
+        if index_info.is_valid()
+            && index_info
+                .index
+                .is_duplicate(tuple, &table_info.schema, rid)
+        {
+            let meta = index_info.index.meta();
+            let key = index_info.index.key(tuple, &table_info.schema);
+
+            return Err(format!(
+                "UNIQUE constraint \"{}\" violated: key {} already exists in table \"{}\"",
+                index_info.name,
+                key.to_string(meta.key_schema()),
+                table_info.name
+            ));
+        }
+    }
+
+    Ok(())
 }
 
 /// Create a new `Tuple` with a single integer column containing the given value.
@@ -64,3 +216,409 @@ pub fn int_tuple(value: i32) -> Tuple {
         &Schema::with_types(vec![ColumnType::Integer]),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs::remove_file, sync::Arc};
+
+    use crate::{
+        catalog::{constraint::TableConstraint, Catalog},
+        disk::buffer_pool_manager::BufferPoolManager,
+        exec::expression::{
+            boolean::{BooleanExpression, BooleanType},
+            string_function::{StringFunction, StringFunctionExpression},
+            Expression,
+        },
+        index::IndexMeta,
+        table::{
+            schema::{Column, ColumnType, Schema},
+            tuple::Tuple,
+            value::{ColumnValue, VarcharValue},
+        },
+        test_utils::{bool_value, column_with, const_bool, const_int, decimal_value, int_value},
+    };
+
+    use super::{
+        apply_defaults, check_constraints, delete_from_table_and_indexes,
+        insert_tuple_in_table_and_indexes, update_tuple_in_table_and_indexes,
+    };
+
+    /// Schema: `(id INTEGER PRIMARY KEY CHECK (id > 0), active BOOLEAN DEFAULT TRUE, price DECIMAL)`
+    fn constrained_table() -> (Schema, Vec<TableConstraint>) {
+        let schema = Schema::new(vec![
+            Column::new_named("id".to_string(), ColumnType::Integer).not_null(),
+            Column::new_named("active".to_string(), ColumnType::Boolean)
+                .with_default(bool_value(true)),
+            Column::new_named("price".to_string(), ColumnType::Decimal),
+        ]);
+        let constraints = vec![
+            TableConstraint::PrimaryKey(vec![0]),
+            TableConstraint::Check {
+                name: "items_id_check".to_string(),
+                expr: Expression::Boolean(BooleanExpression {
+                    left: Box::new(column_with(0, ColumnType::Integer)),
+                    right: Box::new(const_int(0)),
+                    typ: BooleanType::GT,
+                }),
+            },
+        ];
+
+        (schema, constraints)
+    }
+
+    #[test]
+    fn enforce_constraints() {
+        // init
+        let db_path = temp_dir().join("util_enforce_constraints.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 2, 2));
+        bpm.new_page(); // this is needed as table heaps assume page with PID 0 is not used
+        let catalog = Catalog::new(bpm);
+
+        let (schema, constraints) = constrained_table();
+        let table_info = catalog
+            .create_table_with_constraints("items", schema.clone(), constraints)
+            .unwrap();
+        let index_infos = catalog.get_table_indexes("items");
+
+        let mut table_info = table_info.lock().unwrap();
+        let index_infos = index_infos
+            .iter()
+            .map(|i| i.lock().unwrap())
+            .collect::<Vec<_>>();
+
+        // test
+        let tuple = Tuple::new(
+            vec![
+                int_value(1),
+                ColumnValue::Null(ColumnType::Boolean),
+                ColumnValue::Null(ColumnType::Decimal),
+            ],
+            &schema,
+        );
+        let tuple = apply_defaults(tuple, &schema);
+        assert_eq!(tuple.get_value(&schema, 1), bool_value(true));
+        assert!(tuple.get_value(&schema, 2).is_null());
+
+        assert_eq!(check_constraints(&table_info, &index_infos, &tuple, None), Ok(()));
+        let rid = insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, tuple.clone())
+            .unwrap();
+
+        // duplicate key, unless it belongs to the tuple being updated
+        assert_eq!(
+            check_constraints(&table_info, &index_infos, &tuple, None),
+            Err(
+                "UNIQUE constraint \"items_pkey\" violated: key { 1 } already exists in table \"items\""
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            check_constraints(&table_info, &index_infos, &tuple, Some(&rid)),
+            Ok(())
+        );
+
+        // null primary key
+        let tuple = Tuple::new(
+            vec![
+                ColumnValue::Null(ColumnType::Integer),
+                bool_value(false),
+                decimal_value(1.5),
+            ],
+            &schema,
+        );
+        assert_eq!(
+            check_constraints(&table_info, &index_infos, &tuple, None),
+            Err(
+                "NOT NULL constraint violated: column \"id\" of table \"items\" can't be NULL"
+                    .to_string()
+            )
+        );
+
+        // failing check
+        let tuple = Tuple::new(
+            vec![int_value(-1), bool_value(false), decimal_value(1.5)],
+            &schema,
+        );
+        assert!(check_constraints(&table_info, &index_infos, &tuple, None)
+            .unwrap_err()
+            .starts_with("CHECK constraint \"items_id_check\" violated"));
+
+        // cleanup
+        drop(index_infos);
+        drop(table_info);
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn delete_keeps_rows_with_same_key() {
+        // init
+        let db_path = temp_dir().join("util_delete_keeps_rows_with_same_key.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 2, 2));
+        bpm.new_page(); // this is needed as table heaps assume page with PID 0 is not used
+        let catalog = Catalog::new(bpm);
+
+        let schema = Schema::with_types(vec![ColumnType::Integer, ColumnType::Integer]);
+        let table_info = catalog.create_table("t", schema.clone()).unwrap();
+        let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+        let key_size = Tuple::new(vec![int_value(0)], &key_schema).size();
+        catalog
+            .create_index(
+                "t_value",
+                "t",
+                schema.clone(),
+                key_schema.clone(),
+                vec![1],
+                key_size,
+            )
+            .unwrap();
+        let index_infos = catalog.get_table_indexes("t");
+
+        let mut table_info = table_info.lock().unwrap();
+        let index_infos = index_infos
+            .iter()
+            .map(|i| i.lock().unwrap())
+            .collect::<Vec<_>>();
+
+        // test
+        let tuple = |id| Tuple::new(vec![int_value(id), int_value(10)], &schema);
+        let rid_1 =
+            insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, tuple(1)).unwrap();
+        let rid_2 =
+            insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, tuple(2)).unwrap();
+
+        delete_from_table_and_indexes(&table_info, &index_infos, &rid_1);
+
+        let key = Tuple::new(vec![int_value(10)], &key_schema);
+        assert_eq!(index_infos[0].index.scan(key), vec![rid_2]);
+
+        // cleanup
+        drop(index_infos);
+        drop(table_info);
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn unique_index_rejects_duplicates() {
+        // init
+        let db_path = temp_dir().join("util_unique_index_rejects_duplicates.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 2, 2));
+        bpm.new_page(); // this is needed as table heaps assume page with PID 0 is not used
+        let catalog = Catalog::new(bpm);
+
+        let schema = Schema::with_types(vec![ColumnType::Integer, ColumnType::Integer]);
+        let table_info = catalog.create_table("t", schema.clone()).unwrap();
+        let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+        let key_size = Tuple::new(vec![int_value(0)], &key_schema).size();
+        let unique_meta =
+            || IndexMeta::new(key_schema.clone(), "t_value".to_string(), vec![1]).unique();
+        let tuple = |id, value| Tuple::new(vec![int_value(id), value], &schema);
+
+        // test
+        let mut table = table_info.lock().unwrap();
+        let rid_1 = insert_tuple_in_table_and_indexes(&mut table, &vec![], tuple(1, int_value(10)))
+            .unwrap();
+        let rid_2 = insert_tuple_in_table_and_indexes(&mut table, &vec![], tuple(2, int_value(10)))
+            .unwrap();
+        drop(table);
+
+        // existing rows are checked when the index is built
+        assert!(catalog
+            .create_index_with_meta(unique_meta(), "t", schema.clone(), key_size)
+            .is_err());
+
+        let mut table = table_info.lock().unwrap();
+        update_tuple_in_table_and_indexes(
+            &mut table,
+            &vec![],
+            &rid_2,
+            &tuple(2, int_value(10)),
+            tuple(2, int_value(20)),
+        )
+        .unwrap();
+        drop(table);
+        catalog
+            .create_index_with_meta(unique_meta(), "t", schema.clone(), key_size)
+            .unwrap();
+        let index_infos = catalog.get_table_indexes("t");
+
+        let mut table_info = table_info.lock().unwrap();
+        let index_infos = index_infos
+            .iter()
+            .map(|i| i.lock().unwrap())
+            .collect::<Vec<_>>();
+
+        let expected_error = Err(
+            "UNIQUE constraint \"t_value\" violated: key { 10 } already exists in table \"t\""
+                .to_string(),
+        );
+        assert_eq!(
+            insert_tuple_in_table_and_indexes(
+                &mut table_info,
+                &index_infos,
+                tuple(3, int_value(10))
+            ),
+            expected_error
+        );
+        assert_eq!(
+            update_tuple_in_table_and_indexes(
+                &mut table_info,
+                &index_infos,
+                &rid_2,
+                &tuple(2, int_value(20)),
+                tuple(2, int_value(10)),
+            ),
+            expected_error
+        );
+        // updating a row without changing its key is not a duplicate
+        assert_eq!(
+            update_tuple_in_table_and_indexes(
+                &mut table_info,
+                &index_infos,
+                &rid_1,
+                &tuple(1, int_value(10)),
+                tuple(4, int_value(10)),
+            ),
+            Ok(rid_1)
+        );
+
+        // keys with nulls are never duplicates
+        let null = || ColumnValue::Null(ColumnType::Integer);
+        insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, tuple(5, null())).unwrap();
+        insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, tuple(6, null())).unwrap();
+
+        // cleanup
+        drop(index_infos);
+        drop(table_info);
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn expression_and_partial_indexes() {
+        // init
+        let db_path = temp_dir().join("util_expression_and_partial_indexes.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 10, 2));
+        bpm.new_page(); // this is needed as table heaps assume page with PID 0 is not used
+        let catalog = Catalog::new(bpm);
+
+        let schema = Schema::with_types(vec![
+            ColumnType::Integer,
+            ColumnType::Varchar(10),
+            ColumnType::Boolean,
+        ]);
+        let table_info = catalog.create_table("t", schema.clone()).unwrap();
+
+        // UNIQUE (LOWER(name))
+        let name_schema = Schema::with_types(vec![ColumnType::Varchar(10)]);
+        let lower_name = Expression::StringFunction(StringFunctionExpression {
+            function: StringFunction::Lower,
+            expr: Box::new(column_with(1, ColumnType::Varchar(10))),
+        });
+        let meta = IndexMeta::with_expressions(
+            name_schema.clone(),
+            "t_lower_name".to_string(),
+            vec![lower_name],
+        )
+        .unique();
+        catalog
+            .create_index_with_meta(meta, "t", schema.clone(), name_schema.get_tuple_len())
+            .unwrap();
+
+        // (id) WHERE active = true
+        let id_schema = Schema::with_types(vec![ColumnType::Integer]);
+        let is_active = Expression::Boolean(BooleanExpression {
+            left: Box::new(column_with(2, ColumnType::Boolean)),
+            right: Box::new(const_bool(true)),
+            typ: BooleanType::EQ,
+        });
+        let meta = IndexMeta::new(id_schema.clone(), "t_active_id".to_string(), vec![0])
+            .with_predicate(is_active);
+        catalog
+            .create_index_with_meta(meta, "t", schema.clone(), id_schema.get_tuple_len())
+            .unwrap();
+
+        let index_infos = catalog.get_table_indexes("t");
+        let mut table_info = table_info.lock().unwrap();
+        let index_infos = index_infos
+            .iter()
+            .map(|i| i.lock().unwrap())
+            .collect::<Vec<_>>();
+        let index = |name: &str| &index_infos.iter().find(|i| i.name == name).unwrap().index;
+
+        let varchar = |value: &str| {
+            ColumnValue::Varchar(VarcharValue {
+                value: value.to_string(),
+                length: 10,
+            })
+        };
+        let tuple = |id, name, active| {
+            Tuple::new(
+                vec![int_value(id), varchar(name), bool_value(active)],
+                &schema,
+            )
+        };
+        let name_key = |name| Tuple::new(vec![varchar(name)], &name_schema);
+        let id_key = |id| Tuple::new(vec![int_value(id)], &id_schema);
+
+        // test
+        let rid_1 = insert_tuple_in_table_and_indexes(
+            &mut table_info,
+            &index_infos,
+            tuple(1, "Alice", true),
+        )
+        .unwrap();
+        let rid_2 = insert_tuple_in_table_and_indexes(
+            &mut table_info,
+            &index_infos,
+            tuple(2, "Bob", false),
+        )
+        .unwrap();
+
+        assert_eq!(
+            index("t_lower_name").scan(name_key("bob")),
+            vec![rid_2.clone()]
+        );
+        assert_eq!(index("t_active_id").scan(id_key(1)), vec![rid_1.clone()]);
+        assert_eq!(index("t_active_id").scan(id_key(2)), vec![]);
+
+        // the unique key is computed by the expression
+        assert_eq!(
+            insert_tuple_in_table_and_indexes(&mut table_info, &index_infos, tuple(3, "ALICE", true)),
+            Err(
+                "UNIQUE constraint \"t_lower_name\" violated: key { alice } already exists in table \"t\""
+                    .to_string()
+            )
+        );
+
+        // changing a column of the predicate moves the row in or out of the partial index
+        update_tuple_in_table_and_indexes(
+            &mut table_info,
+            &index_infos,
+            &rid_2,
+            &tuple(2, "Bob", false),
+            tuple(2, "Bob", true),
+        )
+        .unwrap();
+        update_tuple_in_table_and_indexes(
+            &mut table_info,
+            &index_infos,
+            &rid_1,
+            &tuple(1, "Alice", true),
+            tuple(1, "Alice", false),
+        )
+        .unwrap();
+        assert_eq!(index("t_active_id").scan(id_key(1)), vec![]);
+        assert_eq!(index("t_active_id").scan(id_key(2)), vec![rid_2.clone()]);
+
+        delete_from_table_and_indexes(&table_info, &index_infos, &rid_2);
+        assert_eq!(index("t_lower_name").scan(name_key("bob")), vec![]);
+        assert_eq!(index("t_active_id").scan(id_key(2)), vec![]);
+
+        // cleanup
+        drop(index_infos);
+        drop(table_info);
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+}
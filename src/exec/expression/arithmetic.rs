@@ -1,7 +1,14 @@
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
 use crate::table::{
+    datetime::{Interval, MICROS_PER_DAY},
+    numeric::{NumericError, RoundingMode, MAX_NUMERIC_PRECISION},
     schema::{Column, ColumnType, Schema},
     tuple::Tuple,
-    value::{ColumnValue, IntegerValue},
+    value::{
+        ColumnValue, DateValue, DecimalValue, DoubleValue, FloatValue, IntegerValue, IntervalValue,
+        NumericValue, TimestampValue,
+    },
 };
 
 use super::{Evaluate, Expression};
@@ -20,30 +27,275 @@ pub struct ArithmeticExpression {
     pub left: Box<Expression>,
     pub right: Box<Expression>,
     pub typ: ArithmeticType,
+    /// How numeric results are rounded to the scale of the result type
+    pub rounding: RoundingMode,
 }
 
+/// Minimum scale of the result of a numeric division, so that `1 / 3` isn't `0`
+const MIN_DIVISION_SCALE: u8 = 6;
+
 impl ArithmeticExpression {
+    /// Evaluates the expression like `evaluate`, but returns the errors of numeric operations (overflow or division by
+    /// zero) instead of panicking.
+    pub fn try_evaluate(&self, tuple: &Tuple, schema: &Schema) -> Result<ColumnValue, String> {
+        let l_val = self.left.try_evaluate(tuple, schema)?;
+        let r_val = self.right.try_evaluate(tuple, schema)?;
+        self.try_compute(l_val, r_val)
+    }
+
+    /// # Panics
+    /// Will panic if the operation isn't supported for the operands or if a numeric result can't be computed.
     fn compute(&self, l: ColumnValue, r: ColumnValue) -> ColumnValue {
+        self.try_compute(l, r).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// # Errors
+    /// Will return `Err` if a numeric result can't be computed (see `compute_numeric`).
+    ///
+    /// # Panics
+    /// Will panic if the operation isn't supported for the operands.
+    fn try_compute(&self, l: ColumnValue, r: ColumnValue) -> Result<ColumnValue, String> {
+        if l.is_null() || r.is_null() {
+            return Ok(ColumnValue::Null(self.result_type(&l.typ(), &r.typ())));
+        }
+
+        if let Some((precision, scale)) = self.numeric_result_type(&l.typ(), &r.typ()) {
+            return self.compute_numeric(l, r, precision, scale);
+        }
+
         // TODO: consider casting to decimal before computation
-        match (l, r) {
-            (ColumnValue::Integer(l), ColumnValue::Integer(r)) => match self.typ {
-                ArithmeticType::Plus => ColumnValue::Integer(IntegerValue {
+        let result = match (l, r) {
+            (ColumnValue::Integer(l), ColumnValue::Integer(r)) => {
+                ColumnValue::Integer(IntegerValue {
+                    value: self.apply(l.value, r.value),
+                })
+            }
+            (ColumnValue::Decimal(l), ColumnValue::Decimal(r)) => {
+                ColumnValue::Decimal(DecimalValue {
+                    value: self.apply(l.value, r.value),
+                })
+            }
+            (ColumnValue::Float(l), ColumnValue::Float(r)) => ColumnValue::Float(FloatValue {
+                value: self.apply(l.value, r.value),
+            }),
+            (ColumnValue::Double(l), ColumnValue::Double(r)) => ColumnValue::Double(DoubleValue {
+                value: self.apply(l.value, r.value),
+            }),
+            // numerics with approximate numbers give approximate results
+            (ColumnValue::Numeric(l), ColumnValue::Decimal(r)) => {
+                ColumnValue::Decimal(DecimalValue {
+                    value: self.apply(l.value.to_f64(), r.value),
+                })
+            }
+            (ColumnValue::Decimal(l), ColumnValue::Numeric(r)) => {
+                ColumnValue::Decimal(DecimalValue {
+                    value: self.apply(l.value, r.value.to_f64()),
+                })
+            }
+            (ColumnValue::Numeric(l), ColumnValue::Float(r)) => ColumnValue::Float(FloatValue {
+                value: self.apply(l.value.to_f64() as f32, r.value),
+            }),
+            (ColumnValue::Float(l), ColumnValue::Numeric(r)) => ColumnValue::Float(FloatValue {
+                value: self.apply(l.value, r.value.to_f64() as f32),
+            }),
+            (ColumnValue::Numeric(l), ColumnValue::Double(r)) => ColumnValue::Double(DoubleValue {
+                value: self.apply(l.value.to_f64(), r.value),
+            }),
+            (ColumnValue::Double(l), ColumnValue::Numeric(r)) => ColumnValue::Double(DoubleValue {
+                value: self.apply(l.value, r.value.to_f64()),
+            }),
+            // date +/- number of days
+            (ColumnValue::Date(l), ColumnValue::Integer(r)) => match self.typ {
+                ArithmeticType::Plus => ColumnValue::Date(DateValue {
                     value: l.value + r.value,
                 }),
+                ArithmeticType::Minus => ColumnValue::Date(DateValue {
+                    value: l.value - r.value,
+                }),
+                _ => panic!("Only + and - are supported for (Date, Integer)"),
+            },
+            (ColumnValue::Integer(l), ColumnValue::Date(r)) => match self.typ {
+                ArithmeticType::Plus => ColumnValue::Date(DateValue {
+                    value: l.value + r.value,
+                }),
+                _ => panic!("Only + is supported for (Integer, Date)"),
+            },
+            // number of days between dates
+            (ColumnValue::Date(l), ColumnValue::Date(r)) => match self.typ {
                 ArithmeticType::Minus => ColumnValue::Integer(IntegerValue {
                     value: l.value - r.value,
                 }),
-                ArithmeticType::Divide => ColumnValue::Integer(IntegerValue {
-                    value: l.value / r.value,
+                _ => panic!("Only - is supported for (Date, Date)"),
+            },
+            // calendar-aware interval arithmetic
+            (ColumnValue::Timestamp(l), ColumnValue::Interval(r)) => match self.typ {
+                ArithmeticType::Plus => self.shift_timestamp(l.value, r.value)?,
+                ArithmeticType::Minus => self.shift_timestamp(l.value, r.value.negate())?,
+                _ => panic!("Only + and - are supported for (Timestamp, Interval)"),
+            },
+            (ColumnValue::Interval(l), ColumnValue::Timestamp(r)) => match self.typ {
+                ArithmeticType::Plus => self.shift_timestamp(r.value, l.value)?,
+                _ => panic!("Only + is supported for (Interval, Timestamp)"),
+            },
+            (ColumnValue::Date(l), ColumnValue::Interval(r)) => {
+                let interval = match self.typ {
+                    ArithmeticType::Plus => r.value,
+                    ArithmeticType::Minus => r.value.negate(),
+                    _ => panic!("Only + and - are supported for (Date, Interval)"),
+                };
+                // the date is converted to the timestamp of its midnight
+                let timestamp = (l.value as i64)
+                    .checked_mul(MICROS_PER_DAY as i64)
+                    .ok_or_else(|| self.out_of_range_error())?;
+                self.shift_timestamp(timestamp, interval)?
+            }
+            (ColumnValue::Timestamp(l), ColumnValue::Timestamp(r)) => match self.typ {
+                ArithmeticType::Minus => ColumnValue::Interval(IntervalValue {
+                    value: Interval::between(r.value, l.value),
                 }),
-                ArithmeticType::Multiply => ColumnValue::Integer(IntegerValue {
-                    value: l.value * r.value,
+                _ => panic!("Only - is supported for (Timestamp, Timestamp)"),
+            },
+            (ColumnValue::Interval(l), ColumnValue::Interval(r)) => match self.typ {
+                ArithmeticType::Plus => ColumnValue::Interval(IntervalValue {
+                    value: l.value.add(r.value),
                 }),
-                ArithmeticType::Mod => ColumnValue::Integer(IntegerValue {
-                    value: l.value % r.value,
+                ArithmeticType::Minus => ColumnValue::Interval(IntervalValue {
+                    value: l.value.sub(r.value),
                 }),
+                _ => panic!("Only + and - are supported for (Interval, Interval)"),
             },
-            _ => panic!("Only supprted compute operands are (Integer, Integer)"),
+            (ColumnValue::Interval(l), ColumnValue::Integer(r)) => match self.typ {
+                ArithmeticType::Multiply => ColumnValue::Interval(IntervalValue {
+                    value: l.value.multiply(r.value),
+                }),
+                _ => panic!("Only * is supported for (Interval, Integer)"),
+            },
+            _ => panic!("Only supprted compute operands are numbers of the same type (or numerics with other numbers), dates with integers and timestamps with intervals"),
+        };
+
+        Ok(result)
+    }
+
+    /// Returns the type of the result of the operation on operands of types `l` and `r`.
+    fn result_type(&self, l: &ColumnType, r: &ColumnType) -> ColumnType {
+        if let Some((precision, scale)) = self.numeric_result_type(l, r) {
+            return ColumnType::Numeric(precision, scale);
+        }
+
+        match (l, r) {
+            (ColumnType::Date, ColumnType::Date) => ColumnType::Integer,
+            (ColumnType::Integer, ColumnType::Date) => ColumnType::Date,
+            (ColumnType::Date, ColumnType::Interval) => ColumnType::Timestamp,
+            (ColumnType::Interval, ColumnType::Timestamp) => ColumnType::Timestamp,
+            (ColumnType::Timestamp, ColumnType::Timestamp) => ColumnType::Interval,
+            (
+                ColumnType::Numeric(_, _),
+                approximate @ (ColumnType::Decimal | ColumnType::Float | ColumnType::Double),
+            ) => approximate.clone(),
+            (left, _) => left.clone(),
+        }
+    }
+
+    /// Returns the timestamp that is `interval` after `timestamp`.
+    ///
+    /// # Errors
+    /// Will return `Err` if the result is out of the range of timestamps.
+    fn shift_timestamp(&self, timestamp: i64, interval: Interval) -> Result<ColumnValue, String> {
+        let value = interval
+            .add_to(timestamp)
+            .map_err(|_| self.out_of_range_error())?;
+
+        Ok(ColumnValue::Timestamp(TimestampValue { value }))
+    }
+
+    fn out_of_range_error(&self) -> String {
+        format!(
+            "Failed to compute {}: result is out of the range of timestamps",
+            self.to_string()
+        )
+    }
+
+    /// Computes the exact result of the operation on numerics (or a numeric and an integer), rounded to `scale`.
+    ///
+    /// # Errors
+    /// Will return `Err` if the result can't be computed, i.e. on overflow (the result has more than `precision` digits)
+    /// or on division by zero.
+    fn compute_numeric(
+        &self,
+        l: ColumnValue,
+        r: ColumnValue,
+        precision: u8,
+        scale: u8,
+    ) -> Result<ColumnValue, String> {
+        let l = l.to_numeric().unwrap(); // unwrap is fine because the result type is numeric
+        let r = r.to_numeric().unwrap();
+
+        let value = match self.typ {
+            ArithmeticType::Plus => l.add(r),
+            ArithmeticType::Minus => l.sub(r),
+            ArithmeticType::Multiply => l.mul(r),
+            ArithmeticType::Divide => l.div(r, scale, self.rounding),
+            ArithmeticType::Mod => l.rem(r),
+        }
+        .and_then(|result| result.rescale(scale, self.rounding))
+        .and_then(|result| result.check_precision(precision))
+        .map_err(|e| match e {
+            NumericError::DivisionByZero => {
+                format!("Failed to compute {}: division by zero", self.to_string())
+            }
+            _ => format!(
+                "Failed to compute {}: result doesn't fit in NUMERIC({}, {})",
+                self.to_string(),
+                precision,
+                scale
+            ),
+        })?;
+
+        Ok(ColumnValue::Numeric(NumericValue { value, precision }))
+    }
+
+    /// Returns the `(precision, scale)` of the result if at least one operand is a numeric and the other one is a
+    /// numeric or an integer, `None` otherwise.
+    fn numeric_result_type(&self, l: &ColumnType, r: &ColumnType) -> Option<(u8, u8)> {
+        if !matches!(l, ColumnType::Numeric(_, _)) && !matches!(r, ColumnType::Numeric(_, _)) {
+            return None;
+        }
+        let (p1, s1) = numeric_precision(l)?;
+        let (p2, s2) = numeric_precision(r)?;
+
+        // digits before the point of the operands
+        let int_digits = p1.saturating_sub(s1).max(p2.saturating_sub(s2));
+        let (precision, scale) = match self.typ {
+            ArithmeticType::Plus | ArithmeticType::Minus => {
+                let scale = s1.max(s2);
+                (int_digits as u32 + scale as u32 + 1, scale as u32)
+            }
+            ArithmeticType::Multiply => (p1 as u32 + p2 as u32 + 1, s1 as u32 + s2 as u32),
+            ArithmeticType::Divide => {
+                let scale = s1.max(s2).max(MIN_DIVISION_SCALE);
+                (MAX_NUMERIC_PRECISION as u32, scale as u32)
+            }
+            ArithmeticType::Mod => {
+                let scale = s1.max(s2);
+                (int_digits as u32 + scale as u32, scale as u32)
+            }
+        };
+
+        let max = MAX_NUMERIC_PRECISION as u32;
+        let scale = scale.min(max) as u8;
+        Some((precision.min(max).max(scale as u32) as u8, scale))
+    }
+
+    fn apply<T>(&self, l: T, r: T) -> T
+    where
+        T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Rem<Output = T>,
+    {
+        match self.typ {
+            ArithmeticType::Plus => l + r,
+            ArithmeticType::Minus => l - r,
+            ArithmeticType::Divide => l / r,
+            ArithmeticType::Multiply => l * r,
+            ArithmeticType::Mod => l % r,
         }
     }
 }
@@ -72,7 +324,10 @@ impl Evaluate for ArithmeticExpression {
     }
 
     fn return_type(&self) -> Column {
-        Column::new_named("_result_".to_string(), ColumnType::Integer)
+        let (left, right) = (self.left.return_type(), self.right.return_type());
+        let typ = self.result_type(left.col_type(), right.col_type());
+
+        Column::new_named("_result_".to_string(), typ)
     }
 
     fn to_string(&self) -> String {
@@ -90,3 +345,15 @@ impl Evaluate for ArithmeticExpression {
         )
     }
 }
+
+/// Returns the `(precision, scale)` needed to represent exactly the values of `typ`, if it is an integer or a numeric.
+fn numeric_precision(typ: &ColumnType) -> Option<(u8, u8)> {
+    match typ {
+        ColumnType::TinyInt => Some((3, 0)),
+        ColumnType::SmallInt => Some((5, 0)),
+        ColumnType::Integer => Some((10, 0)),
+        ColumnType::BigInt => Some((19, 0)),
+        ColumnType::Numeric(precision, scale) => Some((*precision, *scale)),
+        _ => None,
+    }
+}
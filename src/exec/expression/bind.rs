@@ -0,0 +1,323 @@
+use crate::{
+    index::full_text::TextQuery,
+    parser::{
+        ast::general::{
+            AndCondition, CompareType, Condition, Expression as AstExpression, Factor, FactorRight,
+            Function, Operand, OperandRight, Operation, Term,
+        },
+        token::value::Value,
+    },
+    table::{
+        numeric::{Numeric, RoundingMode},
+        schema::{ColumnType, Schema},
+        value::{
+            BigIntValue, BooleanValue, ColumnValue, DecimalValue, IntegerValue, NumericValue,
+            VarcharValue,
+        },
+    },
+};
+
+use super::{
+    arithmetic::{ArithmeticExpression, ArithmeticType},
+    boolean::{BooleanExpression, BooleanType},
+    constant::ConstantExpression,
+    string_function::{StringFunction, StringFunctionExpression},
+    text_match::MatchExpression,
+    value::{ColumnValueExpression, JoinSide},
+    Evaluate, Expression,
+};
+
+/// Binds a boolean expression of the AST (e.g. the condition of a `CHECK` constraint) to an expression that can be
+/// evaluated on tuples of `schema`. Columns are resolved by name and literals compared with columns are converted to the
+/// type of the column.
+///
+/// Only comparisons, `BETWEEN`, `AND`/`OR` and arithmetic on columns and literals are supported.
+///
+/// # Errors
+/// Will return `Err` if the expression references unknown columns or uses unsupported operations.
+pub fn bind_expression(expression: &AstExpression, schema: &Schema) -> Result<Expression, String> {
+    let and_conditions = expression
+        .and_conditions
+        .iter()
+        .map(|c| bind_and_condition(c, schema))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(fold_boolean(and_conditions, BooleanType::Or))
+}
+
+fn bind_and_condition(and_condition: &AndCondition, schema: &Schema) -> Result<Expression, String> {
+    let conditions = and_condition
+        .conditions
+        .iter()
+        .map(|c| bind_condition(c, schema))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(fold_boolean(conditions, BooleanType::And))
+}
+
+fn bind_condition(condition: &Condition, schema: &Schema) -> Result<Expression, String> {
+    match condition {
+        Condition::Positive(expression) => bind_expression(expression, schema),
+        Condition::Negative(_) => Err("NOT conditions are not supported".to_string()),
+        Condition::Operation {
+            operand,
+            operation: None,
+        } => bind_operand(operand, schema),
+        Condition::Operation {
+            operand,
+            operation:
+                Some(Operation::Comparison {
+                    cmp_type,
+                    operand: right,
+                }),
+        } => {
+            let typ = match cmp_type {
+                CompareType::EQ => BooleanType::EQ,
+                CompareType::NE => BooleanType::NE,
+                CompareType::GT => BooleanType::GT,
+                CompareType::GTE => BooleanType::GE,
+                CompareType::LT => BooleanType::LT,
+                CompareType::LTE => BooleanType::LE,
+            };
+
+            bind_comparison(operand, right, typ, schema)
+        }
+        Condition::Operation {
+            operand,
+            operation: Some(Operation::Between { not, start, end }),
+        } => {
+            let (start_type, end_type, combine) = if *not {
+                (BooleanType::LT, BooleanType::GT, BooleanType::Or)
+            } else {
+                (BooleanType::GE, BooleanType::LE, BooleanType::And)
+            };
+
+            Ok(boolean(
+                bind_comparison(operand, start, start_type, schema)?,
+                bind_comparison(operand, end, end_type, schema)?,
+                combine,
+            ))
+        }
+        Condition::Operation {
+            operation: Some(operation),
+            ..
+        } => Err(format!("Operation {:?} is not supported", operation)),
+    }
+}
+
+/// Binds `left <typ> right`, converting a literal operand to the type of the other operand.
+fn bind_comparison(
+    left: &Operand,
+    right: &Operand,
+    typ: BooleanType,
+    schema: &Schema,
+) -> Result<Expression, String> {
+    let (left, right) = match (literal(left), literal(right)) {
+        (None, Some(value)) => {
+            let left = bind_operand(left, schema)?;
+            let right = bind_literal_as(value, left.return_type().col_type())?;
+            (left, right)
+        }
+        (Some(value), None) => {
+            let right = bind_operand(right, schema)?;
+            let left = bind_literal_as(value, right.return_type().col_type())?;
+            (left, right)
+        }
+        _ => (bind_operand(left, schema)?, bind_operand(right, schema)?),
+    };
+
+    Ok(boolean(left, right, typ))
+}
+
+fn bind_operand(operand: &Operand, schema: &Schema) -> Result<Expression, String> {
+    let mut expression = bind_factor(&operand.left, schema)?;
+    for right in operand.right.iter() {
+        expression = match right {
+            OperandRight::Plus(factor) => arithmetic(
+                expression,
+                bind_factor(factor, schema)?,
+                ArithmeticType::Plus,
+            ),
+            OperandRight::Minus(factor) => arithmetic(
+                expression,
+                bind_factor(factor, schema)?,
+                ArithmeticType::Minus,
+            ),
+        };
+    }
+
+    Ok(expression)
+}
+
+fn bind_factor(factor: &Factor, schema: &Schema) -> Result<Expression, String> {
+    let mut expression = bind_term(&factor.left, schema)?;
+    for right in factor.right.iter() {
+        expression = match right {
+            FactorRight::Mult(term) => arithmetic(
+                expression,
+                bind_term(term, schema)?,
+                ArithmeticType::Multiply,
+            ),
+            FactorRight::Div(term) => {
+                arithmetic(expression, bind_term(term, schema)?, ArithmeticType::Divide)
+            }
+        };
+    }
+
+    Ok(expression)
+}
+
+/// Binds a term of the AST (e.g. a key of an expression index, like `LOWER(name)`) to an expression that can be
+/// evaluated on tuples of `schema`.
+///
+/// # Errors
+/// Will return `Err` if the term references unknown columns or uses unsupported operations or functions.
+pub fn bind_term(term: &Term, schema: &Schema) -> Result<Expression, String> {
+    match term {
+        Term::Value(value) => bind_literal(value),
+        Term::Operand(operand) => bind_operand(operand, schema),
+        Term::Column { name, .. } => {
+            let col_index = schema
+                .get_col_index(name)
+                .ok_or(format!("Unknown column {}", name))?;
+
+            Ok(Expression::ColumnValue(ColumnValueExpression {
+                join_side: JoinSide::Left,
+                col_index,
+                return_type: schema.get_column(col_index).clone(),
+            }))
+        }
+        Term::Function(Function::Lower(term)) => {
+            bind_string_function(term, StringFunction::Lower, schema)
+        }
+        Term::Function(Function::Upper(term)) => {
+            bind_string_function(term, StringFunction::Upper, schema)
+        }
+        Term::Function(Function::Match { term, query }) => bind_match(term, query, schema),
+        Term::Function(function) => Err(format!("Function {:?} is not supported", function)),
+        Term::RowValueConstructor(_) => Err("Row value constructors are not supported".to_string()),
+    }
+}
+
+fn bind_string_function(
+    term: &Term,
+    function: StringFunction,
+    schema: &Schema,
+) -> Result<Expression, String> {
+    let expr = bind_term(term, schema)?;
+    if !matches!(expr.return_type().col_type(), ColumnType::Varchar(_)) {
+        return Err(format!(
+            "{} can only be applied to strings",
+            function.name()
+        ));
+    }
+
+    Ok(Expression::StringFunction(StringFunctionExpression {
+        function,
+        expr: Box::new(expr),
+    }))
+}
+
+/// Binds `MATCH(term, query)`, whose query has to be a string literal.
+fn bind_match(term: &Term, query: &Term, schema: &Schema) -> Result<Expression, String> {
+    let expr = bind_term(term, schema)?;
+    if !matches!(expr.return_type().col_type(), ColumnType::Varchar(_)) {
+        return Err("MATCH can only be applied to strings".to_string());
+    }
+    let Term::Value(Value::String(query)) = query else {
+        return Err("The query of MATCH must be a string literal".to_string());
+    };
+
+    Ok(Expression::Match(MatchExpression {
+        expr: Box::new(expr),
+        query: TextQuery::parse(query)?,
+    }))
+}
+
+/// Binds a literal that isn't compared with a column: integers are `INTEGER` (or `BIGINT` if they don't fit), decimals
+/// are exact `NUMERIC`s with their own precision and scale (or `DECIMAL` if they have too many digits), strings are
+/// `VARCHAR(255)` and untyped nulls are boolean.
+fn bind_literal(value: &Value) -> Result<Expression, String> {
+    let value = match value {
+        Value::Integer(value) => match i32::try_from(*value) {
+            Ok(value) => ColumnValue::Integer(IntegerValue { value }),
+            Err(_) => ColumnValue::BigInt(BigIntValue { value: *value }),
+        },
+        Value::Float(value) => match Numeric::parse(value) {
+            Ok(numeric) => {
+                let digits = numeric.value.unsigned_abs().to_string().len() as u8;
+                ColumnValue::Numeric(NumericValue {
+                    value: numeric,
+                    precision: digits.max(numeric.scale),
+                })
+            }
+            Err(_) => ColumnValue::Decimal(DecimalValue {
+                value: value
+                    .parse()
+                    .map_err(|_| format!("Invalid number {}", value))?,
+            }),
+        },
+        Value::String(value) if value.len() <= 255 => ColumnValue::Varchar(VarcharValue {
+            value: value.clone(),
+            length: 255,
+        }),
+        Value::String(value) => return Err(format!("String literal {} is too long", value)),
+        Value::Boolean(value) => ColumnValue::Boolean(BooleanValue { value: *value }),
+        Value::Null => ColumnValue::Null(ColumnType::Boolean),
+    };
+
+    Ok(Expression::Constant(ConstantExpression { value }))
+}
+
+/// Binds a literal as a value of type `typ`, falling back to its default type if it can't be converted. Decimal
+/// literals compared with numerics keep their own scale, since rounding them to the scale of `typ` would change the
+/// result of the comparison (numerics of different scales are compared exactly).
+fn bind_literal_as(value: &Value, typ: &ColumnType) -> Result<Expression, String> {
+    if let (Value::Float(_), ColumnType::Numeric(..)) = (value, typ) {
+        return bind_literal(value);
+    }
+
+    match ColumnValue::from_literal(value, typ) {
+        Ok(value) => Ok(Expression::Constant(ConstantExpression { value })),
+        Err(_) => bind_literal(value),
+    }
+}
+
+/// Returns the literal if `operand` is just a literal.
+fn literal(operand: &Operand) -> Option<&Value> {
+    match (
+        operand.right.is_empty(),
+        operand.left.right.is_empty(),
+        operand.left.left.as_ref(),
+    ) {
+        (true, true, Term::Value(value)) => Some(value),
+        _ => None,
+    }
+}
+
+fn arithmetic(left: Expression, right: Expression, typ: ArithmeticType) -> Expression {
+    Expression::Arithmetic(ArithmeticExpression {
+        left: Box::new(left),
+        right: Box::new(right),
+        typ,
+        rounding: RoundingMode::HalfUp,
+    })
+}
+
+fn boolean(left: Expression, right: Expression, typ: BooleanType) -> Expression {
+    Expression::Boolean(BooleanExpression {
+        left: Box::new(left),
+        right: Box::new(right),
+        typ,
+    })
+}
+
+/// Combines the expressions (at least one) with `typ` (`AND` or `OR`).
+fn fold_boolean(expressions: Vec<Expression>, typ: BooleanType) -> Expression {
+    let mut expressions = expressions.into_iter();
+    let first = expressions
+        .next()
+        .expect("Expected at least one expression");
+
+    expressions.fold(first, |acc, e| boolean(acc, e, typ.clone()))
+}
@@ -30,21 +30,43 @@ pub struct BooleanExpression {
 }
 
 impl BooleanExpression {
+    /// Evaluates the expression like `evaluate`, but returns the errors of the operands (e.g. a numeric overflow)
+    /// instead of panicking.
+    pub fn try_evaluate(&self, tuple: &Tuple, schema: &Schema) -> Result<ColumnValue, String> {
+        let l_val = self.left.try_evaluate(tuple, schema)?;
+        let r_val = self.right.try_evaluate(tuple, schema)?;
+        Ok(self.compute(l_val, r_val))
+    }
+
     fn compute(&self, l: ColumnValue, r: ColumnValue) -> ColumnValue {
-        // boolean composite
+        if l.is_null() || r.is_null() {
+            return self.compute_null(l, r);
+        }
+
+        // boolean composite, booleans are otherwise compared like other values (`false < true`)
         if let (ColumnValue::Boolean(ref l), ColumnValue::Boolean(ref r)) = (&l, &r) {
-            return match self.typ {
-                BooleanType::And => ColumnValue::Boolean(BooleanValue {
-                    value: l.value && r.value,
-                }),
-                BooleanType::Or => ColumnValue::Boolean(BooleanValue {
-                    value: l.value || r.value,
-                }),
-                _ => panic!("Only supported boolean operations are (And, Or)"),
-            };
+            match self.typ {
+                BooleanType::And => {
+                    return ColumnValue::Boolean(BooleanValue {
+                        value: l.value && r.value,
+                    })
+                }
+                BooleanType::Or => {
+                    return ColumnValue::Boolean(BooleanValue {
+                        value: l.value || r.value,
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        // comparisons, numerics are compared exactly with each other and with integers
+        if matches!(l, ColumnValue::Numeric(_)) || matches!(r, ColumnValue::Numeric(_)) {
+            if let (Ok(num_l), Ok(num_r)) = (l.to_numeric(), r.to_numeric()) {
+                return self.compare_ordering(num_l.compare(&num_r));
+            }
         }
 
-        // comparisons
         if let (Ok(dec_l), Ok(dec_r)) = (l.to_decimal(), r.to_decimal()) {
             return self.compute_comparison(dec_l, dec_r).unwrap(); // unwrap is fine because we cast both to decimal
         }
@@ -54,6 +76,21 @@ impl BooleanExpression {
             .expect("Failed to compute comparison. Types do not match.");
     }
 
+    /// Computes the operation when at least one of the operands is null, using three-valued logic: comparisons with null
+    /// are unknown (null), `false AND null` is `false`, `true OR null` is `true` and everything else is null.
+    fn compute_null(&self, l: ColumnValue, r: ColumnValue) -> ColumnValue {
+        let known = |value: &ColumnValue| match value {
+            ColumnValue::Boolean(boolean_value) => Some(boolean_value.value),
+            _ => None,
+        };
+
+        match (&self.typ, known(&l).or(known(&r))) {
+            (BooleanType::And, Some(false)) => ColumnValue::Boolean(BooleanValue { value: false }),
+            (BooleanType::Or, Some(true)) => ColumnValue::Boolean(BooleanValue { value: true }),
+            _ => ColumnValue::Null(ColumnType::Boolean),
+        }
+    }
+
     /// Compute the comparison between two `ColumnValue`s.
     ///
     /// # Errors
@@ -62,27 +99,25 @@ impl BooleanExpression {
     /// # Panics
     /// Will panic if the comparison operation is not supported.
     fn compute_comparison(&self, l: ColumnValue, r: ColumnValue) -> Result<ColumnValue, ()> {
-        return match self.typ {
-            BooleanType::EQ => Ok(ColumnValue::Boolean(BooleanValue {
-                value: l.compare(&r)? == Ordering::Equal,
-            })),
-            BooleanType::NE => Ok(ColumnValue::Boolean(BooleanValue {
-                value: l.compare(&r)? != Ordering::Equal,
-            })),
-            BooleanType::GT => Ok(ColumnValue::Boolean(BooleanValue {
-                value: l.compare(&r)? == Ordering::Greater,
-            })),
-            BooleanType::GE => Ok(ColumnValue::Boolean(BooleanValue {
-                value: l.compare(&r)? != Ordering::Less,
-            })),
-            BooleanType::LT => Ok(ColumnValue::Boolean(BooleanValue {
-                value: l.compare(&r)? == Ordering::Less,
-            })),
-            BooleanType::LE => Ok(ColumnValue::Boolean(BooleanValue {
-                value: l.compare(&r)? != Ordering::Greater,
-            })),
+        Ok(self.compare_ordering(l.compare(&r)?))
+    }
+
+    /// Returns the result of the comparison given the `ordering` of the two operands.
+    ///
+    /// # Panics
+    /// Will panic if the comparison operation is not supported.
+    fn compare_ordering(&self, ordering: Ordering) -> ColumnValue {
+        let value = match self.typ {
+            BooleanType::EQ => ordering == Ordering::Equal,
+            BooleanType::NE => ordering != Ordering::Equal,
+            BooleanType::GT => ordering == Ordering::Greater,
+            BooleanType::GE => ordering != Ordering::Less,
+            BooleanType::LT => ordering == Ordering::Less,
+            BooleanType::LE => ordering != Ordering::Greater,
             _ => panic!("Only supported comparison operations are (EQ, NE, GT, GE, LT, LE)"),
         };
+
+        ColumnValue::Boolean(BooleanValue { value })
     }
 }
 
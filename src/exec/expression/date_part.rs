@@ -0,0 +1,74 @@
+use crate::table::{
+    datetime::{split_timestamp, DatePart},
+    schema::{Column, ColumnType, Schema},
+    tuple::Tuple,
+    value::{BigIntValue, ColumnValue, IntegerValue},
+};
+
+use super::{Evaluate, Expression};
+
+/// Extracts a part (year, hour, etc.) of a date, time or timestamp, i.e. `EXTRACT(part FROM expr)`.
+#[derive(Clone)]
+pub struct DatePartExpression {
+    pub part: DatePart,
+    pub expr: Box<Expression>,
+}
+
+impl DatePartExpression {
+    /// # Panics
+    /// Will panic if `value` is not a date, time or timestamp, or if a date part is extracted from a time.
+    fn compute(&self, value: ColumnValue) -> ColumnValue {
+        let (days, micros) = match value {
+            ColumnValue::Null(_) => return ColumnValue::Null(self.return_type().col_type().clone()),
+            ColumnValue::Date(date) => (date.value, 0),
+            ColumnValue::Timestamp(timestamp) => split_timestamp(timestamp.value),
+            ColumnValue::Time(time) if self.part.is_time_part() => (0, time.value),
+            ColumnValue::Time(_) => panic!("Can't extract {} from a time", self.part.name()),
+            _ => panic!("Date parts can only be extracted from dates, times and timestamps"),
+        };
+
+        let value = self.part.extract(days, micros);
+        match self.part {
+            DatePart::Epoch => ColumnValue::BigInt(BigIntValue { value }),
+            _ => ColumnValue::Integer(IntegerValue {
+                value: value as i32,
+            }),
+        }
+    }
+}
+
+impl Evaluate for DatePartExpression {
+    fn evaluate(&self, tuple: &Tuple, schema: &Schema) -> ColumnValue {
+        self.compute(self.expr.evaluate(tuple, schema))
+    }
+
+    fn evaluate_join(
+        &self,
+        l_tuple: &Tuple,
+        l_schema: &Schema,
+        r_tuple: &Tuple,
+        r_schema: &Schema,
+    ) -> ColumnValue {
+        self.compute(
+            self.expr
+                .evaluate_join(l_tuple, l_schema, r_tuple, r_schema),
+        )
+    }
+
+    fn return_type(&self) -> Column {
+        let typ = match self.part {
+            DatePart::Epoch => ColumnType::BigInt,
+            _ => ColumnType::Integer,
+        };
+
+        Column::new_named("_result_".to_string(), typ)
+    }
+
+    fn to_string(&self) -> String {
+        format!(
+            "EXTRACT({} FROM {})",
+            self.part.name(),
+            self.expr.to_string()
+        )
+    }
+}
@@ -1,6 +1,10 @@
 use arithmetic::ArithmeticExpression;
-use boolean::BooleanExpression;
+use boolean::{BooleanExpression, BooleanType};
 use constant::ConstantExpression;
+use date_part::DatePartExpression;
+use now::NowExpression;
+use string_function::StringFunctionExpression;
+use text_match::MatchExpression;
 use value::ColumnValueExpression;
 
 use crate::table::{
@@ -10,8 +14,13 @@ use crate::table::{
 };
 
 pub mod arithmetic;
+pub mod bind;
 pub mod boolean;
 pub mod constant;
+pub mod date_part;
+pub mod now;
+pub mod string_function;
+pub mod text_match;
 pub mod value;
 
 #[cfg(test)]
@@ -52,6 +61,54 @@ pub enum Expression {
     Arithmetic(ArithmeticExpression),
     Boolean(BooleanExpression),
     ColumnValue(ColumnValueExpression),
+    DatePart(DatePartExpression),
+    Now(NowExpression),
+    StringFunction(StringFunctionExpression),
+    Match(MatchExpression),
+}
+
+impl Expression {
+    /// Returns the indexes of the columns read by the expression.
+    pub fn columns(&self) -> Vec<usize> {
+        match self {
+            Expression::Constant(_) | Expression::Now(_) => vec![],
+            Expression::Arithmetic(expr) => [expr.left.columns(), expr.right.columns()].concat(),
+            Expression::Boolean(expr) => [expr.left.columns(), expr.right.columns()].concat(),
+            Expression::ColumnValue(expr) => vec![expr.col_index],
+            Expression::DatePart(expr) => expr.expr.columns(),
+            Expression::StringFunction(expr) => expr.expr.columns(),
+            Expression::Match(expr) => expr.expr.columns(),
+        }
+    }
+
+    /// Returns `true` if both expressions compute the same value (same operations on the same columns and constants).
+    /// The expressions are compared by their textual form.
+    pub fn is_equivalent(&self, other: &Expression) -> bool {
+        self.to_string() == other.to_string()
+    }
+
+    /// Evaluates the expression like `evaluate`, but returns the errors of the operations (e.g. a numeric overflow)
+    /// instead of panicking. Used for the expressions that are evaluated while writing to tables.
+    ///
+    /// # Errors
+    /// Will return `Err` with a description of the operation that failed.
+    pub fn try_evaluate(&self, tuple: &Tuple, schema: &Schema) -> Result<ColumnValue, String> {
+        match self {
+            Expression::Arithmetic(expr) => expr.try_evaluate(tuple, schema),
+            Expression::Boolean(expr) => expr.try_evaluate(tuple, schema),
+            _ => Ok(self.evaluate(tuple, schema)),
+        }
+    }
+
+    /// Returns the conditions combined with `AND` by the expression, or the expression itself if it isn't an `AND`.
+    pub fn conjuncts(&self) -> Vec<&Expression> {
+        match self {
+            Expression::Boolean(expr) if expr.typ == BooleanType::And => {
+                [expr.left.conjuncts(), expr.right.conjuncts()].concat()
+            }
+            _ => vec![self],
+        }
+    }
 }
 
 impl Evaluate for Expression {
@@ -61,6 +118,10 @@ impl Evaluate for Expression {
             Expression::Arithmetic(expr) => expr.evaluate(tuple, schema),
             Expression::Boolean(expr) => expr.evaluate(tuple, schema),
             Expression::ColumnValue(expr) => expr.evaluate(tuple, schema),
+            Expression::DatePart(expr) => expr.evaluate(tuple, schema),
+            Expression::Now(expr) => expr.evaluate(tuple, schema),
+            Expression::StringFunction(expr) => expr.evaluate(tuple, schema),
+            Expression::Match(expr) => expr.evaluate(tuple, schema),
         }
     }
 
@@ -80,6 +141,12 @@ impl Evaluate for Expression {
             Expression::ColumnValue(expr) => {
                 expr.evaluate_join(l_tuple, l_schema, r_tuple, r_schema)
             }
+            Expression::DatePart(expr) => expr.evaluate_join(l_tuple, l_schema, r_tuple, r_schema),
+            Expression::Now(expr) => expr.evaluate_join(l_tuple, l_schema, r_tuple, r_schema),
+            Expression::StringFunction(expr) => {
+                expr.evaluate_join(l_tuple, l_schema, r_tuple, r_schema)
+            }
+            Expression::Match(expr) => expr.evaluate_join(l_tuple, l_schema, r_tuple, r_schema),
         }
     }
 
@@ -89,6 +156,10 @@ impl Evaluate for Expression {
             Expression::Arithmetic(expr) => expr.return_type(),
             Expression::Boolean(expr) => expr.return_type(),
             Expression::ColumnValue(expr) => expr.return_type(),
+            Expression::DatePart(expr) => expr.return_type(),
+            Expression::Now(expr) => expr.return_type(),
+            Expression::StringFunction(expr) => expr.return_type(),
+            Expression::Match(expr) => expr.return_type(),
         }
     }
 
@@ -98,6 +169,10 @@ impl Evaluate for Expression {
             Expression::Arithmetic(expr) => expr.to_string(),
             Expression::Boolean(expr) => expr.to_string(),
             Expression::ColumnValue(expr) => expr.to_string(),
+            Expression::DatePart(expr) => expr.to_string(),
+            Expression::Now(expr) => expr.to_string(),
+            Expression::StringFunction(expr) => expr.to_string(),
+            Expression::Match(expr) => expr.to_string(),
         }
     }
 }
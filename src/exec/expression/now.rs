@@ -0,0 +1,53 @@
+use crate::{
+    exec::executor::ExecutorContext,
+    table::{
+        schema::{Column, ColumnType, Schema},
+        tuple::Tuple,
+        value::{ColumnValue, TimestampValue},
+    },
+};
+
+use super::Evaluate;
+
+/// `NOW()` / `CURRENT_TIMESTAMP`. Evaluates to the time at which the statement started, so that all the rows of a
+/// statement see the same value.
+#[derive(Clone)]
+pub struct NowExpression {
+    pub timestamp: i64,
+}
+
+impl NowExpression {
+    pub fn new(context: &ExecutorContext) -> Self {
+        Self {
+            timestamp: context.statement_timestamp,
+        }
+    }
+}
+
+impl Evaluate for NowExpression {
+    fn evaluate(&self, _tuple: &Tuple, _schema: &Schema) -> ColumnValue {
+        ColumnValue::Timestamp(TimestampValue {
+            value: self.timestamp,
+        })
+    }
+
+    fn evaluate_join(
+        &self,
+        _l_tuple: &Tuple,
+        _l_schema: &Schema,
+        _r_tuple: &Tuple,
+        _r_schema: &Schema,
+    ) -> ColumnValue {
+        ColumnValue::Timestamp(TimestampValue {
+            value: self.timestamp,
+        })
+    }
+
+    fn return_type(&self) -> Column {
+        Column::new_named("_now_".to_string(), ColumnType::Timestamp)
+    }
+
+    fn to_string(&self) -> String {
+        "NOW()".to_string()
+    }
+}
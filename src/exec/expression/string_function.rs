@@ -0,0 +1,78 @@
+use crate::table::{
+    schema::{Column, Schema},
+    tuple::Tuple,
+    value::{ColumnValue, VarcharValue},
+};
+
+use super::{Evaluate, Expression};
+
+/// Function transforming a string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StringFunction {
+    Lower,
+    Upper,
+}
+
+impl StringFunction {
+    pub fn name(&self) -> &str {
+        match self {
+            StringFunction::Lower => "LOWER",
+            StringFunction::Upper => "UPPER",
+        }
+    }
+}
+
+/// Applies a function to a string, e.g. `LOWER(expr)`. The result has the type of the string.
+#[derive(Clone)]
+pub struct StringFunctionExpression {
+    pub function: StringFunction,
+    pub expr: Box<Expression>,
+}
+
+impl StringFunctionExpression {
+    /// # Panics
+    /// Will panic if `value` is not a string.
+    fn compute(&self, value: ColumnValue) -> ColumnValue {
+        match value {
+            ColumnValue::Null(typ) => ColumnValue::Null(typ),
+            ColumnValue::Varchar(varchar) => ColumnValue::Varchar(VarcharValue {
+                value: match self.function {
+                    StringFunction::Lower => varchar.value.to_lowercase(),
+                    StringFunction::Upper => varchar.value.to_uppercase(),
+                },
+                length: varchar.length,
+            }),
+            _ => panic!("{} can only be applied to strings", self.function.name()),
+        }
+    }
+}
+
+impl Evaluate for StringFunctionExpression {
+    fn evaluate(&self, tuple: &Tuple, schema: &Schema) -> ColumnValue {
+        self.compute(self.expr.evaluate(tuple, schema))
+    }
+
+    fn evaluate_join(
+        &self,
+        l_tuple: &Tuple,
+        l_schema: &Schema,
+        r_tuple: &Tuple,
+        r_schema: &Schema,
+    ) -> ColumnValue {
+        self.compute(
+            self.expr
+                .evaluate_join(l_tuple, l_schema, r_tuple, r_schema),
+        )
+    }
+
+    fn return_type(&self) -> Column {
+        Column::new_named(
+            "_result_".to_string(),
+            self.expr.return_type().col_type().clone(),
+        )
+    }
+
+    fn to_string(&self) -> String {
+        format!("{}({})", self.function.name(), self.expr.to_string())
+    }
+}
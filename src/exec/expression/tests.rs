@@ -1,13 +1,31 @@
+use std::{env::temp_dir, fs::remove_file, sync::Arc};
+
 use crate::{
-    exec::expression::{
-        arithmetic::{ArithmeticExpression, ArithmeticType},
-        boolean::{BooleanExpression, BooleanType},
-        constant::ConstantExpression,
-        value::{ColumnValueExpression, JoinSide}, Evaluate, Expression,
+    catalog::Catalog,
+    disk::buffer_pool_manager::BufferPoolManager,
+    exec::{
+        executor::ExecutorContext,
+        expression::{
+            arithmetic::{ArithmeticExpression, ArithmeticType},
+            bind::{bind_expression, bind_term},
+            boolean::{BooleanExpression, BooleanType},
+            constant::ConstantExpression,
+            date_part::DatePartExpression,
+            now::NowExpression,
+            value::{ColumnValueExpression, JoinSide}, Evaluate, Expression,
+        },
     },
     table::{
-        schema::{Column, ColumnType, Schema}, tuple::Tuple, value::{BooleanValue, ColumnValue, DecimalValue, IntegerValue}
-    }, test_utils::{const_decimal, const_int, const_timestamp, const_varchar},
+        datetime::{parse_timestamp, DatePart, Interval},
+        numeric::{Numeric, RoundingMode}, schema::{Column, ColumnType, Schema}, tuple::Tuple, value::{BigIntValue, BooleanValue, ColumnValue, DateValue, DecimalValue, DoubleValue, FloatValue, IntegerValue, IntervalValue, NumericValue, TimestampValue, VarcharValue}
+    }, test_utils::{column_with, const_bool, const_decimal, const_int, const_timestamp, const_varchar},
+    parser::{
+        ast::general::{
+            AndCondition, CompareType, Condition, Expression as AstExpression, Factor,
+            FactorRight, Function, Operand, Operation, Term,
+        },
+        token::value::Value,
+    },
 };
 
 #[test]
@@ -99,6 +117,7 @@ fn arithmetic_expression() {
             return_type: Column::new_named("col2".to_string(), ColumnType::Integer),
         })),
         typ: ArithmeticType::Plus,
+        rounding: RoundingMode::HalfUp,
     };
     assert_eq!(
         expr.evaluate(&tuple, &schema),
@@ -117,6 +136,7 @@ fn arithmetic_expression() {
             return_type: Column::new_named("col2".to_string(), ColumnType::Integer),
         })),
         typ: ArithmeticType::Minus,
+        rounding: RoundingMode::HalfUp,
     };
     assert_eq!(
         expr.evaluate(&tuple, &schema),
@@ -124,6 +144,444 @@ fn arithmetic_expression() {
     );
 }
 
+#[test]
+fn arithmetic_expression_other_types() {
+    let schema = Schema::new(vec![
+        Column::new_named("date".to_string(), ColumnType::Date),
+        Column::new_named("double".to_string(), ColumnType::Double),
+    ]);
+    let tuple = Tuple::new(
+        vec![
+            ColumnValue::Date(DateValue { value: 100 }),
+            ColumnValue::Double(DoubleValue { value: 2.5 }),
+        ],
+        &schema,
+    );
+    let date_col = || {
+        Box::new(Expression::ColumnValue(ColumnValueExpression {
+            join_side: JoinSide::Left,
+            col_index: 0,
+            return_type: Column::new_named("date".to_string(), ColumnType::Date),
+        }))
+    };
+    let constant = |value: ColumnValue| Box::new(Expression::Constant(ConstantExpression { value }));
+
+    // date + days
+    let expr = ArithmeticExpression {
+        left: date_col(),
+        right: constant(ColumnValue::Integer(IntegerValue { value: 7 })),
+        typ: ArithmeticType::Plus,
+        rounding: RoundingMode::HalfUp,
+    };
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Date);
+    assert_eq!(
+        expr.evaluate(&tuple, &schema),
+        ColumnValue::Date(DateValue { value: 107 })
+    );
+
+    // date - date
+    let expr = ArithmeticExpression {
+        left: date_col(),
+        right: constant(ColumnValue::Date(DateValue { value: 40 })),
+        typ: ArithmeticType::Minus,
+        rounding: RoundingMode::HalfUp,
+    };
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Integer);
+    assert_eq!(
+        expr.evaluate(&tuple, &schema),
+        ColumnValue::Integer(IntegerValue { value: 60 })
+    );
+
+    // double * double
+    let expr = ArithmeticExpression {
+        left: Box::new(Expression::ColumnValue(ColumnValueExpression {
+            join_side: JoinSide::Left,
+            col_index: 1,
+            return_type: Column::new_named("double".to_string(), ColumnType::Double),
+        })),
+        right: constant(ColumnValue::Double(DoubleValue { value: 4.0 })),
+        typ: ArithmeticType::Multiply,
+        rounding: RoundingMode::HalfUp,
+    };
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Double);
+    assert_eq!(
+        expr.evaluate(&tuple, &schema),
+        ColumnValue::Double(DoubleValue { value: 10.0 })
+    );
+
+    // float / float
+    let expr = ArithmeticExpression {
+        left: constant(ColumnValue::Float(FloatValue { value: 1.0 })),
+        right: constant(ColumnValue::Float(FloatValue { value: 4.0 })),
+        typ: ArithmeticType::Divide,
+        rounding: RoundingMode::HalfUp,
+    };
+    assert_eq!(
+        expr.evaluate(&tuple, &schema),
+        ColumnValue::Float(FloatValue { value: 0.25 })
+    );
+}
+
+#[test]
+fn arithmetic_expression_numeric() {
+    let schema = Schema::new(vec![Column::new_named(
+        "price".to_string(),
+        ColumnType::Numeric(5, 2),
+    )]);
+    let tuple = Tuple::new(
+        vec![ColumnValue::Numeric(NumericValue {
+            value: Numeric::parse("10.25").unwrap(),
+            precision: 5,
+        })],
+        &schema,
+    );
+    let price_col = || {
+        Box::new(Expression::ColumnValue(ColumnValueExpression {
+            join_side: JoinSide::Left,
+            col_index: 0,
+            return_type: Column::new_named("price".to_string(), ColumnType::Numeric(5, 2)),
+        }))
+    };
+    let numeric = |raw: &str, precision: u8| {
+        Box::new(Expression::Constant(ConstantExpression {
+            value: ColumnValue::Numeric(NumericValue {
+                value: Numeric::parse(raw).unwrap(),
+                precision,
+            }),
+        }))
+    };
+    let arithmetic = |left, right, typ, rounding| ArithmeticExpression {
+        left,
+        right,
+        typ,
+        rounding,
+    };
+
+    // exact addition, with the scale of the most precise operand
+    let expr = arithmetic(price_col(), numeric("0.105", 4), ArithmeticType::Plus, RoundingMode::HalfUp);
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Numeric(7, 3));
+    assert_eq!(expr.evaluate(&tuple, &schema).to_string(), "10.355");
+    // operands can have a larger scale than precision
+    let expr = arithmetic(numeric("0.005", 2), numeric("0.005", 2), ArithmeticType::Plus, RoundingMode::HalfUp);
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Numeric(4, 3));
+    assert_eq!(expr.evaluate(&tuple, &schema).to_string(), "0.010");
+
+    // integers are promoted to numerics
+    let expr = arithmetic(
+        price_col(),
+        Box::new(Expression::Constant(ConstantExpression {
+            value: ColumnValue::Integer(IntegerValue { value: 3 }),
+        })),
+        ArithmeticType::Multiply,
+        RoundingMode::HalfUp,
+    );
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Numeric(16, 2));
+    assert_eq!(expr.evaluate(&tuple, &schema).to_string(), "30.75");
+
+    // division is rounded according to the rounding mode
+    let expr = arithmetic(price_col(), numeric("3", 1), ArithmeticType::Divide, RoundingMode::HalfUp);
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Numeric(38, 6));
+    assert_eq!(expr.evaluate(&tuple, &schema).to_string(), "3.416667");
+    let expr = arithmetic(price_col(), numeric("3", 1), ArithmeticType::Divide, RoundingMode::Down);
+    assert_eq!(expr.evaluate(&tuple, &schema).to_string(), "3.416666");
+
+    // numerics are compared exactly with each other and with integers
+    let expr = BooleanExpression {
+        left: price_col(),
+        right: numeric("10.250", 5),
+        typ: BooleanType::EQ,
+    };
+    assert_eq!(
+        expr.evaluate(&tuple, &schema),
+        ColumnValue::Boolean(BooleanValue { value: true })
+    );
+    let expr = BooleanExpression {
+        left: price_col(),
+        right: Box::new(Expression::Constant(ConstantExpression {
+            value: ColumnValue::Integer(IntegerValue { value: 10 }),
+        })),
+        typ: BooleanType::GT,
+    };
+    assert_eq!(
+        expr.evaluate(&tuple, &schema),
+        ColumnValue::Boolean(BooleanValue { value: true })
+    );
+
+    // numerics with approximate numbers are approximate
+    let expr = arithmetic(
+        price_col(),
+        Box::new(Expression::Constant(ConstantExpression {
+            value: ColumnValue::Decimal(DecimalValue { value: 2.0 }),
+        })),
+        ArithmeticType::Multiply,
+        RoundingMode::HalfUp,
+    );
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Decimal);
+    assert_eq!(
+        expr.evaluate(&tuple, &schema),
+        ColumnValue::Decimal(DecimalValue { value: 20.5 })
+    );
+    let expr = arithmetic(
+        Box::new(Expression::Constant(ConstantExpression {
+            value: ColumnValue::Double(DoubleValue { value: 0.5 }),
+        })),
+        price_col(),
+        ArithmeticType::Plus,
+        RoundingMode::HalfUp,
+    );
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Double);
+    assert_eq!(
+        expr.evaluate(&tuple, &schema),
+        ColumnValue::Double(DoubleValue { value: 10.75 })
+    );
+}
+
+#[test]
+fn arithmetic_expression_numeric_overflow() {
+    let schema = Schema::new(vec![Column::new_named(
+        "col1".to_string(),
+        ColumnType::Integer,
+    )]);
+    let tuple = Tuple::new(
+        vec![ColumnValue::Integer(IntegerValue { value: 10 })],
+        &schema,
+    );
+    let big = || {
+        Box::new(Expression::Constant(ConstantExpression {
+            value: ColumnValue::Numeric(NumericValue {
+                value: Numeric::new(10i128.pow(37), 0),
+                precision: 38,
+            }),
+        }))
+    };
+
+    let arithmetic = |right, typ| ArithmeticExpression {
+        left: big(),
+        right,
+        typ,
+        rounding: RoundingMode::HalfUp,
+    };
+
+    // results that can't be computed are errors
+    let expr = Expression::Arithmetic(arithmetic(big(), ArithmeticType::Multiply));
+    assert_eq!(
+        expr.try_evaluate(&tuple, &schema),
+        Err(format!(
+            "Failed to compute ({} * {}): result doesn't fit in NUMERIC(38, 0)",
+            10i128.pow(37),
+            10i128.pow(37)
+        ))
+    );
+    let zero = Box::new(Expression::Constant(ConstantExpression {
+        value: ColumnValue::Integer(IntegerValue { value: 0 }),
+    }));
+    let expr = Expression::Arithmetic(arithmetic(zero, ArithmeticType::Divide));
+    assert_eq!(
+        expr.try_evaluate(&tuple, &schema),
+        Err(format!(
+            "Failed to compute ({} / 0): division by zero",
+            10i128.pow(37)
+        ))
+    );
+
+    // errors are propagated through comparisons
+    let expr = Expression::Boolean(BooleanExpression {
+        left: Box::new(expr),
+        right: big(),
+        typ: BooleanType::LT,
+    });
+    assert!(expr.try_evaluate(&tuple, &schema).is_err());
+}
+
+#[test]
+#[should_panic]
+fn arithmetic_expression_numeric_overflow_panics() {
+    let schema = Schema::new(vec![Column::new_named(
+        "col1".to_string(),
+        ColumnType::Integer,
+    )]);
+    let tuple = Tuple::new(
+        vec![ColumnValue::Integer(IntegerValue { value: 10 })],
+        &schema,
+    );
+    let big = || {
+        Box::new(Expression::Constant(ConstantExpression {
+            value: ColumnValue::Numeric(NumericValue {
+                value: Numeric::new(10i128.pow(37), 0),
+                precision: 38,
+            }),
+        }))
+    };
+
+    let expr = ArithmeticExpression {
+        left: big(),
+        right: big(),
+        typ: ArithmeticType::Multiply,
+        rounding: RoundingMode::HalfUp,
+    };
+    expr.evaluate(&tuple, &schema);
+}
+
+#[test]
+fn arithmetic_expression_timestamps() {
+    let schema = Schema::new(vec![Column::new_named(
+        "created_at".to_string(),
+        ColumnType::Timestamp,
+    )]);
+    let created_at = parse_timestamp("2024-01-31T22:00:00").unwrap();
+    let tuple = Tuple::new(
+        vec![ColumnValue::Timestamp(TimestampValue { value: created_at })],
+        &schema,
+    );
+    let timestamp_col = || {
+        Box::new(Expression::ColumnValue(ColumnValueExpression {
+            join_side: JoinSide::Left,
+            col_index: 0,
+            return_type: Column::new_named("created_at".to_string(), ColumnType::Timestamp),
+        }))
+    };
+    let interval = |value| {
+        Box::new(Expression::Constant(ConstantExpression {
+            value: ColumnValue::Interval(IntervalValue { value }),
+        }))
+    };
+    let arithmetic = |left, right, typ| ArithmeticExpression {
+        left,
+        right,
+        typ,
+        rounding: RoundingMode::HalfUp,
+    };
+
+    // timestamp + interval
+    let expr = arithmetic(
+        timestamp_col(),
+        interval(Interval::new(1, 0, 0).add(Interval::hours(3))),
+        ArithmeticType::Plus,
+    );
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Timestamp);
+    assert_eq!(expr.evaluate(&tuple, &schema).to_string(), "2024-03-01T01:00:00");
+
+    // timestamp - interval
+    let expr = arithmetic(timestamp_col(), interval(Interval::days(31)), ArithmeticType::Minus);
+    assert_eq!(expr.evaluate(&tuple, &schema).to_string(), "2023-12-31T22:00:00");
+
+    // timestamp - timestamp
+    let expr = arithmetic(
+        timestamp_col(),
+        Box::new(const_timestamp(parse_timestamp("2024-01-30").unwrap())),
+        ArithmeticType::Minus,
+    );
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Interval);
+    assert_eq!(expr.evaluate(&tuple, &schema).to_string(), "P1DT22H");
+
+    // before 1970
+    let expr = arithmetic(
+        Box::new(const_timestamp(
+            parse_timestamp("1969-12-31T23:00:00").unwrap(),
+        )),
+        interval(Interval::new(-1, 0, 0)),
+        ArithmeticType::Plus,
+    );
+    assert_eq!(
+        expr.evaluate(&tuple, &schema).to_string(),
+        "1969-11-30T23:00:00"
+    );
+    let expr = arithmetic(
+        Box::new(Expression::Constant(ConstantExpression {
+            value: ColumnValue::Date(DateValue { value: -10 }),
+        })),
+        interval(Interval::hours(6)),
+        ArithmeticType::Minus,
+    );
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Timestamp);
+    assert_eq!(
+        expr.evaluate(&tuple, &schema).to_string(),
+        "1969-12-21T18:00:00"
+    );
+
+    // out of range
+    let expr = arithmetic(
+        timestamp_col(),
+        interval(Interval::new(12 * 400_000, 0, 0)),
+        ArithmeticType::Plus,
+    );
+    assert_eq!(
+        expr.try_evaluate(&tuple, &schema),
+        Err(
+            "Failed to compute (#0 + P400000Y): result is out of the range of timestamps"
+                .to_string()
+        )
+    );
+
+    // interval * integer
+    let expr = arithmetic(
+        interval(Interval::hours(2)),
+        Box::new(const_int(3)),
+        ArithmeticType::Multiply,
+    );
+    assert_eq!(expr.evaluate(&tuple, &schema).to_string(), "PT6H");
+
+    // date parts
+    let expr = DatePartExpression {
+        part: DatePart::Month,
+        expr: timestamp_col(),
+    };
+    assert_eq!(expr.to_string(), "EXTRACT(MONTH FROM #0)");
+    assert_eq!(
+        expr.evaluate(&tuple, &schema),
+        ColumnValue::Integer(IntegerValue { value: 1 })
+    );
+    let expr = DatePartExpression {
+        part: DatePart::Epoch,
+        expr: timestamp_col(),
+    };
+    assert_eq!(*expr.return_type().col_type(), ColumnType::BigInt);
+    assert_eq!(
+        expr.evaluate(&tuple, &schema),
+        ColumnValue::BigInt(BigIntValue {
+            value: created_at / 1_000_000
+        })
+    );
+}
+
+#[test]
+fn now_expression_stable_within_statement() {
+    // init
+    let db_path = temp_dir().join("expression_now_expression_stable_within_statement.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path, 2, 2));
+    let catalog = Arc::new(Catalog::new(bpm.clone()));
+    let context = ExecutorContext::new(catalog, bpm);
+
+    let schema = Schema::new(vec![Column::new_named(
+        "col1".to_string(),
+        ColumnType::Integer,
+    )]);
+    let tuple = Tuple::new(
+        vec![ColumnValue::Integer(IntegerValue { value: 10 })],
+        &schema,
+    );
+
+    // test
+    let expr = NowExpression::new(&context);
+    let first = expr.evaluate(&tuple, &schema);
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    assert_eq!(expr.evaluate(&tuple, &schema), first);
+    assert_eq!(
+        NowExpression::new(&context.clone()).evaluate(&tuple, &schema),
+        first
+    );
+    assert_eq!(
+        first,
+        ColumnValue::Timestamp(TimestampValue {
+            value: context.statement_timestamp
+        })
+    );
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
 #[test]
 fn boolean_expression() {
     let schema = Schema::new(vec![
@@ -294,3 +752,370 @@ fn bool_varchar_comparisson() {
         ColumnValue::Boolean(BooleanValue { value: false })
     );
 }
+
+#[test]
+fn null_three_valued_logic() {
+    let null = || {
+        Box::new(Expression::Constant(ConstantExpression {
+            value: ColumnValue::Null(ColumnType::Boolean),
+        }))
+    };
+    let cases = vec![
+        (BooleanType::And, false, ColumnValue::Boolean(BooleanValue { value: false })),
+        (BooleanType::And, true, ColumnValue::Null(ColumnType::Boolean)),
+        (BooleanType::Or, true, ColumnValue::Boolean(BooleanValue { value: true })),
+        (BooleanType::Or, false, ColumnValue::Null(ColumnType::Boolean)),
+        (BooleanType::EQ, true, ColumnValue::Null(ColumnType::Boolean)),
+    ];
+
+    for (typ, value, expected) in cases {
+        let expr = BooleanExpression {
+            left: Box::new(const_bool(value)),
+            right: null(),
+            typ,
+        };
+        assert_eq!(expr.evaluate(&dummy_tuple(), &dummy_schema()), expected);
+    }
+}
+
+#[test]
+fn null_arithmetic() {
+    let expr = ArithmeticExpression {
+        left: Box::new(const_decimal(1.5)),
+        right: Box::new(Expression::Constant(ConstantExpression {
+            value: ColumnValue::Null(ColumnType::Integer),
+        })),
+        typ: ArithmeticType::Plus,
+        rounding: RoundingMode::HalfUp,
+    };
+
+    assert_eq!(
+        expr.evaluate(&dummy_tuple(), &dummy_schema()),
+        ColumnValue::Null(ColumnType::Decimal)
+    );
+}
+
+fn ast_operand(term: Term) -> Operand {
+    Operand {
+        left: Factor {
+            left: Box::new(term),
+            right: vec![],
+        },
+        right: vec![],
+    }
+}
+
+fn ast_column(name: &str) -> Term {
+    Term::Column {
+        table_alias: None,
+        name: name.to_string(),
+    }
+}
+
+#[test]
+fn bind_check_expression() {
+    let schema = Schema::new(vec![
+        Column::new_named("price".to_string(), ColumnType::Integer),
+        Column::new_named("sold".to_string(), ColumnType::Timestamp),
+    ]);
+    let tuple = Tuple::new(
+        vec![
+            ColumnValue::Integer(IntegerValue { value: 15 }),
+            ColumnValue::Timestamp(TimestampValue {
+                value: parse_timestamp("2024-03-01T10:00:00").unwrap(),
+            }),
+        ],
+        &schema,
+    );
+
+    // price * 2 BETWEEN 10 AND 30 AND sold > '2024-01-01T00:00:00'
+    let expression = AstExpression {
+        and_conditions: vec![AndCondition {
+            conditions: vec![
+                Condition::Operation {
+                    operand: Operand {
+                        left: Factor {
+                            left: Box::new(ast_column("price")),
+                            right: vec![FactorRight::Mult(Term::Value(Value::Integer(2)))],
+                        },
+                        right: vec![],
+                    },
+                    operation: Some(Operation::Between {
+                        not: false,
+                        start: ast_operand(Term::Value(Value::Integer(10))),
+                        end: ast_operand(Term::Value(Value::Integer(30))),
+                    }),
+                },
+                Condition::Operation {
+                    operand: ast_operand(ast_column("sold")),
+                    operation: Some(Operation::Comparison {
+                        cmp_type: CompareType::GT,
+                        operand: ast_operand(Term::Value(Value::String(
+                            "2024-01-01T00:00:00".to_string(),
+                        ))),
+                    }),
+                },
+            ],
+        }],
+    };
+    assert_eq!(
+        bind_expression(&expression, &schema)
+            .unwrap()
+            .evaluate(&tuple, &schema),
+        ColumnValue::Boolean(BooleanValue { value: true })
+    );
+
+    // price NOT BETWEEN 0 AND 20
+    let expression = AstExpression {
+        and_conditions: vec![AndCondition {
+            conditions: vec![Condition::Operation {
+                operand: ast_operand(ast_column("price")),
+                operation: Some(Operation::Between {
+                    not: true,
+                    start: ast_operand(Term::Value(Value::Integer(0))),
+                    end: ast_operand(Term::Value(Value::Integer(20))),
+                }),
+            }],
+        }],
+    };
+    assert_eq!(
+        bind_expression(&expression, &schema)
+            .unwrap()
+            .evaluate(&tuple, &schema),
+        ColumnValue::Boolean(BooleanValue { value: false })
+    );
+
+    // missing > 1
+    let expression = AstExpression {
+        and_conditions: vec![AndCondition {
+            conditions: vec![Condition::Operation {
+                operand: ast_operand(ast_column("missing")),
+                operation: Some(Operation::Comparison {
+                    cmp_type: CompareType::GT,
+                    operand: ast_operand(Term::Value(Value::Integer(1))),
+                }),
+            }],
+        }],
+    };
+    assert!(bind_expression(&expression, &schema).is_err());
+}
+
+#[test]
+fn bind_numeric_comparison() {
+    let schema = Schema::new(vec![Column::new_named(
+        "price".to_string(),
+        ColumnType::Numeric(10, 2),
+    )]);
+    let tuple = Tuple::new(
+        vec![ColumnValue::Numeric(NumericValue {
+            value: Numeric::parse("1.56").unwrap(),
+            precision: 10,
+        })],
+        &schema,
+    );
+    let compare = |cmp_type, raw: &str| {
+        let expression = AstExpression {
+            and_conditions: vec![AndCondition {
+                conditions: vec![Condition::Operation {
+                    operand: ast_operand(ast_column("price")),
+                    operation: Some(Operation::Comparison {
+                        cmp_type,
+                        operand: ast_operand(Term::Value(Value::Float(raw.to_string()))),
+                    }),
+                }],
+            }],
+        };
+
+        bind_expression(&expression, &schema)
+            .unwrap()
+            .evaluate(&tuple, &schema)
+    };
+
+    // literals are not rounded to the scale of the column
+    assert_eq!(
+        compare(CompareType::GT, "1.555"),
+        ColumnValue::Boolean(BooleanValue { value: true })
+    );
+    assert_eq!(
+        compare(CompareType::EQ, "1.555"),
+        ColumnValue::Boolean(BooleanValue { value: false })
+    );
+    assert_eq!(
+        compare(CompareType::EQ, "1.5600"),
+        ColumnValue::Boolean(BooleanValue { value: true })
+    );
+}
+
+#[test]
+fn bind_numeric_arithmetic() {
+    let schema = Schema::new(vec![Column::new_named(
+        "price".to_string(),
+        ColumnType::Numeric(10, 2),
+    )]);
+    let tuple = Tuple::new(
+        vec![ColumnValue::Numeric(NumericValue {
+            value: Numeric::parse("1.56").unwrap(),
+            precision: 10,
+        })],
+        &schema,
+    );
+    let price_times = |raw: &str| Operand {
+        left: Factor {
+            left: Box::new(ast_column("price")),
+            right: vec![FactorRight::Mult(Term::Value(Value::Float(
+                raw.to_string(),
+            )))],
+        },
+        right: vec![],
+    };
+
+    // decimal literals are exact numerics
+    let expr = bind_term(&Term::Operand(price_times("1.5")), &schema).unwrap();
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Numeric(13, 3));
+    assert_eq!(expr.evaluate(&tuple, &schema).to_string(), "2.340");
+
+    // price * 1.1 < 100
+    let expression = AstExpression {
+        and_conditions: vec![AndCondition {
+            conditions: vec![Condition::Operation {
+                operand: price_times("1.1"),
+                operation: Some(Operation::Comparison {
+                    cmp_type: CompareType::LT,
+                    operand: ast_operand(Term::Value(Value::Integer(100))),
+                }),
+            }],
+        }],
+    };
+    assert_eq!(
+        bind_expression(&expression, &schema)
+            .unwrap()
+            .evaluate(&tuple, &schema),
+        ColumnValue::Boolean(BooleanValue { value: true })
+    );
+}
+
+#[test]
+fn expression_columns() {
+    let sum = Expression::Arithmetic(ArithmeticExpression {
+        left: Box::new(column_with(2, ColumnType::Integer)),
+        right: Box::new(const_int(1)),
+        typ: ArithmeticType::Plus,
+        rounding: RoundingMode::HalfUp,
+    });
+    let comparison = Expression::Boolean(BooleanExpression {
+        left: Box::new(sum),
+        right: Box::new(column_with(0, ColumnType::Integer)),
+        typ: BooleanType::LT,
+    });
+
+    assert_eq!(comparison.columns(), vec![2, 0]);
+    assert_eq!(const_int(1).columns(), vec![]);
+}
+
+#[test]
+fn bind_string_function_term() {
+    let schema = Schema::new(vec![
+        Column::new_named("id".to_string(), ColumnType::Integer),
+        Column::new_named("name".to_string(), ColumnType::Varchar(10)),
+    ]);
+    let tuple = Tuple::new(
+        vec![
+            ColumnValue::Integer(IntegerValue { value: 1 }),
+            ColumnValue::Varchar(VarcharValue {
+                value: "Alice".to_string(),
+                length: 10,
+            }),
+        ],
+        &schema,
+    );
+
+    // LOWER(name), UPPER(name)
+    let lower = bind_term(
+        &Term::Function(Function::Lower(Box::new(ast_column("name")))),
+        &schema,
+    )
+    .unwrap();
+    let upper = bind_term(
+        &Term::Function(Function::Upper(Box::new(ast_column("name")))),
+        &schema,
+    )
+    .unwrap();
+
+    assert_eq!(lower.to_string(), "LOWER(#1)");
+    assert_eq!(lower.columns(), vec![1]);
+    assert_eq!(*lower.return_type().col_type(), ColumnType::Varchar(10));
+    assert_eq!(
+        lower.evaluate(&tuple, &schema),
+        ColumnValue::Varchar(VarcharValue {
+            value: "alice".to_string(),
+            length: 10,
+        })
+    );
+    assert_eq!(
+        upper.evaluate(&tuple, &schema),
+        ColumnValue::Varchar(VarcharValue {
+            value: "ALICE".to_string(),
+            length: 10,
+        })
+    );
+    assert!(lower.is_equivalent(&lower.clone()));
+    assert!(!lower.is_equivalent(&upper));
+
+    // LOWER(id)
+    assert!(bind_term(
+        &Term::Function(Function::Lower(Box::new(ast_column("id")))),
+        &schema,
+    )
+    .is_err());
+}
+
+#[test]
+fn bind_match_term() {
+    let schema = Schema::new(vec![
+        Column::new_named("id".to_string(), ColumnType::Integer),
+        Column::new_named("body".to_string(), ColumnType::Varchar(64)),
+    ]);
+    let tuple = |body: &str| {
+        Tuple::new(
+            vec![
+                ColumnValue::Integer(IntegerValue { value: 1 }),
+                ColumnValue::Varchar(VarcharValue {
+                    value: body.to_string(),
+                    length: 64,
+                }),
+            ],
+            &schema,
+        )
+    };
+    let bind_match = |column: &str, query: &str| {
+        bind_term(
+            &Term::Function(Function::Match {
+                term: Box::new(ast_column(column)),
+                query: Box::new(Term::Value(Value::String(query.to_string()))),
+            }),
+            &schema,
+        )
+    };
+
+    // MATCH(body, 'Rust database OR sql')
+    let expr = bind_match("body", "Rust database OR sql").unwrap();
+    assert_eq!(expr.to_string(), "MATCH(#1, 'rust AND database OR sql')");
+    assert_eq!(expr.columns(), vec![1]);
+    assert_eq!(*expr.return_type().col_type(), ColumnType::Boolean);
+    assert_eq!(
+        expr.evaluate(&tuple("A database, in Rust!"), &schema),
+        ColumnValue::Boolean(BooleanValue { value: true })
+    );
+    assert_eq!(
+        expr.evaluate(&tuple("Rusty databases"), &schema),
+        ColumnValue::Boolean(BooleanValue { value: false })
+    );
+    assert_eq!(
+        expr.evaluate(&tuple("SQL"), &schema),
+        ColumnValue::Boolean(BooleanValue { value: true })
+    );
+
+    // MATCH(id, 'rust'), MATCH(body, 'OR rust')
+    assert!(bind_match("id", "rust").is_err());
+    assert!(bind_match("body", "OR rust").is_err());
+}
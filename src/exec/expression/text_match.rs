@@ -0,0 +1,59 @@
+use crate::{
+    index::full_text::TextQuery,
+    table::{
+        schema::{Column, ColumnType, Schema},
+        tuple::Tuple,
+        value::{BooleanValue, ColumnValue},
+    },
+};
+
+use super::{Evaluate, Expression};
+
+/// Full-text search `MATCH(expr, 'query')`: true if the string has the terms of the query (see `TextQuery`). Can be
+/// answered by a full-text index on the column.
+#[derive(Clone)]
+pub struct MatchExpression {
+    pub expr: Box<Expression>,
+    pub query: TextQuery,
+}
+
+impl MatchExpression {
+    /// # Panics
+    /// Will panic if `value` is not a string.
+    fn compute(&self, value: ColumnValue) -> ColumnValue {
+        match value {
+            ColumnValue::Null(_) => ColumnValue::Null(ColumnType::Boolean),
+            ColumnValue::Varchar(varchar) => ColumnValue::Boolean(BooleanValue {
+                value: self.query.matches(&varchar.value),
+            }),
+            _ => panic!("MATCH can only be applied to strings"),
+        }
+    }
+}
+
+impl Evaluate for MatchExpression {
+    fn evaluate(&self, tuple: &Tuple, schema: &Schema) -> ColumnValue {
+        self.compute(self.expr.evaluate(tuple, schema))
+    }
+
+    fn evaluate_join(
+        &self,
+        l_tuple: &Tuple,
+        l_schema: &Schema,
+        r_tuple: &Tuple,
+        r_schema: &Schema,
+    ) -> ColumnValue {
+        self.compute(
+            self.expr
+                .evaluate_join(l_tuple, l_schema, r_tuple, r_schema),
+        )
+    }
+
+    fn return_type(&self) -> Column {
+        Column::new_named("_result_".to_string(), ColumnType::Boolean)
+    }
+
+    fn to_string(&self) -> String {
+        format!("MATCH({}, '{}')", self.expr.to_string(), self.query)
+    }
+}
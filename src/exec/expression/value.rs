@@ -6,7 +6,7 @@ use crate::table::{
 
 use super::Evaluate;
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum JoinSide {
     Left,
     Right,
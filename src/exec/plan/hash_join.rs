@@ -0,0 +1,98 @@
+use crate::{
+    config::DB_HASH_JOIN_MEMORY_BUDGET,
+    exec::{
+        expression::{boolean::BooleanExpression, value::JoinSide, Evaluate, Expression},
+        plan::{
+            nested_loop_join::{join_schema, JoinType},
+            AbstractPlanNode, PlanNode,
+        },
+    },
+    table::schema::Schema,
+};
+
+/// Equi-join of two children: the rows of one of them (the build side) are put in a hash table keyed by their join key,
+/// which is then looked up with the key of every row of the other child (the probe side). When the build side doesn't
+/// fit in the memory budget, both children are partitioned on disk by their key (Grace hash join) and the partitions are
+/// joined one at a time.
+///
+/// The plan node doesn't know the sizes of its children, so the build side isn't chosen automatically: it is the right
+/// child unless set with `with_build_side`, and whoever creates the plan should pick the smaller child.
+#[derive(Clone)]
+pub struct HashJoinPlanNode {
+    pub output_schema: Schema,
+    pub join_type: JoinType,
+    /// Condition of the join (`ON`), checked for every pair of rows with equal keys
+    pub predicate: BooleanExpression,
+    pub left: Box<PlanNode>,
+    pub right: Box<PlanNode>,
+    /// Key of the left rows, one expression on the left row per column of the key
+    pub left_key_exprs: Vec<Expression>,
+    /// Key of the right rows, one expression on the right row per column of the key, with the same types as the columns
+    /// of the left key (keys are compared by their serialized values)
+    pub right_key_exprs: Vec<Expression>,
+    /// Child whose rows are put in the hash table
+    pub build_side: JoinSide,
+    /// Bytes of build rows kept in memory before spilling to disk
+    pub memory_budget: usize,
+}
+
+impl HashJoinPlanNode {
+    /// Creates a hash join building the hash table with the right child, with the default memory budget.
+    ///
+    /// # Panics
+    /// Will panic if the keys of the sides don't have the same column types (e.g. an `INTEGER` and a `BIGINT`, or
+    /// varchars of different lengths), as their values would never be equal once serialized. Such keys have to be cast
+    /// to a common type first.
+    pub fn new(
+        join_type: JoinType,
+        predicate: BooleanExpression,
+        left: PlanNode,
+        right: PlanNode,
+        left_key_exprs: Vec<Expression>,
+        right_key_exprs: Vec<Expression>,
+    ) -> Self {
+        let key_types = |key_exprs: &[Expression]| {
+            key_exprs
+                .iter()
+                .map(|expr| expr.return_type().col_type().clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            key_types(&left_key_exprs),
+            key_types(&right_key_exprs),
+            "The keys of the sides of a hash join must have the same column types"
+        );
+
+        Self {
+            output_schema: join_schema(left.get_output_schema(), right.get_output_schema()),
+            join_type,
+            predicate,
+            left: Box::new(left),
+            right: Box::new(right),
+            left_key_exprs,
+            right_key_exprs,
+            build_side: JoinSide::Right,
+            memory_budget: DB_HASH_JOIN_MEMORY_BUDGET,
+        }
+    }
+
+    pub fn with_build_side(mut self, build_side: JoinSide) -> Self {
+        self.build_side = build_side;
+        self
+    }
+
+    pub fn with_memory_budget(mut self, memory_budget: usize) -> Self {
+        self.memory_budget = memory_budget;
+        self
+    }
+}
+
+impl AbstractPlanNode for HashJoinPlanNode {
+    fn get_children(&self) -> Vec<&PlanNode> {
+        vec![&self.left, &self.right]
+    }
+
+    fn get_output_schema(&self) -> &Schema {
+        &self.output_schema
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    catalog::{info::TableInfo, OID},
+    exec::{
+        expression::{boolean::BooleanExpression, Expression},
+        plan::{
+            nested_loop_join::{join_schema, JoinType},
+            AbstractPlanNode, PlanNode,
+        },
+    },
+    table::schema::Schema,
+};
+
+/// Joins the rows of a child (the left, outer side) with the rows of a table (the right, inner side) by looking up the
+/// key of every outer row in an index of the table, instead of reading the whole table for each of them.
+#[derive(Clone)]
+pub struct IdxNestedLoopJoinPlanNode {
+    pub output_schema: Schema,
+    pub join_type: JoinType,
+    /// Condition of the join (`ON`), checked for every row found in the index
+    pub predicate: BooleanExpression,
+    pub left: Box<PlanNode>,
+    pub table_oid: OID,
+    pub table_name: String,
+    pub index_name: String,
+    /// Key looked up in the index for an outer row, one expression on the outer row per column of the index key
+    pub key_exprs: Vec<Expression>,
+}
+
+impl IdxNestedLoopJoinPlanNode {
+    /// Creates a join of the rows of `left` with the rows of the table with `table_info` (the inner side), found
+    /// through its index named `index_name`.
+    pub fn new(
+        join_type: JoinType,
+        predicate: BooleanExpression,
+        left: PlanNode,
+        table_info: &TableInfo,
+        index_name: String,
+        key_exprs: Vec<Expression>,
+    ) -> Self {
+        Self {
+            output_schema: join_schema(left.get_output_schema(), &table_info.schema),
+            join_type,
+            predicate,
+            left: Box::new(left),
+            table_oid: table_info.oid,
+            table_name: table_info.name.clone(),
+            index_name,
+            key_exprs,
+        }
+    }
+}
+
+impl AbstractPlanNode for IdxNestedLoopJoinPlanNode {
+    fn get_children(&self) -> Vec<&PlanNode> {
+        vec![&self.left]
+    }
+
+    fn get_output_schema(&self) -> &Schema {
+        &self.output_schema
+    }
+}
@@ -0,0 +1,154 @@
+use std::ops::Bound;
+
+use crate::{
+    catalog::OID,
+    exec::{
+        expression::{
+            boolean::{BooleanExpression, BooleanType},
+            constant::ConstantExpression,
+            Evaluate, Expression,
+        },
+        plan::{AbstractPlanNode, PlanNode},
+    },
+    index::ScanDirection,
+    table::schema::{ColumnType, Schema},
+};
+
+/// Bound of the range of an index range scan.
+pub type KeyBound = Bound<Box<Expression>>;
+
+/// Scans the rows of a table whose values of a column are in a range, through an ordered index on the column. The
+/// rows are returned sorted by the column in the scan direction, so the scan can also be used for `ORDER BY`.
+#[derive(Clone)]
+pub struct IdxRangeScanPlanNode {
+    pub output_schema: Schema,
+    pub table_oid: OID,
+    pub table_name: String,
+    /// Column of the table on which the index is built
+    pub col_index: usize,
+    /// Bounds of the range, expressions that don't depend on the row (they are evaluated once, when the scan starts)
+    pub lower: KeyBound,
+    pub upper: KeyBound,
+    pub direction: ScanDirection,
+    /// Columns of the table read by the parent nodes, `None` if all of them are. If the index key has all of them, the
+    /// rows are built from the keys without reading the table (the other columns are `NULL`).
+    pub columns: Option<Vec<usize>>,
+}
+
+impl IdxRangeScanPlanNode {
+    /// Creates a range scan for the rows matching `filter_expr`. The filter has to be a comparison (`=`, `<`, `<=`,
+    /// `>` or `>=`) of a column with an expression that doesn't depend on the row, or the `AND` of a lower and an
+    /// upper bound of the same column (e.g. a `BETWEEN`). Constants of another type than the column are cast to it.
+    ///
+    /// # Errors
+    /// Will return `Err` if the filter can't be turned into the bounds of a range, or if a bound doesn't have the type
+    /// of the column and can't be cast to it exactly.
+    pub fn from_filter(
+        output_schema: Schema,
+        table_oid: OID,
+        table_name: String,
+        filter_expr: &BooleanExpression,
+        direction: ScanDirection,
+    ) -> Result<Self, ()> {
+        let (col_index, lower, upper) = match filter_expr.typ {
+            BooleanType::And => {
+                let (Expression::Boolean(left), Expression::Boolean(right)) =
+                    (filter_expr.left.as_ref(), filter_expr.right.as_ref())
+                else {
+                    return Err(());
+                };
+                let (left_col, left_lower, left_upper) = Self::comparison_bounds(left)?;
+                let (right_col, right_lower, right_upper) = Self::comparison_bounds(right)?;
+                if left_col != right_col {
+                    return Err(());
+                }
+
+                (
+                    left_col,
+                    Self::combine_bounds(left_lower, right_lower)?,
+                    Self::combine_bounds(left_upper, right_upper)?,
+                )
+            }
+            _ => Self::comparison_bounds(filter_expr)?,
+        };
+        let col_type = output_schema.get_col_type(col_index);
+        let lower = Self::cast_bound(lower, &col_type)?;
+        let upper = Self::cast_bound(upper, &col_type)?;
+
+        Ok(Self {
+            output_schema,
+            table_oid,
+            table_name,
+            col_index,
+            lower,
+            upper,
+            direction,
+            columns: None,
+        })
+    }
+
+    /// Returns the column and the bounds described by `column <cmp> expression`.
+    fn comparison_bounds(expr: &BooleanExpression) -> Result<(usize, KeyBound, KeyBound), ()> {
+        let col_index = match expr.left.as_ref() {
+            Expression::ColumnValue(col_val_expression) => col_val_expression.col_index,
+            _ => return Err(()),
+        };
+        if matches!(expr.right.as_ref(), Expression::ColumnValue(_)) {
+            return Err(());
+        }
+
+        let value = expr.right.clone();
+        let (lower, upper) = match expr.typ {
+            BooleanType::EQ => (Bound::Included(value.clone()), Bound::Included(value)),
+            BooleanType::GT => (Bound::Excluded(value), Bound::Unbounded),
+            BooleanType::GE => (Bound::Included(value), Bound::Unbounded),
+            BooleanType::LT => (Bound::Unbounded, Bound::Excluded(value)),
+            BooleanType::LE => (Bound::Unbounded, Bound::Included(value)),
+            _ => return Err(()),
+        };
+
+        Ok((col_index, lower, upper))
+    }
+
+    /// Casts the value of a bound to the type of the indexed column, so it can be used as a key of the index.
+    fn cast_bound(bound: KeyBound, typ: &ColumnType) -> Result<KeyBound, ()> {
+        let cast = |expression: Box<Expression>| {
+            if expression.return_type().col_type() == typ {
+                return Ok(expression);
+            }
+
+            match *expression {
+                Expression::Constant(constant) => {
+                    Ok(Box::new(Expression::Constant(ConstantExpression {
+                        value: constant.value.cast(typ)?,
+                    })))
+                }
+                _ => Err(()),
+            }
+        };
+
+        Ok(match bound {
+            Bound::Included(expression) => Bound::Included(cast(expression)?),
+            Bound::Excluded(expression) => Bound::Excluded(cast(expression)?),
+            Bound::Unbounded => Bound::Unbounded,
+        })
+    }
+
+    /// Combines the same bound of two comparisons, at most one of which can be bounded.
+    fn combine_bounds(left: KeyBound, right: KeyBound) -> Result<KeyBound, ()> {
+        match (left, right) {
+            (Bound::Unbounded, bound) | (bound, Bound::Unbounded) => Ok(bound),
+            _ => Err(()),
+        }
+    }
+}
+
+impl AbstractPlanNode for IdxRangeScanPlanNode {
+    fn get_children(&self) -> Vec<&PlanNode> {
+        vec![]
+    }
+
+    fn get_output_schema(&self) -> &Schema {
+        &self.output_schema
+    }
+}
@@ -13,6 +13,9 @@ pub struct IdxScanPlanNode {
     pub table_oid: OID,
     pub table_name: String,
     pub filter_expr: BooleanExpression,
+    /// Columns of the table read by the parent nodes, `None` if all of them are. If the index key has all of them, the
+    /// rows are built from the keys without reading the table (the other columns are `NULL`).
+    pub columns: Option<Vec<usize>>,
 }
 
 impl AbstractPlanNode for IdxScanPlanNode {
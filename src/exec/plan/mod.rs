@@ -6,14 +6,27 @@ use seq_scan::SeqScanPlanNode;
 use update::UpdatePlanNode;
 use values::ValuesPlanNode;
 
-use crate::{exec::plan::idx_scan::IdxScanPlanNode, table::schema::Schema};
+use crate::{
+    exec::plan::{
+        hash_join::HashJoinPlanNode, idx_nested_loop_join::IdxNestedLoopJoinPlanNode,
+        idx_range_scan::IdxRangeScanPlanNode, idx_scan::IdxScanPlanNode,
+        nested_loop_join::NestedLoopJoinPlanNode, sort::SortPlanNode, text_scan::TextScanPlanNode,
+    },
+    table::schema::Schema,
+};
 
 pub mod delete;
 pub mod filter;
+pub mod hash_join;
+pub mod idx_nested_loop_join;
+pub mod idx_range_scan;
 pub mod idx_scan;
 pub mod insert;
+pub mod nested_loop_join;
 pub mod projection;
 pub mod seq_scan;
+pub mod sort;
+pub mod text_scan;
 pub mod update;
 pub mod values;
 
@@ -34,6 +47,12 @@ pub enum PlanNode {
     Delete(DeletePlanNode),
     Update(UpdatePlanNode),
     IdxScan(IdxScanPlanNode),
+    IdxRangeScan(IdxRangeScanPlanNode),
+    TextScan(TextScanPlanNode),
+    NestedLoopJoin(NestedLoopJoinPlanNode),
+    IdxNestedLoopJoin(IdxNestedLoopJoinPlanNode),
+    HashJoin(HashJoinPlanNode),
+    Sort(SortPlanNode),
 }
 
 impl AbstractPlanNode for PlanNode {
@@ -47,6 +66,12 @@ impl AbstractPlanNode for PlanNode {
             PlanNode::Delete(node) => node.get_children(),
             PlanNode::Update(node) => node.get_children(),
             PlanNode::IdxScan(node) => node.get_children(),
+            PlanNode::IdxRangeScan(node) => node.get_children(),
+            PlanNode::TextScan(node) => node.get_children(),
+            PlanNode::NestedLoopJoin(node) => node.get_children(),
+            PlanNode::IdxNestedLoopJoin(node) => node.get_children(),
+            PlanNode::HashJoin(node) => node.get_children(),
+            PlanNode::Sort(node) => node.get_children(),
         }
     }
 
@@ -60,6 +85,12 @@ impl AbstractPlanNode for PlanNode {
             PlanNode::Delete(node) => node.get_output_schema(),
             PlanNode::Update(node) => node.get_output_schema(),
             PlanNode::IdxScan(node) => node.get_output_schema(),
+            PlanNode::IdxRangeScan(node) => node.get_output_schema(),
+            PlanNode::TextScan(node) => node.get_output_schema(),
+            PlanNode::NestedLoopJoin(node) => node.get_output_schema(),
+            PlanNode::IdxNestedLoopJoin(node) => node.get_output_schema(),
+            PlanNode::HashJoin(node) => node.get_output_schema(),
+            PlanNode::Sort(node) => node.get_output_schema(),
         }
     }
 }
@@ -0,0 +1,96 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    exec::{
+        expression::boolean::BooleanExpression,
+        plan::{AbstractPlanNode, PlanNode},
+    },
+    table::schema::{Column, Schema},
+};
+
+/// Type of a join, i.e. which rows without a match on the other side are also returned (padded with `NULL`s).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinType {
+    /// Only the pairs of matching rows
+    Inner,
+    /// Also the left rows without a match
+    Left,
+    /// Also the right rows without a match
+    Right,
+    /// Also the rows without a match of both sides
+    Outer,
+}
+
+impl JoinType {
+    /// Returns `true` if the left rows without a match are returned.
+    pub fn keeps_left(&self) -> bool {
+        matches!(self, JoinType::Left | JoinType::Outer)
+    }
+
+    /// Returns `true` if the right rows without a match are returned.
+    pub fn keeps_right(&self) -> bool {
+        matches!(self, JoinType::Right | JoinType::Outer)
+    }
+}
+
+impl Display for JoinType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            JoinType::Inner => "INNER",
+            JoinType::Left => "LEFT",
+            JoinType::Right => "RIGHT",
+            JoinType::Outer => "FULL OUTER",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Returns the schema of the rows of a join: the columns of the left rows followed by the ones of the right rows.
+pub fn join_schema(left: &Schema, right: &Schema) -> Schema {
+    let columns = (0..left.get_cols_count())
+        .map(|i| left.get_column(i))
+        .chain((0..right.get_cols_count()).map(|i| right.get_column(i)))
+        .cloned()
+        .collect::<Vec<Column>>();
+
+    Schema::new(columns)
+}
+
+/// Joins the rows of two children, comparing every row of the left (outer) child with every row of the right (inner)
+/// child. The predicate is evaluated with `Evaluate::evaluate_join`, so its columns have to say which side they read.
+#[derive(Clone)]
+pub struct NestedLoopJoinPlanNode {
+    pub output_schema: Schema,
+    pub join_type: JoinType,
+    /// Condition of the join (`ON`), the pairs for which it isn't true don't match
+    pub predicate: BooleanExpression,
+    pub left: Box<PlanNode>,
+    pub right: Box<PlanNode>,
+}
+
+impl NestedLoopJoinPlanNode {
+    pub fn new(
+        join_type: JoinType,
+        predicate: BooleanExpression,
+        left: PlanNode,
+        right: PlanNode,
+    ) -> Self {
+        Self {
+            output_schema: join_schema(left.get_output_schema(), right.get_output_schema()),
+            join_type,
+            predicate,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+}
+
+impl AbstractPlanNode for NestedLoopJoinPlanNode {
+    fn get_children(&self) -> Vec<&PlanNode> {
+        vec![&self.left, &self.right]
+    }
+
+    fn get_output_schema(&self) -> &Schema {
+        &self.output_schema
+    }
+}
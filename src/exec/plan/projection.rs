@@ -9,6 +9,16 @@ pub struct ProjectionPlanNode {
     pub child: Box<PlanNode>,
 }
 
+impl ProjectionPlanNode {
+    /// Returns the columns of the child read by the projection.
+    pub fn columns(&self) -> Vec<usize> {
+        self.expressions
+            .iter()
+            .flat_map(|expression| expression.columns())
+            .collect()
+    }
+}
+
 impl AbstractPlanNode for ProjectionPlanNode {
     fn get_children(&self) -> Vec<&PlanNode> {
         vec![&self.child]
@@ -0,0 +1,61 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{config::DB_SORT_MEMORY_BUDGET, exec::expression::Expression, table::schema::Schema};
+
+use super::{AbstractPlanNode, PlanNode};
+
+/// Direction in which a key of a sort is ordered. Nulls are ordered after all the other values, i.e. they come last in
+/// ascending order and first in descending order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl Display for SortOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SortOrder::Asc => write!(f, "ASC"),
+            SortOrder::Desc => write!(f, "DESC"),
+        }
+    }
+}
+
+/// Sorts the rows of the child (`ORDER BY`). Rows that don't fit in memory are sorted in runs written to disk, which are
+/// then merged.
+#[derive(Clone)]
+pub struct SortPlanNode {
+    pub output_schema: Schema,
+    /// Keys of the sort, compared in order: a key is only compared when the previous ones are equal
+    pub order_by: Vec<(Expression, SortOrder)>,
+    pub child: Box<PlanNode>,
+    /// Bytes of rows sorted in memory before spilling to disk
+    pub memory_budget: usize,
+}
+
+impl SortPlanNode {
+    /// Creates a sort of the rows of `child`, with the default memory budget.
+    pub fn new(order_by: Vec<(Expression, SortOrder)>, child: PlanNode) -> Self {
+        Self {
+            output_schema: child.get_output_schema().clone(),
+            order_by,
+            child: Box::new(child),
+            memory_budget: DB_SORT_MEMORY_BUDGET,
+        }
+    }
+
+    pub fn with_memory_budget(mut self, memory_budget: usize) -> Self {
+        self.memory_budget = memory_budget;
+        self
+    }
+}
+
+impl AbstractPlanNode for SortPlanNode {
+    fn get_children(&self) -> Vec<&PlanNode> {
+        vec![&self.child]
+    }
+
+    fn get_output_schema(&self) -> &Schema {
+        &self.output_schema
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    catalog::OID,
+    exec::{
+        expression::Expression,
+        plan::{AbstractPlanNode, PlanNode},
+    },
+    index::full_text::TextQuery,
+    table::schema::Schema,
+};
+
+/// Scans the rows of a table whose string column matches a full-text query, through a full-text index on the column.
+/// The rows are returned in the order of their RIDs.
+#[derive(Clone)]
+pub struct TextScanPlanNode {
+    pub output_schema: Schema,
+    pub table_oid: OID,
+    pub table_name: String,
+    /// Column of the table on which the index is built
+    pub col_index: usize,
+    pub query: TextQuery,
+}
+
+impl TextScanPlanNode {
+    /// Creates a full-text scan for the rows matching `filter_expr`, which has to be a `MATCH` on a column.
+    ///
+    /// # Errors
+    /// Will return `Err` if the filter isn't a `MATCH` on a column.
+    pub fn from_filter(
+        output_schema: Schema,
+        table_oid: OID,
+        table_name: String,
+        filter_expr: &Expression,
+    ) -> Result<Self, ()> {
+        let Expression::Match(match_expression) = filter_expr else {
+            return Err(());
+        };
+        let Expression::ColumnValue(col_val_expression) = match_expression.expr.as_ref() else {
+            return Err(());
+        };
+
+        Ok(Self {
+            output_schema,
+            table_oid,
+            table_name,
+            col_index: col_val_expression.col_index,
+            query: match_expression.query.clone(),
+        })
+    }
+}
+
+impl AbstractPlanNode for TextScanPlanNode {
+    fn get_children(&self) -> Vec<&PlanNode> {
+        vec![]
+    }
+
+    fn get_output_schema(&self) -> &Schema {
+        &self.output_schema
+    }
+}
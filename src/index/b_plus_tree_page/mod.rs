@@ -0,0 +1,383 @@
+use std::cmp::Ordering;
+
+use crate::{
+    config::DB_PAGE_SIZE,
+    disk::disk_manager::PageID,
+    table::{
+        schema::Schema,
+        tuple::{Tuple, RID},
+    },
+};
+
+use super::{
+    get_four_bytes_group,
+    serial::{Deserialize, Serialize},
+};
+
+#[cfg(test)]
+mod tests;
+
+const B_PLUS_TREE_PAGE_HEADER_SIZE: usize = 1 + 4 + 4 + 4 + 4; // 1 byte for page type, 4 bytes for size, 4 bytes for key_size, 2 * 4 bytes for page IDs
+const B_PLUS_TREE_PAGE_DATA_SIZE: usize = DB_PAGE_SIZE as usize - B_PLUS_TREE_PAGE_HEADER_SIZE;
+
+const LEAF_PAGE_TYPE: u8 = 1;
+const INTERNAL_PAGE_TYPE: u8 = 2;
+
+/// Compares two entries of a B+ tree. Entries are ordered by key first and by RID second, which makes every entry
+/// unique even when the index contains duplicate keys.
+pub fn compare_entries(
+    key: &Tuple,
+    rid: &RID,
+    other_key: &Tuple,
+    other_rid: &RID,
+    key_schema: &Schema,
+) -> Ordering {
+    key.compare(other_key, key_schema)
+        .then_with(|| rid.get().cmp(&other_rid.get()))
+}
+
+/// Node of a disk B+ tree. Its structure looks like this on disk:
+/// - `page_type` (0): `1` for leaf pages, `2` for internal pages
+/// - `size` (1-4): The number of entries in the page
+/// - `key_size` (5-8): The size of a serialized key
+/// - `page_id` (9-12): For leaf pages, the page ID of the next leaf (`0` if this is the last leaf). For internal pages,
+///   the page ID of the leftmost child
+/// - `prev_page_id` (13-16): For leaf pages, the page ID of the previous leaf (`0` if this is the first leaf). Unused
+///   for internal pages
+/// - `data` (17-4095): The entries, in an array form. Leaf entries are `| key | RID |` and internal entries are
+///   `| key | RID | child page ID |`
+#[derive(Debug, PartialEq)]
+pub enum BPlusTreePage {
+    Leaf(BPlusTreeLeafPage),
+    Internal(BPlusTreeInternalPage),
+}
+
+/// Leaf node of a B+ tree, holding `(key, RID)` entries sorted by `compare_entries`.
+///
+/// # Note
+/// Page `0` is never a leaf page (it's either the first page of the DB or the header page of the tree), so it's used
+/// to mark the ends of the leaf list.
+#[derive(Debug, PartialEq)]
+pub struct BPlusTreeLeafPage {
+    max_size: u32,
+    key_size: u32,
+    entries: Vec<(Tuple, RID)>,
+    next_page_id: Option<PageID>,
+    prev_page_id: Option<PageID>,
+}
+
+impl BPlusTreeLeafPage {
+    pub fn new_empty(key_size: u32) -> Self {
+        Self {
+            max_size: Self::max_size_for(key_size),
+            key_size,
+            entries: vec![],
+            next_page_id: None,
+            prev_page_id: None,
+        }
+    }
+
+    /// Returns the number of entries that fit in a leaf page with keys of size `key_size`.
+    pub fn max_size_for(key_size: u32) -> u32 {
+        (B_PLUS_TREE_PAGE_DATA_SIZE / (key_size as usize + RID::size())) as u32
+    }
+
+    /// Inserts the entry at its sorted position. Returns `false` if the exact entry is already in the page.
+    ///
+    /// # Note
+    /// The page is allowed to hold one entry over its max size (so that it can be split), see `is_overflowing`.
+    pub fn insert(&mut self, key: Tuple, rid: RID, key_schema: &Schema) -> bool {
+        match self
+            .entries
+            .binary_search_by(|(k, r)| compare_entries(k, r, &key, &rid, key_schema))
+        {
+            Ok(_) => false,
+            Err(index) => {
+                self.entries.insert(index, (key, rid));
+                true
+            }
+        }
+    }
+
+    /// Removes the exact entry. Returns `false` if the entry wasn't found.
+    pub fn remove(&mut self, key: &Tuple, rid: &RID, key_schema: &Schema) -> bool {
+        match self
+            .entries
+            .binary_search_by(|(k, r)| compare_entries(k, r, key, rid, key_schema))
+        {
+            Ok(index) => {
+                self.entries.remove(index);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the index of the first entry whose key is greater than or equal to `key`.
+    pub fn lower_bound(&self, key: &Tuple, key_schema: &Schema) -> usize {
+        self.entries
+            .partition_point(|(k, _)| k.compare(key, key_schema) == Ordering::Less)
+    }
+
+    /// Moves the upper half of the entries to a new page, which is returned. Linking the new page in the leaf list is
+    /// left to the caller, since its page ID isn't known here.
+    pub fn split(&mut self) -> BPlusTreeLeafPage {
+        let entries = self.entries.split_off(self.entries.len() / 2);
+
+        BPlusTreeLeafPage {
+            max_size: self.max_size,
+            key_size: self.key_size,
+            entries,
+            next_page_id: None,
+            prev_page_id: None,
+        }
+    }
+
+    pub fn entries(&self) -> &[(Tuple, RID)] {
+        &self.entries
+    }
+
+    pub fn entries_mut(&mut self) -> &mut Vec<(Tuple, RID)> {
+        &mut self.entries
+    }
+
+    pub fn into_entries(self) -> Vec<(Tuple, RID)> {
+        self.entries
+    }
+
+    pub fn next_page_id(&self) -> Option<PageID> {
+        self.next_page_id
+    }
+
+    pub fn set_next_page_id(&mut self, next_page_id: Option<PageID>) {
+        self.next_page_id = next_page_id;
+    }
+
+    pub fn prev_page_id(&self) -> Option<PageID> {
+        self.prev_page_id
+    }
+
+    pub fn set_prev_page_id(&mut self, prev_page_id: Option<PageID>) {
+        self.prev_page_id = prev_page_id;
+    }
+
+    /// Returns current number of entries in the page.
+    pub fn size(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the page holds more entries than it can store on disk and needs to be split.
+    pub fn is_overflowing(&self) -> bool {
+        self.entries.len() as u32 > self.max_size
+    }
+}
+
+/// Internal node of a B+ tree. Each entry holds a separator `(key, RID)` and the child that contains the entries
+/// greater than or equal to the separator (and smaller than the next separator). Entries smaller than the first
+/// separator are stored under the leftmost child.
+#[derive(Debug, PartialEq)]
+pub struct BPlusTreeInternalPage {
+    max_size: u32,
+    key_size: u32,
+    first_child: PageID,
+    entries: Vec<(Tuple, RID, PageID)>,
+}
+
+impl BPlusTreeInternalPage {
+    pub fn new(first_child: PageID, entries: Vec<(Tuple, RID, PageID)>, key_size: u32) -> Self {
+        let max_size = Self::max_size_for(key_size);
+        assert!(entries.len() as u32 <= max_size);
+
+        Self {
+            max_size,
+            key_size,
+            first_child,
+            entries,
+        }
+    }
+
+    /// Returns the number of separators that fit in an internal page with keys of size `key_size`.
+    pub fn max_size_for(key_size: u32) -> u32 {
+        (B_PLUS_TREE_PAGE_DATA_SIZE / (key_size as usize + RID::size() + 4)) as u32
+    }
+
+    /// Returns the child that should contain the entry `(key, rid)`.
+    pub fn child_for_entry(&self, key: &Tuple, rid: &RID, key_schema: &Schema) -> PageID {
+        let index = self.entries.partition_point(|(k, r, _)| {
+            compare_entries(k, r, key, rid, key_schema) != Ordering::Greater
+        });
+
+        self.child_at(index)
+    }
+
+    /// Returns the leftmost child that can contain entries with `key`.
+    pub fn child_for_key(&self, key: &Tuple, key_schema: &Schema) -> PageID {
+        let index = self
+            .entries
+            .partition_point(|(k, _, _)| k.compare(key, key_schema) == Ordering::Less);
+
+        self.child_at(index)
+    }
+
+    /// Returns the rightmost child that can contain entries with `key`.
+    pub fn last_child_for_key(&self, key: &Tuple, key_schema: &Schema) -> PageID {
+        let index = self
+            .entries
+            .partition_point(|(k, _, _)| k.compare(key, key_schema) != Ordering::Greater);
+
+        self.child_at(index)
+    }
+
+    /// Returns the child at `index`, where `0` is the leftmost child and `i` is the child of the separator `i - 1`.
+    pub fn child_at(&self, index: usize) -> PageID {
+        if index == 0 {
+            self.first_child
+        } else {
+            self.entries[index - 1].2
+        }
+    }
+
+    /// Inserts a new separator and the child to its right, after a split of one of the children.
+    pub fn insert(&mut self, key: Tuple, rid: RID, child: PageID, key_schema: &Schema) {
+        let index = self.entries.partition_point(|(k, r, _)| {
+            compare_entries(k, r, &key, &rid, key_schema) == Ordering::Less
+        });
+
+        self.entries.insert(index, (key, rid, child));
+    }
+
+    /// Moves the upper half of the separators to a new page. The middle separator is removed from both pages and is
+    /// returned with the new page, so that it can be pushed to the parent.
+    pub fn split(&mut self) -> ((Tuple, RID), BPlusTreeInternalPage) {
+        let mut entries = self.entries.split_off(self.entries.len() / 2);
+        let (key, rid, first_child) = entries.remove(0);
+
+        let page = BPlusTreeInternalPage {
+            max_size: self.max_size,
+            key_size: self.key_size,
+            first_child,
+            entries,
+        };
+
+        ((key, rid), page)
+    }
+
+    /// Returns all the children, from left to right.
+    pub fn children(&self) -> Vec<PageID> {
+        (0..=self.entries.len()).map(|i| self.child_at(i)).collect()
+    }
+
+    /// Returns current number of separators in the page.
+    pub fn size(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the page holds more separators than it can store on disk and needs to be split.
+    pub fn is_overflowing(&self) -> bool {
+        self.entries.len() as u32 > self.max_size
+    }
+}
+
+impl Serialize for BPlusTreePage {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![];
+
+        match self {
+            BPlusTreePage::Leaf(leaf) => {
+                data.push(LEAF_PAGE_TYPE);
+                data.extend_from_slice(&(leaf.size() as u32).to_be_bytes()); // usize needs cast to u32 for serialization
+                data.extend_from_slice(&leaf.key_size.to_be_bytes());
+                data.extend_from_slice(&leaf.next_page_id.unwrap_or(0).to_be_bytes());
+                data.extend_from_slice(&leaf.prev_page_id.unwrap_or(0).to_be_bytes());
+
+                for (key, rid) in leaf.entries.iter() {
+                    data.extend_from_slice(&key.serialize());
+                    data.extend_from_slice(&rid.serialize());
+                }
+            }
+            BPlusTreePage::Internal(internal) => {
+                data.push(INTERNAL_PAGE_TYPE);
+                data.extend_from_slice(&(internal.size() as u32).to_be_bytes());
+                data.extend_from_slice(&internal.key_size.to_be_bytes());
+                data.extend_from_slice(&internal.first_child.to_be_bytes());
+                data.extend_from_slice(&0u32.to_be_bytes());
+
+                for (key, rid, child) in internal.entries.iter() {
+                    data.extend_from_slice(&key.serialize());
+                    data.extend_from_slice(&rid.serialize());
+                    data.extend_from_slice(&child.to_be_bytes());
+                }
+            }
+        }
+
+        assert!(
+            data.len() <= DB_PAGE_SIZE as usize,
+            "B+ tree page is overflowing"
+        );
+        data.resize(DB_PAGE_SIZE as usize, 0);
+
+        data
+    }
+}
+
+impl Deserialize for BPlusTreePage {
+    fn deserialize(data: &[u8]) -> Self {
+        let page_type = data[0];
+        let header = &data[1..B_PLUS_TREE_PAGE_HEADER_SIZE];
+        let size = u32::from_be_bytes(get_four_bytes_group(header, 0)) as usize;
+        let key_size = u32::from_be_bytes(get_four_bytes_group(header, 1));
+        let page_id = u32::from_be_bytes(get_four_bytes_group(header, 2));
+        let prev_page_id = u32::from_be_bytes(get_four_bytes_group(header, 3));
+
+        let key_len = key_size as usize;
+        let rid_len = RID::size();
+        match page_type {
+            LEAF_PAGE_TYPE => {
+                let entry_size = key_len + rid_len;
+                let entries = (0..size)
+                    .map(|i| {
+                        let offset = B_PLUS_TREE_PAGE_HEADER_SIZE + i * entry_size;
+                        (
+                            Tuple::deserialize(&data[offset..offset + key_len]),
+                            RID::deserialize(&data[offset + key_len..offset + entry_size]),
+                        )
+                    })
+                    .collect();
+
+                BPlusTreePage::Leaf(BPlusTreeLeafPage {
+                    max_size: BPlusTreeLeafPage::max_size_for(key_size),
+                    key_size,
+                    entries,
+                    next_page_id: if page_id == 0 { None } else { Some(page_id) },
+                    prev_page_id: if prev_page_id == 0 {
+                        None
+                    } else {
+                        Some(prev_page_id)
+                    },
+                })
+            }
+            INTERNAL_PAGE_TYPE => {
+                let entry_size = key_len + rid_len + 4;
+                let entries = (0..size)
+                    .map(|i| {
+                        let offset = B_PLUS_TREE_PAGE_HEADER_SIZE + i * entry_size;
+                        let child_offset = offset + key_len + rid_len;
+                        (
+                            Tuple::deserialize(&data[offset..offset + key_len]),
+                            RID::deserialize(&data[offset + key_len..child_offset]),
+                            u32::from_be_bytes(get_four_bytes_group(&data[child_offset..], 0)),
+                        )
+                    })
+                    .collect();
+
+                BPlusTreePage::Internal(BPlusTreeInternalPage {
+                    max_size: BPlusTreeInternalPage::max_size_for(key_size),
+                    key_size,
+                    first_child: page_id,
+                    entries,
+                })
+            }
+            _ => panic!("Invalid B+ tree page type: {}", page_type),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{
+    index::serial::{Deserialize, Serialize},
+    table::{
+        schema::{ColumnType, Schema},
+        tuple::{Tuple, RID},
+    },
+    test_utils::int_value,
+};
+
+use super::{BPlusTreeInternalPage, BPlusTreeLeafPage, BPlusTreePage};
+
+fn key_schema() -> Schema {
+    Schema::with_types(vec![ColumnType::Integer])
+}
+
+fn key(value: i32) -> Tuple {
+    Tuple::new(vec![int_value(value)], &key_schema())
+}
+
+fn key_size() -> u32 {
+    key(0).size() as u32
+}
+
+#[test]
+fn max_size() {
+    // integer key = 4 bytes + 1 byte null bitmap + 4 bytes length => leaf entry = 17 bytes, internal entry = 21 bytes
+    assert_eq!(BPlusTreeLeafPage::max_size_for(key_size()), 239); // 4079 / 17
+    assert_eq!(BPlusTreeInternalPage::max_size_for(key_size()), 194); // 4079 / 21
+}
+
+#[test]
+fn leaf_insert_remove() {
+    let schema = key_schema();
+    let mut leaf = BPlusTreeLeafPage::new_empty(key_size());
+
+    assert!(leaf.insert(key(3), RID::new(3, 0), &schema));
+    assert!(leaf.insert(key(1), RID::new(1, 1), &schema));
+    assert!(leaf.insert(key(1), RID::new(1, 0), &schema));
+    assert!(leaf.insert(key(2), RID::new(2, 0), &schema));
+    assert!(!leaf.insert(key(2), RID::new(2, 0), &schema)); // exact duplicate
+
+    assert_eq!(
+        leaf.entries(),
+        &[
+            (key(1), RID::new(1, 0)),
+            (key(1), RID::new(1, 1)),
+            (key(2), RID::new(2, 0)),
+            (key(3), RID::new(3, 0)),
+        ]
+    );
+    assert_eq!(leaf.lower_bound(&key(1), &schema), 0);
+    assert_eq!(leaf.lower_bound(&key(2), &schema), 2);
+    assert_eq!(leaf.lower_bound(&key(99), &schema), 4);
+
+    assert!(leaf.remove(&key(1), &RID::new(1, 1), &schema));
+    assert!(!leaf.remove(&key(1), &RID::new(1, 1), &schema));
+    assert_eq!(leaf.size(), 3);
+}
+
+#[test]
+fn leaf_split() {
+    let schema = key_schema();
+    let mut leaf = BPlusTreeLeafPage::new_empty(key_size());
+    for i in 0..=239 {
+        leaf.insert(key(i), RID::new(i as u32, 0), &schema);
+    }
+    assert!(leaf.is_overflowing());
+
+    let right = leaf.split();
+    assert_eq!(leaf.size(), 120);
+    assert_eq!(right.size(), 120);
+    assert!(!leaf.is_overflowing());
+    assert_eq!(right.entries()[0], (key(120), RID::new(120, 0)));
+}
+
+#[test]
+fn internal_routing() {
+    let schema = key_schema();
+    let mut internal = BPlusTreeInternalPage::new(
+        10,
+        vec![(key(5), RID::new(5, 0), 11), (key(5), RID::new(6, 0), 12)],
+        key_size(),
+    );
+    internal.insert(key(9), RID::new(9, 0), 13, &schema);
+
+    assert_eq!(internal.children(), vec![10, 11, 12, 13]);
+
+    assert_eq!(
+        internal.child_for_entry(&key(1), &RID::new(1, 0), &schema),
+        10
+    );
+    assert_eq!(
+        internal.child_for_entry(&key(5), &RID::new(5, 0), &schema),
+        11
+    );
+    assert_eq!(
+        internal.child_for_entry(&key(5), &RID::new(7, 0), &schema),
+        12
+    );
+    assert_eq!(
+        internal.child_for_entry(&key(10), &RID::new(1, 0), &schema),
+        13
+    );
+
+    // the leftmost child that can hold the key
+    assert_eq!(internal.child_for_key(&key(5), &schema), 10);
+    assert_eq!(internal.child_for_key(&key(6), &schema), 12);
+    assert_eq!(internal.last_child_for_key(&key(5), &schema), 12);
+    assert_eq!(internal.last_child_for_key(&key(4), &schema), 10);
+    assert_eq!(internal.last_child_for_key(&key(9), &schema), 13);
+}
+
+#[test]
+fn internal_split() {
+    let schema = key_schema();
+    let mut internal = BPlusTreeInternalPage::new(1, vec![], key_size());
+    for i in 0..5 {
+        internal.insert(key(i), RID::new(i as u32, 0), 100 + i as u32, &schema);
+    }
+
+    let ((middle_key, middle_rid), right) = internal.split();
+    assert_eq!((middle_key, middle_rid), (key(2), RID::new(2, 0)));
+    assert_eq!(internal.children(), vec![1, 100, 101]);
+    assert_eq!(right.children(), vec![102, 103, 104]);
+}
+
+#[test]
+fn serialization_consistency() {
+    let schema = key_schema();
+    let mut leaf = BPlusTreeLeafPage::new_empty(key_size());
+    leaf.insert(key(1), RID::new(1, 2), &schema);
+    leaf.insert(key(2), RID::new(3, 4), &schema);
+    leaf.set_next_page_id(Some(7));
+    leaf.set_prev_page_id(Some(5));
+    let leaf = BPlusTreePage::Leaf(leaf);
+
+    let data = leaf.serialize();
+    assert_eq!(data.len(), 4096);
+    assert_eq!(BPlusTreePage::deserialize(&data), leaf);
+
+    let internal = BPlusTreePage::Internal(BPlusTreeInternalPage::new(
+        3,
+        vec![(key(1), RID::new(1, 2), 4), (key(2), RID::new(3, 4), 5)],
+        key_size(),
+    ));
+    assert_eq!(BPlusTreePage::deserialize(&internal.serialize()), internal);
+}
@@ -8,14 +8,16 @@ use super::{
 #[cfg(test)]
 mod tests;
 
-const HASH_TABLE_BUCKET_PAGE_DATA_SIZE: usize = DB_PAGE_SIZE as usize - 4 - 4 - 4 - 4; // 4 bytes for max_size, 4 bytes for size, 4 bytes for key_size, 4 bytes for value_size
-
-/// Bucket page for extendinble hashing index. Its structure looks like this on disk:
-/// - `max_size` (0-3): The number of key-value pairs in bucket
-/// - `size` (4-7): The max number of key-value pairs that the bucket can hold
-/// - `key_size` (8-11): The size of the key
-/// - `value_size` (12-15): The size of the value
-/// - `data` (16-4095): The data of the key-value pairs stored, in an array form
+const HASH_TABLE_BUCKET_PAGE_DATA_SIZE: usize = DB_PAGE_SIZE as usize - 4; // 4 bytes for size
+const SLOT_SIZE: usize = 2 + 2; // 2 bytes for the entry offset, 2 bytes for the key size
+
+/// Bucket page for extendinble hashing index. Keys and values can have any size, so the page uses a slot directory.
+/// Its structure looks like this on disk:
+/// - `size` (0-3): The number of key-value pairs in bucket
+/// - `slots` (4-...): For every entry, its offset in the page (2 bytes) and the size of its key (2 bytes)
+/// - free space
+/// - `entries` (...-4095): The serialized key-value pairs, stored from the end of the page towards the slots. The
+///   entry of a slot ends where the entry of the previous slot starts (or at the end of the page for the first slot)
 ///
 /// # Note
 /// This bucket supports **non-unique** keys.
@@ -25,10 +27,9 @@ where
     K: Serialize + Deserialize + Eq,
     V: Serialize + Deserialize,
 {
-    max_size: u32,
     data: Vec<(K, V)>,
-    key_size: u32,
-    value_size: u32,
+    /// Bytes taken by the slots and the entries
+    used_space: usize,
 }
 
 impl<K, V> HashTableBucketPage<K, V>
@@ -36,24 +37,20 @@ where
     K: Serialize + Deserialize + Eq,
     V: Serialize + Deserialize,
 {
-    pub fn new(data: Vec<(K, V)>, key_size: u32, value_size: u32) -> Self {
-        // using this instead of `size_of::<K, V>()` because of memory alignment, which is undesirable for serialization
-        let pair_size = key_size + value_size;
-        let max_size = (HASH_TABLE_BUCKET_PAGE_DATA_SIZE / pair_size as usize) as u32;
+    pub fn new(data: Vec<(K, V)>) -> Self {
+        let used_space = data.iter().map(|(k, v)| Self::entry_space(k, v)).sum();
+        assert!(used_space <= HASH_TABLE_BUCKET_PAGE_DATA_SIZE);
 
-        let size = data.len() as u32;
-        assert!(size <= max_size);
+        Self { data, used_space }
+    }
 
-        Self {
-            max_size,
-            data,
-            key_size,
-            value_size,
-        }
+    pub fn new_empty() -> Self {
+        HashTableBucketPage::<K, V>::new(vec![])
     }
 
-    pub fn new_empty(key_size: u32, value_size: u32) -> Self {
-        HashTableBucketPage::<K, V>::new(vec![], key_size, value_size)
+    /// Returns the number of bytes taken by the `key`-`value` pair and its slot.
+    fn entry_space(key: &K, value: &V) -> usize {
+        SLOT_SIZE + key.serialize().len() + value.serialize().len()
     }
 
     /// Returns the values associated to `key`.
@@ -65,15 +62,21 @@ where
             .collect()
     }
 
+    /// Returns the `key`-value pairs stored in the bucket.
+    pub fn lookup_entries(&self, key: &K) -> Vec<&(K, V)> {
+        self.data.iter().filter(|(k, _)| k == key).collect()
+    }
+
     /// Inserts the `key`-`value` pair.
     ///
     /// # Errors
-    /// Will return `Err` if trying to insert when bucket is full.
+    /// Will return `Err` if there isn't enough free space in the bucket for the pair.
     pub fn insert(&mut self, key: K, value: V) -> Result<(), ()> {
-        if self.is_full() {
+        if !self.fits(&key, &value) {
             return Err(());
         }
 
+        self.used_space += Self::entry_space(&key, &value);
         self.data.push((key, value));
 
         Ok(())
@@ -82,26 +85,47 @@ where
     /// Removes all elements associated with `key`. Returns how many elements were removed.
     pub fn remove(&mut self, key: K) -> usize {
         let mut count = 0;
+        let mut freed_space = 0;
 
-        self.data.retain(|(k, _)| {
+        self.data.retain(|(k, v)| {
             if *k == key {
                 count += 1;
+                freed_space += Self::entry_space(k, v);
                 false
             } else {
                 true
             }
         });
+        self.used_space -= freed_space;
 
         count
     }
 
+    /// Removes one `key`-`value` pair, leaving the other values associated with `key` in place. Returns `true` if the
+    /// pair was in the bucket.
+    pub fn remove_entry(&mut self, key: K, value: &V) -> bool
+    where
+        V: PartialEq,
+    {
+        match self.data.iter().position(|(k, v)| *k == key && v == value) {
+            Some(index) => {
+                self.remove_at(index);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Removes the entry at `index`. Will return the removed entry, or `None` if trying to index outside of bounds.
     pub fn remove_at(&mut self, index: usize) -> Option<(K, V)> {
         if index > self.size() - 1 {
             return None;
         }
 
-        Some(self.data.remove(index))
+        let (key, value) = self.data.remove(index);
+        self.used_space -= Self::entry_space(&key, &value);
+
+        Some((key, value))
     }
 
     /// Returns key at `index`.
@@ -128,14 +152,14 @@ where
         self.data.len()
     }
 
-    /// Returns max size that the header page can handle.
-    pub fn max_size(&self) -> u32 {
-        self.max_size
+    /// Returns the number of bytes that are still available for entries and their slots.
+    pub fn free_space(&self) -> usize {
+        HASH_TABLE_BUCKET_PAGE_DATA_SIZE - self.used_space
     }
 
-    /// Returns `true` if the bucket is full.
-    pub fn is_full(&self) -> bool {
-        self.data.len() as u32 >= self.max_size
+    /// Returns `true` if the `key`-`value` pair can be inserted in the bucket.
+    pub fn fits(&self, key: &K, value: &V) -> bool {
+        Self::entry_space(key, value) <= self.free_space()
     }
 
     /// Returns `true` if the current size of the bucket is `0`.
@@ -150,25 +174,32 @@ where
     V: Serialize + Deserialize,
 {
     fn serialize(&self) -> Vec<u8> {
-        let mut data = vec![];
+        let mut data = vec![0; DB_PAGE_SIZE as usize];
+        data[0..4].copy_from_slice(&(self.size() as u32).to_be_bytes()); // usize needs cast to u32 for serialization
 
-        data.extend_from_slice(&self.max_size.to_be_bytes());
-        data.extend_from_slice(&(self.size() as u32).to_be_bytes()); // usize needs cast to u32 for serialization
-        data.extend_from_slice(&self.key_size.to_be_bytes());
-        data.extend_from_slice(&self.value_size.to_be_bytes());
+        let mut entry_end = DB_PAGE_SIZE as usize;
+        for (i, (key, value)) in self.data.iter().enumerate() {
+            let key = key.serialize();
+            let value = value.serialize();
+            let entry_offset = entry_end - key.len() - value.len();
 
-        for (key, value) in self.data.iter() {
-            data.extend_from_slice(&key.serialize());
-            data.extend_from_slice(&value.serialize());
-        }
+            data[entry_offset..entry_offset + key.len()].copy_from_slice(&key);
+            data[entry_offset + key.len()..entry_end].copy_from_slice(&value);
 
-        data.resize(DB_PAGE_SIZE as usize, 0);
+            let slot_offset = SLOTS_OFFSET + i * SLOT_SIZE;
+            data[slot_offset..slot_offset + 2]
+                .copy_from_slice(&(entry_offset as u16).to_be_bytes());
+            data[slot_offset + 2..slot_offset + 4]
+                .copy_from_slice(&(key.len() as u16).to_be_bytes());
+
+            entry_end = entry_offset;
+        }
 
         data
     }
 }
 
-const DATA_OFFSET: usize = 16;
+const SLOTS_OFFSET: usize = 4;
 
 impl<K, V> Deserialize for HashTableBucketPage<K, V>
 where
@@ -176,27 +207,27 @@ where
     V: Serialize + Deserialize,
 {
     fn deserialize(data: &[u8]) -> Self {
-        let max_size = u32::from_be_bytes(get_four_bytes_group(data, 0));
-        let size = u32::from_be_bytes(get_four_bytes_group(data, 1));
+        let size = u32::from_be_bytes(get_four_bytes_group(data, 0)) as usize;
         let mut entries = vec![];
 
-        // TODO: this will need to be changed - in serialization, we need to store the size of the key and value in the header of the page
-        let key_size = u32::from_be_bytes(get_four_bytes_group(data, 2)) as usize;
-        let value_size = u32::from_be_bytes(get_four_bytes_group(data, 3)) as usize;
-        let entry_size = key_size + value_size;
+        let mut entry_end = DB_PAGE_SIZE as usize;
         for i in 0..size {
-            let pair_offset = DATA_OFFSET + i as usize * entry_size;
-
-            let key = K::deserialize(&data[pair_offset..pair_offset + key_size]);
-            let value = V::deserialize(&data[pair_offset + key_size..pair_offset + entry_size]);
+            let slot_offset = SLOTS_OFFSET + i * SLOT_SIZE;
+            let entry_offset =
+                u16::from_be_bytes([data[slot_offset], data[slot_offset + 1]]) as usize;
+            let key_size =
+                u16::from_be_bytes([data[slot_offset + 2], data[slot_offset + 3]]) as usize;
+
+            let key = K::deserialize(&data[entry_offset..entry_offset + key_size]);
+            let value = V::deserialize(&data[entry_offset + key_size..entry_end]);
             entries.push((key, value));
+
+            entry_end = entry_offset;
         }
 
         Self {
-            max_size,
             data: entries,
-            key_size: key_size as u32,
-            value_size: value_size as u32,
+            used_space: size * SLOT_SIZE + DB_PAGE_SIZE as usize - entry_end,
         }
     }
 }
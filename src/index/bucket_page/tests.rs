@@ -3,23 +3,23 @@ use crate::index::serial::{Deserialize, Serialize};
 use super::HashTableBucketPage;
 
 #[test]
-fn new_max_size() {
-    let bucket = HashTableBucketPage::new(vec![(1, 2)], 4, 4);
-    assert_eq!(bucket.max_size, 510); // (i32, i32) = 8 => max_size = 4080 / 8 = 510
+fn new_free_space() {
+    let bucket = HashTableBucketPage::new(vec![(1, 2)]);
+    assert_eq!(bucket.free_space(), 4080); // 4092 - (4 + 4 + 4)
 
-    let bucket = HashTableBucketPage::new(vec![(1u32, 2u8)], 4, 1);
-    assert_eq!(bucket.max_size, 816); // (u32, u8) = 5 => max_size = 4080 / 5 = 816
+    let bucket = HashTableBucketPage::new(vec![(1u32, 2u8)]);
+    assert_eq!(bucket.free_space(), 4083); // 4092 - (4 + 4 + 1)
 }
 
 #[test]
 #[should_panic]
 fn new_panic() {
-    let _ = HashTableBucketPage::new([(1, 2); 512].to_vec(), 4, 4); // 512 > 510
+    let _ = HashTableBucketPage::new([(1, 2); 342].to_vec()); // 342 * 12 > 4092
 }
 
 #[test]
 fn lookup() {
-    let bucket = HashTableBucketPage::new(vec![(3, 4), (1, 2), (1, 5)], 4, 4);
+    let bucket = HashTableBucketPage::new(vec![(3, 4), (1, 2), (1, 5)]);
 
     let res: Vec<i32> = bucket.lookup(1).iter().map(|v| **v).collect();
     assert_eq!(res, vec![2, 5]);
@@ -33,13 +33,13 @@ fn lookup() {
 
 #[test]
 fn insert() {
-    let mut bucket = HashTableBucketPage::<i32, i32>::new(vec![], 4, 4);
+    let mut bucket = HashTableBucketPage::<i32, i32>::new(vec![]);
     bucket.insert(1, 2).unwrap();
 
     let res: Vec<i32> = bucket.lookup(1).iter().map(|v| **v).collect();
     assert_eq!(res, vec![2]);
 
-    for i in 0..509 {
+    for i in 0..340 {
         bucket.insert(1, i).unwrap();
     }
 
@@ -49,7 +49,7 @@ fn insert() {
 
 #[test]
 fn remove() {
-    let mut bucket = HashTableBucketPage::new(vec![(1, 1), (1, 2), (1, 3), (3, 4)], 4, 4);
+    let mut bucket = HashTableBucketPage::new(vec![(1, 1), (1, 2), (1, 3), (3, 4)]);
 
     let removed = bucket.remove(1);
     assert_eq!(removed, 3);
@@ -64,9 +64,20 @@ fn remove() {
     assert_eq!(removed, 0);
 }
 
+#[test]
+fn remove_entry() {
+    let mut bucket = HashTableBucketPage::new(vec![(1, 1), (1, 2), (1, 3), (3, 2)]);
+
+    assert!(bucket.remove_entry(1, &2));
+    assert!(!bucket.remove_entry(1, &2));
+    assert!(!bucket.remove_entry(2, &1));
+    assert_eq!(bucket.lookup(1), vec![&1, &3]);
+    assert_eq!(bucket.lookup(3), vec![&2]);
+}
+
 #[test]
 fn remove_at() {
-    let mut bucket = HashTableBucketPage::new(vec![(1, 1), (1, 2), (1, 3), (3, 4)], 4, 4);
+    let mut bucket = HashTableBucketPage::new(vec![(1, 1), (1, 2), (1, 3), (3, 4)]);
 
     let res = bucket.remove_at(1).unwrap();
     assert_eq!(res, (1, 2));
@@ -78,7 +89,7 @@ fn remove_at() {
 
 #[test]
 fn entry_key_value_at() {
-    let bucket = HashTableBucketPage::new(vec![(1, 1), (1, 2), (1, 3), (3, 4)], 4, 4);
+    let bucket = HashTableBucketPage::new(vec![(1, 1), (1, 2), (1, 3), (3, 4)]);
 
     assert_eq!(*bucket.entry_at(3).unwrap(), (3, 4));
     assert!(bucket.entry_at(4).is_none());
@@ -89,9 +100,44 @@ fn entry_key_value_at() {
 
 #[test]
 fn serialization() {
-    let bucket = HashTableBucketPage::new(vec![(1, 1), (1, 2), (1, 3), (3, 4)], 4, 4);
+    let bucket = HashTableBucketPage::new(vec![(1, 1), (1, 2), (1, 3), (3, 4)]);
     let bucked_deserialized = HashTableBucketPage::<i32, i32>::deserialize(&bucket.serialize());
 
-    assert_eq!(bucket.max_size, bucked_deserialized.max_size);
+    assert_eq!(bucket.used_space, bucked_deserialized.used_space);
+    assert_eq!(bucket.data, bucked_deserialized.data);
+}
+
+#[test]
+fn variable_length_entries() {
+    let key = |len: usize| vec![len as u8; len];
+    let mut bucket = HashTableBucketPage::<Vec<u8>, u32>::new_empty();
+
+    // short keys only take the space they need
+    for len in 1..=50 {
+        bucket.insert(key(len), len as u32).unwrap();
+    }
+    assert_eq!(
+        bucket.free_space(),
+        4092 - 50 * (4 + 4) - (1..=50).sum::<usize>()
+    );
+    assert_eq!(bucket.lookup(key(7)), vec![&7]);
+
+    let bucked_deserialized = HashTableBucketPage::<Vec<u8>, u32>::deserialize(&bucket.serialize());
     assert_eq!(bucket.data, bucked_deserialized.data);
+    assert_eq!(bucket.free_space(), bucked_deserialized.free_space());
+
+    // a key that doesn't fit in the free space is rejected, removing entries makes space for it
+    let long_key = vec![0; 2900];
+    assert!(!bucket.fits(&long_key, &0));
+    assert!(bucket.insert(long_key.clone(), 0).is_err());
+
+    for len in 1..=40 {
+        assert_eq!(bucket.remove(key(len)), 1);
+    }
+    bucket.insert(long_key.clone(), 0).unwrap();
+
+    let bucked_deserialized = HashTableBucketPage::<Vec<u8>, u32>::deserialize(&bucket.serialize());
+    assert_eq!(bucked_deserialized.lookup(long_key), vec![&0]);
+    assert_eq!(bucked_deserialized.lookup(key(41)), vec![&41]);
+    assert_eq!(bucked_deserialized.size(), 11);
 }
@@ -0,0 +1,428 @@
+use std::{collections::VecDeque, ops::Bound, sync::Arc};
+
+use crate::{
+    disk::{
+        buffer_pool_manager::{BufferPoolManager, DiskRead, DiskWrite, PageReadGuard},
+        disk_manager::PageID,
+    },
+    table::{
+        schema::Schema,
+        tuple::{Tuple, RID},
+    },
+};
+
+use super::{
+    b_plus_tree_page::{BPlusTreeInternalPage, BPlusTreeLeafPage, BPlusTreePage},
+    get_four_bytes_group,
+    serial::{Deserialize, Serialize},
+    ScanDirection,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// B+ tree index whose nodes are pages of the buffer pool manager. Keys are tuples ordered by the key schema (see
+/// `Tuple::compare`) and values are RIDs. Duplicate keys are supported, since entries are ordered by `(key, RID)`.
+/// Leaves are linked from left to right, so entries can be read in order without going through the internal pages.
+///
+/// The header page of the tree only stores the page ID of the root: `| root_page_id (0-3) |`. Its latch is used as
+/// a latch for the whole tree: writers hold it exclusively and readers hold it shared for the whole operation.
+///
+/// # Note
+/// Removing entries never merges or redistributes pages, so pages can become sparse (or even empty) after many
+/// deletes. The tree stays correct, since separators only have to bound the entries of their children.
+pub struct DiskBPlusTree {
+    bpm: Arc<BufferPoolManager>,
+    header_page_id: PageID,
+    key_schema: Schema,
+    key_size: u32,
+    name: String,
+}
+
+impl DiskBPlusTree {
+    /// Creates an empty tree, made of the header page and an empty root leaf.
+    ///
+    /// # Panics
+    /// Will panic if the keys of `key_schema` are too large to fit at least 3 entries in a page.
+    pub fn new(bpm: Arc<BufferPoolManager>, key_schema: Schema, name: String) -> Self {
+        let key_size = Self::key_size_for(&key_schema);
+        assert!(
+            BPlusTreeInternalPage::max_size_for(key_size) >= 3,
+            "Keys are too large for a B+ tree"
+        );
+
+        let header_page_id = bpm.new_page();
+        let root_page_id = bpm.new_page();
+
+        let mut root_page = bpm.get_write_page(root_page_id);
+        root_page.write(BPlusTreePage::Leaf(BPlusTreeLeafPage::new_empty(key_size)).serialize());
+        drop(root_page);
+
+        let mut header_page = bpm.get_write_page(header_page_id);
+        header_page.data_mut()[0..4].copy_from_slice(&root_page_id.serialize());
+        drop(header_page);
+
+        Self {
+            bpm,
+            header_page_id,
+            key_schema,
+            key_size,
+            name,
+        }
+    }
+
+    pub fn from_disk(
+        bpm: Arc<BufferPoolManager>,
+        header_pid: PageID,
+        key_schema: Schema,
+        name: String,
+    ) -> Self {
+        Self {
+            bpm,
+            header_page_id: header_pid,
+            key_size: Self::key_size_for(&key_schema),
+            key_schema,
+            name,
+        }
+    }
+
+    /// Returns the size of a serialized key. Keys always have the same size, since all column types have a fixed
+    /// length.
+    fn key_size_for(key_schema: &Schema) -> u32 {
+        // values + null bitmap + length prefix of the serialized tuple
+        (key_schema.get_tuple_len() + key_schema.get_null_bitmap_len() + 4) as u32
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn key_schema(&self) -> &Schema {
+        &self.key_schema
+    }
+
+    pub fn header_page_id(&self) -> PageID {
+        self.header_page_id
+    }
+
+    fn read_page(&self, page_id: PageID) -> BPlusTreePage {
+        let page = self.bpm.get_read_page(page_id);
+        BPlusTreePage::deserialize(page.read())
+    }
+
+    fn write_page(&self, page_id: PageID, page: BPlusTreePage) {
+        let mut guard = self.bpm.get_write_page(page_id);
+        guard.write(page.serialize());
+    }
+
+    /// Descends from the root to a leaf, choosing the child of each internal page with `choose_child`. Returns the
+    /// internal pages on the path, the page ID of the leaf and the leaf.
+    fn find_leaf<F>(
+        &self,
+        root_page_id: PageID,
+        choose_child: F,
+    ) -> (Vec<PageID>, PageID, BPlusTreeLeafPage)
+    where
+        F: Fn(&BPlusTreeInternalPage) -> PageID,
+    {
+        let mut path = vec![];
+        let mut page_id = root_page_id;
+        loop {
+            match self.read_page(page_id) {
+                BPlusTreePage::Internal(internal) => {
+                    path.push(page_id);
+                    page_id = choose_child(&internal);
+                }
+                BPlusTreePage::Leaf(leaf) => return (path, page_id, leaf),
+            }
+        }
+    }
+
+    fn read_root_page_id(header_page: &impl DiskRead) -> PageID {
+        u32::from_be_bytes(get_four_bytes_group(header_page.read(), 0))
+    }
+
+    fn read_leaf(&self, page_id: PageID) -> BPlusTreeLeafPage {
+        match self.read_page(page_id) {
+            BPlusTreePage::Leaf(leaf) => leaf,
+            BPlusTreePage::Internal(_) => panic!("B+ tree leaves should only link to leaves"),
+        }
+    }
+
+    /// Inserts the `key`-`rid` pair. Inserting an entry that is already in the tree does nothing.
+    pub fn insert(&self, key: Tuple, rid: RID) {
+        let mut header_page = self.bpm.get_write_page(self.header_page_id);
+        let root_page_id = Self::read_root_page_id(&header_page);
+
+        let (mut path, page_id, mut leaf) = self.find_leaf(root_page_id, |internal| {
+            internal.child_for_entry(&key, &rid, &self.key_schema)
+        });
+
+        if !leaf.insert(key, rid, &self.key_schema) || !leaf.is_overflowing() {
+            self.write_page(page_id, BPlusTreePage::Leaf(leaf));
+            return;
+        }
+
+        // split the leaf and link the new page after it
+        let mut right = leaf.split();
+        let right_page_id = self.bpm.new_page();
+        right.set_next_page_id(leaf.next_page_id());
+        right.set_prev_page_id(Some(page_id));
+        leaf.set_next_page_id(Some(right_page_id));
+
+        if let Some(next_page_id) = right.next_page_id() {
+            let mut next = self.read_leaf(next_page_id);
+            next.set_prev_page_id(Some(right_page_id));
+            self.write_page(next_page_id, BPlusTreePage::Leaf(next));
+        }
+
+        let (separator_key, separator_rid) = right.entries()[0].clone();
+        self.write_page(page_id, BPlusTreePage::Leaf(leaf));
+        self.write_page(right_page_id, BPlusTreePage::Leaf(right));
+
+        // push the separators up, splitting the full internal pages
+        let mut separator = (separator_key, separator_rid, right_page_id);
+        let mut left_page_id = page_id;
+        while let Some(parent_page_id) = path.pop() {
+            let mut parent = match self.read_page(parent_page_id) {
+                BPlusTreePage::Internal(internal) => internal,
+                BPlusTreePage::Leaf(_) => panic!("B+ tree path should only contain internal pages"),
+            };
+
+            let (key, rid, child) = separator;
+            parent.insert(key, rid, child, &self.key_schema);
+            if !parent.is_overflowing() {
+                self.write_page(parent_page_id, BPlusTreePage::Internal(parent));
+                return;
+            }
+
+            let ((middle_key, middle_rid), right) = parent.split();
+            let right_page_id = self.bpm.new_page();
+            self.write_page(parent_page_id, BPlusTreePage::Internal(parent));
+            self.write_page(right_page_id, BPlusTreePage::Internal(right));
+
+            separator = (middle_key, middle_rid, right_page_id);
+            left_page_id = parent_page_id;
+        }
+
+        // the root was split, so the tree grows by one level
+        let (key, rid, child) = separator;
+        let new_root =
+            BPlusTreeInternalPage::new(left_page_id, vec![(key, rid, child)], self.key_size);
+        let new_root_page_id = self.bpm.new_page();
+        self.write_page(new_root_page_id, BPlusTreePage::Internal(new_root));
+
+        header_page.data_mut()[0..4].copy_from_slice(&new_root_page_id.serialize());
+    }
+
+    /// Returns the values associated with `key`, ordered by RID.
+    pub fn lookup(&self, key: &Tuple) -> Vec<RID> {
+        let header_page = self.bpm.get_read_page(self.header_page_id);
+        let root_page_id = Self::read_root_page_id(&header_page);
+
+        let (_, _, mut leaf) =
+            self.find_leaf(root_page_id, |internal| internal.child_for_key(key, &self.key_schema));
+
+        // entries with the same key can span multiple leaves
+        let mut rids = vec![];
+        loop {
+            let start = leaf.lower_bound(key, &self.key_schema);
+            let entries = &leaf.entries()[start..];
+            let matching = entries
+                .iter()
+                .take_while(|(k, _)| k.compare(key, &self.key_schema).is_eq())
+                .map(|(_, rid)| rid.clone())
+                .collect::<Vec<_>>();
+
+            let reached_end = matching.len() == entries.len();
+            rids.extend(matching);
+
+            match leaf.next_page_id() {
+                Some(next_page_id) if reached_end => leaf = self.read_leaf(next_page_id),
+                _ => break,
+            }
+        }
+
+        rids
+    }
+
+    /// Removes the `key`-`rid` pair. Returns `false` if the entry wasn't in the tree.
+    pub fn remove(&self, key: &Tuple, rid: &RID) -> bool {
+        let header_page = self.bpm.get_write_page(self.header_page_id);
+        let root_page_id = Self::read_root_page_id(&header_page);
+
+        let (_, page_id, mut leaf) = self.find_leaf(root_page_id, |internal| {
+            internal.child_for_entry(key, rid, &self.key_schema)
+        });
+
+        let removed = leaf.remove(key, rid, &self.key_schema);
+        if removed {
+            self.write_page(page_id, BPlusTreePage::Leaf(leaf));
+        }
+
+        removed
+    }
+
+    /// Removes all entries associated with `key`. Returns the amount of entries that were removed.
+    pub fn remove_key(&self, key: &Tuple) -> usize {
+        let header_page = self.bpm.get_write_page(self.header_page_id);
+        let root_page_id = Self::read_root_page_id(&header_page);
+
+        let (_, mut page_id, mut leaf) =
+            self.find_leaf(root_page_id, |internal| internal.child_for_key(key, &self.key_schema));
+
+        let mut removed_count = 0;
+        loop {
+            let start = leaf.lower_bound(key, &self.key_schema);
+            let count = leaf.entries()[start..]
+                .iter()
+                .take_while(|(k, _)| k.compare(key, &self.key_schema).is_eq())
+                .count();
+            let reached_end = start + count == leaf.size();
+            let next_page_id = leaf.next_page_id();
+
+            if count > 0 {
+                leaf.entries_mut().drain(start..start + count);
+                self.write_page(page_id, BPlusTreePage::Leaf(leaf));
+                removed_count += count;
+            }
+
+            match next_page_id {
+                Some(next_page_id) if reached_end => {
+                    page_id = next_page_id;
+                    leaf = self.read_leaf(next_page_id);
+                }
+                _ => break,
+            }
+        }
+
+        removed_count
+    }
+
+    /// Returns an iterator over the entries with keys between `lower` and `upper`, ordered by `(key, RID)` for
+    /// `ScanDirection::Forward` and in the reverse order for `ScanDirection::Backward`.
+    ///
+    /// # Note
+    /// The iterator holds a shared latch on the whole tree until it is dropped, so writing to the tree from the same
+    /// thread while iterating will deadlock.
+    pub fn range(
+        &self,
+        lower: Bound<Tuple>,
+        upper: Bound<Tuple>,
+        direction: ScanDirection,
+    ) -> BPlusTreeRange<'_> {
+        let header_page = self.bpm.get_read_page(self.header_page_id);
+        let root_page_id = Self::read_root_page_id(&header_page);
+
+        let (_, _, leaf) = match direction {
+            ScanDirection::Forward => self.find_leaf(root_page_id, |internal| match &lower {
+                Bound::Included(key) | Bound::Excluded(key) => {
+                    internal.child_for_key(key, &self.key_schema)
+                }
+                Bound::Unbounded => internal.child_at(0),
+            }),
+            ScanDirection::Backward => self.find_leaf(root_page_id, |internal| match &upper {
+                Bound::Included(key) | Bound::Excluded(key) => {
+                    internal.last_child_for_key(key, &self.key_schema)
+                }
+                Bound::Unbounded => internal.child_at(internal.size()),
+            }),
+        };
+
+        let next_leaf = match direction {
+            ScanDirection::Forward => leaf.next_page_id(),
+            ScanDirection::Backward => leaf.prev_page_id(),
+        };
+
+        BPlusTreeRange {
+            tree: self,
+            _header_page: header_page,
+            lower,
+            upper,
+            direction,
+            entries: leaf.into_entries().into(),
+            next_leaf,
+            finished: false,
+        }
+    }
+
+    /// Returns an iterator over all the entries of the tree, ordered by `(key, RID)`.
+    pub fn iter(&self) -> BPlusTreeRange<'_> {
+        self.range(Bound::Unbounded, Bound::Unbounded, ScanDirection::Forward)
+    }
+}
+
+/// Iterator over a range of entries of a `DiskBPlusTree`, reading one leaf at a time through the leaf links.
+pub struct BPlusTreeRange<'a> {
+    tree: &'a DiskBPlusTree,
+    /// Shared latch on the tree, so that the leaves can't be split while iterating
+    _header_page: PageReadGuard<'a>,
+    lower: Bound<Tuple>,
+    upper: Bound<Tuple>,
+    direction: ScanDirection,
+    /// Entries of the current leaf that weren't returned yet
+    entries: VecDeque<(Tuple, RID)>,
+    next_leaf: Option<PageID>,
+    finished: bool,
+}
+
+impl<'a> BPlusTreeRange<'a> {
+    fn is_below_lower(&self, key: &Tuple) -> bool {
+        match &self.lower {
+            Bound::Included(lower) => key.compare(lower, &self.tree.key_schema).is_lt(),
+            Bound::Excluded(lower) => key.compare(lower, &self.tree.key_schema).is_le(),
+            Bound::Unbounded => false,
+        }
+    }
+
+    fn is_above_upper(&self, key: &Tuple) -> bool {
+        match &self.upper {
+            Bound::Included(upper) => key.compare(upper, &self.tree.key_schema).is_gt(),
+            Bound::Excluded(upper) => key.compare(upper, &self.tree.key_schema).is_ge(),
+            Bound::Unbounded => false,
+        }
+    }
+}
+
+impl<'a> Iterator for BPlusTreeRange<'a> {
+    type Item = (Tuple, RID);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let entry = match self.direction {
+                ScanDirection::Forward => self.entries.pop_front(),
+                ScanDirection::Backward => self.entries.pop_back(),
+            };
+
+            let (key, rid) = match entry {
+                Some(entry) => entry,
+                None => {
+                    // move to the next leaf in the scan direction
+                    let Some(page_id) = self.next_leaf else {
+                        self.finished = true;
+                        break;
+                    };
+                    let leaf = self.tree.read_leaf(page_id);
+                    self.next_leaf = match self.direction {
+                        ScanDirection::Forward => leaf.next_page_id(),
+                        ScanDirection::Backward => leaf.prev_page_id(),
+                    };
+                    self.entries = leaf.into_entries().into();
+                    continue;
+                }
+            };
+
+            let (before_start, after_end) = match self.direction {
+                ScanDirection::Forward => (self.is_below_lower(&key), self.is_above_upper(&key)),
+                ScanDirection::Backward => (self.is_above_upper(&key), self.is_below_lower(&key)),
+            };
+            if after_end {
+                self.finished = true;
+            } else if !before_start {
+                return Some((key, rid));
+            }
+        }
+
+        None
+    }
+}
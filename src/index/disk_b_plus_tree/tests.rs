@@ -0,0 +1,294 @@
+use std::{env::temp_dir, fs::remove_file, ops::Bound, sync::Arc, thread};
+
+use crate::{
+    disk::buffer_pool_manager::BufferPoolManager,
+    index::ScanDirection,
+    table::{
+        schema::{ColumnType, Schema},
+        tuple::{Tuple, RID},
+        value::ColumnValue,
+    },
+    test_utils::int_value,
+};
+
+use super::DiskBPlusTree;
+
+fn get_tree(db_file_path: String) -> DiskBPlusTree {
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+    DiskBPlusTree::new(
+        bpm,
+        Schema::with_types(vec![ColumnType::Integer]),
+        String::from("index"),
+    )
+}
+
+fn key(value: i32) -> Tuple {
+    Tuple::new(
+        vec![int_value(value)],
+        &Schema::with_types(vec![ColumnType::Integer]),
+    )
+}
+
+#[test]
+fn simple_insert() {
+    // init
+    let db_path = temp_dir().join("bpt_simple_insert.db");
+    let tree = get_tree(db_path.to_str().unwrap().to_string());
+
+    // test
+    tree.insert(key(3), RID::new(3, 0));
+    tree.insert(key(1), RID::new(1, 0));
+    tree.insert(key(2), RID::new(2, 0));
+    tree.insert(key(1), RID::new(1, 1));
+    tree.insert(key(1), RID::new(1, 1)); // exact duplicate is ignored
+
+    assert_eq!(tree.lookup(&key(1)), vec![RID::new(1, 0), RID::new(1, 1)]);
+    assert_eq!(tree.lookup(&key(2)), vec![RID::new(2, 0)]);
+    assert_eq!(tree.lookup(&key(3)), vec![RID::new(3, 0)]);
+    assert_eq!(tree.lookup(&key(4)), vec![]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn insert_with_splits() {
+    // init
+    let db_path = temp_dir().join("bpt_insert_with_splits.db");
+    let tree = get_tree(db_path.to_str().unwrap().to_string());
+
+    // test (enough entries for splitting internal pages too: 239 entries per leaf, 194 separators per internal page)
+    let count = 50_000;
+    for i in 0..count {
+        // insert in an order that is neither ascending nor descending
+        let value = (i * 7919) % count;
+        tree.insert(key(value), RID::new(value as u32, 0));
+    }
+
+    for i in 0..count {
+        assert_eq!(tree.lookup(&key(i)), vec![RID::new(i as u32, 0)]);
+    }
+    assert_eq!(tree.lookup(&key(count)), vec![]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn duplicate_keys_across_leaves() {
+    // init
+    let db_path = temp_dir().join("bpt_duplicate_keys_across_leaves.db");
+    let tree = get_tree(db_path.to_str().unwrap().to_string());
+
+    // test
+    for i in 0..1000 {
+        tree.insert(key(i % 3), RID::new(i as u32, 0));
+    }
+
+    let rids = tree.lookup(&key(1));
+    assert_eq!(rids.len(), 333);
+    assert!(rids.windows(2).all(|w| w[0].get() < w[1].get()));
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn null_keys() {
+    // init
+    let db_path = temp_dir().join("bpt_null_keys.db");
+    let tree = get_tree(db_path.to_str().unwrap().to_string());
+    let null_key = Tuple::new(
+        vec![ColumnValue::Null(ColumnType::Integer)],
+        &Schema::with_types(vec![ColumnType::Integer]),
+    );
+
+    // test
+    tree.insert(key(0), RID::new(1, 0));
+    tree.insert(null_key.clone(), RID::new(2, 0));
+
+    assert_eq!(tree.lookup(&null_key), vec![RID::new(2, 0)]);
+    assert_eq!(tree.lookup(&key(0)), vec![RID::new(1, 0)]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn remove() {
+    // init
+    let db_path = temp_dir().join("bpt_remove.db");
+    let tree = get_tree(db_path.to_str().unwrap().to_string());
+
+    for i in 0..2000 {
+        tree.insert(key(i % 100), RID::new(i as u32, 0));
+    }
+
+    // test
+    assert!(tree.remove(&key(5), &RID::new(105, 0)));
+    assert!(!tree.remove(&key(5), &RID::new(105, 0)));
+    assert!(!tree.remove(&key(5), &RID::new(106, 0)));
+    assert_eq!(tree.lookup(&key(5)).len(), 19);
+
+    assert_eq!(tree.remove_key(&key(7)), 20);
+    assert_eq!(tree.remove_key(&key(7)), 0);
+    assert_eq!(tree.lookup(&key(7)), vec![]);
+    assert_eq!(tree.lookup(&key(8)).len(), 20);
+
+    // removing everything leaves empty pages, which must not break lookups or inserts
+    for i in 0..100 {
+        tree.remove_key(&key(i));
+    }
+    assert_eq!(tree.lookup(&key(8)), vec![]);
+
+    tree.insert(key(8), RID::new(1, 1));
+    assert_eq!(tree.lookup(&key(8)), vec![RID::new(1, 1)]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn from_disk() {
+    // init
+    let db_path = temp_dir().join("bpt_from_disk.db");
+    let tree = get_tree(db_path.to_str().unwrap().to_string());
+
+    for i in 0..1000 {
+        tree.insert(key(i), RID::new(i as u32, 0));
+    }
+
+    // test
+    let reopened = DiskBPlusTree::from_disk(
+        Arc::clone(&tree.bpm),
+        tree.header_page_id(),
+        tree.key_schema().clone(),
+        tree.name().to_string(),
+    );
+    assert_eq!(reopened.lookup(&key(999)), vec![RID::new(999, 0)]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn concurrent_inserts() {
+    // init
+    let db_path = temp_dir().join("bpt_concurrent_inserts.db");
+    let tree = Arc::new(get_tree(db_path.to_str().unwrap().to_string()));
+
+    // test
+    let handles = (0..4)
+        .map(|t| {
+            let tree = Arc::clone(&tree);
+            thread::spawn(move || {
+                for i in 0..2000 {
+                    let value = i * 4 + t;
+                    tree.insert(key(value), RID::new(value as u32, 0));
+                    assert_eq!(tree.lookup(&key(value)), vec![RID::new(value as u32, 0)]);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    for i in 0..8000 {
+        assert_eq!(tree.lookup(&key(i)), vec![RID::new(i as u32, 0)]);
+    }
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+fn values(entries: impl Iterator<Item = (Tuple, RID)>) -> Vec<u32> {
+    entries.map(|(_, rid)| rid.page_id).collect()
+}
+
+#[test]
+fn range() {
+    // init
+    let db_path = temp_dir().join("bpt_range.db");
+    let tree = get_tree(db_path.to_str().unwrap().to_string());
+
+    for i in (0..2000).rev() {
+        tree.insert(key(i), RID::new(i as u32, 0));
+    }
+
+    // test
+    let forward = |lower, upper| values(tree.range(lower, upper, ScanDirection::Forward));
+    let backward = |lower, upper| values(tree.range(lower, upper, ScanDirection::Backward));
+
+    assert_eq!(values(tree.iter()), (0..2000).collect::<Vec<_>>());
+    assert_eq!(
+        forward(Bound::Included(key(500)), Bound::Included(key(1500))),
+        (500..=1500).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        forward(Bound::Excluded(key(500)), Bound::Excluded(key(1500))),
+        (501..1500).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        forward(Bound::Unbounded, Bound::Excluded(key(3))),
+        vec![0, 1, 2]
+    );
+    assert_eq!(
+        forward(Bound::Excluded(key(1996)), Bound::Unbounded),
+        vec![1997, 1998, 1999]
+    );
+    assert_eq!(forward(Bound::Included(key(5000)), Bound::Unbounded), vec![]);
+    assert_eq!(forward(Bound::Included(key(7)), Bound::Excluded(key(7))), vec![]);
+
+    assert_eq!(
+        backward(Bound::Unbounded, Bound::Unbounded),
+        (0..2000).rev().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        backward(Bound::Included(key(500)), Bound::Included(key(1500))),
+        (500..=1500).rev().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        backward(Bound::Excluded(key(1996)), Bound::Unbounded),
+        vec![1999, 1998, 1997]
+    );
+    assert_eq!(
+        backward(Bound::Unbounded, Bound::Excluded(key(3))),
+        vec![2, 1, 0]
+    );
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn range_duplicate_keys() {
+    // init
+    let db_path = temp_dir().join("bpt_range_duplicate_keys.db");
+    let tree = get_tree(db_path.to_str().unwrap().to_string());
+
+    for i in 0..1000 {
+        tree.insert(key(i % 4), RID::new(i as u32, 0));
+    }
+
+    // test
+    let forward = values(tree.range(
+        Bound::Included(key(1)),
+        Bound::Included(key(2)),
+        ScanDirection::Forward,
+    ));
+    let mut expected = (0..1000).filter(|i| i % 4 == 1).collect::<Vec<_>>();
+    expected.extend((0..1000).filter(|i| i % 4 == 2));
+    assert_eq!(forward, expected);
+
+    let backward = values(tree.range(
+        Bound::Included(key(1)),
+        Bound::Included(key(2)),
+        ScanDirection::Backward,
+    ));
+    expected.reverse();
+    assert_eq!(backward, expected);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
@@ -1,4 +1,4 @@
-use std::{io::Cursor, marker::PhantomData, sync::Arc};
+use std::{collections::HashSet, io::Cursor, marker::PhantomData, sync::Arc};
 
 use ascii_tree::{write_tree, Tree};
 use murmur3::murmur3_32;
@@ -80,12 +80,12 @@ where
     ///
     /// # Errors
     /// Will return `Err` if it's not possible to insert. This can happen if the directory page is already at its maximum capacity
-    /// and all buckets are full.
-    /// 
-    /// # Note
-    /// The `key_size` and `value_size` are used to determine the size of the key and value in the bucket page. These
-    /// were added as a quick fix to the serialization/deserialization of the bucket page.
-    pub fn insert(&self, key: K, value: V, key_size: u32, value_size: u32) -> Result<(), ()> {
+    /// and all buckets are full, or if the pair doesn't fit in an empty bucket page.
+    pub fn insert(&self, key: K, value: V) -> Result<(), ()> {
+        if !HashTableBucketPage::<K, V>::new_empty().fits(&key, &value) {
+            return Err(());
+        }
+
         let hash = self.hash(&key);
 
         // get directory page ID from header if exists, if not create empty one
@@ -95,7 +95,7 @@ where
         let d_pid = match header.get_directory_page_id(d_index) {
             Some(pid) => pid,
             None => {
-                let empty_dir_pid = self.new_empty_directory(key_size, value_size);
+                let empty_dir_pid = self.new_empty_directory();
 
                 header
                     .set_directory_page_id(d_index, empty_dir_pid)
@@ -117,7 +117,7 @@ where
         let mut b_page = self.bpm.get_write_page(b_pid);
         let mut bucket = HashTableBucketPage::<K, V>::deserialize(b_page.read());
 
-        if !bucket.is_full() {
+        if bucket.fits(&key, &value) {
             bucket.insert(key, value).unwrap();
             b_page.write(bucket.serialize());
 
@@ -135,7 +135,7 @@ where
         }
 
         // split the bucket
-        let mut split_image_bucket = HashTableBucketPage::<K, V>::new_empty(key_size, value_size);
+        let mut split_image_bucket = HashTableBucketPage::<K, V>::new_empty();
 
         // increase local depth or buckets
         let old_local_depth = directory.increment_local_depth(b_index).unwrap();
@@ -175,11 +175,18 @@ where
         d_page.write(directory.serialize()); // directory no longer needed
 
         assert!(insert_bucket_pid == b_pid || insert_bucket_pid == split_image_bucket_pid);
-        if insert_bucket_pid == b_pid {
-            bucket.insert(key, value).unwrap();
+        let insert_bucket = if insert_bucket_pid == b_pid {
+            &mut bucket
         } else {
-            split_image_bucket.insert(key, value).unwrap();
-        }
+            &mut split_image_bucket
+        };
+        let retry_entry = if insert_bucket.fits(&key, &value) {
+            insert_bucket.insert(key, value).unwrap();
+            None
+        } else {
+            // the entries mostly hash to the same bucket, which has to be split again
+            Some((key, value))
+        };
 
         // write all bucket and directory data
         b_page.write(bucket.serialize());
@@ -190,13 +197,16 @@ where
         drop(split_image_bucket_page);
         drop(d_page); // only safe to release latch on directory after split bucket data is done writing
 
-        Ok(())
+        match retry_entry {
+            Some((key, value)) => self.insert(key, value),
+            None => Ok(()),
+        }
     }
 
-    fn new_empty_directory(&self, key_size: u32, value_size: u32) -> PageID {
+    fn new_empty_directory(&self) -> PageID {
         // create bucket page
         let empty_bucket_pid = self.bpm.new_page();
-        let bucket = HashTableBucketPage::<K, V>::new_empty(key_size, value_size);
+        let bucket = HashTableBucketPage::<K, V>::new_empty();
 
         let mut empty_bucket_page = self.bpm.get_write_page(empty_bucket_pid);
         empty_bucket_page.write(bucket.serialize());
@@ -216,6 +226,14 @@ where
 
     /// Get values associated with `key`.
     pub fn lookup(&self, key: K) -> Vec<V> {
+        self.lookup_entries(key)
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    /// Get the key-value pairs stored for `key`, with the keys as they are stored in the table.
+    pub fn lookup_entries(&self, key: K) -> Vec<(K, V)> {
         let hash = self.hash(&key);
 
         let h_page = self.bpm.get_read_page(self.header_page_id);
@@ -237,15 +255,45 @@ where
         let bucket = HashTableBucketPage::<K, V>::deserialize(b_page.read());
 
         bucket
-            .lookup(key)
-            .iter()
-            .map(|v| (*v).clone())
+            .lookup_entries(&key)
+            .into_iter()
+            .cloned()
             .collect::<Vec<_>>()
     }
 
+    /// Returns all the key-value pairs stored in the table, bucket by bucket.
+    pub fn entries(&self) -> Vec<(K, V)> {
+        let mut entries = vec![];
+        self.for_each_bucket(|_, _, _, bucket| {
+            entries.extend((0..bucket.size()).filter_map(|i| bucket.entry_at(i).cloned()));
+        });
+
+        entries
+    }
+
     /// Remove entries associated with `key` from the table. Returns the amount of entries that were removed.
     pub fn remove(&self, key: K) -> usize {
-        let hash = self.hash(&key);
+        self.remove_from_bucket(&key, |bucket| bucket.remove(key.clone()))
+    }
+
+    /// Removes a single `key`-`value` pair from the table, keeping the other values associated with `key`. Returns
+    /// `true` if the pair was in the table.
+    pub fn remove_entry(&self, key: K, value: &V) -> bool
+    where
+        V: PartialEq,
+    {
+        self.remove_from_bucket(&key, |bucket| {
+            usize::from(bucket.remove_entry(key.clone(), value))
+        }) == 1
+    }
+
+    /// Removes entries from the bucket of `key` with `remove`, merging the bucket if it becomes empty. Returns the
+    /// amount of entries that were removed.
+    fn remove_from_bucket<F>(&self, key: &K, remove: F) -> usize
+    where
+        F: FnOnce(&mut HashTableBucketPage<K, V>) -> usize,
+    {
+        let hash = self.hash(key);
 
         let h_page = self.bpm.get_read_page(self.header_page_id);
         let header = HashTableHeaderPage::deserialize(h_page.read());
@@ -265,7 +313,7 @@ where
         let mut bucket = HashTableBucketPage::<K, V>::deserialize(b_page.read());
 
         // remove entries
-        let removed_count = bucket.remove(key);
+        let removed_count = remove(&mut bucket);
         b_page.write(bucket.serialize());
         drop(b_page);
 
@@ -329,6 +377,99 @@ where
         removed_count
     }
 
+    /// Checks the invariants of the pages of the table and returns a description of each violation:
+    /// - the depths of every directory are coherent (see `HashTableDirectoryPage::is_valid`) and under its max depth
+    /// - every entry is stored in the directory and the bucket its key hashes to
+    pub fn check_integrity(&self) -> Vec<String> {
+        let mut problems = vec![];
+        let mut invalid_directories = HashSet::new();
+        let h_page = self.bpm.get_read_page(self.header_page_id);
+        let header = HashTableHeaderPage::deserialize(h_page.read());
+        drop(h_page);
+
+        for d_index in 0..header.max_size() {
+            let Some(d_pid) = header.get_directory_page_id(d_index) else {
+                continue;
+            };
+            let d_page = self.bpm.get_read_page(d_pid);
+            let directory = HashTableDirectoryPage::deserialize(d_page.read());
+            drop(d_page);
+
+            if directory.global_depth() > directory.max_depth() {
+                problems.push(format!(
+                    "directory page {d_pid}: global depth {} is greater than the max depth {}",
+                    directory.global_depth(),
+                    directory.max_depth()
+                ));
+                invalid_directories.insert(d_index);
+            } else if !directory.is_valid() {
+                problems.push(format!(
+                    "directory page {d_pid}: local depths are incoherent with the global depth {}",
+                    directory.global_depth()
+                ));
+                invalid_directories.insert(d_index);
+            }
+        }
+
+        // the buckets of invalid directories can't be found from the hashes, their entries aren't checked
+        self.for_each_bucket(|d_index, directory, b_pid, bucket| {
+            if invalid_directories.contains(&d_index) {
+                return;
+            }
+
+            for i in 0..bucket.size() {
+                let (key, _) = bucket.entry_at(i).unwrap();
+                let hash = self.hash(key);
+                let expected_pid = directory.get_bucket_page_id(directory.hash_to_bucket_index(hash));
+                if header.hash_to_directory_page_index(hash) != d_index
+                    || expected_pid != Some(b_pid)
+                {
+                    problems.push(format!(
+                        "bucket page {b_pid}: entry {i} is stored in a bucket its key doesn't hash to"
+                    ));
+                }
+            }
+        });
+
+        problems
+    }
+
+    /// Calls `f` with the header index of the directory, the directory, the page ID of the bucket and the bucket, for
+    /// every bucket of the table (once, even if several directory slots point to it).
+    fn for_each_bucket<F>(&self, mut f: F)
+    where
+        F: FnMut(usize, &HashTableDirectoryPage, PageID, &HashTableBucketPage<K, V>),
+    {
+        let h_page = self.bpm.get_read_page(self.header_page_id);
+        let header = HashTableHeaderPage::deserialize(h_page.read());
+        drop(h_page);
+
+        for d_index in 0..header.max_size() {
+            let Some(d_pid) = header.get_directory_page_id(d_index) else {
+                continue;
+            };
+            let d_page = self.bpm.get_read_page(d_pid);
+            let directory = HashTableDirectoryPage::deserialize(d_page.read());
+            drop(d_page);
+
+            let mut visited = HashSet::new();
+            for b_index in 0..directory.size().min(directory.max_size()) {
+                let Some(b_pid) = directory.get_bucket_page_id(b_index) else {
+                    continue;
+                };
+                if !visited.insert(b_pid) {
+                    continue;
+                }
+
+                let b_page = self.bpm.get_read_page(b_pid);
+                let bucket = HashTableBucketPage::<K, V>::deserialize(b_page.read());
+                drop(b_page);
+
+                f(d_index, &directory, b_pid, &bucket);
+            }
+        }
+    }
+
     /// Returns 32-bit hashed value of `key`.
     fn hash(&self, key: &K) -> u32 {
         murmur3_32(&mut Cursor::new(key.serialize()), 0).expect("Hashing error")
@@ -356,12 +497,12 @@ where
                     drop(b_page);
 
                     buckets_nodes.push(Tree::Leaf(vec![format!(
-                        "[{}] pid: {} | d: {} (sz: {}/{})",
+                        "[{}] pid: {} | d: {} (sz: {}, free: {})",
                         to_binary(j as u32, directory.global_depth()),
                         b_pid,
                         directory.get_local_depth(j).unwrap(),
                         bucket.size(),
-                        bucket.max_size()
+                        bucket.free_space()
                     )
                     .to_string()]));
                 }
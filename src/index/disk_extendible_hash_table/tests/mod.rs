@@ -1,10 +1,12 @@
 use std::{env::temp_dir, fs::remove_file, sync::Arc};
 
 use crate::{
-    disk::buffer_pool_manager::{BufferPoolManager, DiskRead},
+    disk::buffer_pool_manager::{BufferPoolManager, DiskRead, DiskWrite},
     index::{
-        directory_page::HashTableDirectoryPage, header_page::HashTableHeaderPage,
-        serial::Deserialize,
+        bucket_page::HashTableBucketPage,
+        directory_page::HashTableDirectoryPage,
+        header_page::HashTableHeaderPage,
+        serial::{Deserialize, Serialize},
     },
 };
 
@@ -22,10 +24,10 @@ fn simple_insert() {
         DiskExtendibleHashTable::<i32, i32>::new(Arc::clone(&bpm), 0, 0, String::from("index"));
 
     // simple inserts
-    ht.insert(0, 1, 4, 4).unwrap();
-    ht.insert(1, 2, 4, 4).unwrap();
-    ht.insert(1, 9, 4, 4).unwrap();
-    ht.insert(3, 4, 4, 4).unwrap();
+    ht.insert(0, 1).unwrap();
+    ht.insert(1, 2).unwrap();
+    ht.insert(1, 9).unwrap();
+    ht.insert(3, 4).unwrap();
 
     assert_eq!(ht.lookup(0), vec![1]);
     assert_eq!(ht.lookup(1), vec![2, 9]);
@@ -45,11 +47,11 @@ fn bucket_single_split_insert_overflow() {
         DiskExtendibleHashTable::<i32, i32>::new(Arc::clone(&bpm), 0, 0, String::from("index")); // depths too small
 
     // simple inserts
-    for i in 0..510 {
-        ht.insert(i, i + 1, 4, 4).unwrap();
+    for i in 0..341 {
+        ht.insert(i, i + 1).unwrap();
     }
 
-    assert!(ht.insert(1, 1, 4, 4).is_err());
+    assert!(ht.insert(1, 1).is_err());
 
     // cleanup
     remove_file(db_path).expect("Couldn't remove test DB file");
@@ -66,7 +68,7 @@ fn bucket_single_split_insert() {
 
     // simple inserts
     for i in 0..513 {
-        ht.insert(i, i + 1, 4, 4).unwrap();
+        ht.insert(i, i + 1).unwrap();
         ht.print();
     }
 
@@ -89,11 +91,11 @@ fn bucket_split_insert_directory_double() {
         DiskExtendibleHashTable::<i32, i32>::new(Arc::clone(&bpm), 0, 2, String::from("index"));
 
     // simple inserts
-    for i in 0..2000 {
-        ht.insert(i, i + 1, 4, 4).unwrap();
+    for i in 0..1200 {
+        ht.insert(i, i + 1).unwrap();
     }
 
-    for i in 0..2000 {
+    for i in 0..1200 {
         let res = ht.lookup(i);
         assert_eq!(res, vec![i + 1]);
     }
@@ -113,7 +115,7 @@ fn serialization() {
 
     // insert data
     for i in 0..513 {
-        ht.insert(i, i + 1, 4, 4).unwrap();
+        ht.insert(i, i + 1).unwrap();
     }
     bpm.flush_all_pages();
 
@@ -143,7 +145,7 @@ fn remove_simple() {
         DiskExtendibleHashTable::<i32, i32>::new(Arc::clone(&bpm), 0, 2, String::from("index"));
 
     // insert and remove
-    ht.insert(1, 1, 4, 4).unwrap();
+    ht.insert(1, 1).unwrap();
     assert_eq!(ht.lookup(1), vec![1]);
 
     assert_eq!(ht.remove(1), 1);
@@ -163,10 +165,10 @@ fn remove_multiple() {
         DiskExtendibleHashTable::<i32, i32>::new(Arc::clone(&bpm), 0, 2, String::from("index"));
 
     // insert and remove
-    ht.insert(1, 1, 4, 4).unwrap();
-    ht.insert(1, 2, 4, 4).unwrap();
-    ht.insert(1, 3, 4, 4).unwrap();
-    ht.insert(2, 2, 4, 4).unwrap();
+    ht.insert(1, 1).unwrap();
+    ht.insert(1, 2).unwrap();
+    ht.insert(1, 3).unwrap();
+    ht.insert(2, 2).unwrap();
     assert_eq!(ht.lookup(1), vec![1, 2, 3]);
 
     assert_eq!(ht.remove(1), 3);
@@ -176,6 +178,32 @@ fn remove_multiple() {
     remove_file(db_path).expect("Couldn't remove test DB file");
 }
 
+#[test]
+fn remove_entry() {
+    // init
+    let db_path = temp_dir().join("deht_remove_entry.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+    let ht =
+        DiskExtendibleHashTable::<i32, i32>::new(Arc::clone(&bpm), 0, 2, String::from("index"));
+
+    // insert and remove single pairs
+    ht.insert(1, 1).unwrap();
+    ht.insert(1, 2).unwrap();
+    ht.insert(2, 2).unwrap();
+
+    assert!(ht.remove_entry(1, &2));
+    assert!(!ht.remove_entry(1, &2));
+    assert_eq!(ht.lookup(1), vec![1]);
+    assert_eq!(ht.lookup(2), vec![2]);
+
+    assert!(ht.remove_entry(1, &1));
+    assert_eq!(ht.lookup(1), vec![]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
 #[test]
 fn remove_merges() {
     // init
@@ -190,13 +218,13 @@ fn remove_merges() {
 
     // split once
     for i in 0..513 {
-        ht.insert(i, i, 4, 4).unwrap();
+        ht.insert(i, i).unwrap();
     }
     assert_eq!(get_directories(&ht)[0].size(), 2);
 
     // split second time
     for i in 513..1025 {
-        ht.insert(i, i, 4, 4).unwrap();
+        ht.insert(i, i).unwrap();
     }
     assert_eq!(get_directories(&ht)[0].size(), 4);
 
@@ -210,6 +238,99 @@ fn remove_merges() {
     remove_file(db_path).expect("Couldn't remove test DB file");
 }
 
+#[test]
+fn variable_length_keys() {
+    // init
+    let db_path = temp_dir().join("deht_variable_length_keys.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+    let ht =
+        DiskExtendibleHashTable::<Vec<u8>, u32>::new(Arc::clone(&bpm), 0, 3, String::from("index"));
+    let key = |i: u32| format!("key-{i}").repeat(i as usize % 20 + 1).into_bytes();
+
+    // keys of different lengths, some of them need splits
+    for i in 0..300 {
+        ht.insert(key(i), i).unwrap();
+    }
+    for i in 0..300 {
+        assert_eq!(ht.lookup(key(i)), vec![i]);
+    }
+
+    for i in (0..300).step_by(2) {
+        assert!(ht.remove_entry(key(i), &i));
+    }
+    assert_eq!(ht.lookup(key(10)), vec![]);
+    assert_eq!(ht.lookup(key(11)), vec![11]);
+
+    // entries that can't fit in a page are rejected
+    assert!(ht.insert(vec![0; 5000], 0).is_err());
+    assert_eq!(ht.lookup(vec![0; 5000]), vec![]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn check_integrity() {
+    // init
+    let db_path = temp_dir().join("deht_check_integrity.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+    let ht =
+        DiskExtendibleHashTable::<i32, i32>::new(Arc::clone(&bpm), 0, 9, String::from("index"));
+
+    for i in 0..2000 {
+        ht.insert(i, i).unwrap();
+    }
+
+    // test
+    let mut entries = ht.entries();
+    entries.sort();
+    assert_eq!(entries, (0..2000).map(|i| (i, i)).collect::<Vec<_>>());
+    assert!(ht.check_integrity().is_empty());
+
+    let h_page = ht.bpm.get_read_page(ht.header_page_id);
+    let header = HashTableHeaderPage::deserialize(h_page.read());
+    drop(h_page);
+    let d_pid = header.get_directory_page_id(0).unwrap();
+    let d_page = ht.bpm.get_read_page(d_pid);
+    let mut directory = HashTableDirectoryPage::deserialize(d_page.read());
+    drop(d_page);
+    assert!(directory.global_depth() > 0);
+
+    // an entry stored in a bucket its key doesn't hash to
+    let b_pid = directory.get_bucket_page_id(0).unwrap();
+    let key = (2000..)
+        .find(|key| {
+            directory.get_bucket_page_id(directory.hash_to_bucket_index(ht.hash(key)))
+                != Some(b_pid)
+        })
+        .unwrap();
+    let mut b_page = ht.bpm.get_write_page(b_pid);
+    let mut bucket = HashTableBucketPage::<i32, i32>::deserialize(b_page.read());
+    bucket.insert(key, key).unwrap();
+    b_page.write(bucket.serialize());
+    drop(b_page);
+
+    let problems = ht.check_integrity();
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("doesn't hash to"));
+
+    // a local depth greater than the global depth
+    let global_depth = directory.global_depth() as u8;
+    directory.set_local_depth(0, global_depth + 1).unwrap();
+    let mut d_page = ht.bpm.get_write_page(d_pid);
+    d_page.write(directory.serialize());
+    drop(d_page);
+
+    let problems = ht.check_integrity();
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("local depths are incoherent"));
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
 /// Returns all the managed directory pages (order is as stored internally, but shouldn't be relied on).
 fn get_directories(ht: &DiskExtendibleHashTable<i32, i32>) -> Vec<HashTableDirectoryPage> {
     let mut directories = vec![];
@@ -0,0 +1,126 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::table::{
+    schema::{ColumnType, Schema},
+    tuple::Tuple,
+    value::{ColumnValue, VarcharValue},
+};
+
+/// Maximum length (in bytes) of the terms of full-text indexes. Longer terms are truncated, so they match any term with
+/// the same prefix.
+pub const FULL_TEXT_TERM_MAX_LENGTH: usize = 32;
+
+/// Splits `text` into the terms of full-text search: the lowercased runs of alphanumeric characters, truncated to
+/// `FULL_TEXT_TERM_MAX_LENGTH` bytes. Every term is returned once, in the order of its first occurrence.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut term = word.to_lowercase();
+            if term.len() > FULL_TEXT_TERM_MAX_LENGTH {
+                let end = (0..=FULL_TEXT_TERM_MAX_LENGTH)
+                    .rev()
+                    .find(|i| term.is_char_boundary(*i))
+                    .unwrap();
+                term.truncate(end);
+            }
+
+            term
+        })
+        .filter(|term| seen.insert(term.clone()))
+        .collect()
+}
+
+/// Returns the schema of the keys of full-text indexes, a single term.
+pub fn term_schema() -> Schema {
+    Schema::with_types(vec![ColumnType::Varchar(FULL_TEXT_TERM_MAX_LENGTH)])
+}
+
+/// Returns the key of `term` in full-text indexes.
+pub fn term_key(term: &str) -> Tuple {
+    let value = ColumnValue::Varchar(VarcharValue {
+        value: term.to_string(),
+        length: FULL_TEXT_TERM_MAX_LENGTH,
+    });
+
+    Tuple::new(vec![value], &term_schema())
+}
+
+/// Query of full-text search: terms combined with `AND` and `OR` (e.g. `rust AND database OR sql`). `AND` binds tighter
+/// than `OR` and adjacent terms are combined with `AND`. The words of the query are split into terms like the searched
+/// text (see `tokenize`), so `full-text` is the same as `full AND text`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextQuery {
+    /// Alternatives of the query (combined with `OR`), each one the terms that must all be in the text
+    alternatives: Vec<Vec<String>>,
+}
+
+impl TextQuery {
+    /// Parses a query. The operators are the words `AND` and `OR`, in uppercase.
+    ///
+    /// # Errors
+    /// Will return `Err` if the query has no term or if an operator isn't between two terms.
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let mut alternatives = vec![vec![]];
+        // whether the previous word was a term (so an operator can follow it)
+        let mut after_term = false;
+        for word in query.split_whitespace() {
+            match word {
+                "AND" | "OR" if !after_term => {
+                    return Err(format!("Missing term before {} in query '{}'", word, query));
+                }
+                "AND" => after_term = false,
+                "OR" => {
+                    alternatives.push(vec![]);
+                    after_term = false;
+                }
+                _ => {
+                    let terms = tokenize(word);
+                    after_term = after_term || !terms.is_empty();
+                    alternatives.last_mut().unwrap().extend(terms);
+                }
+            }
+        }
+
+        if !after_term {
+            return Err(format!("Missing term at the end of query '{}'", query));
+        }
+        for terms in alternatives.iter_mut() {
+            let mut seen = HashSet::new();
+            terms.retain(|term| seen.insert(term.clone()));
+        }
+
+        Ok(Self { alternatives })
+    }
+
+    /// Returns the alternatives of the query, each one the terms that must all be in a text matching it.
+    pub fn alternatives(&self) -> &[Vec<String>] {
+        &self.alternatives
+    }
+
+    /// Returns `true` if `text` has all the terms of one of the alternatives of the query.
+    pub fn matches(&self, text: &str) -> bool {
+        let terms = tokenize(text).into_iter().collect::<HashSet<_>>();
+
+        self.alternatives
+            .iter()
+            .any(|alternative| alternative.iter().all(|term| terms.contains(term)))
+    }
+}
+
+impl Display for TextQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let alternatives = self
+            .alternatives
+            .iter()
+            .map(|alternative| alternative.join(" AND "))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", alternatives.join(" OR "))
+    }
+}
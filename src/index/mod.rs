@@ -1,25 +1,33 @@
-use std::sync::Arc;
+use std::{collections::HashSet, ops::Bound, sync::Arc};
 
+use b_plus_tree_page::compare_entries;
+use disk_b_plus_tree::DiskBPlusTree;
 use disk_extendible_hash_table::DiskExtendibleHashTable;
+use full_text::{term_key, term_schema, tokenize, TextQuery};
 
 use crate::{
     config::{DB_EHT_DIRECTORY_MAX_DEPTH, DB_EHT_HEADER_MAX_DEPTH},
     disk::buffer_pool_manager::BufferPoolManager,
+    exec::expression::{Evaluate, Expression},
     table::{
-        schema::Schema,
+        schema::{ColumnType, Schema},
         tuple::{Tuple, RID},
+        value::{BooleanValue, ColumnValue},
     },
 };
 
 #[cfg(test)]
 mod tests;
 
+pub mod b_plus_tree_page;
 pub mod bucket_page;
 pub mod directory_page;
 pub mod header_page;
 pub mod serial;
 
+pub mod disk_b_plus_tree;
 pub mod disk_extendible_hash_table;
+pub mod full_text;
 
 /// Returns the `count` most significant bits of `input`. If value is greater than or equal with `32`, will return `input`.
 fn get_msb(input: u32, count: usize) -> u32 {
@@ -46,6 +54,28 @@ fn get_four_bytes_group(data: &[u8], group_index: usize) -> [u8; 4] {
     ]
 }
 
+/// Data structure backing an index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexType {
+    /// Disk extendible hash table, only supports point lookups.
+    Hash,
+    /// Disk B+ tree, keeps the keys ordered.
+    BPlusTree,
+    /// Inverted index on a `VARCHAR` column for full-text search: a disk B+ tree mapping each term of the values (see
+    /// `full_text::tokenize`) to the rows containing it, its posting list.
+    FullText,
+}
+
+/// Order in which an ordered index is scanned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanDirection {
+    /// From the smallest key to the largest key.
+    Forward,
+    /// From the largest key to the smallest key.
+    Backward,
+}
+
+#[derive(Clone)]
 pub struct IndexMeta {
     key_schema: Schema,
     index_name: String,
@@ -53,7 +83,17 @@ pub struct IndexMeta {
     ///
     /// # Example
     /// For example, if the schema has the attributes `["a", "b", "c"]` and the index key is `["b", "a"]`, then the `pub key_attrs` will be `[1, 0]`.
+    ///
+    /// Empty for expression indexes.
     key_attrs: Vec<usize>,
+    /// Expressions computing the key attributes from the tuples (e.g. `LOWER(name)`), `None` if the key attributes are
+    /// the columns in `key_attrs`.
+    key_exprs: Option<Vec<Expression>>,
+    /// Condition of a partial index: only the tuples for which it's true are in the index. `None` if all of them are.
+    predicate: Option<Expression>,
+    /// Whether the index rejects duplicate keys (enforced by the insert and update executors).
+    unique: bool,
+    index_type: IndexType,
 }
 
 impl IndexMeta {
@@ -62,9 +102,43 @@ impl IndexMeta {
             key_schema,
             index_name,
             key_attrs,
+            key_exprs: None,
+            predicate: None,
+            unique: false,
+            index_type: IndexType::Hash,
         }
     }
 
+    /// Creates the meta of an index whose key attributes are computed by `key_exprs`, one per column of `key_schema`.
+    pub fn with_expressions(
+        key_schema: Schema,
+        index_name: String,
+        key_exprs: Vec<Expression>,
+    ) -> Self {
+        Self {
+            key_exprs: Some(key_exprs),
+            ..Self::new(key_schema, index_name, vec![])
+        }
+    }
+
+    /// Makes the index partial: only the tuples for which `predicate` is true are in the index.
+    pub fn with_predicate(mut self, predicate: Expression) -> Self {
+        self.predicate = Some(predicate);
+        self
+    }
+
+    /// Sets the data structure backing the index (`IndexType::Hash` by default).
+    pub fn with_type(mut self, index_type: IndexType) -> Self {
+        self.index_type = index_type;
+        self
+    }
+
+    /// Marks the index as unique.
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
     pub fn key_schema(&self) -> &Schema {
         &self.key_schema
     }
@@ -76,24 +150,128 @@ impl IndexMeta {
     pub fn key_attrs(&self) -> &[usize] {
         &self.key_attrs
     }
+
+    pub fn key_exprs(&self) -> Option<&[Expression]> {
+        self.key_exprs.as_deref()
+    }
+
+    pub fn predicate(&self) -> Option<&Expression> {
+        self.predicate.as_ref()
+    }
+
+    /// Returns the columns of the table read for computing the key and checking the predicate of a tuple. The entry of
+    /// a tuple only changes if one of them does.
+    pub fn columns(&self) -> Vec<usize> {
+        let mut columns = match &self.key_exprs {
+            Some(exprs) => exprs.iter().flat_map(|expr| expr.columns()).collect(),
+            None => self.key_attrs.clone(),
+        };
+        if let Some(predicate) = &self.predicate {
+            columns.extend(predicate.columns());
+        }
+
+        columns
+    }
+
+    /// Returns `true` if the key is made of exactly the `columns` of the table and every tuple is in the index, so it
+    /// can be used for any lookup on them (it's neither an expression nor a partial index).
+    pub fn is_on_columns(&self, columns: &[usize]) -> bool {
+        self.key_exprs.is_none()
+            && self.predicate.is_none()
+            && self.index_type != IndexType::FullText
+            && self.key_attrs == columns
+    }
+
+    /// Returns `true` if the index has a single key attribute, computed by `expr` (a column or an expression of the
+    /// columns), and has all the tuples matching the `conditions` (combined with `AND`): every tuple if the index isn't
+    /// partial, otherwise each condition of its predicate must be one of the `conditions`.
+    pub fn matches(&self, expr: &Expression, conditions: &[&Expression]) -> bool {
+        let key_matches = match (&self.key_exprs, expr) {
+            _ if self.index_type == IndexType::FullText => false,
+            (Some(exprs), _) => exprs.len() == 1 && exprs[0].is_equivalent(expr),
+            (None, Expression::ColumnValue(column)) => self.key_attrs == [column.col_index],
+            (None, _) => false,
+        };
+        let predicate_holds = self.predicate.as_ref().is_none_or(|predicate| {
+            predicate
+                .conjuncts()
+                .iter()
+                .all(|p| conditions.iter().any(|c| c.is_equivalent(p)))
+        });
+
+        key_matches && predicate_holds
+    }
+
+    /// Returns `true` if the index can be built on a table with `table_schema`. Full-text indexes must be on a single
+    /// `VARCHAR` column and can't be unique, the other indexes can be built on any column.
+    pub fn is_supported_by(&self, table_schema: &Schema) -> bool {
+        if self.index_type != IndexType::FullText {
+            return true;
+        }
+
+        !self.unique
+            && self.key_exprs.is_none()
+            && self.key_attrs.len() == 1
+            && self.key_attrs[0] < table_schema.get_cols_count()
+            && matches!(
+                table_schema.get_col_type(self.key_attrs[0]),
+                ColumnType::Varchar(_)
+            )
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    pub fn index_type(&self) -> IndexType {
+        self.index_type
+    }
+
+    /// Returns `true` if all the `columns` of the table are part of the key, so queries that only read them can be
+    /// answered from the index, without reading the table. Keys computed by expressions and terms of full-text indexes
+    /// don't cover any column.
+    pub fn covers(&self, columns: &[usize]) -> bool {
+        self.key_exprs.is_none()
+            && self.index_type != IndexType::FullText
+            && columns.iter().all(|col| self.key_attrs.contains(col))
+    }
+}
+
+enum IndexStorage {
+    /// Keys are stored in their compact serialization, so they only take as much space as their actual data.
+    Hash(DiskExtendibleHashTable<Vec<u8>, RID>),
+    BPlusTree(DiskBPlusTree),
+    /// Keys are the terms of the values, with the schema `full_text::term_schema`.
+    FullText(DiskBPlusTree),
 }
 
 pub struct Index {
     meta: IndexMeta,
-    deht: DiskExtendibleHashTable<Tuple, RID>,
+    storage: IndexStorage,
 }
 
 impl Index {
     pub fn new(meta: IndexMeta, bpm: Arc<BufferPoolManager>) -> Self {
-        Self {
-            deht: DiskExtendibleHashTable::new(
+        let storage = match meta.index_type() {
+            IndexType::Hash => IndexStorage::Hash(DiskExtendibleHashTable::new(
                 bpm,
                 DB_EHT_HEADER_MAX_DEPTH as u32,
                 DB_EHT_DIRECTORY_MAX_DEPTH as u32,
                 meta.index_name().to_string(),
-            ),
-            meta,
-        }
+            )),
+            IndexType::BPlusTree => IndexStorage::BPlusTree(DiskBPlusTree::new(
+                bpm,
+                meta.key_schema().clone(),
+                meta.index_name().to_string(),
+            )),
+            IndexType::FullText => IndexStorage::FullText(DiskBPlusTree::new(
+                bpm,
+                term_schema(),
+                meta.index_name().to_string(),
+            )),
+        };
+
+        Self { meta, storage }
     }
 
     pub fn meta(&self) -> &IndexMeta {
@@ -107,24 +285,100 @@ impl Index {
     /// a *uncasted* tuple, use `insert`.
     ///
     /// # Errors
-    /// Will return `Err` if there was an internal error in the extendible hash table (the data structure is full).
+    /// Will return `Err` if the index is unique and another row already has the key, or if there was an internal error
+    /// in the extendible hash table (the data structure is full). B+ tree indexes can't be full.
     fn insert_raw(&self, key: Tuple, rid: RID) -> Result<(), ()> {
-        let key_size = key.size() as u32;
-        let value_size = RID::size() as u32;
+        if self.is_duplicate_raw(&key, Some(&rid)) {
+            return Err(());
+        }
 
-        self.deht.insert(key, rid, key_size, value_size)
+        match &self.storage {
+            IndexStorage::Hash(deht) => deht.insert(self.hash_key(&key), rid),
+            IndexStorage::BPlusTree(tree) | IndexStorage::FullText(tree) => {
+                tree.insert(key, rid);
+                Ok(())
+            }
+        }
     }
 
-    /// Inserts a tuple in the index, casting it to the index key schema.
+    /// Inserts a tuple in the index, casting it to the index key schema (or inserting an entry for each of its terms
+    /// for full-text indexes). Tuples that don't match the predicate of a partial index are skipped.
     pub fn insert(&self, tuple: &Tuple, tuple_schema: &Schema, rid: RID) -> Result<(), ()> {
-        let key = Tuple::from_projection(
-            tuple,
-            tuple_schema,
-            &self.meta.key_schema(),
-            self.meta.key_attrs(),
-        );
+        if !self.has_tuple(tuple, tuple_schema) {
+            return Ok(());
+        }
+
+        self.keys(tuple, tuple_schema)
+            .into_iter()
+            .try_for_each(|key| self.insert_raw(key, rid.clone()))
+    }
 
-        self.insert_raw(key, rid)
+    /// Returns the keys of the entries of the tuple in the index: its key (see `key`), or the keys of the terms of its
+    /// value for full-text indexes (none if the value is `NULL`).
+    pub fn keys(&self, tuple: &Tuple, tuple_schema: &Schema) -> Vec<Tuple> {
+        if self.meta.index_type() != IndexType::FullText {
+            return vec![self.key(tuple, tuple_schema)];
+        }
+
+        match tuple.get_value(tuple_schema, self.meta.key_attrs()[0]) {
+            ColumnValue::Varchar(text) => tokenize(&text.value)
+                .iter()
+                .map(|term| term_key(term))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns the key of the tuple in the index: the key attributes of the tuple, or the values of the key expressions.
+    pub fn key(&self, tuple: &Tuple, tuple_schema: &Schema) -> Tuple {
+        match self.meta.key_exprs() {
+            Some(exprs) => {
+                let values = exprs
+                    .iter()
+                    .map(|expr| expr.evaluate(tuple, tuple_schema))
+                    .collect();
+                Tuple::new(values, self.meta.key_schema())
+            }
+            None => Tuple::from_projection(
+                tuple,
+                tuple_schema,
+                self.meta.key_schema(),
+                self.meta.key_attrs(),
+            ),
+        }
+    }
+
+    /// Returns `true` if the tuple belongs in the index: always, unless the index is partial and its predicate isn't
+    /// true for the tuple.
+    pub fn has_tuple(&self, tuple: &Tuple, tuple_schema: &Schema) -> bool {
+        self.meta.predicate().is_none_or(|predicate| {
+            predicate.evaluate(tuple, tuple_schema) == ColumnValue::Boolean(BooleanValue { value: true })
+        })
+    }
+
+    /// Returns `true` if the index is unique and `key` is already used by a row other than `rid`. Keys with nulls are
+    /// never duplicates.
+    ///
+    /// # Assumptions
+    /// This method **EXPECTS** the key to have the same schema as the index key schema.
+    pub fn is_duplicate_raw(&self, key: &Tuple, rid: Option<&RID>) -> bool {
+        if !self.meta.is_unique() {
+            return false;
+        }
+
+        let key_schema = self.meta.key_schema();
+        if (0..key_schema.get_cols_count()).any(|i| key.get_value(key_schema, i).is_null()) {
+            return false;
+        }
+
+        self.scan(key.clone()).iter().any(|r| Some(r) != rid)
+    }
+
+    /// Returns `true` if the index is unique and the key of the tuple is already used by a row other than `rid`. The
+    /// tuple is casted to the index key schema.
+    pub fn is_duplicate(&self, tuple: &Tuple, tuple_schema: &Schema, rid: Option<&RID>) -> bool {
+        self.has_tuple(tuple, tuple_schema)
+            && self.is_duplicate_raw(&self.key(tuple, tuple_schema), rid)
     }
 
     /// Deleted all values associated with the key.
@@ -133,22 +387,209 @@ impl Index {
     /// This method **EXPECTS** the key to have the same schema as the index key schema. For deleting
     /// a *uncasted* tuple, use `delete`.
     pub fn delete_raw(&self, key: Tuple) {
-        self.deht.remove(key);
+        match &self.storage {
+            IndexStorage::Hash(deht) => {
+                deht.remove(self.hash_key(&key));
+            }
+            IndexStorage::BPlusTree(tree) | IndexStorage::FullText(tree) => {
+                tree.remove_key(&key);
+            }
+        }
     }
 
-    /// Deletes all values associated with the tuple from the index. The tuple is casted to the index key schema.
+    /// Deletes all values associated with the tuple from the index. The tuple is casted to the index key schema. For
+    /// full-text indexes, the whole posting lists of the terms of the tuple are deleted.
     pub fn delete(&self, tuple: &Tuple, tuple_schema: &Schema) {
-        let key = Tuple::from_projection(
-            tuple,
-            tuple_schema,
-            &self.meta.key_schema(),
-            self.meta.key_attrs(),
-        );
+        if self.has_tuple(tuple, tuple_schema) {
+            for key in self.keys(tuple, tuple_schema) {
+                self.delete_raw(key);
+            }
+        }
+    }
 
-        self.delete_raw(key);
+    /// Deletes the `key`-`rid` entry, keeping the entries of other rows with the same key. Returns `true` if the entry
+    /// was in the index.
+    ///
+    /// # Assumptions
+    /// This method **EXPECTS** the key to have the same schema as the index key schema. For deleting
+    /// a *uncasted* tuple, use `delete_entry`.
+    pub fn delete_entry_raw(&self, key: Tuple, rid: &RID) -> bool {
+        match &self.storage {
+            IndexStorage::Hash(deht) => deht.remove_entry(self.hash_key(&key), rid),
+            IndexStorage::BPlusTree(tree) | IndexStorage::FullText(tree) => tree.remove(&key, rid),
+        }
+    }
+
+    /// Deletes the entries of the tuple with `rid` from the index (one per term for full-text indexes). The tuple is
+    /// casted to the index key schema. Returns `true` if an entry was in the index.
+    pub fn delete_entry(&self, tuple: &Tuple, tuple_schema: &Schema, rid: &RID) -> bool {
+        if !self.has_tuple(tuple, tuple_schema) {
+            return false;
+        }
+
+        // every entry is deleted, even after one is found
+        let deleted = self
+            .keys(tuple, tuple_schema)
+            .into_iter()
+            .filter(|key| self.delete_entry_raw(key.clone(), rid))
+            .count();
+
+        deleted > 0
+    }
+
+    /// Returns the values whose keys are between `lower` and `upper`, sorted by key in the given `direction`.
+    ///
+    /// # Errors
+    /// Will return `Err` if the index doesn't keep its keys ordered (hash indexes).
+    pub fn range_scan(
+        &self,
+        lower: Bound<Tuple>,
+        upper: Bound<Tuple>,
+        direction: ScanDirection,
+    ) -> Result<Vec<RID>, ()> {
+        Ok(self
+            .range_scan_entries(lower, upper, direction)?
+            .into_iter()
+            .map(|(_, rid)| rid)
+            .collect())
+    }
+
+    /// Returns the entries whose keys are between `lower` and `upper`, sorted by key in the given `direction`. The keys
+    /// have the index key schema.
+    ///
+    /// # Errors
+    /// Will return `Err` if the index doesn't keep its keys ordered (hash and full-text indexes).
+    pub fn range_scan_entries(
+        &self,
+        lower: Bound<Tuple>,
+        upper: Bound<Tuple>,
+        direction: ScanDirection,
+    ) -> Result<Vec<(Tuple, RID)>, ()> {
+        match &self.storage {
+            IndexStorage::Hash(_) | IndexStorage::FullText(_) => Err(()),
+            IndexStorage::BPlusTree(tree) => Ok(tree.range(lower, upper, direction).collect()),
+        }
     }
 
     pub fn scan(&self, key: Tuple) -> Vec<RID> {
-        self.deht.lookup(key)
+        match &self.storage {
+            IndexStorage::Hash(deht) => deht.lookup(self.hash_key(&key)),
+            IndexStorage::BPlusTree(tree) | IndexStorage::FullText(tree) => tree.lookup(&key),
+        }
+    }
+
+    /// Returns the rows whose values match the full-text `query`, sorted by RID. The posting lists of the terms of
+    /// each alternative of the query are intersected, then the results of the alternatives are merged.
+    ///
+    /// # Errors
+    /// Will return `Err` if the index isn't a full-text index.
+    pub fn search(&self, query: &TextQuery) -> Result<Vec<RID>, ()> {
+        let IndexStorage::FullText(tree) = &self.storage else {
+            return Err(());
+        };
+
+        let mut rids = HashSet::new();
+        for alternative in query.alternatives() {
+            let mut posting_lists = alternative.iter().map(|term| tree.lookup(&term_key(term)));
+            let first = posting_lists.next().unwrap_or_default();
+            let matching = posting_lists.fold(first, |matching, posting_list| {
+                let posting_list = posting_list.into_iter().collect::<HashSet<_>>();
+                matching
+                    .into_iter()
+                    .filter(|rid| posting_list.contains(rid))
+                    .collect()
+            });
+            rids.extend(matching);
+        }
+
+        let mut rids = rids.into_iter().collect::<Vec<_>>();
+        rids.sort_by_key(RID::get);
+
+        Ok(rids)
+    }
+
+    /// Returns the entries with `key`, along with their keys as stored in the index (decoded with the index key
+    /// schema).
+    pub fn scan_entries(&self, key: Tuple) -> Vec<(Tuple, RID)> {
+        match &self.storage {
+            IndexStorage::Hash(deht) => deht
+                .lookup_entries(self.hash_key(&key))
+                .into_iter()
+                .map(|(key, rid)| (Tuple::deserialize_compact(&key, self.meta.key_schema()), rid))
+                .collect(),
+            IndexStorage::BPlusTree(tree) | IndexStorage::FullText(tree) => tree
+                .range(
+                    Bound::Included(key.clone()),
+                    Bound::Included(key),
+                    ScanDirection::Forward,
+                )
+                .collect(),
+        }
+    }
+
+    /// Returns all the entries of the index, with their keys decoded with the index key schema. B+ tree entries are
+    /// sorted by `(key, RID)`, hash entries aren't sorted.
+    pub fn entries(&self) -> Vec<(Tuple, RID)> {
+        match &self.storage {
+            IndexStorage::Hash(deht) => deht
+                .entries()
+                .into_iter()
+                .map(|(key, rid)| {
+                    (
+                        Tuple::deserialize_compact(&key, self.meta.key_schema()),
+                        rid,
+                    )
+                })
+                .collect(),
+            IndexStorage::BPlusTree(tree) | IndexStorage::FullText(tree) => tree.iter().collect(),
+        }
+    }
+
+    /// Checks the invariants of the data structure backing the index and returns a description of each violation. On
+    /// top of the checks of the data structure (e.g. `DiskExtendibleHashTable::check_integrity`), the entries of B+
+    /// trees must be sorted and every entry must be found by a lookup of its key.
+    pub fn check_integrity(&self) -> Vec<String> {
+        let mut problems = match &self.storage {
+            IndexStorage::Hash(deht) => deht.check_integrity(),
+            IndexStorage::BPlusTree(_) | IndexStorage::FullText(_) => vec![],
+        };
+
+        let entries = self.entries();
+        if let IndexStorage::BPlusTree(tree) | IndexStorage::FullText(tree) = &self.storage {
+            let key_schema = tree.key_schema();
+            for (i, pair) in entries.windows(2).enumerate() {
+                let ((key, rid), (next_key, next_rid)) = (&pair[0], &pair[1]);
+                if compare_entries(key, rid, next_key, next_rid, key_schema).is_ge() {
+                    problems.push(format!("entries {i} and {} are out of order", i + 1));
+                }
+            }
+        }
+        for (key, rid) in entries {
+            if !self.scan(key).contains(&rid) {
+                problems.push(format!("entry of {rid:?} can't be found from its key"));
+            }
+        }
+
+        problems
+    }
+
+    /// Builds a row of the table from a `key` of the index: the key columns have the values of the key and the other
+    /// columns are `NULL`.
+    pub fn row_from_key(&self, key: &Tuple, table_schema: &Schema) -> Tuple {
+        let key_schema = self.meta.key_schema();
+        let values = (0..table_schema.get_cols_count())
+            .map(|col| match self.meta.key_attrs().iter().position(|attr| *attr == col) {
+                Some(i) => key.get_value(key_schema, i),
+                None => ColumnValue::Null(table_schema.get_col_type(col)),
+            })
+            .collect();
+
+        Tuple::new(values, table_schema)
+    }
+
+    /// Returns the key stored in hash indexes for `key`: its compact serialization, without the padding of variable
+    /// length values.
+    fn hash_key(&self, key: &Tuple) -> Vec<u8> {
+        key.serialize_compact(self.meta.key_schema())
     }
 }
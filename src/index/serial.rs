@@ -47,3 +47,15 @@ impl Deserialize for u8 {
     }
 }
 
+// raw bytes, stored as they are
+impl Serialize for Vec<u8> {
+    fn serialize(&self) -> Vec<u8> {
+        self.clone()
+    }
+}
+
+impl Deserialize for Vec<u8> {
+    fn deserialize(data: &[u8]) -> Self {
+        data.to_vec()
+    }
+}
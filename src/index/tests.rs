@@ -1,15 +1,24 @@
-use std::{env::temp_dir, fs::remove_file, sync::Arc};
+use std::{env::temp_dir, fs::remove_file, ops::Bound, sync::Arc};
 
 use crate::{
     disk::buffer_pool_manager::BufferPoolManager,
+    exec::expression::{
+        boolean::{BooleanExpression, BooleanType},
+        string_function::{StringFunction, StringFunctionExpression},
+        Expression,
+    },
     table::{
         schema::{ColumnType, Schema},
         tuple::{Tuple, RID},
+        value::{ColumnValue, VarcharValue},
     },
-    test_utils::{bool_value, int_value},
+    test_utils::{bool_value, column_with, const_bool, const_int, int_value},
 };
 
-use super::{Index, IndexMeta};
+use super::{
+    full_text::{tokenize, TextQuery},
+    Index, IndexMeta, IndexType, ScanDirection,
+};
 
 fn get_index(db_file_path: String) -> Index {
     let bpm = Arc::new(BufferPoolManager::new(String::from(db_file_path), 100, 2));
@@ -102,3 +111,438 @@ fn insert_non_key_tuples() {
     // cleanup
     remove_file(db_path).expect("Couldn't remove test DB file");
 }
+
+#[test]
+fn b_plus_tree_index() {
+    // init
+    let db_path = temp_dir().join("index_b_plus_tree_index.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+    let meta = IndexMeta::new(
+        Schema::with_types(vec![ColumnType::Integer]),
+        String::from("id"),
+        vec![1],
+    )
+    .with_type(IndexType::BPlusTree);
+    let index = Index::new(meta, bpm);
+
+    // test
+    let tuple_schema = Schema::with_types(vec![ColumnType::Boolean, ColumnType::Integer]);
+    for i in 0..1000 {
+        let tuple = Tuple::new(vec![bool_value(i % 2 == 0), int_value(i % 10)], &tuple_schema);
+        index
+            .insert(&tuple, &tuple_schema, RID::new(i as u32, 0))
+            .unwrap();
+    }
+
+    let key_3 = Tuple::new(vec![int_value(3)], index.meta().key_schema());
+    let rids = index.scan(key_3.clone());
+    assert_eq!(rids.len(), 100);
+    assert_eq!(rids[0], RID::new(3, 0));
+
+    index.delete_raw(key_3.clone());
+    assert_eq!(index.scan(key_3), vec![]);
+    assert_eq!(
+        index
+            .scan(Tuple::new(vec![int_value(4)], index.meta().key_schema()))
+            .len(),
+        100
+    );
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn delete_entry_duplicate_keys() {
+    for (name, index_type) in [("hash", IndexType::Hash), ("bpt", IndexType::BPlusTree)] {
+        // init
+        let db_path = temp_dir().join(format!("index_delete_entry_duplicate_keys_{}.db", name));
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+        let meta = IndexMeta::new(
+            Schema::with_types(vec![ColumnType::Integer]),
+            String::from("id"),
+            vec![1],
+        )
+        .with_type(index_type);
+        let index = Index::new(meta, bpm);
+
+        // test
+        let tuple_schema = Schema::with_types(vec![ColumnType::Boolean, ColumnType::Integer]);
+        let tuple = Tuple::new(vec![bool_value(true), int_value(7)], &tuple_schema);
+        let key = Tuple::new(vec![int_value(7)], index.meta().key_schema());
+
+        index.insert(&tuple, &tuple_schema, RID::new(1, 0)).unwrap();
+        index.insert(&tuple, &tuple_schema, RID::new(2, 0)).unwrap();
+
+        // only the entry of the deleted row goes away
+        assert!(index.delete_entry(&tuple, &tuple_schema, &RID::new(1, 0)));
+        assert!(!index.delete_entry(&tuple, &tuple_schema, &RID::new(1, 0)));
+        assert_eq!(index.scan(key.clone()), vec![RID::new(2, 0)]);
+
+        assert!(index.delete_entry_raw(key.clone(), &RID::new(2, 0)));
+        assert_eq!(index.scan(key), vec![]);
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+}
+
+#[test]
+fn hash_index_varchar_keys() {
+    // init
+    let db_path = temp_dir().join("index_hash_index_varchar_keys.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+    let meta = IndexMeta::new(
+        Schema::with_types(vec![ColumnType::Varchar(3000)]),
+        String::from("name"),
+        vec![0],
+    );
+    let index = Index::new(meta, bpm);
+    let key = |name: String| {
+        Tuple::new(
+            vec![ColumnValue::Varchar(VarcharValue {
+                value: name,
+                length: 3000,
+            })],
+            index.meta().key_schema(),
+        )
+    };
+
+    // test
+    // padded keys would take most of a bucket page, so rows with the same key wouldn't fit in a single bucket
+    for i in 0..200 {
+        index
+            .insert_raw(key(format!("name-{}", i % 50)), RID::new(i, 0))
+            .unwrap();
+    }
+
+    assert_eq!(
+        index.scan(key("name-7".to_string())),
+        vec![
+            RID::new(7, 0),
+            RID::new(57, 0),
+            RID::new(107, 0),
+            RID::new(157, 0)
+        ]
+    );
+    assert_eq!(index.scan(key("name-50".to_string())), vec![]);
+
+    assert!(index.delete_entry_raw(key("name-7".to_string()), &RID::new(57, 0)));
+    index.delete_raw(key("name-8".to_string()));
+    assert_eq!(index.scan(key("name-7".to_string())).len(), 3);
+    assert_eq!(index.scan(key("name-8".to_string())), vec![]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn scan_entries_and_row_from_key() {
+    for (name, index_type) in [("hash", IndexType::Hash), ("bpt", IndexType::BPlusTree)] {
+        // init
+        let db_path = temp_dir().join(format!("index_scan_entries_and_row_from_key_{}.db", name));
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+        let meta = IndexMeta::new(
+            Schema::with_types(vec![ColumnType::Integer, ColumnType::Boolean]),
+            String::from("id_flag"),
+            vec![1, 0],
+        )
+        .with_type(index_type);
+        let index = Index::new(meta, bpm);
+
+        // test
+        assert!(index.meta().covers(&[0, 1, 0]));
+        assert!(!index.meta().covers(&[0, 2]));
+
+        let tuple_schema = Schema::with_types(vec![
+            ColumnType::Boolean,
+            ColumnType::Integer,
+            ColumnType::Integer,
+        ]);
+        let tuple = Tuple::new(
+            vec![bool_value(true), int_value(7), int_value(100)],
+            &tuple_schema,
+        );
+        index.insert(&tuple, &tuple_schema, RID::new(1, 0)).unwrap();
+        index.insert(&tuple, &tuple_schema, RID::new(2, 0)).unwrap();
+
+        let key = Tuple::new(
+            vec![int_value(7), bool_value(true)],
+            index.meta().key_schema(),
+        );
+        let entries = index.scan_entries(key.clone());
+        assert_eq!(
+            entries,
+            vec![(key.clone(), RID::new(1, 0)), (key.clone(), RID::new(2, 0))]
+        );
+
+        // the non-key column can't be rebuilt from the key
+        assert_eq!(
+            index.row_from_key(&entries[0].0, &tuple_schema),
+            Tuple::new(
+                vec![
+                    bool_value(true),
+                    int_value(7),
+                    ColumnValue::Null(ColumnType::Integer)
+                ],
+                &tuple_schema,
+            )
+        );
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+}
+
+#[test]
+fn expression_index() {
+    // init
+    let db_path = temp_dir().join("index_expression_index.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+    let lower_name = Expression::StringFunction(StringFunctionExpression {
+        function: StringFunction::Lower,
+        expr: Box::new(column_with(1, ColumnType::Varchar(10))),
+    });
+    let meta = IndexMeta::with_expressions(
+        Schema::with_types(vec![ColumnType::Varchar(10)]),
+        String::from("lower_name"),
+        vec![lower_name.clone()],
+    )
+    .unique();
+    let index = Index::new(meta, bpm);
+
+    let tuple_schema = Schema::with_types(vec![ColumnType::Integer, ColumnType::Varchar(10)]);
+    let varchar = |value: &str| {
+        ColumnValue::Varchar(VarcharValue {
+            value: value.to_string(),
+            length: 10,
+        })
+    };
+    let tuple = |id, name| Tuple::new(vec![int_value(id), varchar(name)], &tuple_schema);
+
+    // test
+    assert_eq!(index.meta().columns(), vec![1]);
+    assert!(index.meta().matches(&lower_name, &[]));
+    assert!(!index
+        .meta()
+        .matches(&column_with(1, ColumnType::Varchar(10)), &[]));
+    assert!(!index.meta().is_on_columns(&[1]));
+    assert!(!index.meta().covers(&[1]));
+
+    index
+        .insert(&tuple(1, "Alice"), &tuple_schema, RID::new(1, 0))
+        .unwrap();
+    index
+        .insert(&tuple(2, "BOB"), &tuple_schema, RID::new(2, 0))
+        .unwrap();
+    assert_eq!(
+        index.key(&tuple(1, "Alice"), &tuple_schema),
+        Tuple::new(vec![varchar("alice")], index.meta().key_schema())
+    );
+
+    // keys are compared after the expression is applied
+    let key = |name| Tuple::new(vec![varchar(name)], index.meta().key_schema());
+    assert_eq!(index.scan(key("bob")), vec![RID::new(2, 0)]);
+    assert_eq!(index.scan(key("BOB")), vec![]);
+    assert!(index.is_duplicate(&tuple(3, "ALICE"), &tuple_schema, None));
+    assert!(!index.is_duplicate(&tuple(1, "alice"), &tuple_schema, Some(&RID::new(1, 0))));
+
+    assert!(index.delete_entry(&tuple(2, "BOB"), &tuple_schema, &RID::new(2, 0)));
+    assert_eq!(index.scan(key("bob")), vec![]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn partial_index() {
+    // init
+    let db_path = temp_dir().join("index_partial_index.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+    let is_active = Expression::Boolean(BooleanExpression {
+        left: Box::new(column_with(1, ColumnType::Boolean)),
+        right: Box::new(const_bool(true)),
+        typ: BooleanType::EQ,
+    });
+    let meta = IndexMeta::new(
+        Schema::with_types(vec![ColumnType::Integer]),
+        String::from("active_id"),
+        vec![0],
+    )
+    .with_predicate(is_active.clone())
+    .unique();
+    let index = Index::new(meta, bpm);
+
+    let tuple_schema = Schema::with_types(vec![ColumnType::Integer, ColumnType::Boolean]);
+    let tuple = |id, active: ColumnValue| Tuple::new(vec![int_value(id), active], &tuple_schema);
+    let key = |id| Tuple::new(vec![int_value(id)], index.meta().key_schema());
+
+    // test
+    let id = column_with(0, ColumnType::Integer);
+    let id_positive = Expression::Boolean(BooleanExpression {
+        left: Box::new(id.clone()),
+        right: Box::new(const_int(0)),
+        typ: BooleanType::GT,
+    });
+    assert_eq!(index.meta().columns(), vec![0, 1]);
+    assert!(!index.meta().is_on_columns(&[0]));
+    assert!(index.meta().matches(&id, &[&id_positive, &is_active]));
+    assert!(!index.meta().matches(&id, &[&id_positive]));
+
+    index
+        .insert(&tuple(1, bool_value(true)), &tuple_schema, RID::new(1, 0))
+        .unwrap();
+    index
+        .insert(&tuple(2, bool_value(false)), &tuple_schema, RID::new(2, 0))
+        .unwrap();
+    index
+        .insert(
+            &tuple(3, ColumnValue::Null(ColumnType::Boolean)),
+            &tuple_schema,
+            RID::new(3, 0),
+        )
+        .unwrap();
+
+    // only the rows matching the predicate are indexed, and checked for duplicates
+    assert_eq!(index.scan(key(1)), vec![RID::new(1, 0)]);
+    assert_eq!(index.scan(key(2)), vec![]);
+    assert_eq!(index.scan(key(3)), vec![]);
+    assert!(index.is_duplicate(&tuple(1, bool_value(true)), &tuple_schema, None));
+    assert!(!index.is_duplicate(&tuple(1, bool_value(false)), &tuple_schema, None));
+
+    assert!(!index.delete_entry(&tuple(2, bool_value(false)), &tuple_schema, &RID::new(2, 0)));
+    assert!(index.delete_entry(&tuple(1, bool_value(true)), &tuple_schema, &RID::new(1, 0)));
+    assert_eq!(index.scan(key(1)), vec![]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
+
+#[test]
+fn tokenize_and_parse_text_query() {
+    assert_eq!(
+        tokenize("Full-text search, full TEXT!"),
+        vec!["full", "text", "search"]
+    );
+    assert_eq!(tokenize("  ..  "), Vec::<String>::new());
+    assert_eq!(tokenize(&"a".repeat(40)), vec!["a".repeat(32)]);
+
+    let query = TextQuery::parse("Rust full-text AND search OR sql").unwrap();
+    assert_eq!(
+        query.alternatives(),
+        [
+            vec![
+                "rust".to_string(),
+                "full".to_string(),
+                "text".to_string(),
+                "search".to_string()
+            ],
+            vec!["sql".to_string()]
+        ]
+    );
+    assert_eq!(
+        query.to_string(),
+        "rust AND full AND text AND search OR sql"
+    );
+    assert!(query.matches("Search the full text of Rust code"));
+    assert!(query.matches("SQL"));
+    assert!(!query.matches("Rust search"));
+
+    assert!(TextQuery::parse("").is_err());
+    assert!(TextQuery::parse("AND rust").is_err());
+    assert!(TextQuery::parse("rust OR").is_err());
+    assert!(TextQuery::parse("rust AND OR sql").is_err());
+}
+
+#[test]
+fn full_text_index() {
+    // init
+    let db_path = temp_dir().join("index_full_text_index.db");
+    let db_file_path = db_path.to_str().unwrap().to_string();
+    let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+    let meta = IndexMeta::new(
+        Schema::with_types(vec![ColumnType::Varchar(64)]),
+        String::from("body"),
+        vec![1],
+    )
+    .with_type(IndexType::FullText);
+    let index = Index::new(meta, bpm);
+
+    let tuple_schema = Schema::with_types(vec![ColumnType::Integer, ColumnType::Varchar(64)]);
+    let tuple = |id, body: &str| {
+        let body = ColumnValue::Varchar(VarcharValue {
+            value: body.to_string(),
+            length: 64,
+        });
+        Tuple::new(vec![int_value(id), body], &tuple_schema)
+    };
+    let search = |query| index.search(&TextQuery::parse(query).unwrap()).unwrap();
+
+    // test
+    assert!(index.meta().is_supported_by(&tuple_schema));
+    assert!(!index.meta().clone().unique().is_supported_by(&tuple_schema));
+    let on_id = IndexMeta::new(
+        Schema::with_types(vec![ColumnType::Integer]),
+        String::from("id"),
+        vec![0],
+    )
+    .with_type(IndexType::FullText);
+    assert!(!on_id.is_supported_by(&tuple_schema));
+    assert!(!index.meta().is_on_columns(&[1]));
+
+    let rows = [
+        (1, "Rust database engine"),
+        (2, "The database of the engine"),
+        (3, "Rust web servers"),
+    ];
+    for (id, body) in rows {
+        index
+            .insert(&tuple(id, body), &tuple_schema, RID::new(id as u32, 0))
+            .unwrap();
+    }
+    let null_body = Tuple::new(
+        vec![int_value(4), ColumnValue::Null(ColumnType::Varchar(64))],
+        &tuple_schema,
+    );
+    index
+        .insert(&null_body, &tuple_schema, RID::new(4, 0))
+        .unwrap();
+
+    assert_eq!(
+        index.keys(&tuple(1, "Rust rust DB"), &tuple_schema).len(),
+        2
+    );
+    assert_eq!(index.keys(&null_body, &tuple_schema), vec![]);
+    assert_eq!(index.entries().len(), 10);
+    assert!(index.check_integrity().is_empty());
+
+    assert_eq!(search("database"), vec![RID::new(1, 0), RID::new(2, 0)]);
+    assert_eq!(search("rust engine"), vec![RID::new(1, 0)]);
+    assert_eq!(search("web OR the"), vec![RID::new(2, 0), RID::new(3, 0)]);
+    assert_eq!(search("sql"), vec![]);
+    assert!(index
+        .range_scan_entries(Bound::Unbounded, Bound::Unbounded, ScanDirection::Forward)
+        .is_err());
+
+    assert!(index.delete_entry(
+        &tuple(1, "Rust database engine"),
+        &tuple_schema,
+        &RID::new(1, 0)
+    ));
+    assert!(!index.delete_entry(
+        &tuple(1, "Rust database engine"),
+        &tuple_schema,
+        &RID::new(1, 0)
+    ));
+    assert_eq!(search("database"), vec![RID::new(2, 0)]);
+    assert_eq!(search("rust"), vec![RID::new(3, 0)]);
+
+    // cleanup
+    remove_file(db_path).expect("Couldn't remove test DB file");
+}
@@ -1,4 +1,7 @@
-use crate::parser::token::{data_type::DataType, value::Value};
+use crate::{
+    parser::token::{data_type::DataType, value::Value},
+    table::datetime::DatePart,
+};
 
 #[derive(Debug, PartialEq)]
 pub enum Term {
@@ -23,7 +26,20 @@ pub enum Function {
     Avg(Box<Term>),
     Min(Box<Term>),
     Max(Box<Term>),
+    /// `NOW()` or `CURRENT_TIMESTAMP`
     Now,
+    /// `EXTRACT(part FROM term)`
+    Extract {
+        part: DatePart,
+        term: Box<Term>,
+    },
+    Lower(Box<Term>),
+    Upper(Box<Term>),
+    /// `MATCH(term, query)`, full-text search
+    Match {
+        term: Box<Term>,
+        query: Box<Term>,
+    },
 }
 
 #[derive(Debug, PartialEq)]
@@ -127,4 +143,41 @@ pub struct ColumnDef {
     pub name: String,
     /// At the moment varchar is of set size (255)
     pub data_type: DataType,
+    /// 0+
+    pub constraints: Vec<ColumnConstraint>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ColumnConstraint {
+    NotNull,
+    PrimaryKey,
+    Unique,
+    Default(Value),
+    Check(Expression),
+    References(References),
+}
+
+/// Table-level `FOREIGN KEY` clause of a `CREATE TABLE` statement.
+#[derive(Debug, PartialEq)]
+pub struct ForeignKeyDef {
+    /// 1+
+    pub columns: Vec<String>,
+    pub references: References,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct References {
+    pub table_name: String,
+    /// 1+ (as many as the referencing columns)
+    pub columns: Vec<String>,
+    pub on_delete: ReferentialAction,
+    pub on_update: ReferentialAction,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ReferentialAction {
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
 }
@@ -1,4 +1,4 @@
-use general::{ColumnDef, Expression, TableExpression, Term};
+use general::{ColumnDef, Expression, ForeignKeyDef, TableExpression, Term};
 
 use super::token::value::Value;
 
@@ -53,14 +53,20 @@ pub struct CreateTableStatement {
     pub table_name: String,
     /// 1+
     pub columns: Vec<ColumnDef>,
+    /// 0+
+    pub foreign_keys: Vec<ForeignKeyDef>,
 }
 
 #[derive(Debug, PartialEq)]
 pub struct CreateIndexStatement {
+    /// `CREATE UNIQUE INDEX`
+    pub unique: bool,
     pub index_name: String,
     pub table_name: String, // ON
-    /// 1+
-    pub columns: Vec<String>,
+    /// 1+, columns or expressions of the columns (e.g. `LOWER(name)`)
+    pub columns: Vec<Term>,
+    /// `WHERE` condition of a partial index
+    pub where_expression: Option<Expression>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -16,18 +16,18 @@ use token::{
     function::{self, Function},
     keyword::Keyword,
     value::Value,
-    Token,
+    Token, Tokenizer,
 };
 
 #[cfg(test)]
 mod tests;
 
-mod ast;
+pub mod ast;
 mod parse;
-mod token;
+pub mod token;
 
 #[derive(Debug, PartialEq)]
-enum SqlStatement {
+pub enum SqlStatement {
     CreateTable(CreateTableStatement),
     CreateIndex(CreateIndexStatement),
     Select(SelectStatement),
@@ -38,6 +38,18 @@ enum SqlStatement {
     Transaction(TransactionStatement),
 }
 
+/// Parses a single SQL statement from `sql`.
+///
+/// # Errors
+/// Will return an `Err` if there was a lexing error, or if there was a syntax error.
+pub fn parse_sql(sql: &str) -> Result<SqlStatement, String> {
+    let tokens = Tokenizer::new()
+        .tokenize(sql)
+        .map_err(|_| "LEX: Invalid SQL statement".to_string())?;
+
+    SqlParser::new(tokens).parse()
+}
+
 struct SqlParser {
     tokens: Vec<Token>,
     cursor: usize,
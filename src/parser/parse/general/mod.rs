@@ -1,16 +1,19 @@
 use crate::parser::{
     ast::{
         general::{
-            AndCondition, ColumnDef, Condition, CountType, Expression, Factor, FactorRight,
-            Function, Operand, OperandRight, Operation, TableExpression, Term,
+            AndCondition, ColumnConstraint, ColumnDef, Condition, CountType, Expression, Factor, FactorRight,
+            ForeignKeyDef, Function, Operand, OperandRight, Operation, ReferentialAction, References,
+            TableExpression, Term,
         },
         JoinExpression, JoinType, OrderByExpression, SelectExpression,
     },
     token::{
-        delimiter::Delimiter, function, keyword::Keyword, operator::Operator, value::Value, Token,
+        data_type::DataType, delimiter::Delimiter, function, keyword::Keyword, operator::Operator,
+        value::Value, Token,
     },
     SqlParser,
 };
+use crate::table::datetime::DatePart;
 
 #[cfg(test)]
 mod tests;
@@ -129,8 +132,8 @@ fn parse_function(parser: &mut SqlParser) -> Result<Function, String> {
         function::Function::Avg => Ok(Function::Avg(Box::new(parse_paren_term(parser)?))),
         function::Function::Min => Ok(Function::Min(Box::new(parse_paren_term(parser)?))),
         function::Function::Max => Ok(Function::Max(Box::new(parse_paren_term(parser)?))),
-        function::Function::Upper => Err("STX: UPPER function not supported".to_string()),
-        function::Function::Lower => Err("STX: LOWER function not supported".to_string()),
+        function::Function::Upper => Ok(Function::Upper(Box::new(parse_paren_term(parser)?))),
+        function::Function::Lower => Ok(Function::Lower(Box::new(parse_paren_term(parser)?))),
         function::Function::Length => Err("STX: LENGTH function not supported".to_string()),
         function::Function::Round => Err("STX: ROUND function not supported".to_string()),
         function::Function::Now => {
@@ -139,7 +142,34 @@ fn parse_function(parser: &mut SqlParser) -> Result<Function, String> {
 
             Ok(Function::Now)
         }
+        function::Function::CurrentTimestamp => Ok(Function::Now),
+        function::Function::Extract => {
+            parser.match_next(Token::Delimiter(Delimiter::OpenParen))?;
+            let name = parser.match_next_identifier()?;
+            let part = DatePart::from_name(&name)
+                .ok_or(format!("STX: Unknown date part {}", name))?;
+            parser.match_next(Token::Keyword(Keyword::From))?;
+            let term = parse_term(parser)?;
+            parser.match_next(Token::Delimiter(Delimiter::CloseParen))?;
+
+            Ok(Function::Extract {
+                part,
+                term: Box::new(term),
+            })
+        }
         function::Function::Coalesce => Err("STX: COALESCE function not supported".to_string()),
+        function::Function::Match => {
+            parser.match_next(Token::Delimiter(Delimiter::OpenParen))?;
+            let term = parse_term(parser)?;
+            parser.match_next(Token::Delimiter(Delimiter::Comma))?;
+            let query = parse_term(parser)?;
+            parser.match_next(Token::Delimiter(Delimiter::CloseParen))?;
+
+            Ok(Function::Match {
+                term: Box::new(term),
+                query: Box::new(query),
+            })
+        }
     }
 }
 
@@ -510,10 +540,7 @@ pub fn parse_join_expression(parser: &mut SqlParser) -> Result<JoinExpression, S
 /// Parse expression matching `"(" , column_def , { "," , column_def } , ")"`.
 pub fn parse_column_defs(parser: &mut SqlParser) -> Result<Vec<ColumnDef>, String> {
     parser.match_next(Token::Delimiter(Delimiter::OpenParen))?;
-    let mut column_defs = vec![ColumnDef {
-        name: parser.match_next_identifier()?,
-        data_type: parser.match_next_data_type()?,
-    }];
+    let mut column_defs = vec![parse_column_def(parser)?];
 
     loop {
         if parser
@@ -523,10 +550,7 @@ pub fn parse_column_defs(parser: &mut SqlParser) -> Result<Vec<ColumnDef>, Strin
             break;
         }
 
-        column_defs.push(ColumnDef {
-            name: parser.match_next_identifier()?,
-            data_type: parser.match_next_data_type()?,
-        });
+        column_defs.push(parse_column_def(parser)?);
     }
 
     parser.match_next(Token::Delimiter(Delimiter::CloseParen))?;
@@ -534,6 +558,172 @@ pub fn parse_column_defs(parser: &mut SqlParser) -> Result<Vec<ColumnDef>, Strin
     Ok(column_defs)
 }
 
+/// Parse expression matching `"(" , ( column_def | foreign_key_def ) , { "," , ( column_def | foreign_key_def ) } , ")"`
+/// with at least one column definition.
+pub fn parse_table_elements(
+    parser: &mut SqlParser,
+) -> Result<(Vec<ColumnDef>, Vec<ForeignKeyDef>), String> {
+    parser.match_next(Token::Delimiter(Delimiter::OpenParen))?;
+
+    let mut column_defs = vec![];
+    let mut foreign_keys = vec![];
+    loop {
+        if parser.match_next(Token::Keyword(Keyword::ForeignKey)).is_ok() {
+            foreign_keys.push(ForeignKeyDef {
+                columns: parse_identifiers(parser)?,
+                references: parse_references(parser)?,
+            });
+        } else {
+            column_defs.push(parse_column_def(parser)?);
+        }
+
+        if parser
+            .match_next(Token::Delimiter(Delimiter::Comma))
+            .is_err()
+        {
+            break;
+        }
+    }
+
+    parser.match_next(Token::Delimiter(Delimiter::CloseParen))?;
+
+    if column_defs.is_empty() {
+        return Err("STX: Expected at least one column definition".to_string());
+    }
+
+    Ok((column_defs, foreign_keys))
+}
+
+/// Parse expression matching `name , data_type , [ "(" , precision , [ "," , scale ] , ")" ] , { column_constraint }`.
+/// The precision and scale can only be given for decimals.
+fn parse_column_def(parser: &mut SqlParser) -> Result<ColumnDef, String> {
+    let name = parser.match_next_identifier()?;
+    let mut data_type = parser.match_next_data_type()?;
+    if data_type == DataType::Decimal(None) {
+        data_type = DataType::Decimal(parse_precision_and_scale(parser)?);
+    }
+
+    let mut constraints = vec![];
+    while let Some(constraint) = parse_column_constraint(parser)? {
+        constraints.push(constraint);
+    }
+
+    Ok(ColumnDef {
+        name,
+        data_type,
+        constraints,
+    })
+}
+
+/// Parse expression matching `[ "(" , precision , [ "," , scale ] , ")" ]`. The scale defaults to 0.
+fn parse_precision_and_scale(parser: &mut SqlParser) -> Result<Option<(u8, u8)>, String> {
+    if parser
+        .match_next(Token::Delimiter(Delimiter::OpenParen))
+        .is_err()
+    {
+        return Ok(None);
+    }
+
+    let precision = parse_digits(parser)?;
+    let scale = if parser
+        .match_next(Token::Delimiter(Delimiter::Comma))
+        .is_ok()
+    {
+        parse_digits(parser)?
+    } else {
+        0
+    };
+    parser.match_next(Token::Delimiter(Delimiter::CloseParen))?;
+
+    if precision == 0 || scale > precision {
+        return Err(format!(
+            "STX: Invalid precision and scale ({}, {})",
+            precision, scale
+        ));
+    }
+
+    Ok(Some((precision, scale)))
+}
+
+/// Parse a number of digits of a data type.
+fn parse_digits(parser: &mut SqlParser) -> Result<u8, String> {
+    match parser.match_next_value()? {
+        Value::Integer(value) => {
+            u8::try_from(value).map_err(|_| format!("STX: Invalid number of digits {}", value))
+        }
+        value => Err(format!("STX: Expected a number of digits, got {:?}", value)),
+    }
+}
+
+/// Parse expression matching `"NOT NULL" | "PRIMARY KEY" | "UNIQUE" | "DEFAULT" , value | "CHECK" , "(" , expression , ")" | references`.
+/// Returns `None` if the next token doesn't start a column constraint.
+fn parse_column_constraint(parser: &mut SqlParser) -> Result<Option<ColumnConstraint>, String> {
+    if parser.match_next(Token::Keyword(Keyword::NotNull)).is_ok() {
+        return Ok(Some(ColumnConstraint::NotNull));
+    }
+    if parser.match_next(Token::Keyword(Keyword::PrimaryKey)).is_ok() {
+        return Ok(Some(ColumnConstraint::PrimaryKey));
+    }
+    if parser.match_next(Token::Keyword(Keyword::Unique)).is_ok() {
+        return Ok(Some(ColumnConstraint::Unique));
+    }
+    if parser.match_next(Token::Keyword(Keyword::Default)).is_ok() {
+        return Ok(Some(ColumnConstraint::Default(parser.match_next_value()?)));
+    }
+    if parser.match_next(Token::Keyword(Keyword::Check)).is_ok() {
+        parser.match_next(Token::Delimiter(Delimiter::OpenParen))?;
+        let expression = parse_expression(parser)?;
+        parser.match_next(Token::Delimiter(Delimiter::CloseParen))?;
+
+        return Ok(Some(ColumnConstraint::Check(expression)));
+    }
+    if parser.peek() == Ok(&Token::Keyword(Keyword::References)) {
+        return Ok(Some(ColumnConstraint::References(parse_references(parser)?)));
+    }
+
+    Ok(None)
+}
+
+/// Parse expression matching `"REFERENCES" , name , "(" , name , { "," , name } , ")" , { "ON" , ( "DELETE" | "UPDATE" ) , referential_action }`.
+/// Actions that are not specified default to `NO ACTION`.
+fn parse_references(parser: &mut SqlParser) -> Result<References, String> {
+    parser.match_next(Token::Keyword(Keyword::References))?;
+    let table_name = parser.match_next_identifier()?;
+    let columns = parse_identifiers(parser)?;
+
+    let mut on_delete = ReferentialAction::NoAction;
+    let mut on_update = ReferentialAction::NoAction;
+    while parser.match_next(Token::Keyword(Keyword::On)).is_ok() {
+        if parser.match_next(Token::Keyword(Keyword::Delete)).is_ok() {
+            on_delete = parse_referential_action(parser)?;
+        } else {
+            parser.match_next(Token::Keyword(Keyword::Update))?;
+            on_update = parse_referential_action(parser)?;
+        }
+    }
+
+    Ok(References {
+        table_name,
+        columns,
+        on_delete,
+        on_update,
+    })
+}
+
+/// Parse expression matching `"NO ACTION" | "RESTRICT" | "CASCADE" | "SET" , "NULL"`.
+fn parse_referential_action(parser: &mut SqlParser) -> Result<ReferentialAction, String> {
+    match parser.pop()? {
+        Token::Keyword(Keyword::NoAction) => Ok(ReferentialAction::NoAction),
+        Token::Keyword(Keyword::Restrict) => Ok(ReferentialAction::Restrict),
+        Token::Keyword(Keyword::Cascade) => Ok(ReferentialAction::Cascade),
+        Token::Keyword(Keyword::Set) => {
+            parser.match_next(Token::Value(Value::Null))?;
+            Ok(ReferentialAction::SetNull)
+        }
+        token => Err(format!("STX: Expected referential action, got {:?}", token)),
+    }
+}
+
 /// Parse expression matching `"(" , name , { "," , name } , ")"`.
 pub fn parse_identifiers(parser: &mut SqlParser) -> Result<Vec<String>, String> {
     parser.match_next(Token::Delimiter(Delimiter::OpenParen))?;
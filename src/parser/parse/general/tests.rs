@@ -2,7 +2,7 @@ use crate::parser::{
     self,
     ast::{
         general::{
-            AndCondition, ColumnDef, CompareType, Condition, CountType, Expression, Factor,
+            AndCondition, ColumnConstraint, ColumnDef, CompareType, Condition, CountType, Expression, Factor,
             FactorRight, Function, Operand, OperandRight, Operation, TableExpression, Term,
         },
         JoinExpression, JoinType, OrderByExpression, SelectExpression,
@@ -18,6 +18,7 @@ use crate::parser::{
     token::{data_type::DataType, value::Value, Token, Tokenizer},
     SqlParser,
 };
+use crate::table::datetime::DatePart;
 
 use super::parse_table_expression;
 
@@ -50,7 +51,7 @@ fn parse_paren_term_test() {
 
     assert_eq!(
         parse_paren_term(&mut p).unwrap(),
-        Term::Value(Value::Float(1.12))
+        Term::Value(Value::Float("1.12".to_string()))
     );
 
     let tokens = Tokenizer::new().tokenize("('test')").unwrap();
@@ -170,6 +171,45 @@ fn parse_function_sum() {
 fn parse_function_now() {
     let mut parser = get_parser("NOW()");
     assert_eq!(parse_function(&mut parser).unwrap(), Function::Now);
+
+    let mut parser = get_parser("CURRENT_TIMESTAMP");
+    assert_eq!(parse_function(&mut parser).unwrap(), Function::Now);
+}
+
+#[test]
+fn parse_function_extract() {
+    let mut parser = get_parser("EXTRACT(year FROM created_at)");
+    assert_eq!(
+        parse_function(&mut parser).unwrap(),
+        Function::Extract {
+            part: DatePart::Year,
+            term: Box::new(Term::Column {
+                table_alias: None,
+                name: "created_at".to_string()
+            })
+        }
+    );
+
+    let mut parser = get_parser("EXTRACT(fortnight FROM created_at)");
+    assert!(parse_function(&mut parser).is_err());
+}
+
+#[test]
+fn parse_function_match() {
+    let mut parser = get_parser("MATCH(body, 'rust OR database')");
+    assert_eq!(
+        parse_function(&mut parser).unwrap(),
+        Function::Match {
+            term: Box::new(Term::Column {
+                table_alias: None,
+                name: "body".to_string()
+            }),
+            query: Box::new(Term::Value(Value::String("rust OR database".to_string())))
+        }
+    );
+
+    let mut parser = get_parser("MATCH(body)");
+    assert!(parse_function(&mut parser).is_err());
 }
 
 #[test]
@@ -669,7 +709,8 @@ fn parse_column_defs_test() {
         parse_column_defs(&mut parser).unwrap(),
         vec![ColumnDef {
             name: "column".to_string(),
-            data_type: DataType::Integer
+            data_type: DataType::Integer,
+            constraints: vec![]
         }]
     );
 
@@ -679,11 +720,13 @@ fn parse_column_defs_test() {
         vec![
             ColumnDef {
                 name: "column1".to_string(),
-                data_type: DataType::Integer
+                data_type: DataType::Integer,
+                constraints: vec![]
             },
             ColumnDef {
                 name: "column2".to_string(),
-                data_type: DataType::BigInt
+                data_type: DataType::BigInt,
+                constraints: vec![]
             }
         ]
     );
@@ -692,6 +735,83 @@ fn parse_column_defs_test() {
     assert!(parse_column_defs(&mut parser).is_err());
 }
 
+#[test]
+fn parse_column_defs_precision_and_scale() {
+    let data_types = |raw: &str| {
+        let mut parser = get_parser(raw);
+        parse_column_defs(&mut parser).map(|defs| {
+            defs.into_iter()
+                .map(|def| def.data_type)
+                .collect::<Vec<_>>()
+        })
+    };
+
+    assert_eq!(
+        data_types("(a NUMERIC, b NUMERIC(10, 2), c DECIMAL(5) NOT NULL)").unwrap(),
+        vec![
+            DataType::Decimal(None),
+            DataType::Decimal(Some((10, 2))),
+            DataType::Decimal(Some((5, 0)))
+        ]
+    );
+    // scale larger than the precision
+    assert!(data_types("(a NUMERIC(2, 3))").is_err());
+    assert!(data_types("(a NUMERIC(0))").is_err());
+    assert!(data_types("(a NUMERIC(1000, 2))").is_err());
+    assert!(data_types("(a NUMERIC(10, 2)").is_err());
+    // only decimals have a precision
+    assert!(data_types("(a INTEGER(10))").is_err());
+}
+
+#[test]
+fn parse_column_defs_constraints() {
+    let mut parser = get_parser(
+        "(id INTEGER PRIMARY KEY, name VARCHAR NOT NULL UNIQUE, age INTEGER DEFAULT 18 CHECK (age >= 0))",
+    );
+    let column_defs = parse_column_defs(&mut parser).unwrap();
+
+    assert_eq!(column_defs[0].constraints, vec![ColumnConstraint::PrimaryKey]);
+    assert_eq!(
+        column_defs[1].constraints,
+        vec![ColumnConstraint::NotNull, ColumnConstraint::Unique]
+    );
+    assert_eq!(
+        column_defs[2].constraints,
+        vec![
+            ColumnConstraint::Default(Value::Integer(18)),
+            ColumnConstraint::Check(Expression {
+                and_conditions: vec![AndCondition {
+                    conditions: vec![Condition::Operation {
+                        operand: Operand {
+                            left: Factor {
+                                left: Box::new(Term::Column {
+                                    table_alias: None,
+                                    name: "age".to_string()
+                                }),
+                                right: vec![]
+                            },
+                            right: vec![]
+                        },
+                        operation: Some(Operation::Comparison {
+                            cmp_type: CompareType::GTE,
+                            operand: Operand {
+                                left: Factor {
+                                    left: Box::new(Term::Value(Value::Integer(0))),
+                                    right: vec![]
+                                },
+                                right: vec![]
+                            }
+                        })
+                    }]
+                }]
+            })
+        ]
+    );
+
+    let mut parser = get_parser("(age INTEGER CHECK age >= 0)");
+    assert!(parse_column_defs(&mut parser).is_err());
+}
+
 #[test]
 fn parse_identifiers_test() {
     let mut parser = get_parser("(test)");
@@ -1,7 +1,7 @@
 use general::{
-    parse_column_defs, parse_expression, parse_expressions, parse_identifiers,
+    parse_expression, parse_expressions, parse_identifiers,
     parse_join_expression, parse_order_by_expression, parse_select_expressions, parse_set_values,
-    parse_table_expression, parse_terms,
+    parse_table_elements, parse_table_expression, parse_terms,
 };
 
 use super::{
@@ -101,25 +101,41 @@ pub fn parse_select_statement(parser: &mut SqlParser) -> Result<SelectStatement,
 pub fn parse_create_table_statement(parser: &mut SqlParser) -> Result<CreateTableStatement, String> {
     parser.match_next(Token::Keyword(Keyword::CreateTable))?;
     let table_name = parser.match_next_identifier()?;
-    let columns = parse_column_defs(parser)?;
+    let (columns, foreign_keys) = parse_table_elements(parser)?;
 
     return Ok(CreateTableStatement {
         table_name,
         columns,
+        foreign_keys,
     });
 }
 
 pub fn parse_create_index_statement(parser: &mut SqlParser) -> Result<CreateIndexStatement, String> {
-    parser.match_next(Token::Keyword(Keyword::CreateIndex))?;
+    let unique = match parser.match_next_option(&[
+        Token::Keyword(Keyword::CreateIndex),
+        Token::Keyword(Keyword::CreateUniqueIndex),
+    ])? {
+        Some(Token::Keyword(Keyword::CreateUniqueIndex)) => true,
+        Some(_) => false,
+        None => return Err("STX: Expected CREATE INDEX or CREATE UNIQUE INDEX".to_string()),
+    };
     let index_name = parser.match_next_identifier()?;
     parser.match_next(Token::Keyword(Keyword::On))?;
     let table_name = parser.match_next_identifier()?;
-    let columns = parse_identifiers(parser)?;
+    let columns = parse_terms(parser)?;
+
+    let where_expression = if parser.match_next(Token::Keyword(Keyword::Where)).is_ok() {
+        Some(parse_expression(parser)?)
+    } else {
+        None
+    };
 
     Ok(CreateIndexStatement {
+        unique,
         index_name,
         table_name,
         columns,
+        where_expression,
     })
 }
 
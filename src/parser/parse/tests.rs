@@ -1,8 +1,8 @@
 use crate::parser::{
     ast::{
         general::{
-            AndCondition, ColumnDef, CompareType, Condition, Expression, Factor, Operand,
-            Operation, TableExpression, Term,
+            AndCondition, ColumnConstraint, ColumnDef, CompareType, Condition, Expression, Factor,
+            ForeignKeyDef, Function, Operand, Operation, ReferentialAction, References, TableExpression, Term,
         }, CreateIndexStatement, CreateTableStatement, DeleteStatement, ExplainStatement, InsertStatement, JoinExpression, JoinType, OrderByExpression, SelectExpression, SelectStatement, TransactionStatement, UpdateStatement
     },
     parse::{
@@ -127,24 +127,141 @@ fn parse_create_table_statement_test() {
             ColumnDef {
                 name: "a".to_string(),
                 data_type: DataType::Integer,
+                constraints: vec![],
             },
             ColumnDef {
                 name: "b".to_string(),
                 data_type: DataType::Varchar,
+                constraints: vec![],
             },
         ],
+        foreign_keys: vec![],
     };
 
     assert_eq!(parse_create_table_statement(&mut parser).unwrap(), expected);
 }
 
+#[test]
+fn parse_create_table_statement_foreign_keys_test() {
+    let mut parser = get_parser(
+        "CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users (id) ON DELETE CASCADE, \
+         FOREIGN KEY (id, user_id) REFERENCES items (order_id, user_id) ON UPDATE SET NULL ON DELETE RESTRICT)",
+    );
+    let expected = CreateTableStatement {
+        table_name: "orders".to_string(),
+        columns: vec![
+            ColumnDef {
+                name: "id".to_string(),
+                data_type: DataType::Integer,
+                constraints: vec![ColumnConstraint::PrimaryKey],
+            },
+            ColumnDef {
+                name: "user_id".to_string(),
+                data_type: DataType::Integer,
+                constraints: vec![ColumnConstraint::References(References {
+                    table_name: "users".to_string(),
+                    columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::Cascade,
+                    on_update: ReferentialAction::NoAction,
+                })],
+            },
+        ],
+        foreign_keys: vec![ForeignKeyDef {
+            columns: vec!["id".to_string(), "user_id".to_string()],
+            references: References {
+                table_name: "items".to_string(),
+                columns: vec!["order_id".to_string(), "user_id".to_string()],
+                on_delete: ReferentialAction::Restrict,
+                on_update: ReferentialAction::SetNull,
+            },
+        }],
+    };
+
+    assert_eq!(parse_create_table_statement(&mut parser).unwrap(), expected);
+
+    // only foreign keys
+    let mut parser = get_parser("CREATE TABLE t (FOREIGN KEY (a) REFERENCES p (a))");
+    assert!(parse_create_table_statement(&mut parser).is_err());
+}
+
 #[test]
 fn parse_create_index_statement_test() {
     let mut parser = get_parser("CREATE INDEX my_index ON my_table (a, b)");
     let expected = CreateIndexStatement {
+        unique: false,
         index_name: "my_index".to_string(),
         table_name: "my_table".to_string(),
-        columns: vec!["a".to_string(), "b".to_string()],
+        columns: vec![
+            Term::Column {
+                table_alias: None,
+                name: "a".to_string(),
+            },
+            Term::Column {
+                table_alias: None,
+                name: "b".to_string(),
+            },
+        ],
+        where_expression: None,
+    };
+
+    assert_eq!(parse_create_index_statement(&mut parser).unwrap(), expected)
+}
+
+#[test]
+fn parse_create_unique_index_statement_test() {
+    let mut parser = get_parser("CREATE UNIQUE INDEX my_index ON my_table (a)");
+    let expected = CreateIndexStatement {
+        unique: true,
+        index_name: "my_index".to_string(),
+        table_name: "my_table".to_string(),
+        columns: vec![Term::Column {
+            table_alias: None,
+            name: "a".to_string(),
+        }],
+        where_expression: None,
+    };
+
+    assert_eq!(parse_create_index_statement(&mut parser).unwrap(), expected)
+}
+
+#[test]
+fn parse_create_expression_partial_index_statement_test() {
+    let mut parser =
+        get_parser("CREATE INDEX my_index ON my_table (LOWER(name)) WHERE active = true");
+    let expected = CreateIndexStatement {
+        unique: false,
+        index_name: "my_index".to_string(),
+        table_name: "my_table".to_string(),
+        columns: vec![Term::Function(Function::Lower(Box::new(Term::Column {
+            table_alias: None,
+            name: "name".to_string(),
+        })))],
+        where_expression: Some(Expression {
+            and_conditions: vec![AndCondition {
+                conditions: vec![Condition::Operation {
+                    operand: Operand {
+                        left: Factor {
+                            left: Box::new(Term::Column {
+                                table_alias: None,
+                                name: "active".to_string(),
+                            }),
+                            right: vec![],
+                        },
+                        right: vec![],
+                    },
+                    operation: Some(Operation::Comparison {
+                        cmp_type: CompareType::EQ,
+                        operand: Operand {
+                            left: Factor {
+                                left: Box::new(Term::Value(Value::Boolean(true))),
+                                right: vec![],
+                            },
+                            right: vec![],
+                        },
+                    }),
+                }],
+            }],
+        }),
     };
 
     assert_eq!(parse_create_index_statement(&mut parser).unwrap(), expected)
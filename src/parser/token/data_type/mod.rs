@@ -10,7 +10,8 @@ pub enum DataType {
     BigInt,
     Float,
     Double,
-    Decimal,
+    /// `DECIMAL` / `NUMERIC`, with the precision and scale if they are given
+    Decimal(Option<(u8, u8)>),
     Varchar,
     Char,
     Boolean,
@@ -32,7 +33,8 @@ impl DataTypeTokenizer {
                 ("BIGINT", DataType::BigInt),
                 ("FLOAT", DataType::Float),
                 ("DOUBLE", DataType::Double),
-                ("DECIMAL", DataType::Decimal),
+                ("DECIMAL", DataType::Decimal(None)),
+                ("NUMERIC", DataType::Decimal(None)),
                 ("VARCHAR", DataType::Varchar),
                 ("CHAR", DataType::Char),
                 ("BOOLEAN", DataType::Boolean),
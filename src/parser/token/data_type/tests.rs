@@ -18,6 +18,10 @@ fn matches() {
         tokenizer.largest_match("date"),
         Some((Token::DataType(DataType::Date), 4))
     );
+    assert_eq!(
+        tokenizer.largest_match("numeric"),
+        Some((Token::DataType(DataType::Decimal(None)), 7))
+    );
     assert_eq!(
         tokenizer.largest_match("TIMESTAMPs"),
         Some((Token::DataType(DataType::Timestamp), 9))
@@ -16,7 +16,10 @@ pub enum Function {
     Length,
     Round,
     Now,
+    CurrentTimestamp,
+    Extract,
     Coalesce,
+    Match,
 }
 
 pub struct FunctionTokenizer {
@@ -37,7 +40,10 @@ impl FunctionTokenizer {
                 ("LENGTH", Function::Length),
                 ("ROUND", Function::Round),
                 ("NOW", Function::Now),
+                ("CURRENT_TIMESTAMP", Function::CurrentTimestamp),
+                ("EXTRACT", Function::Extract),
                 ("COALESCE", Function::Coalesce),
+                ("MATCH", Function::Match),
             ])
         }
     }
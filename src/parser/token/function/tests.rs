@@ -9,6 +9,11 @@ fn matches() {
     assert_eq!(tokenizer.largest_match("count"), Some((Token::Function(Function::Count), 5)));
     assert_eq!(tokenizer.largest_match("AVG"), Some((Token::Function(Function::Avg), 3)));
     assert_eq!(tokenizer.largest_match("Round"), Some((Token::Function(Function::Round), 5)));
+    assert_eq!(
+        tokenizer.largest_match("current_timestamp"),
+        Some((Token::Function(Function::CurrentTimestamp), 17))
+    );
+    assert_eq!(tokenizer.largest_match("match"), Some((Token::Function(Function::Match), 5)));
 }
 
 #[test]
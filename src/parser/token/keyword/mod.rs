@@ -12,14 +12,19 @@ pub enum Keyword {
     Asc,
     Begin,
     Between,
+    Cascade,
+    Check,
     Commit,
     Create,
     CreateIndex,
     CreateTable,
+    CreateUniqueIndex,
+    Default,
     Delete,
     Desc,
     Distinct,
     Explain,
+    ForeignKey,
     From,
     GroupBy,
     Having,
@@ -31,10 +36,14 @@ pub enum Keyword {
     Join,
     LeftJoin,
     Limit,
+    NoAction,
     NotNull,
     On,
     OrderBy,
     OuterJoin,
+    PrimaryKey,
+    References,
+    Restrict,
     RightJoin,
     Rollback,
     Rownum,
@@ -43,6 +52,7 @@ pub enum Keyword {
     Set,
     Table,
     TruncateTable,
+    Unique,
     Update,
     Values,
     Where,
@@ -62,14 +72,19 @@ impl KeywordTokenizer {
                 ("ASC", Keyword::Asc),
                 ("BEGIN", Keyword::Begin),
                 ("BETWEEN", Keyword::Between),
+                ("CASCADE", Keyword::Cascade),
+                ("CHECK", Keyword::Check),
                 ("COMMIT", Keyword::Commit),
                 ("CREATE", Keyword::Create),
                 ("CREATE INDEX", Keyword::CreateIndex),
                 ("CREATE TABLE", Keyword::CreateTable),
+                ("CREATE UNIQUE INDEX", Keyword::CreateUniqueIndex),
+                ("DEFAULT", Keyword::Default),
                 ("DELETE", Keyword::Delete),
                 ("DESC", Keyword::Desc),
                 ("DISTINCT", Keyword::Distinct),
                 ("EXPLAIN", Keyword::Explain),
+                ("FOREIGN KEY", Keyword::ForeignKey),
                 ("FROM", Keyword::From),
                 ("GROUP BY", Keyword::GroupBy),
                 ("HAVING", Keyword::Having),
@@ -81,10 +96,14 @@ impl KeywordTokenizer {
                 ("JOIN", Keyword::Join),
                 ("LEFT JOIN", Keyword::LeftJoin),
                 ("LIMIT", Keyword::Limit),
+                ("NO ACTION", Keyword::NoAction),
                 ("NOT NULL", Keyword::NotNull),
                 ("ON", Keyword::On),
                 ("ORDER BY", Keyword::OrderBy),
                 ("OUTER JOIN", Keyword::OuterJoin),
+                ("PRIMARY KEY", Keyword::PrimaryKey),
+                ("REFERENCES", Keyword::References),
+                ("RESTRICT", Keyword::Restrict),
                 ("RIGHT JOIN", Keyword::RightJoin),
                 ("ROLLBACK", Keyword::Rollback),
                 ("ROWNUM", Keyword::Rownum),
@@ -93,6 +112,7 @@ impl KeywordTokenizer {
                 ("SET", Keyword::Set),
                 ("TABLE", Keyword::Table),
                 ("TRUNCATE TABLE", Keyword::TruncateTable),
+                ("UNIQUE", Keyword::Unique),
                 ("UPDATE", Keyword::Update),
                 ("VALUES", Keyword::Values),
                 ("WHERE", Keyword::Where),
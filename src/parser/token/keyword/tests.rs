@@ -9,6 +9,7 @@ fn full_match() {
     assert_eq!(tokenizer.largest_match("ASC"), Some((Token::Keyword(Keyword::Asc), 3)));
     assert_eq!(tokenizer.largest_match("BETWEEN"), Some((Token::Keyword(Keyword::Between), 7)));
     assert_eq!(tokenizer.largest_match("create index"), Some((Token::Keyword(Keyword::CreateIndex), 12)));
+    assert_eq!(tokenizer.largest_match("create unique index"), Some((Token::Keyword(Keyword::CreateUniqueIndex), 19)));
     assert_eq!(tokenizer.largest_match("DIStincT"), Some((Token::Keyword(Keyword::Distinct), 8)));
     assert_eq!(tokenizer.largest_match("EXPLAIN"), Some((Token::Keyword(Keyword::Explain), 7)));
     assert_eq!(tokenizer.largest_match("FROM"), Some((Token::Keyword(Keyword::From), 4)));
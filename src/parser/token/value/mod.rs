@@ -7,7 +7,9 @@ mod tests;
 #[derive(Debug, PartialEq, Clone)]
 pub enum Value {
     Integer(i64),
-    Float(f64),
+    /// Number with a fractional part, as written in the statement so that it can be converted exactly to the type it is
+    /// used as (e.g. a `NUMERIC` with more digits than an `f64`)
+    Float(String),
     String(String),
     Boolean(bool),
     Null,
@@ -155,14 +157,9 @@ impl ValueTokenizer {
                 cursor,
             )),
             ValueFsmState::FractionalStart => None,
-            ValueFsmState::Fractional => Some((
-                Token::Value(Value::Float(
-                    raw_value
-                        .parse()
-                        .expect("Float value expected in Float state"),
-                )),
-                cursor,
-            )),
+            ValueFsmState::Fractional => {
+                Some((Token::Value(Value::Float(raw_value.to_string())), cursor))
+            }
             ValueFsmState::String => None,
             ValueFsmState::StringEnd => Some((
                 Token::Value(Value::String(raw_value[1..raw_value.len() - 1].to_string())),
@@ -17,8 +17,8 @@ fn integer() {
 fn float() {
     let t = ValueTokenizer::new();
 
-    assert_eq!(t.largest_match("123.1"), Some((Token::Value(Value::Float(123.1)), 5)));
-    assert_eq!(t.largest_match("999.342"), Some((Token::Value(Value::Float(999.342)), 7)));
+    assert_eq!(t.largest_match("123.1"), Some((Token::Value(Value::Float("123.1".to_string())), 5)));
+    assert_eq!(t.largest_match("999.342"), Some((Token::Value(Value::Float("999.342".to_string())), 7)));
     assert_eq!(t.largest_match("123."), None);
     assert_eq!(t.largest_match("+123.1"), None);
 }
@@ -1,12 +1,14 @@
-use std::sync::Arc;
+use std::{ops::Bound, sync::Arc};
 
 use crate::catalog::{Catalog, OID};
 use crate::exec::executor::delete::DeleteExecutor;
+use crate::exec::executor::idx_range_scan::IdxRangeScanExecutor;
 use crate::exec::executor::idx_scan::IdxScanExecutor;
 use crate::exec::executor::insert::InsertExecutor;
 use crate::exec::executor::update::UpdateExecutor;
 use crate::exec::executor::ExecutorContext;
 use crate::exec::plan::delete::DeletePlanNode;
+use crate::exec::plan::idx_range_scan::IdxRangeScanPlanNode;
 use crate::exec::plan::idx_scan::IdxScanPlanNode;
 use crate::exec::plan::insert::InsertPlanNode;
 use crate::exec::plan::update::UpdatePlanNode;
@@ -27,8 +29,10 @@ use crate::exec::{
         values::ValuesPlanNode, PlanNode,
     },
 };
+use crate::index::{IndexMeta, IndexType, ScanDirection};
 use crate::sample_code::util::create_table_with_values;
 use crate::table::{
+    numeric::RoundingMode,
     schema::{Column, ColumnType, Schema},
     value::{ColumnValue, IntegerValue},
 };
@@ -124,6 +128,7 @@ pub fn filter_executor(child_pln: PlanNode, child_exec: Executor) -> (FilterExec
                 value: ColumnValue::Integer(IntegerValue { value: 2 }),
             })),
             typ: ArithmeticType::Mod,
+            rounding: RoundingMode::HalfUp,
         })),
         right: Box::new(Expression::Constant(ConstantExpression {
             value: ColumnValue::Integer(IntegerValue { value: 0 }),
@@ -258,6 +263,7 @@ pub fn update_executor(
         //         return_type: Column::new(ColumnType::Integer),
         //     })),
         //     typ: ArithmeticType::Multiply,
+        //     rounding: RoundingMode::HalfUp,
         // }),
         const_int(12),
         column_with(1, ColumnType::Boolean),
@@ -312,6 +318,7 @@ pub fn idx_scan_executor(c_type: TableConstructorType) -> (IdxScanExecutor, Tabl
         table_oid,
         table_name: table_name.clone(),
         filter_expr,
+        columns: None,
     };
 
     (
@@ -319,3 +326,68 @@ pub fn idx_scan_executor(c_type: TableConstructorType) -> (IdxScanExecutor, Tabl
         (executor_context, schema, table_oid, table_name),
     )
 }
+
+/// EXEC: () -> (int, bool, decimal)
+/// SIDE: creates a table and inserts six tuples into it; creates B+ tree index for table with "first_col"
+///
+/// Scans the rows matching `filter_expr`, or all the rows (sorted) if there is no filter.
+pub fn idx_range_scan_executor(
+    c_type: TableConstructorType,
+    filter_expr: Option<&BooleanExpression>,
+    direction: ScanDirection,
+) -> (IdxRangeScanExecutor, TableContext) {
+    let (executor_context, schema, table_oid, table_name) = match c_type {
+        TableConstructorType::WithoutTable((executor_context, schema, table_oid, table_name)) => {
+            (executor_context, schema, table_oid, table_name)
+        }
+        TableConstructorType::WithTable(db_file) => {
+            let (executor_context, schema, table_oid, table_name) =
+                create_table_with_values(db_file, &[5, 3, 8, 1, 3, 9]);
+            (executor_context, schema, table_oid, table_name)
+        }
+    };
+
+    let key_schema = Schema::with_types(vec![ColumnType::Integer]);
+    let index_meta = IndexMeta::new(key_schema.clone(), "first_col".to_string(), vec![0])
+        .with_type(IndexType::BPlusTree);
+    let _ = executor_context
+        .catalog
+        .create_index_with_meta(
+            index_meta,
+            &table_name,
+            schema.clone(),
+            key_schema.get_tuple_len(),
+        )
+        .unwrap();
+
+    let output_schema = Schema::with_types(vec![
+        ColumnType::Integer,
+        ColumnType::Boolean,
+        ColumnType::Decimal,
+    ]);
+    let plan = match filter_expr {
+        Some(filter_expr) => IdxRangeScanPlanNode::from_filter(
+            output_schema,
+            table_oid,
+            table_name.clone(),
+            filter_expr,
+            direction,
+        )
+        .unwrap(),
+        None => IdxRangeScanPlanNode {
+            output_schema,
+            table_oid,
+            table_name: table_name.clone(),
+            col_index: 0,
+            lower: Bound::Unbounded,
+            upper: Bound::Unbounded,
+            direction,
+            columns: None,
+        },
+    };
+
+    (
+        IdxRangeScanExecutor::new(executor_context.clone(), plan),
+        (executor_context, schema, table_oid, table_name),
+    )
+}
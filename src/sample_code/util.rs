@@ -56,10 +56,7 @@ pub fn create_table_with_values(
     // init executor context
     let bpm = Arc::new(BufferPoolManager::new(db_file, 2, 2));
     let catalog = Arc::new(Catalog::new(bpm.clone()));
-    let executor_context = ExecutorContext {
-        catalog: catalog.clone(),
-        bpm: bpm.clone(),
-    };
+    let executor_context = ExecutorContext::new(catalog.clone(), bpm.clone());
 
     // create a table
     bpm.new_page(); // this is needed as table heaps assume page with PID 0 is not used
@@ -85,3 +82,39 @@ pub fn create_table_with_values(
 
     (executor_context, schema, table_oid, table_name)
 }
+
+/// Creates the tables `users (id)` with the ids 1, 2 and 3 and `orders (user_id, amount)` with the orders `(1, 10)`,
+/// `(1, 11)`, `(3, 30)` and `(4, 40)`, for joins.
+pub fn create_join_tables(db_file: String) -> ExecutorContext {
+    let bpm = Arc::new(BufferPoolManager::new(db_file, 100, 2));
+    let catalog = Arc::new(Catalog::new(bpm.clone()));
+    let executor_context = ExecutorContext::new(catalog.clone(), bpm.clone());
+    bpm.new_page(); // this is needed as table heaps assume page with PID 0 is not used
+
+    let meta = TupleMeta {
+        ts: 0,
+        is_deleted: false,
+    };
+    let int = |value| ColumnValue::Integer(IntegerValue { value });
+
+    let schema = Schema::with_types(vec![ColumnType::Integer]);
+    let users = catalog.create_table("users", schema.clone()).unwrap();
+    let users = users.lock().unwrap();
+    for id in [1, 2, 3] {
+        users
+            .table
+            .insert_tuple(meta.clone(), Tuple::new(vec![int(id)], &schema));
+    }
+
+    let schema = Schema::with_types(vec![ColumnType::Integer, ColumnType::Integer]);
+    let orders = catalog.create_table("orders", schema.clone()).unwrap();
+    let orders = orders.lock().unwrap();
+    for (user_id, amount) in [(1, 10), (1, 11), (3, 30), (4, 40)] {
+        orders.table.insert_tuple(
+            meta.clone(),
+            Tuple::new(vec![int(user_id), int(amount)], &schema),
+        );
+    }
+
+    executor_context
+}
@@ -0,0 +1,594 @@
+//! Helpers for converting between the integer representation of the date and time column types and their calendar
+//! representation. Dates are stored as days since `1970-01-01`, times as microseconds since midnight and timestamps as
+//! microseconds since `1970-01-01T00:00:00` (UTC).
+
+use std::{
+    fmt::Display,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const MICROS_PER_SECOND: u64 = 1_000_000;
+pub const MICROS_PER_DAY: u64 = 86_400 * MICROS_PER_SECOND;
+
+/// Returns the number of days since `1970-01-01` of the given date in the proleptic Gregorian calendar.
+pub fn days_from_civil(year: i32, month: u32, day: u32) -> i32 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month = month as i32;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i32 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+/// Returns the `(year, month, day)` of the date that is `days` days after `1970-01-01`.
+pub fn civil_from_days(days: i32) -> (i32, u32, u32) {
+    let days = days + 719468;
+    let era = if days >= 0 { days } else { days - 146096 } / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+/// Returns the number of days in `month` of `year`.
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the current timestamp, read from the system clock.
+pub fn now() -> i64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(since_epoch) => since_epoch.as_micros() as i64,
+        Err(e) => -(e.duration().as_micros() as i64),
+    }
+}
+
+/// Splits microseconds since `1970-01-01T00:00:00` into days since `1970-01-01` and microseconds since midnight.
+pub fn split_timestamp(micros: i64) -> (i32, u64) {
+    let micros_per_day = MICROS_PER_DAY as i64;
+
+    (
+        micros.div_euclid(micros_per_day) as i32,
+        micros.rem_euclid(micros_per_day) as u64,
+    )
+}
+
+/// Formats days since `1970-01-01` as `YYYY-MM-DD`.
+pub fn format_date(days: i32) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Formats microseconds since midnight as `HH:MM:SS`, followed by `.ffffff` if there is a fractional part.
+pub fn format_time(micros: u64) -> String {
+    let seconds = micros / MICROS_PER_SECOND;
+    let fraction = micros % MICROS_PER_SECOND;
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+
+    if fraction == 0 {
+        time
+    } else {
+        format!("{}.{:06}", time, fraction)
+    }
+}
+
+/// Formats microseconds since `1970-01-01T00:00:00` as the ISO-8601 `YYYY-MM-DDTHH:MM:SS`, followed by `.ffffff` if there
+/// is a fractional part.
+pub fn format_timestamp(micros: i64) -> String {
+    let (days, time) = split_timestamp(micros);
+    format!("{}T{}", format_date(days), format_time(time))
+}
+
+/// Parses an ISO-8601 date (`YYYY-MM-DD`) into days since `1970-01-01`.
+///
+/// # Errors
+/// Will return `Err` if `raw` is not a valid date.
+pub fn parse_date(raw: &str) -> Result<i32, ()> {
+    let mut parts = raw.splitn(3, '-');
+    let year = parse_digits(parts.next(), 4)? as i32;
+    let month = parse_digits(parts.next(), 2)? as u32;
+    let day = parse_digits(parts.next(), 2)? as u32;
+
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return Err(());
+    }
+
+    Ok(days_from_civil(year, month, day))
+}
+
+/// Parses an ISO-8601 time (`HH:MM[:SS[.f]]`, with up to 6 fractional digits) into microseconds since midnight.
+///
+/// # Errors
+/// Will return `Err` if `raw` is not a valid time.
+pub fn parse_time(raw: &str) -> Result<u64, ()> {
+    let (raw, fraction) = match raw.split_once('.') {
+        Some((raw, fraction)) if (1..=6).contains(&fraction.len()) => (raw, fraction),
+        Some(_) => return Err(()),
+        None => (raw, "0"),
+    };
+    if !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return Err(());
+    }
+    let fraction: u64 = format!("{:0<6}", fraction).parse().map_err(|_| ())?;
+
+    let mut parts = raw.splitn(3, ':');
+    let hours = parse_digits(parts.next(), 2)?;
+    let minutes = parse_digits(parts.next(), 2)?;
+    let seconds = match parts.next() {
+        Some(seconds) => parse_digits(Some(seconds), 2)?,
+        None => 0,
+    };
+
+    if hours > 23 || minutes > 59 || seconds > 59 {
+        return Err(());
+    }
+
+    Ok((hours * 3600 + minutes * 60 + seconds) * MICROS_PER_SECOND + fraction)
+}
+
+/// Parses an ISO-8601 timestamp into microseconds since `1970-01-01T00:00:00`. The date and the time can be separated
+/// by either `T` or a space, the time can be followed by `Z` and can be missing entirely (midnight).
+///
+/// # Errors
+/// Will return `Err` if `raw` is not a valid timestamp.
+pub fn parse_timestamp(raw: &str) -> Result<i64, ()> {
+    let raw = raw.strip_suffix('Z').unwrap_or(raw);
+    let (date, time) = match raw.split_once(['T', ' ']) {
+        Some((date, time)) => (parse_date(date)?, parse_time(time)?),
+        None => (parse_date(raw)?, 0),
+    };
+
+    Ok(date as i64 * MICROS_PER_DAY as i64 + time as i64)
+}
+
+/// Parses a number that has exactly `digits` digits.
+fn parse_digits(raw: Option<&str>, digits: usize) -> Result<u64, ()> {
+    match raw {
+        Some(raw) if raw.len() == digits && raw.chars().all(|c| c.is_ascii_digit()) => {
+            raw.parse().map_err(|_| ())
+        }
+        _ => Err(()),
+    }
+}
+
+/// Amount of time that is added to timestamps calendar-aware: months are added first (clamping the day to the length of
+/// the resulting month, so that `2024-01-31 + 1 month` is `2024-02-29`), then days and then the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub micros: i64,
+}
+
+impl Interval {
+    pub fn new(months: i32, days: i32, micros: i64) -> Self {
+        Self {
+            months,
+            days,
+            micros,
+        }
+    }
+
+    pub fn days(days: i32) -> Self {
+        Self::new(0, days, 0)
+    }
+
+    pub fn hours(hours: i64) -> Self {
+        Self::new(0, 0, hours * 3600 * MICROS_PER_SECOND as i64)
+    }
+
+    /// Parses an ISO-8601 duration, such as `P1Y2M3DT4H5M6.5S` or `P2W`. Each component can be negative.
+    ///
+    /// # Errors
+    /// Will return `Err` if `raw` is not a valid duration.
+    pub fn parse(raw: &str) -> Result<Self, ()> {
+        let raw = raw.strip_prefix('P').ok_or(())?;
+        if raw.is_empty() {
+            return Err(());
+        }
+
+        let mut interval = Self::default();
+        let mut time = false;
+        let mut number = String::new();
+        for c in raw.chars() {
+            match c {
+                'T' if !time && number.is_empty() => time = true,
+                '0'..='9' | '-' | '.' => number.push(c),
+                unit => {
+                    interval = interval.add(Self::component(&number, unit, time)?);
+                    number.clear();
+                }
+            }
+        }
+
+        if !number.is_empty() {
+            return Err(());
+        }
+
+        Ok(interval)
+    }
+
+    /// Returns the interval represented by a single `<number><unit>` component of a duration.
+    fn component(number: &str, unit: char, time: bool) -> Result<Self, ()> {
+        if unit == 'S' && time {
+            let (seconds, fraction) = number.split_once('.').unwrap_or((number, "0"));
+            if fraction.len() > 6 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+                return Err(());
+            }
+            let seconds: i64 = seconds.parse().map_err(|_| ())?;
+            let fraction: i64 = format!("{:0<6}", fraction).parse().map_err(|_| ())?;
+            let fraction = if number.starts_with('-') {
+                -fraction
+            } else {
+                fraction
+            };
+
+            return Ok(Self::new(
+                0,
+                0,
+                seconds * MICROS_PER_SECOND as i64 + fraction,
+            ));
+        }
+
+        let value: i64 = number.parse().map_err(|_| ())?;
+        let micros_per_minute = 60 * MICROS_PER_SECOND as i64;
+        match (unit, time) {
+            ('Y', false) => Ok(Self::new(value as i32 * 12, 0, 0)),
+            ('M', false) => Ok(Self::new(value as i32, 0, 0)),
+            ('W', false) => Ok(Self::days(value as i32 * 7)),
+            ('D', false) => Ok(Self::days(value as i32)),
+            ('H', true) => Ok(Self::hours(value)),
+            ('M', true) => Ok(Self::new(0, 0, value * micros_per_minute)),
+            _ => Err(()),
+        }
+    }
+
+    pub fn add(self, other: Interval) -> Self {
+        Self::new(
+            self.months + other.months,
+            self.days + other.days,
+            self.micros + other.micros,
+        )
+    }
+
+    pub fn sub(self, other: Interval) -> Self {
+        self.add(other.negate())
+    }
+
+    pub fn negate(self) -> Self {
+        Self::new(-self.months, -self.days, -self.micros)
+    }
+
+    pub fn multiply(self, factor: i32) -> Self {
+        Self::new(
+            self.months * factor,
+            self.days * factor,
+            self.micros * factor as i64,
+        )
+    }
+
+    /// Adds the interval to `timestamp`.
+    ///
+    /// # Errors
+    /// Will return `Err` if the result is out of the range of timestamps.
+    pub fn add_to(&self, timestamp: i64) -> Result<i64, ()> {
+        let (days, time) = split_timestamp(timestamp);
+        let (year, month, day) = civil_from_days(days);
+
+        let months = year as i64 * 12 + month as i64 - 1 + self.months as i64;
+        let (year, month) = (months.div_euclid(12), months.rem_euclid(12) as u32 + 1);
+        // timestamps only cover about 292000 years around 1970, further years would overflow the number of days
+        if year.abs() > 300_000 {
+            return Err(());
+        }
+        let year = year as i32;
+        let day = day.min(days_in_month(year, month));
+
+        let days = days_from_civil(year, month, day) as i64 + self.days as i64;
+        days.checked_mul(MICROS_PER_DAY as i64)
+            .and_then(|micros| micros.checked_add(time as i64))
+            .and_then(|micros| micros.checked_add(self.micros))
+            .ok_or(())
+    }
+
+    /// Returns the interval between two timestamps, as days and microseconds.
+    pub fn between(from: i64, to: i64) -> Self {
+        // the difference of two timestamps can be out of the range of timestamps
+        let micros = to as i128 - from as i128;
+        let days = micros / MICROS_PER_DAY as i128;
+
+        Self::new(
+            0,
+            days as i32,
+            (micros - days * MICROS_PER_DAY as i128) as i64,
+        )
+    }
+}
+
+impl Display for Interval {
+    /// Formats the interval as an ISO-8601 duration.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if *self == Self::default() {
+            return write!(f, "PT0S");
+        }
+
+        write!(f, "P")?;
+        for (value, unit) in [
+            (self.months / 12, 'Y'),
+            (self.months % 12, 'M'),
+            (self.days, 'D'),
+        ] {
+            if value != 0 {
+                write!(f, "{}{}", value, unit)?;
+            }
+        }
+
+        if self.micros == 0 {
+            return Ok(());
+        }
+
+        write!(f, "T")?;
+        let sign = if self.micros < 0 { "-" } else { "" };
+        let micros = self.micros.unsigned_abs();
+        let seconds = micros / MICROS_PER_SECOND;
+        let fraction = micros % MICROS_PER_SECOND;
+        for (value, unit) in [(seconds / 3600, 'H'), (seconds / 60 % 60, 'M')] {
+            if value != 0 {
+                write!(f, "{}{}{}", sign, value, unit)?;
+            }
+        }
+
+        match (seconds % 60, fraction) {
+            (0, 0) => Ok(()),
+            (seconds, 0) => write!(f, "{}{}S", sign, seconds),
+            (seconds, fraction) => {
+                let fraction = format!("{:06}", fraction);
+                write!(f, "{}{}.{}S", sign, seconds, fraction.trim_end_matches('0'))
+            }
+        }
+    }
+}
+
+/// Part of a date, time or timestamp that can be extracted with `EXTRACT(part FROM value)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DatePart {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    /// Day of the week, `0` being Sunday
+    DayOfWeek,
+    /// Day of the year, starting from `1`
+    DayOfYear,
+    /// Number of seconds since `1970-01-01T00:00:00`
+    Epoch,
+}
+
+impl DatePart {
+    /// Returns the date part with the SQL `name` (case insensitive), such as `YEAR` or `DOW`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "YEAR" => Some(DatePart::Year),
+            "MONTH" => Some(DatePart::Month),
+            "DAY" => Some(DatePart::Day),
+            "HOUR" => Some(DatePart::Hour),
+            "MINUTE" => Some(DatePart::Minute),
+            "SECOND" => Some(DatePart::Second),
+            "DOW" => Some(DatePart::DayOfWeek),
+            "DOY" => Some(DatePart::DayOfYear),
+            "EPOCH" => Some(DatePart::Epoch),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DatePart::Year => "YEAR",
+            DatePart::Month => "MONTH",
+            DatePart::Day => "DAY",
+            DatePart::Hour => "HOUR",
+            DatePart::Minute => "MINUTE",
+            DatePart::Second => "SECOND",
+            DatePart::DayOfWeek => "DOW",
+            DatePart::DayOfYear => "DOY",
+            DatePart::Epoch => "EPOCH",
+        }
+    }
+
+    /// Returns `true` if the part only depends on the time of the day.
+    pub fn is_time_part(&self) -> bool {
+        matches!(self, DatePart::Hour | DatePart::Minute | DatePart::Second)
+    }
+
+    /// Extracts the part from the point in time that is `days` days after `1970-01-01` and `micros` microseconds after
+    /// midnight.
+    pub fn extract(&self, days: i32, micros: u64) -> i64 {
+        let (year, month, day) = civil_from_days(days);
+        let seconds = (micros / MICROS_PER_SECOND) as i64;
+
+        match self {
+            DatePart::Year => year as i64,
+            DatePart::Month => month as i64,
+            DatePart::Day => day as i64,
+            DatePart::Hour => seconds / 3600,
+            DatePart::Minute => seconds / 60 % 60,
+            DatePart::Second => seconds % 60,
+            // 1970-01-01 was a Thursday
+            DatePart::DayOfWeek => (days as i64 + 4).rem_euclid(7),
+            DatePart::DayOfYear => (days - days_from_civil(year, 1, 1) + 1) as i64,
+            DatePart::Epoch => days as i64 * 86_400 + seconds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        civil_from_days, days_from_civil, format_date, format_time, format_timestamp, parse_date,
+        parse_time, parse_timestamp, split_timestamp, DatePart, Interval, MICROS_PER_DAY,
+        MICROS_PER_SECOND,
+    };
+
+    #[test]
+    fn civil_conversion() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+
+        for days in [-800000, -1, 0, 59, 60, 11016, 11017, 19782, 2932896] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(days_from_civil(2024, 2, 29)), "2024-02-29");
+        assert_eq!(format_time(0), "00:00:00");
+        assert_eq!(
+            format_time((13 * 3600 + 5 * 60 + 9) * MICROS_PER_SECOND),
+            "13:05:09"
+        );
+        assert_eq!(format_time(MICROS_PER_SECOND + 250), "00:00:01.000250");
+    }
+
+    #[test]
+    fn timestamp_parsing() {
+        let timestamp = parse_timestamp("2024-02-29T13:05:09.25").unwrap();
+        assert_eq!(format_timestamp(timestamp), "2024-02-29T13:05:09.250000");
+        assert_eq!(parse_timestamp("2024-02-29 13:05:09.25Z"), Ok(timestamp));
+        assert_eq!(
+            parse_timestamp("2024-02-29"),
+            Ok(days_from_civil(2024, 2, 29) as i64 * MICROS_PER_DAY as i64)
+        );
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00");
+
+        // timestamps before 1970 are negative
+        assert_eq!(
+            parse_timestamp("1969-12-31T23:59:59"),
+            Ok(-(MICROS_PER_SECOND as i64))
+        );
+        assert_eq!(format_timestamp(-1), "1969-12-31T23:59:59.999999");
+        let timestamp = parse_timestamp("1815-06-18T11:30:00").unwrap();
+        assert!(timestamp < 0);
+        assert_eq!(format_timestamp(timestamp), "1815-06-18T11:30:00");
+
+        assert_eq!(parse_date("2023-02-29"), Err(()));
+        assert_eq!(parse_date("2023-13-01"), Err(()));
+        assert_eq!(parse_date("23-01-01"), Err(()));
+        assert_eq!(parse_time("24:00"), Err(()));
+        assert_eq!(parse_time("12:30"), Ok(45_000 * MICROS_PER_SECOND));
+        assert_eq!(parse_time("00:00:00.1234567"), Err(()));
+    }
+
+    #[test]
+    fn interval_parsing_and_formatting() {
+        let interval = Interval::parse("P1Y2M3DT4H5M6.5S").unwrap();
+        assert_eq!(interval.months, 14);
+        assert_eq!(interval.days, 3);
+        assert_eq!(
+            interval.micros,
+            ((4 * 60 + 5) * 60 + 6) * MICROS_PER_SECOND as i64 + 500_000
+        );
+        assert_eq!(interval.to_string(), "P1Y2M3DT4H5M6.5S");
+
+        assert_eq!(Interval::parse("P2W"), Ok(Interval::days(14)));
+        assert_eq!(Interval::parse("PT-36H"), Ok(Interval::hours(-36)));
+        assert_eq!(Interval::hours(-36).to_string(), "PT-36H");
+        assert_eq!(Interval::default().to_string(), "PT0S");
+        assert_eq!(Interval::parse("P"), Err(()));
+        assert_eq!(Interval::parse("P1H"), Err(()));
+        assert_eq!(Interval::parse("P1D2"), Err(()));
+    }
+
+    #[test]
+    fn interval_arithmetic() {
+        let jan_31 = parse_timestamp("2024-01-31T10:00:00").unwrap();
+
+        // months are clamped to the end of the month
+        let one_month = Interval::new(1, 0, 0);
+        assert_eq!(
+            format_timestamp(one_month.add_to(jan_31).unwrap()),
+            "2024-02-29T10:00:00"
+        );
+        assert_eq!(
+            format_timestamp(one_month.negate().add_to(jan_31).unwrap()),
+            "2023-12-31T10:00:00"
+        );
+        assert_eq!(
+            format_timestamp(Interval::hours(15).add_to(jan_31).unwrap()),
+            "2024-02-01T01:00:00"
+        );
+        assert_eq!(
+            format_timestamp(Interval::days(-30_000).add_to(jan_31).unwrap()),
+            "1941-12-12T10:00:00"
+        );
+        assert_eq!(Interval::new(i32::MAX, 0, 0).add_to(jan_31), Err(()));
+        assert_eq!(Interval::new(0, 0, i64::MAX).add_to(jan_31), Err(()));
+
+        let later = parse_timestamp("2024-02-02T12:30:00").unwrap();
+        let between = Interval::between(jan_31, later);
+        assert_eq!(between.to_string(), "P2DT2H30M");
+        assert_eq!(between.add_to(jan_31), Ok(later));
+
+        // before 1970
+        let leap_day = parse_timestamp("1968-02-29T23:00:00").unwrap();
+        assert_eq!(
+            format_timestamp(Interval::new(12, 0, 0).add_to(leap_day).unwrap()),
+            "1969-02-28T23:00:00"
+        );
+        assert_eq!(
+            format_timestamp(Interval::hours(2).add_to(leap_day).unwrap()),
+            "1968-03-01T01:00:00"
+        );
+        let between = Interval::between(later, leap_day);
+        assert_eq!(between.add_to(later), Ok(leap_day));
+    }
+
+    #[test]
+    fn date_parts() {
+        let timestamp = parse_timestamp("2024-03-01T13:05:09").unwrap();
+        let (days, micros) = split_timestamp(timestamp);
+
+        assert_eq!(DatePart::Year.extract(days, micros), 2024);
+        assert_eq!(DatePart::Month.extract(days, micros), 3);
+        assert_eq!(DatePart::Day.extract(days, micros), 1);
+        assert_eq!(DatePart::Hour.extract(days, micros), 13);
+        assert_eq!(DatePart::Minute.extract(days, micros), 5);
+        assert_eq!(DatePart::Second.extract(days, micros), 9);
+        assert_eq!(DatePart::DayOfWeek.extract(days, micros), 5); // Friday
+        assert_eq!(DatePart::DayOfYear.extract(days, micros), 61);
+        assert_eq!(
+            DatePart::Epoch.extract(days, micros),
+            timestamp / MICROS_PER_SECOND as i64
+        );
+        let (days, micros) = split_timestamp(parse_timestamp("1969-12-31T23:00:00").unwrap());
+        assert_eq!(DatePart::Year.extract(days, micros), 1969);
+        assert_eq!(DatePart::Hour.extract(days, micros), 23);
+        assert_eq!(DatePart::Epoch.extract(days, micros), -3600);
+        assert_eq!(DatePart::from_name("dow"), Some(DatePart::DayOfWeek));
+        assert_eq!(DatePart::from_name("week"), None);
+    }
+}
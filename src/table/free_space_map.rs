@@ -0,0 +1,176 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use crate::{config::DB_PAGE_SIZE, disk::disk_manager::PageID};
+
+/// Width in bytes of the free space classes: a page with `free` bytes belongs to the class `free / CLASS_SIZE`.
+const CLASS_SIZE: u16 = 64;
+const NUM_CLASSES: usize = DB_PAGE_SIZE as usize / CLASS_SIZE as usize;
+
+/// Keeps track of the (approximate) amount of free bytes in each of the pages of a table heap. The values are only
+/// hints: they are updated every time a page is written by the heap, but a page might have been changed by someone else
+/// in the meantime, so whoever uses a page picked from the map still needs to check if the tuple actually fits.
+///
+/// Pages are grouped in classes of `CLASS_SIZE` bytes of free space, so finding and updating a page takes constant time
+/// no matter how many pages the heap has. Pages are only picked from the classes where all the pages have enough space,
+/// and the pages of a class are picked in turns, so concurrent inserters are spread over the pages instead of all
+/// waiting for the latch of the same one.
+///
+/// The map is only kept in memory. Heaps are always created empty (the catalog isn't persisted), so it never has to be
+/// rebuilt from the pages; a heap opened from existing pages would have to fill it with the free space of every page.
+pub struct FreeSpaceMap {
+    pages: Mutex<Pages>,
+    /// Incremented on every search, used to pick the page among the ones of a class
+    next_pick: AtomicUsize,
+}
+
+struct Pages {
+    /// IDs of all the pages, in the order they were added to the heap
+    order: Vec<PageID>,
+    /// `page_id -> (free_bytes, position of the page in its class)`
+    entries: HashMap<PageID, (u16, usize)>,
+    /// `classes[c]` has the pages with at least `c * CLASS_SIZE` and less than `(c + 1) * CLASS_SIZE` free bytes
+    classes: Vec<Vec<PageID>>,
+}
+
+impl Pages {
+    fn add_to_class(&mut self, page_id: PageID, free: u16) {
+        let class = &mut self.classes[class_of(free)];
+        class.push(page_id);
+        self.entries.insert(page_id, (free, class.len() - 1));
+    }
+
+    fn remove_from_class(&mut self, free: u16, position: usize) {
+        let class = &mut self.classes[class_of(free)];
+        class.swap_remove(position);
+        if let Some(moved) = class.get(position) {
+            self.entries.get_mut(moved).unwrap().1 = position;
+        }
+    }
+}
+
+impl FreeSpaceMap {
+    pub fn new() -> Self {
+        Self {
+            pages: Mutex::new(Pages {
+                order: vec![],
+                entries: HashMap::new(),
+                classes: vec![vec![]; NUM_CLASSES],
+            }),
+            next_pick: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the ID of a page that has at least `required` free bytes, from the class with the least free space that
+    /// can fit them. Will return `None` if there is no such page.
+    pub fn find_page_with(&self, required: u16) -> Option<PageID> {
+        let pages = self.pages.lock().unwrap();
+        let first_class = required.div_ceil(CLASS_SIZE) as usize;
+        let class = pages
+            .classes
+            .iter()
+            .skip(first_class)
+            .find(|class| !class.is_empty())?;
+
+        let pick = self.next_pick.fetch_add(1, Ordering::Relaxed);
+        Some(class[pick % class.len()])
+    }
+
+    /// Returns the IDs of all the pages, in the order they were added to the heap.
+    pub fn page_ids(&self) -> Vec<PageID> {
+        self.pages.lock().unwrap().order.clone()
+    }
+
+    /// Sets the free bytes of the page with `page_id` to `free`. The page is added to the end of the map if it is not tracked yet.
+    pub fn update(&self, page_id: PageID, free: u16) {
+        let mut pages = self.pages.lock().unwrap();
+
+        match pages.entries.get(&page_id).copied() {
+            Some((old_free, _)) if class_of(old_free) == class_of(free) => {
+                pages.entries.get_mut(&page_id).unwrap().0 = free;
+            }
+            Some((old_free, position)) => {
+                pages.remove_from_class(old_free, position);
+                pages.add_to_class(page_id, free);
+            }
+            None => {
+                pages.order.push(page_id);
+                pages.add_to_class(page_id, free);
+            }
+        }
+    }
+}
+
+fn class_of(free: u16) -> usize {
+    (free / CLASS_SIZE) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::FreeSpaceMap;
+
+    #[test]
+    fn find_smallest_fitting_class() {
+        let fsm = FreeSpaceMap::new();
+        fsm.update(1, 10);
+        fsm.update(2, 100);
+        fsm.update(3, 1000);
+
+        assert_eq!(fsm.find_page_with(0), Some(1));
+        assert_eq!(fsm.find_page_with(5), Some(2));
+        assert_eq!(fsm.find_page_with(50), Some(2));
+        assert_eq!(fsm.find_page_with(100), Some(3));
+        assert_eq!(fsm.find_page_with(500), Some(3));
+        assert_eq!(fsm.find_page_with(1000), None);
+        assert_eq!(fsm.find_page_with(5000), None);
+    }
+
+    #[test]
+    fn update_existing() {
+        let fsm = FreeSpaceMap::new();
+        fsm.update(1, 10);
+        fsm.update(2, 100);
+
+        assert_eq!(fsm.find_page_with(50), Some(2));
+        fsm.update(1, 200);
+        fsm.update(2, 20);
+        assert_eq!(fsm.find_page_with(50), Some(1));
+        assert_eq!(fsm.find_page_with(128), Some(1));
+        assert_eq!(fsm.find_page_with(250), None);
+
+        // pages keep the order they were added in
+        fsm.update(3, 500);
+        fsm.update(1, 0);
+        assert_eq!(fsm.page_ids(), vec![1, 2, 3]);
+        assert_eq!(fsm.find_page_with(50), Some(3));
+    }
+
+    #[test]
+    fn spread_over_class() {
+        let fsm = FreeSpaceMap::new();
+        for page_id in 1..=4 {
+            fsm.update(page_id, 1000 + page_id as u16);
+        }
+        fsm.update(5, 2000);
+
+        let picked = (0..4)
+            .map(|_| fsm.find_page_with(500).unwrap())
+            .collect::<HashSet<_>>();
+        assert_eq!(picked, HashSet::from([1, 2, 3, 4]));
+
+        // pages leaving the class are no longer picked
+        fsm.update(2, 10);
+        fsm.update(4, 10);
+        let picked = (0..4)
+            .map(|_| fsm.find_page_with(500).unwrap())
+            .collect::<HashSet<_>>();
+        assert_eq!(picked, HashSet::from([1, 3]));
+    }
+}
@@ -1,6 +1,12 @@
-use std::sync::Arc;
+use std::{
+    sync::{Arc, Mutex},
+    vec::IntoIter,
+};
 
-use page::{TablePage, TupleMeta};
+use free_space_map::FreeSpaceMap;
+use page::{TablePage, TablePageMut, TablePageRef, TupleMeta};
+use schema::Schema;
+use skip_index::{ColumnPredicate, SkipIndex};
 use tuple::{Tuple, RID};
 
 use crate::disk::{
@@ -8,8 +14,12 @@ use crate::disk::{
     disk_manager::PageID,
 };
 
+pub mod datetime;
+pub mod free_space_map;
+pub mod numeric;
 pub mod page;
 pub mod schema;
+pub mod skip_index;
 pub mod tuple;
 pub mod value;
 
@@ -17,13 +27,21 @@ const END_PAGE_ID: PageID = 0;
 
 /// A table heap is a collection of pages that store tuples. The table heap is a linked list of pages, where each page points to the next page 
 /// in the list. The first page is the head of the list, and the last page is the tail of the list.
+///
+/// Inserts are thread safe: the free space map is used to pick a page that has room for the tuple and only that page's write latch is taken.
+/// The `last_page` lock is only taken when the heap needs to grow, so concurrent inserts into existing pages don't block each other.
+/// The space of deleted tuples counts as free for the map, and it is reclaimed by compacting the page when an insert needs it. Deleted
+/// tuples keep their slot, so their RIDs are never reused, but their data can't be read anymore once reclaimed.
 /// 
 /// # Assumptions
 /// The table heap assumes that the page with `PageID` 0 will never be used in the context of it.
 pub struct TableHeap {
     bpm: Arc<BufferPoolManager>,
     first_page: PageID,
-    last_page: PageID,
+    last_page: Mutex<PageID>,
+    fsm: FreeSpaceMap,
+    /// Summaries of the pages used by scans to skip pages, if the heap has them
+    skip_index: Option<SkipIndex>,
 }
 
 impl TableHeap {
@@ -31,193 +49,291 @@ impl TableHeap {
         let first_page = bpm.new_page();
 
         let mut page = bpm.get_write_page(first_page);
-        page.write(TablePage::empty().serialize());
+        let free_space = TablePageMut::init(page.data_mut()).as_page_ref().free_space();
         drop(page);
 
+        let fsm = FreeSpaceMap::new();
+        fsm.update(first_page, free_space);
+
         Self {
             bpm,
             first_page,
-            last_page: first_page,
+            last_page: Mutex::new(first_page),
+            fsm,
+            skip_index: None,
         }
     }
 
-    /// Insert a tuple in the table heap (**NOT THREAD SAFE**). Will return the RID of the inserted tuple or `None` if the tuple is too large to fit in a single page.
-    pub fn insert_tuple(&mut self, meta: TupleMeta, tuple: Tuple) -> Option<RID> {
-        let mut page = self.bpm.get_write_page(self.last_page);
-        let mut t_page = TablePage::deserialize(page.read());
+    /// Insert a tuple in the table heap. Will return the RID of the inserted tuple or `None` if the tuple is too large to fit in a single page.
+    pub fn insert_tuple(&self, meta: TupleMeta, tuple: Tuple) -> Option<RID> {
+        let required = TablePage::required_space(&tuple);
+        if required > TablePage::empty().free_space() as usize {
+            return None;
+        }
+
+        loop {
+            if let Some(rid) = self.try_insert_in_existing_page(&meta, &tuple, required as u16) {
+                return Some(rid);
+            }
 
-        if let Some(slot) = t_page.insert_tuple(meta.clone(), tuple.clone()) {
-            page.write(t_page.serialize());
+            // no page has space, grow the heap (unless someone else did it while we were waiting)
+            let mut last_page = self.last_page.lock().unwrap();
+            if self.fsm.find_page_with(required as u16).is_some() {
+                continue;
+            }
+
+            // write content of new page
+            let new_pid = self.bpm.new_page();
+            let mut new_page = self.bpm.get_write_page(new_pid);
+            let mut new_t_page = TablePageMut::init(new_page.data_mut());
+            let slot = new_t_page.insert_tuple(meta.clone(), &tuple)?;
+            let free_space = new_t_page.as_page_ref().free_space();
+            self.summarize(new_pid, &tuple);
+            drop(new_page);
+
+            // update next page pointer in old page
+            let mut page = self.bpm.get_write_page(*last_page);
+            TablePageMut::new(page.data_mut()).set_next_page(new_pid);
             drop(page);
 
+            self.fsm.update(new_pid, free_space);
+            *last_page = new_pid;
+
             return Some(RID {
-                page_id: self.last_page,
+                page_id: new_pid,
                 slot_num: slot,
             });
         }
+    }
 
-        // no space in page, create new one
-        let new_pid = self.bpm.new_page();
-        let mut new_t_page = TablePage::empty();
-        let slot = new_t_page.insert_tuple(meta, tuple)?;
+    /// Tries to insert the tuple in one of the pages that the free space map reports as having at least `required` free bytes. Will return
+    /// `None` if there is no such page.
+    fn try_insert_in_existing_page(&self, meta: &TupleMeta, tuple: &Tuple, required: u16) -> Option<RID> {
+        while let Some(pid) = self.fsm.find_page_with(required) {
+            let mut page = self.bpm.get_write_page(pid);
+            let (free_space, available_space) = {
+                let t_page = TablePageRef::new(page.read());
+                (t_page.free_space(), t_page.available_space())
+            };
+            let slot = if free_space >= required {
+                TablePageMut::new(page.data_mut()).insert_tuple(meta.clone(), tuple)
+            } else if available_space >= required {
+                // the space is taken by deleted tuples or left behind by updates, reclaim it
+                let mut t_page = TablePageMut::new(page.data_mut());
+                t_page.compact();
+                t_page.insert_tuple(meta.clone(), tuple)
+            } else {
+                None
+            };
+            if slot.is_some() {
+                self.summarize(pid, tuple);
+            }
 
-        // update next page pointer in old page
-        t_page.next_page = new_pid;
-        page.write(t_page.serialize());
-        drop(page);
-        self.last_page = new_pid;
+            // also fixes the entry if it was stale
+            self.fsm.update(pid, TablePageRef::new(page.read()).available_space());
+            drop(page);
 
-        // write content of new page
-        let mut new_page = self.bpm.get_write_page(new_pid);
-        new_page.write(new_t_page.serialize());
-        drop(new_page);
+            if let Some(slot) = slot {
+                return Some(RID {
+                    page_id: pid,
+                    slot_num: slot,
+                });
+            }
+        }
 
-        Some(RID {
-            page_id: new_pid,
-            slot_num: slot,
-        })
+        None
     }
 
+    /// Updates the meta of the tuple with `rid`. The bytes of deleted tuples are reported to the free space map, they are
+    /// reclaimed when an insert needs them (see `TablePageMut::compact`).
     pub fn update_tuple_meta(&self, meta: TupleMeta, rid: &RID) {
         let mut page = self.bpm.get_write_page(rid.page_id);
-        let mut t_page = TablePage::deserialize(page.read());
 
-        t_page
+        TablePageMut::new(page.data_mut())
             .update_tuple_meta(meta, rid)
             .expect("Invalid RID received for updating tuple meta");
+        self.fsm.update(
+            rid.page_id,
+            TablePageRef::new(page.read()).available_space(),
+        );
+    }
+
+    /// Replaces the tuple with `rid` with `tuple`, keeping its RID. Only the write latch of the page that contains the tuple is taken.
+    /// The free space map is updated with the free space of the page, like after an insert, including the bytes left behind
+    /// when the new tuple is smaller than the old one.
+    ///
+    /// # Errors
+    /// Will return `Err` if there is no tuple with `rid` or if `tuple` is larger than the old tuple, in which case the caller
+    /// should fall back to deleting the old tuple and inserting the new one.
+    pub fn update_tuple_in_place(&self, meta: TupleMeta, tuple: Tuple, rid: &RID) -> Result<(), ()> {
+        let mut page = self.bpm.get_write_page(rid.page_id);
+        TablePageMut::new(page.data_mut()).update_tuple_in_place(meta, &tuple, rid)?;
+        self.summarize(rid.page_id, &tuple);
+        self.fsm.update(
+            rid.page_id,
+            TablePageRef::new(page.read()).available_space(),
+        );
 
-        page.write(t_page.serialize());
+        Ok(())
     }
 
     pub fn get_tuple(&self, rid: &RID) -> Option<(TupleMeta, Tuple)> {
         let page = self.bpm.get_read_page(rid.page_id);
-        let t_page = TablePage::deserialize(page.read());
-        let (meta, tuple) = t_page.get_tuple(rid)?;
 
-        Some((meta.clone(), tuple.clone()))
+        TablePageRef::new(page.read()).get_tuple(rid)
     }
 
     pub fn sequencial_dump(&self) -> Vec<(TupleMeta, Tuple)> {
-        let mut data = vec![];
-        let mut current_pid = self.first_page;
-
-        while current_pid != END_PAGE_ID {
-            let page = self.bpm.get_read_page(current_pid);
-            let t_page = TablePage::deserialize(page.read());
-
-            data.append(
-                &mut t_page
-                    .get_tuples()
-                    .iter()
-                    .map(|(m, t)| ((*m).clone(), (*t).clone()))
-                    .collect(),
-            );
-
-            current_pid = t_page.next_page;
-        }
-
-        data
+        self.iter().map(|(meta, tuple, _)| (meta, tuple)).collect()
     }
 
+    /// Returns a cursor over all the tuples in the table heap (including deleted ones whose data wasn't reclaimed yet), in heap order.
     pub fn iter(&self) -> TableHeapIterator {
-        TableHeapIterator::new(self)
+        TableHeapIterator::new(self.bpm.clone(), self.first_page)
     }
 
-    /// Returns the first tuple in the table heap. Returns `None` if the table is empty.
-    pub fn first_tuple(&self) -> Option<(TupleMeta, Tuple, RID)> {
-        let page = self.bpm.get_read_page(self.first_page);
-        let t_page = TablePage::deserialize(page.read());
+    /// Returns a cursor over the tuples of the pages that can have tuples satisfying all the `predicates`, in heap order.
+    /// Without a skip index, all the pages are read (see `iter`). The tuples of the pages read still have to be filtered.
+    pub fn iter_skipping(&self, predicates: &[ColumnPredicate]) -> TableHeapIterator {
+        let Some(skip_index) = &self.skip_index else {
+            return self.iter();
+        };
+
+        let pages = self
+            .fsm
+            .page_ids()
+            .into_iter()
+            .filter(|pid| skip_index.may_match(*pid, predicates))
+            .collect();
+        TableHeapIterator::with_pages(self.bpm.clone(), pages)
+    }
 
-        let (meta, tuple) = t_page.get_tuples().first()?.clone();
+    pub fn skip_index(&self) -> Option<&SkipIndex> {
+        self.skip_index.as_ref()
+    }
 
-        Some((
-            (*meta).clone(),
-            (*tuple).clone(),
-            RID::new(self.first_page, 0),
-        ))
+    /// Creates a skip index on the `columns` of the heap, replacing the existing one, with the summaries of the live
+    /// tuples of the heap.
+    pub fn create_skip_index(&mut self, schema: Schema, columns: Vec<usize>) {
+        self.skip_index = Some(SkipIndex::new(schema, columns));
+        self.rebuild_skip_index();
     }
 
-    /// Returns the tuple after the given RID. Returns `None` if if the given RID is the last tuple in the table heap.
-    ///
-    /// # Panics
-    /// Panics if there is no tuple with the given RID in the table.
-    pub fn tuple_after(&self, rid: &RID) -> Option<(TupleMeta, Tuple, RID)> {
-        let page = self.bpm.get_read_page(rid.page_id);
-        let t_page = TablePage::deserialize(page.read());
-
-        let next_rid = RID::new(rid.page_id, rid.slot_num + 1);
-        match t_page.get_tuple(&next_rid) {
-            Some((meta, tuple)) => Some(((*meta).clone(), (*tuple).clone(), next_rid)),
-            None => {
-                if t_page.next_page == END_PAGE_ID {
-                    None
-                } else {
-                    let next_pid = t_page.next_page;
-                    let next_page = self.bpm.get_read_page(t_page.next_page);
-                    let next_t_page = TablePage::deserialize(next_page.read());
-                    drop(t_page);
-
-                    let (meta, tuple) = next_t_page.get_tuples().first()?.clone();
-
-                    Some(((*meta).clone(), (*tuple).clone(), RID::new(next_pid, 0)))
-                }
+    /// Rebuilds the summaries of the skip index with the live tuples of the heap (see `Catalog::check_table`), dropping the
+    /// values of the tuples deleted or replaced since it was built, so more pages can be skipped.
+    pub fn rebuild_skip_index(&self) {
+        let Some(skip_index) = &self.skip_index else {
+            return;
+        };
+
+        skip_index.clear();
+        for (meta, tuple, rid) in self.iter() {
+            if !meta.is_deleted {
+                skip_index.add(rid.page_id, &tuple);
             }
         }
     }
+
+    /// Adds `tuple`, written in the page with `page_id`, to the summaries of the skip index. Has to be called while the
+    /// page is latched, so scans can't read the tuple before the page summaries have it.
+    fn summarize(&self, page_id: PageID, tuple: &Tuple) {
+        if let Some(skip_index) = &self.skip_index {
+            skip_index.add(page_id, tuple);
+        }
+    }
 }
 
-pub struct TableHeapIterator<'a> {
-    heap: &'a TableHeap,
-    current_page: PageID,
-    current_slot: usize,
+/// Cursor over the tuples of a table heap. Only one page is kept in memory at a time and tuples are decoded lazily straight
+/// from the page bytes, moving to the next page only when the current one is exhausted.
+///
+/// The cursor isn't zero-copy on purpose: each page is copied out of the buffer pool once and its latch released right
+/// away, instead of holding the read guard until the page is exhausted. Executors further up in the pipeline (e.g. updates
+/// and deletes) write to the page being scanned while the cursor is alive, which would deadlock on a held read latch, and
+/// the guard borrows the buffer pool that the cursor only owns through an `Arc`. The cost is one page copy per page, the
+/// tuples are still decoded one at a time. Tuples inserted in the current page after it was copied will not be returned.
+pub struct TableHeapIterator {
+    bpm: Arc<BufferPoolManager>,
+    /// The page to read once the current one is exhausted. `None` when there are no more pages.
+    next_page: Option<PageID>,
+    /// Pages left to read after `next_page` when only some pages of the heap are read, `None` when the links between the
+    /// pages are followed.
+    pages: Option<IntoIter<PageID>>,
+    /// Copy of the page that is currently iterated (see above for why it isn't borrowed from the buffer pool).
+    page_data: Option<Vec<u8>>,
+    current_slot: u16,
 }
 
-impl<'a> TableHeapIterator<'a> {
-    fn new(heap: &'a TableHeap) -> Self {
+impl TableHeapIterator {
+    fn new(bpm: Arc<BufferPoolManager>, first_page: PageID) -> Self {
         Self {
-            heap,
-            current_page: heap.first_page,
+            bpm,
+            next_page: Some(first_page),
+            pages: None,
+            page_data: None,
+            current_slot: 0,
+        }
+    }
+
+    /// Creates a cursor over the tuples of the given pages of a heap only, in the order of `pages`.
+    fn with_pages(bpm: Arc<BufferPoolManager>, pages: Vec<PageID>) -> Self {
+        let mut pages = pages.into_iter();
+
+        Self {
+            bpm,
+            next_page: pages.next(),
+            pages: Some(pages),
+            page_data: None,
             current_slot: 0,
         }
     }
 }
 
-impl<'a> Iterator for TableHeapIterator<'a> {
+impl Iterator for TableHeapIterator {
     type Item = (TupleMeta, Tuple, RID);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let page = self.heap.bpm.get_read_page(self.current_page);
-        let t_page = TablePage::deserialize(page.read());
-        let tuples = t_page.get_tuples();
-
-        if self.current_page == self.heap.last_page && self.current_slot >= tuples.len() {
-            return None;
-        }
-
-        if self.current_slot >= tuples.len() {
-            self.current_page = t_page.next_page;
-            self.current_slot = 0;
-            return self.next();
-        }
+        loop {
+            if self.page_data.is_none() {
+                let page = self.bpm.get_read_page(self.next_page?);
+                self.page_data = Some(page.read().clone());
+                self.current_slot = 0;
+            }
 
-        let (meta, tuple) = &tuples[self.current_slot];
-        let rid = RID::new(self.current_page, self.current_slot as u16);
+            let data = self.page_data.as_ref().unwrap();
+            let current_page = self.next_page.unwrap();
+            let t_page = TablePageRef::new(data);
+            while self.current_slot < t_page.num_tuples() {
+                let rid = RID::new(current_page, self.current_slot);
+                self.current_slot += 1;
 
-        self.current_slot += 1;
+                // the slots of reclaimed tuples have no data
+                if let Some((meta, tuple)) = t_page.get_tuple(&rid) {
+                    return Some((meta, tuple, rid));
+                }
+            }
 
-        Some(((*meta).clone(), (*tuple).clone(), rid))
+            // page exhausted, move to the next one
+            let next_page = t_page.next_page();
+            self.next_page = match self.pages.as_mut() {
+                Some(pages) => pages.next(),
+                None if next_page == END_PAGE_ID => None,
+                None => Some(next_page),
+            };
+            self.page_data = None;
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, env::temp_dir, fs::remove_file, sync::Arc};
+    use std::{collections::HashMap, env::temp_dir, fs::remove_file, sync::Arc, thread};
 
     use crate::{
-        disk::buffer_pool_manager::BufferPoolManager,
+        disk::buffer_pool_manager::{BufferPoolManager, DiskRead},
         table::{
-            page::TupleMeta,
+            page::{TablePageRef, TupleMeta},
             schema::{Column, ColumnType, Schema},
+            skip_index::{ColumnPredicate, ComparisonType},
             tuple::Tuple,
             value::{ColumnValue, SmallIntValue, VarcharValue},
             TableHeap,
@@ -252,7 +368,7 @@ mod tests {
         let db_path = temp_dir().join("th_insert_different_rids.db");
         let db_file_path = db_path.to_str().unwrap().to_string();
         let bpm = Arc::new(BufferPoolManager::new(String::from(db_file_path), 100, 2));
-        let mut table_heap = TableHeap::new(bpm);
+        let table_heap = TableHeap::new(bpm);
 
         // test
         let simple_schema = simple_schema();
@@ -288,7 +404,7 @@ mod tests {
         let db_path = temp_dir().join("th_inserted_tuples_accessible.db");
         let db_file_path = db_path.to_str().unwrap().to_string();
         let bpm = Arc::new(BufferPoolManager::new(String::from(db_file_path), 100, 2));
-        let mut table_heap = TableHeap::new(bpm);
+        let table_heap = TableHeap::new(bpm);
 
         // test
         let simple_schema = simple_schema();
@@ -324,7 +440,7 @@ mod tests {
         let db_path = temp_dir().join("th_update_tuple_meta.db");
         let db_file_path = db_path.to_str().unwrap().to_string();
         let bpm = Arc::new(BufferPoolManager::new(String::from(db_file_path), 100, 2));
-        let mut table_heap = TableHeap::new(bpm);
+        let table_heap = TableHeap::new(bpm);
 
         // test
         let simple_schema = simple_schema();
@@ -376,8 +492,61 @@ mod tests {
         remove_file(db_path).expect("Couldn't remove test DB file");
     }
 
+    #[test]
+    fn update_tuple_in_place() {
+        // init
+        let db_path = temp_dir().join("th_update_tuple_in_place.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+        let table_heap = TableHeap::new(bpm);
+
+        // test
+        let simple_schema = simple_schema();
+        let meta = TupleMeta {
+            ts: 0,
+            is_deleted: false,
+        };
+        let rid = table_heap
+            .insert_tuple(meta.clone(), simple_tuple("tuple 1", 1, &simple_schema))
+            .unwrap();
+
+        let new_tuple = simple_tuple("tuple 2", 2, &simple_schema);
+        assert!(table_heap
+            .update_tuple_in_place(meta.clone(), new_tuple.clone(), &rid)
+            .is_ok());
+        assert_eq!(table_heap.get_tuple(&rid).unwrap(), (meta.clone(), new_tuple));
+        // the free space map has the free space of the page
+        let page = table_heap.bpm.get_read_page(rid.page_id);
+        let free_space = TablePageRef::new(page.read()).available_space();
+        drop(page);
+        assert_eq!(
+            table_heap.fsm.find_page_with(free_space / 2),
+            Some(rid.page_id)
+        );
+        assert_eq!(table_heap.fsm.find_page_with(free_space + 1), None);
+
+        // larger tuple doesn't fit
+        let big_schema = Schema::new(vec![Column::new_named(
+            "name".to_string(),
+            ColumnType::Varchar(100),
+        )]);
+        let big_tuple = Tuple::new(
+            vec![ColumnValue::Varchar(VarcharValue {
+                value: "big".to_string(),
+                length: 100,
+            })],
+            &big_schema,
+        );
+        assert!(table_heap
+            .update_tuple_in_place(meta, big_tuple, &rid)
+            .is_err());
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
     fn sample_heap(bpm: Arc<BufferPoolManager>) -> (TableHeap, Vec<Option<RID>>) {
-        let mut table_heap = TableHeap::new(bpm);
+        let table_heap = TableHeap::new(bpm);
 
         // test
         let simple_schema = simple_schema();
@@ -423,6 +592,34 @@ mod tests {
         remove_file(db_path).expect("Couldn't remove test DB file");
     }
 
+    #[test]
+    fn iterator_with_modifications() {
+        // init
+        let db_path = temp_dir().join("th_iterator_with_modifications.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 2, 2));
+        let (table_heap, tuples) = sample_heap(bpm);
+
+        // test
+        let mut count = 0;
+        for (meta, _, rid) in table_heap.iter() {
+            // the page that is iterated is not latched by the cursor
+            table_heap.update_tuple_meta(
+                TupleMeta {
+                    ts: 1,
+                    is_deleted: !meta.is_deleted,
+                },
+                &rid,
+            );
+            count += 1;
+        }
+        assert_eq!(count, tuples.len());
+        assert!(table_heap.iter().all(|(meta, _, _)| meta.is_deleted && meta.ts == 1));
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
     #[test]
     fn first_tuple() {
         // init
@@ -432,7 +629,7 @@ mod tests {
         let (table_heap, _) = sample_heap(bpm);
 
         // test
-        let (meta, tuple, rid) = table_heap.first_tuple().unwrap();
+        let (meta, tuple, rid) = table_heap.iter().next().unwrap();
         assert_eq!(rid, RID::new(table_heap.first_page, 0));
         assert_eq!(meta, TupleMeta { ts: 0, is_deleted: false });
         assert_eq!(tuple, simple_tuple("name 0", 0, &simple_schema()));
@@ -450,8 +647,9 @@ mod tests {
         let (table_heap, _) = sample_heap(bpm);
 
         // test
-        let (_, _, first_rid) = table_heap.first_tuple().unwrap();
-        let (meta, tuple, rid) = table_heap.tuple_after(&first_rid).unwrap();
+        let mut iter = table_heap.iter();
+        iter.next().unwrap();
+        let (meta, tuple, rid) = iter.next().unwrap();
         assert_eq!(rid, RID::new(table_heap.first_page, 1));
         assert_eq!(meta, TupleMeta { ts: 0, is_deleted: false });
         assert_eq!(tuple, simple_tuple("name 1", 1, &simple_schema()));
@@ -459,4 +657,256 @@ mod tests {
         // cleanup
         remove_file(db_path).expect("Couldn't remove test DB file");
     }
+
+    #[test]
+    fn concurrent_inserts() {
+        // init
+        let db_path = temp_dir().join("th_concurrent_inserts.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+        let table_heap = Arc::new(TableHeap::new(bpm));
+
+        // test
+        let threads: Vec<_> = (0..4)
+            .map(|t| {
+                let table_heap = table_heap.clone();
+                thread::spawn(move || {
+                    let simple_schema = simple_schema();
+                    (0..1024)
+                        .map(|i| {
+                            let tuple = simple_tuple(&format!("t{t} {i}"), i, &simple_schema);
+                            let rid = table_heap
+                                .insert_tuple(
+                                    TupleMeta {
+                                        ts: 0,
+                                        is_deleted: false,
+                                    },
+                                    tuple.clone(),
+                                )
+                                .unwrap();
+
+                            (rid, tuple)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut tuples_map = HashMap::new();
+        for t in threads {
+            for (rid, tuple) in t.join().unwrap() {
+                // assert all rids are different
+                assert!(tuples_map.insert(rid, tuple).is_none());
+            }
+        }
+
+        // assert all tuples inserted can also be read
+        for (rid, tuple) in tuples_map.iter() {
+            let (_, heap_tuple) = table_heap.get_tuple(rid).unwrap();
+            assert_eq!(tuple.clone(), heap_tuple);
+        }
+        assert_eq!(table_heap.iter().count(), tuples_map.len());
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn insert_reuses_free_space() {
+        // init
+        let db_path = temp_dir().join("th_insert_reuses_free_space.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+        let table_heap = TableHeap::new(bpm);
+
+        let meta = TupleMeta {
+            ts: 0,
+            is_deleted: false,
+        };
+        let big_schema = Schema::new(vec![Column::new_named(
+            "data".to_string(),
+            ColumnType::Varchar(3000),
+        )]);
+        let big_tuple = |c: char| {
+            Tuple::new(
+                vec![ColumnValue::Varchar(VarcharValue {
+                    value: c.to_string().repeat(3000),
+                    length: 3000,
+                })],
+                &big_schema,
+            )
+        };
+
+        // test
+        let rid1 = table_heap.insert_tuple(meta.clone(), big_tuple('a')).unwrap();
+        let rid2 = table_heap.insert_tuple(meta.clone(), big_tuple('b')).unwrap();
+        assert_ne!(rid1.page_id, rid2.page_id);
+
+        // small tuple still fits in one of the pages
+        let rid3 = table_heap
+            .insert_tuple(meta.clone(), simple_tuple("small", 1, &simple_schema()))
+            .unwrap();
+        assert!(rid3.page_id == rid1.page_id || rid3.page_id == rid2.page_id);
+        assert_eq!(*table_heap.last_page.lock().unwrap(), rid2.page_id);
+        assert_eq!(table_heap.fsm.page_ids(), vec![rid1.page_id, rid2.page_id]);
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn insert_reclaims_deleted_space() {
+        // init
+        let db_path = temp_dir().join("th_insert_reclaims_deleted_space.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+        let table_heap = TableHeap::new(bpm);
+
+        let meta = TupleMeta {
+            ts: 0,
+            is_deleted: false,
+        };
+        let deleted = TupleMeta {
+            ts: 0,
+            is_deleted: true,
+        };
+        let big_schema = Schema::new(vec![Column::new_named(
+            "data".to_string(),
+            ColumnType::Varchar(3000),
+        )]);
+        let big_tuple = |c: char| {
+            Tuple::new(
+                vec![ColumnValue::Varchar(VarcharValue {
+                    value: c.to_string().repeat(3000),
+                    length: 3000,
+                })],
+                &big_schema,
+            )
+        };
+        let available_space = |page_id| {
+            let page = table_heap.bpm.get_read_page(page_id);
+            TablePageRef::new(page.read()).available_space()
+        };
+
+        // test
+        let rid1 = table_heap
+            .insert_tuple(meta.clone(), big_tuple('a'))
+            .unwrap();
+        let rid2 = table_heap
+            .insert_tuple(meta.clone(), big_tuple('b'))
+            .unwrap();
+        assert_ne!(rid1.page_id, rid2.page_id);
+
+        // the bytes of the deleted tuple are reported as free and reused by the next insert
+        let before_delete = available_space(rid1.page_id);
+        table_heap.update_tuple_meta(deleted, &rid1);
+        let after_delete = available_space(rid1.page_id);
+        assert!(after_delete > before_delete + 3000);
+        assert_eq!(table_heap.fsm.find_page_with(3000), Some(rid1.page_id));
+
+        let rid3 = table_heap
+            .insert_tuple(meta.clone(), big_tuple('c'))
+            .unwrap();
+        assert_eq!(rid3.page_id, rid1.page_id);
+        assert_ne!(rid3, rid1);
+        assert_eq!(*table_heap.last_page.lock().unwrap(), rid2.page_id);
+        assert_eq!(table_heap.get_tuple(&rid1), None);
+        assert_eq!(
+            table_heap.get_tuple(&rid3).unwrap(),
+            (meta.clone(), big_tuple('c'))
+        );
+
+        // the bytes left behind by a smaller tuple are reclaimed as well
+        let small_tuple = simple_tuple("small", 1, &simple_schema());
+        table_heap
+            .update_tuple_in_place(meta.clone(), small_tuple.clone(), &rid3)
+            .unwrap();
+        assert!(available_space(rid1.page_id) > 3000);
+
+        let rid4 = table_heap
+            .insert_tuple(meta.clone(), big_tuple('d'))
+            .unwrap();
+        assert_eq!(rid4.page_id, rid1.page_id);
+        assert_eq!(*table_heap.last_page.lock().unwrap(), rid2.page_id);
+        assert_eq!(
+            table_heap.get_tuple(&rid3).unwrap(),
+            (meta.clone(), small_tuple)
+        );
+        assert_eq!(
+            table_heap.get_tuple(&rid4).unwrap(),
+            (meta.clone(), big_tuple('d'))
+        );
+
+        // reclaimed tuples are skipped by scans
+        let rids: Vec<_> = table_heap.iter().map(|(_, _, rid)| rid).collect();
+        assert_eq!(rids, vec![rid3, rid4, rid2]);
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
+
+    #[test]
+    fn iter_skipping() {
+        // init
+        let db_path = temp_dir().join("th_iter_skipping.db");
+        let db_file_path = db_path.to_str().unwrap().to_string();
+        let bpm = Arc::new(BufferPoolManager::new(db_file_path, 100, 2));
+        let (mut table_heap, rids) = sample_heap(bpm);
+        let simple_schema = simple_schema();
+        let count_is = |count: i16| {
+            vec![ColumnPredicate {
+                col_index: 1,
+                typ: ComparisonType::EQ,
+                value: ColumnValue::SmallInt(SmallIntValue { value: count }),
+            }]
+        };
+        let pages_read = |table_heap: &TableHeap, count: i16| {
+            let mut pages = table_heap
+                .iter_skipping(&count_is(count))
+                .map(|(_, _, rid)| rid.page_id)
+                .collect::<Vec<_>>();
+            pages.dedup();
+            pages
+        };
+
+        // test
+        let first_page = rids[0].clone().unwrap().page_id;
+        let all_pages = table_heap.fsm.page_ids().len();
+        assert!(all_pages > 1);
+        // without a skip index every page is read
+        assert_eq!(table_heap.iter_skipping(&count_is(5)).count(), rids.len());
+
+        table_heap.create_skip_index(simple_schema.clone(), vec![1]);
+        assert_eq!(pages_read(&table_heap, 5), vec![first_page]);
+        assert_eq!(pages_read(&table_heap, 10000), vec![]);
+        assert_eq!(table_heap.iter_skipping(&[]).count(), rids.len());
+
+        // inserts are added to the summaries
+        let meta = TupleMeta {
+            ts: 0,
+            is_deleted: false,
+        };
+        let rid = table_heap
+            .insert_tuple(meta, simple_tuple("new", 10000, &simple_schema))
+            .unwrap();
+        assert_eq!(pages_read(&table_heap, 10000), vec![rid.page_id]);
+
+        // deleted tuples are only dropped from the summaries when they are rebuilt
+        for rid in rids.iter().flatten().filter(|rid| rid.page_id == first_page) {
+            table_heap.update_tuple_meta(
+                TupleMeta {
+                    ts: 1,
+                    is_deleted: true,
+                },
+                rid,
+            );
+        }
+        assert_eq!(pages_read(&table_heap, 5), vec![first_page]);
+        table_heap.rebuild_skip_index();
+        assert_eq!(pages_read(&table_heap, 5), vec![]);
+        assert_eq!(pages_read(&table_heap, 10000), vec![rid.page_id]);
+
+        // cleanup
+        remove_file(db_path).expect("Couldn't remove test DB file");
+    }
 }
@@ -0,0 +1,344 @@
+use std::{cmp::Ordering, fmt::Display};
+
+/// Maximum number of digits a `Numeric` can have, which is the largest precision that fits in an `i128`.
+pub const MAX_NUMERIC_PRECISION: u8 = 38;
+
+/// How the digits that don't fit in the scale of a result are discarded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoundingMode {
+    /// Round to nearest, ties away from zero (`2.5 -> 3`, `-2.5 -> -3`)
+    HalfUp,
+    /// Round to nearest, ties to the even neighbour (`2.5 -> 2`, `3.5 -> 4`)
+    HalfEven,
+    /// Round towards zero (truncate)
+    Down,
+    /// Round away from zero
+    Up,
+    /// Round towards negative infinity
+    Floor,
+    /// Round towards positive infinity
+    Ceiling,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum NumericError {
+    /// The result doesn't fit in the required precision (or in an `i128`).
+    Overflow,
+    DivisionByZero,
+    InvalidFormat,
+}
+
+/// Exact fixed-point number, represented as the unscaled `value` and a `scale`, meaning the number is
+/// `value * 10^(-scale)`. For example, `12.50` is `{ value: 1250, scale: 2 }`.
+///
+/// All the operations are exact and will return `NumericError::Overflow` instead of losing digits. When the result needs
+/// to be brought to a smaller scale, the digits are discarded according to a `RoundingMode`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Numeric {
+    pub value: i128,
+    pub scale: u8,
+}
+
+impl Numeric {
+    pub fn new(value: i128, scale: u8) -> Self {
+        Self { value, scale }
+    }
+
+    pub fn from_int(value: i128) -> Self {
+        Self { value, scale: 0 }
+    }
+
+    /// Parses a number written as `[-]digits[.digits]`. The scale of the result is the number of digits after the point.
+    pub fn parse(raw: &str) -> Result<Self, NumericError> {
+        let (negative, digits) = match raw.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, raw),
+        };
+        let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+
+        if int_part.is_empty()
+            || !int_part.chars().all(|c| c.is_ascii_digit())
+            || !frac_part.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(NumericError::InvalidFormat);
+        }
+        if int_part.len() + frac_part.len() > MAX_NUMERIC_PRECISION as usize {
+            return Err(NumericError::Overflow);
+        }
+
+        let value: i128 = format!("{}{}", int_part, frac_part)
+            .parse()
+            .map_err(|_| NumericError::InvalidFormat)?;
+
+        Ok(Self {
+            value: if negative { -value } else { value },
+            scale: frac_part.len() as u8,
+        })
+    }
+
+    /// Returns the same number with a different scale, rounding with `mode` if the scale is decreased.
+    pub fn rescale(self, scale: u8, mode: RoundingMode) -> Result<Self, NumericError> {
+        let value = match scale.cmp(&self.scale) {
+            Ordering::Equal => self.value,
+            Ordering::Greater => self
+                .value
+                .checked_mul(pow10((scale - self.scale) as u32)?)
+                .ok_or(NumericError::Overflow)?,
+            Ordering::Less => divide_rounded(self.value, pow10((self.scale - scale) as u32)?, mode),
+        };
+
+        Ok(Self { value, scale })
+    }
+
+    /// Returns the number if it has at most `precision` digits.
+    pub fn check_precision(self, precision: u8) -> Result<Self, NumericError> {
+        if precision < MAX_NUMERIC_PRECISION
+            && self.value.unsigned_abs() >= pow10(precision as u32)? as u128
+        {
+            return Err(NumericError::Overflow);
+        }
+
+        Ok(self)
+    }
+
+    /// Exact sum, with the scale of the operand with the largest scale.
+    pub fn add(self, other: Numeric) -> Result<Self, NumericError> {
+        let (l, r) = align(self, other)?;
+        let value = l.value.checked_add(r.value).ok_or(NumericError::Overflow)?;
+
+        Ok(Self::new(value, l.scale))
+    }
+
+    /// Exact difference, with the scale of the operand with the largest scale.
+    pub fn sub(self, other: Numeric) -> Result<Self, NumericError> {
+        let (l, r) = align(self, other)?;
+        let value = l.value.checked_sub(r.value).ok_or(NumericError::Overflow)?;
+
+        Ok(Self::new(value, l.scale))
+    }
+
+    /// Exact product, with the sum of the scales of the operands.
+    pub fn mul(self, other: Numeric) -> Result<Self, NumericError> {
+        let value = self
+            .value
+            .checked_mul(other.value)
+            .ok_or(NumericError::Overflow)?;
+        let scale = self.scale as u32 + other.scale as u32;
+        if scale > MAX_NUMERIC_PRECISION as u32 {
+            return Err(NumericError::Overflow);
+        }
+
+        Ok(Self::new(value, scale as u8))
+    }
+
+    /// Quotient with `scale` digits after the point, rounded with `mode`.
+    pub fn div(self, other: Numeric, scale: u8, mode: RoundingMode) -> Result<Self, NumericError> {
+        if other.value == 0 {
+            return Err(NumericError::DivisionByZero);
+        }
+
+        // self / other = (self.value / other.value) * 10^(other.scale - self.scale), which needs to be multiplied by 10^scale
+        let exp = scale as i32 + other.scale as i32 - self.scale as i32;
+        let (numerator, denominator) = if exp >= 0 {
+            let numerator = self.value.checked_mul(pow10(exp as u32)?);
+            (numerator.ok_or(NumericError::Overflow)?, other.value)
+        } else {
+            let denominator = other.value.checked_mul(pow10((-exp) as u32)?);
+            (self.value, denominator.ok_or(NumericError::Overflow)?)
+        };
+
+        Ok(Self::new(
+            divide_rounded(numerator, denominator, mode),
+            scale,
+        ))
+    }
+
+    /// Exact remainder of the truncated division, with the scale of the operand with the largest scale.
+    pub fn rem(self, other: Numeric) -> Result<Self, NumericError> {
+        if other.value == 0 {
+            return Err(NumericError::DivisionByZero);
+        }
+
+        let (l, r) = align(self, other)?;
+
+        Ok(Self::new(l.value % r.value, l.scale))
+    }
+
+    /// Compares the two numbers exactly, regardless of their scales.
+    pub fn compare(&self, other: &Numeric) -> Ordering {
+        // compare integer parts first, so that aligning the scales can't overflow
+        let self_int = self.value / pow10(self.scale as u32).unwrap();
+        let other_int = other.value / pow10(other.scale as u32).unwrap();
+
+        let scale = self.scale.max(other.scale);
+        let self_frac = self.value % pow10(self.scale as u32).unwrap()
+            * pow10((scale - self.scale) as u32).unwrap();
+        let other_frac = other.value % pow10(other.scale as u32).unwrap()
+            * pow10((scale - other.scale) as u32).unwrap();
+
+        (self_int, self_frac).cmp(&(other_int, other_frac))
+    }
+
+    /// Returns the `f64` closest to the number.
+    pub fn to_f64(self) -> f64 {
+        // converting the digits and dividing by the scale would round twice
+        self.to_string()
+            .parse()
+            .expect("Numeric is formatted as a valid float")
+    }
+}
+
+impl Display for Numeric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = self.value.unsigned_abs().to_string();
+        let sign = if self.value < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, int_part, frac_part)
+    }
+}
+
+fn pow10(exp: u32) -> Result<i128, NumericError> {
+    10i128.checked_pow(exp).ok_or(NumericError::Overflow)
+}
+
+/// Brings both numbers to the largest of the two scales.
+fn align(l: Numeric, r: Numeric) -> Result<(Numeric, Numeric), NumericError> {
+    let scale = l.scale.max(r.scale);
+
+    Ok((
+        l.rescale(scale, RoundingMode::Down)?,
+        r.rescale(scale, RoundingMode::Down)?,
+    ))
+}
+
+/// Returns `numerator / denominator`, rounded according to `mode`.
+fn divide_rounded(numerator: i128, denominator: i128, mode: RoundingMode) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder == 0 {
+        return quotient;
+    }
+
+    let negative = (numerator < 0) != (denominator < 0);
+    let away_from_zero = if negative { quotient - 1 } else { quotient + 1 };
+
+    // compare remainder with half of the denominator without overflowing
+    let remainder = remainder.unsigned_abs();
+    let rest = denominator.unsigned_abs() - remainder;
+
+    let round_away = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Up => true,
+        RoundingMode::Floor => negative,
+        RoundingMode::Ceiling => !negative,
+        RoundingMode::HalfUp => remainder >= rest,
+        RoundingMode::HalfEven => remainder > rest || (remainder == rest && quotient % 2 != 0),
+    };
+
+    if round_away {
+        away_from_zero
+    } else {
+        quotient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{Numeric, NumericError, RoundingMode};
+
+    fn num(raw: &str) -> Numeric {
+        Numeric::parse(raw).unwrap()
+    }
+
+    #[test]
+    fn parse_and_format() {
+        assert_eq!(num("12.50"), Numeric::new(1250, 2));
+        assert_eq!(num("-0.05"), Numeric::new(-5, 2));
+        assert_eq!(num("42"), Numeric::new(42, 0));
+        assert_eq!(Numeric::parse("1.2.3"), Err(NumericError::InvalidFormat));
+        assert_eq!(Numeric::parse(".5"), Err(NumericError::InvalidFormat));
+
+        assert_eq!(num("12.50").to_string(), "12.50");
+        assert_eq!(num("-0.05").to_string(), "-0.05");
+        assert_eq!(num("-7").to_string(), "-7");
+
+        assert_eq!(num("-0.05").to_f64(), -0.05);
+        assert_eq!(
+            num("1473356951206565.4352769849").to_f64(),
+            1473356951206565.5
+        );
+    }
+
+    #[test]
+    fn exact_arithmetic() {
+        // the classic floating point example
+        assert_eq!(num("0.1").add(num("0.2")).unwrap(), num("0.3"));
+        assert_eq!(num("10.00").sub(num("0.01")).unwrap(), num("9.99"));
+        assert_eq!(num("1.5").mul(num("-0.25")).unwrap(), num("-0.375"));
+        assert_eq!(num("7.5").rem(num("2")).unwrap(), num("1.5"));
+        assert_eq!(
+            num("1").div(num("3"), 4, RoundingMode::HalfUp).unwrap(),
+            num("0.3333")
+        );
+        assert_eq!(
+            num("2").div(num("3"), 4, RoundingMode::HalfUp).unwrap(),
+            num("0.6667")
+        );
+        assert_eq!(
+            num("1").div(num("0"), 2, RoundingMode::HalfUp),
+            Err(NumericError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let round = |raw: &str, mode| num(raw).rescale(0, mode).unwrap().value;
+
+        assert_eq!(round("2.5", RoundingMode::HalfUp), 3);
+        assert_eq!(round("-2.5", RoundingMode::HalfUp), -3);
+        assert_eq!(round("2.5", RoundingMode::HalfEven), 2);
+        assert_eq!(round("3.5", RoundingMode::HalfEven), 4);
+        assert_eq!(round("2.51", RoundingMode::HalfEven), 3);
+        assert_eq!(round("2.9", RoundingMode::Down), 2);
+        assert_eq!(round("-2.9", RoundingMode::Down), -2);
+        assert_eq!(round("2.1", RoundingMode::Up), 3);
+        assert_eq!(round("-2.1", RoundingMode::Up), -3);
+        assert_eq!(round("-2.1", RoundingMode::Floor), -3);
+        assert_eq!(round("2.1", RoundingMode::Floor), 2);
+        assert_eq!(round("-2.9", RoundingMode::Ceiling), -2);
+        assert_eq!(round("2.1", RoundingMode::Ceiling), 3);
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(num("999.99").check_precision(5), Ok(num("999.99")));
+        assert_eq!(
+            num("-1000.00").check_precision(5),
+            Err(NumericError::Overflow)
+        );
+
+        let big = Numeric::new(10i128.pow(37), 0);
+        assert_eq!(big.mul(big), Err(NumericError::Overflow));
+        assert_eq!(
+            big.rescale(10, RoundingMode::HalfUp),
+            Err(NumericError::Overflow)
+        );
+    }
+
+    #[test]
+    fn compare() {
+        assert_eq!(num("1.50").compare(&num("1.5")), Ordering::Equal);
+        assert_eq!(num("-1.5").compare(&num("-1.2")), Ordering::Less);
+        assert_eq!(num("-0.5").compare(&num("0.2")), Ordering::Less);
+        assert_eq!(num("10").compare(&num("9.999")), Ordering::Greater);
+    }
+}
@@ -35,6 +35,9 @@ const MAX_TUPLE_SIZE: u16 = DB_PAGE_SIZE as u16 - TABLE_PAGE_HEADER_SIZE - TUPLE
 /// ```text
 /// | tuple_offset (2) | tuple_size (2) | ts (8) | is_deleted (1) |
 /// ```
+///
+/// This is the owned representation of the page, which copies all the tuples out of the page buffer. The table heap works
+/// on the page buffer directly, through `TablePageRef` and `TablePageMut`.
 #[derive(Debug, PartialEq)]
 pub struct TablePage {
     pub next_page: PageID,
@@ -58,39 +61,20 @@ impl TablePage {
     }
 
     pub fn deserialize(data: &[u8]) -> Self {
-        assert_eq!(data.len(), DB_PAGE_SIZE as usize);
-        let next_page = u32::from_be_bytes(data[0..4].try_into().unwrap());
-        let num_tuples = u16::from_be_bytes(data[4..6].try_into().unwrap());
-        let num_deleted_tuples =
-            u16::from_be_bytes(data[6..TABLE_PAGE_HEADER_SIZE as usize].try_into().unwrap());
+        let page = TablePageRef::new(data);
+        let num_tuples = page.num_tuples();
 
         let mut tuples_info = vec![];
         let mut tuples_data = vec![];
-        for i in 0..num_tuples as usize {
-            // info
-            let info_start = TABLE_PAGE_HEADER_SIZE as usize + i * TUPLE_INFO_SIZE as usize;
-            let offset = u16::from_be_bytes(data[info_start..info_start + 2].try_into().unwrap());
-            let size = u16::from_be_bytes(data[info_start + 2..info_start + 4].try_into().unwrap());
-            let meta = TupleMeta {
-                ts: u64::from_be_bytes(data[info_start + 4..info_start + 12].try_into().unwrap()),
-                is_deleted: if data[info_start + 12] == 1 {
-                    true
-                } else {
-                    false
-                },
-            };
-            let tuple_info = (offset, size, meta);
-            tuples_info.push(tuple_info);
-
-            // data
-            let tuple_data = Tuple::deserialize(&data[offset as usize..(offset + size) as usize]);
-            tuples_data.push(tuple_data);
+        for slot in 0..num_tuples {
+            tuples_info.push(page.tuple_info(slot).unwrap());
+            tuples_data.push(Tuple::deserialize(page.tuple_bytes(slot).unwrap()));
         }
 
         Self {
-            next_page,
+            next_page: page.next_page(),
             num_tuples,
-            num_deleted_tuples,
+            num_deleted_tuples: page.num_deleted_tuples(),
             tuples_info,
             tuples_data,
         }
@@ -163,6 +147,23 @@ impl TablePage {
         Some(tuple_offset)
     }
 
+    /// Returns the number of bytes between the end of the page header and the start of the tuples data.
+    pub fn free_space(&self) -> u16 {
+        let data_start = if self.num_tuples > 0 {
+            self.tuples_info[self.num_tuples as usize - 1].0
+        } else {
+            DB_PAGE_SIZE as u16
+        };
+        let header_end = TABLE_PAGE_HEADER_SIZE + self.num_tuples * TUPLE_INFO_SIZE;
+
+        data_start - header_end
+    }
+
+    /// Returns the number of free bytes a page needs in order to fit `tuple` (tuple data + tuple info).
+    pub fn required_space(tuple: &Tuple) -> usize {
+        tuple.size() + TUPLE_INFO_SIZE as usize
+    }
+
     /// Updates a tuple's meta data, returning the RID of the tuple.
     pub fn update_tuple_meta(&mut self, meta: TupleMeta, rid: &RID) -> Result<RID, ()> {
         let slot = rid.slot_num as usize;
@@ -170,7 +171,7 @@ impl TablePage {
 
         if !old_meta.is_deleted && meta.is_deleted {
             self.num_deleted_tuples += 1;
-        } else if old_meta.is_deleted && meta.is_deleted {
+        } else if old_meta.is_deleted && !meta.is_deleted {
             self.num_deleted_tuples -= 1;
         }
 
@@ -179,6 +180,25 @@ impl TablePage {
         Ok((*rid).clone())
     }
 
+    /// Replaces the tuple at `rid` with `tuple` without moving it, returning the RID of the tuple. The new tuple is written at the
+    /// same offset as the old one, so this only works if the new tuple is not larger than the old one.
+    ///
+    /// # Errors
+    /// Will return `Err` if the slot is out of bounds or if the new tuple is larger than the old one.
+    pub fn update_tuple_in_place(&mut self, meta: TupleMeta, tuple: Tuple, rid: &RID) -> Result<RID, ()> {
+        let slot = rid.slot_num as usize;
+        let (_, size, _) = self.tuples_info.get(slot).ok_or(())?;
+        if tuple.size() > *size as usize {
+            return Err(());
+        }
+
+        // the bytes left at the end of the old tuple are not reclaimed
+        self.tuples_info[slot].1 = tuple.size() as u16;
+        self.tuples_data[slot] = tuple;
+
+        self.update_tuple_meta(meta, rid)
+    }
+
     /// Returns the tuple at the given RID. Returns `None` if the slot number is out of bounds.
     pub fn get_tuple(&self, rid: &RID) -> Option<(&TupleMeta, &Tuple)> {
         assert_eq!(self.tuples_data.len(), self.tuples_info.len());
@@ -189,28 +209,244 @@ impl TablePage {
 
         Some((&self.tuples_info[slot].2, &self.tuples_data[slot]))
     }
+}
 
-    pub fn get_tuples(&self) -> Vec<(&TupleMeta, &Tuple)> {
-        assert_eq!(self.tuples_data.len(), self.tuples_info.len());
+/// Read-only view of a serialized `TablePage` that decodes the header, slots and tuples straight from the page buffer
+/// (i.e. the data of a `PageReadGuard`), without copying the whole page.
+pub struct TablePageRef<'a> {
+    data: &'a [u8],
+}
 
-        self.tuples_info
-            .iter()
-            .map(|(_, _, meta)| meta)
-            .zip(self.tuples_data.iter())
-            .collect()
+impl<'a> TablePageRef<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        assert_eq!(data.len(), DB_PAGE_SIZE as usize);
+        Self { data }
+    }
+
+    pub fn next_page(&self) -> PageID {
+        u32::from_be_bytes(self.data[0..4].try_into().unwrap())
+    }
+
+    pub fn num_tuples(&self) -> u16 {
+        u16::from_be_bytes(self.data[4..6].try_into().unwrap())
+    }
+
+    pub fn num_deleted_tuples(&self) -> u16 {
+        u16::from_be_bytes(self.data[6..TABLE_PAGE_HEADER_SIZE as usize].try_into().unwrap())
+    }
+
+    /// Returns the number of bytes between the end of the page header and the start of the tuples data.
+    pub fn free_space(&self) -> u16 {
+        self.data_start() - header_end(self.num_tuples())
+    }
+
+    /// Returns the number of free bytes the page would have once compacted (see `TablePageMut::compact`), i.e. the free
+    /// space plus the bytes of the deleted tuples and the bytes left behind by tuples that were updated in place.
+    pub fn available_space(&self) -> u16 {
+        let num_tuples = self.num_tuples();
+        let live_bytes: u16 = (0..num_tuples)
+            .filter_map(|slot| self.tuple_info(slot))
+            .filter(|(_, _, meta)| !meta.is_deleted)
+            .map(|(_, size, _)| size)
+            .sum();
+
+        DB_PAGE_SIZE as u16 - header_end(num_tuples) - live_bytes
+    }
+
+    /// Returns the info of the tuple in `slot`. Returns `None` if the slot number is out of bounds.
+    pub fn tuple_info(&self, slot: u16) -> Option<TupleInfo> {
+        if slot >= self.num_tuples() {
+            return None;
+        }
+
+        let info_start = info_start(slot);
+        let info = &self.data[info_start..info_start + TUPLE_INFO_SIZE as usize];
+        let offset = u16::from_be_bytes(info[0..2].try_into().unwrap());
+        let size = u16::from_be_bytes(info[2..4].try_into().unwrap());
+        let meta = TupleMeta {
+            ts: u64::from_be_bytes(info[4..12].try_into().unwrap()),
+            is_deleted: info[12] == 1,
+        };
+
+        Some((offset, size, meta))
+    }
+
+    /// Returns the serialized tuple in `slot`, borrowed from the page buffer. Returns `None` if the slot number is out of bounds.
+    pub fn tuple_bytes(&self, slot: u16) -> Option<&'a [u8]> {
+        let (offset, size, _) = self.tuple_info(slot)?;
+
+        Some(&self.data[offset as usize..(offset + size) as usize])
+    }
+
+    /// Returns the tuple at the given RID. Returns `None` if the slot number is out of bounds or if the tuple was deleted
+    /// and its bytes were reclaimed by compacting the page.
+    pub fn get_tuple(&self, rid: &RID) -> Option<(TupleMeta, Tuple)> {
+        let (_, size, meta) = self.tuple_info(rid.slot_num)?;
+        if size == 0 {
+            return None;
+        }
+        let tuple = Tuple::deserialize(self.tuple_bytes(rid.slot_num)?);
+
+        Some((meta, tuple))
+    }
+
+    /// Offset where the data of the last inserted tuple starts.
+    fn data_start(&self) -> u16 {
+        match self.num_tuples() {
+            0 => DB_PAGE_SIZE as u16,
+            n => self.tuple_info(n - 1).unwrap().0,
+        }
     }
 }
 
+/// Mutable view of a serialized `TablePage` that patches the header, slots and tuples directly inside the page buffer
+/// (i.e. the data of a `PageWriteGuard`), so only the bytes that change are written.
+pub struct TablePageMut<'a> {
+    data: &'a mut [u8],
+}
+
+impl<'a> TablePageMut<'a> {
+    pub fn new(data: &'a mut [u8]) -> Self {
+        assert_eq!(data.len(), DB_PAGE_SIZE as usize);
+        Self { data }
+    }
+
+    /// Writes the header of an empty page in `data` and returns a view over it.
+    pub fn init(data: &'a mut [u8]) -> Self {
+        let mut page = Self::new(data);
+        page.set_next_page(END_PAGE_ID);
+        page.set_num_tuples(0);
+        page.set_num_deleted_tuples(0);
+
+        page
+    }
+
+    pub fn as_page_ref(&self) -> TablePageRef<'_> {
+        TablePageRef { data: self.data }
+    }
+
+    pub fn set_next_page(&mut self, page_id: PageID) {
+        self.data[0..4].copy_from_slice(&page_id.to_be_bytes());
+    }
+
+    /// Inserts tuple in the page and returns the slot number of the tuple. Will return `None` in case of error (i.e. no space left).
+    pub fn insert_tuple(&mut self, meta: TupleMeta, tuple: &Tuple) -> Option<u16> {
+        if tuple.size() > MAX_TUPLE_SIZE as usize {
+            return None;
+        }
+
+        let page = self.as_page_ref();
+        let slot = page.num_tuples();
+        let tuple_offset = page.data_start().checked_sub(tuple.size() as u16)?;
+        if tuple_offset < header_end(slot + 1) {
+            return None;
+        }
+
+        let tuple_end = tuple_offset as usize + tuple.size();
+        self.data[tuple_offset as usize..tuple_end].copy_from_slice(&tuple.serialize());
+        self.write_tuple_info(slot, (tuple_offset, tuple.size() as u16, meta));
+        self.set_num_tuples(slot + 1);
+
+        Some(slot)
+    }
+
+    /// Updates a tuple's meta data, returning the RID of the tuple.
+    pub fn update_tuple_meta(&mut self, meta: TupleMeta, rid: &RID) -> Result<RID, ()> {
+        let page = self.as_page_ref();
+        let (offset, size, old_meta) = page.tuple_info(rid.slot_num).ok_or(())?;
+        let num_deleted_tuples = page.num_deleted_tuples();
+
+        if !old_meta.is_deleted && meta.is_deleted {
+            self.set_num_deleted_tuples(num_deleted_tuples + 1);
+        } else if old_meta.is_deleted && !meta.is_deleted {
+            self.set_num_deleted_tuples(num_deleted_tuples - 1);
+        }
+
+        self.write_tuple_info(rid.slot_num, (offset, size, meta));
+
+        Ok((*rid).clone())
+    }
+
+    /// Replaces the tuple at `rid` with `tuple` without moving it, returning the RID of the tuple.
+    ///
+    /// # Errors
+    /// Will return `Err` if the slot is out of bounds or if the new tuple is larger than the old one.
+    pub fn update_tuple_in_place(&mut self, meta: TupleMeta, tuple: &Tuple, rid: &RID) -> Result<RID, ()> {
+        let (offset, size, old_meta) = self.as_page_ref().tuple_info(rid.slot_num).ok_or(())?;
+        if tuple.size() > size as usize {
+            return Err(());
+        }
+
+        // the bytes left at the end of the old tuple are only reclaimed when the page is compacted
+        let tuple_end = offset as usize + tuple.size();
+        self.data[offset as usize..tuple_end].copy_from_slice(&tuple.serialize());
+        self.write_tuple_info(rid.slot_num, (offset, tuple.size() as u16, old_meta));
+
+        self.update_tuple_meta(meta, rid)
+    }
+
+    /// Moves the data of the live tuples to the end of the page, so the bytes of the deleted tuples and the bytes left
+    /// behind by tuples updated in place become free space. The slots of the deleted tuples are kept (with no data), so
+    /// the RIDs of the other tuples don't change and a RID never points to a different tuple; only the data of deleted
+    /// tuples is lost, `TablePageRef::get_tuple` returns `None` for them from then on.
+    pub fn compact(&mut self) {
+        let num_tuples = self.as_page_ref().num_tuples();
+
+        // tuples are stored in reverse order of their slots, so moving them towards the end of the page one slot at a
+        // time never overwrites the data of a tuple that wasn't moved yet
+        let mut data_start = DB_PAGE_SIZE as u16;
+        for slot in 0..num_tuples {
+            let (offset, size, meta) = self.as_page_ref().tuple_info(slot).unwrap();
+            if meta.is_deleted {
+                self.write_tuple_info(slot, (data_start, 0, meta));
+                continue;
+            }
+
+            data_start -= size;
+            self.data.copy_within(
+                offset as usize..(offset + size) as usize,
+                data_start as usize,
+            );
+            self.write_tuple_info(slot, (data_start, size, meta));
+        }
+    }
+
+    fn set_num_tuples(&mut self, num_tuples: u16) {
+        self.data[4..6].copy_from_slice(&num_tuples.to_be_bytes());
+    }
+
+    fn set_num_deleted_tuples(&mut self, num_deleted_tuples: u16) {
+        self.data[6..TABLE_PAGE_HEADER_SIZE as usize].copy_from_slice(&num_deleted_tuples.to_be_bytes());
+    }
+
+    fn write_tuple_info(&mut self, slot: u16, (offset, size, meta): TupleInfo) {
+        let info_start = info_start(slot);
+        let info = &mut self.data[info_start..info_start + TUPLE_INFO_SIZE as usize];
+        info[0..2].copy_from_slice(&offset.to_be_bytes());
+        info[2..4].copy_from_slice(&size.to_be_bytes());
+        info[4..12].copy_from_slice(&meta.ts.to_be_bytes());
+        info[12] = if meta.is_deleted { 1 } else { 0 };
+    }
+}
+
+fn info_start(slot: u16) -> usize {
+    TABLE_PAGE_HEADER_SIZE as usize + slot as usize * TUPLE_INFO_SIZE as usize
+}
+
+fn header_end(num_tuples: u16) -> u16 {
+    TABLE_PAGE_HEADER_SIZE + num_tuples * TUPLE_INFO_SIZE
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::table::{
+    use crate::{config::DB_PAGE_SIZE, table::{
         page::MAX_TUPLE_SIZE,
         schema::{Column, ColumnType, Schema},
         tuple::{Tuple, RID},
         value::{BooleanValue, ColumnValue, VarcharValue},
-    };
+    }};
 
-    use super::{TablePage, TupleMeta};
+    use super::{TablePage, TablePageMut, TablePageRef, TupleMeta};
 
     fn get_simple_tuple() -> Tuple {
         Tuple::new(
@@ -241,16 +477,16 @@ mod tests {
             num_deleted_tuples: 1,
             tuples_info: vec![
                 (
-                    4091,
-                    5,
+                    4090,
+                    6,
                     TupleMeta {
                         ts: 123,
                         is_deleted: true,
                     },
                 ),
                 (
-                    4086,
-                    5,
+                    4084,
+                    6,
                     TupleMeta {
                         ts: 456,
                         is_deleted: false,
@@ -301,12 +537,12 @@ mod tests {
             is_deleted: false,
         };
 
-        // 8 = 4 (length of the string) + 4 (length of the tuple)
+        // 9 = 4 (length of the string) + 1 (null bitmap) + 4 (length of the tuple)
         assert!(page
-            .insert_tuple(meta.clone(), get_varchar_tuple(MAX_TUPLE_SIZE as usize - 8))
+            .insert_tuple(meta.clone(), get_varchar_tuple(MAX_TUPLE_SIZE as usize - 9))
             .is_some());
         assert!(page
-            .insert_tuple(meta.clone(), get_varchar_tuple(MAX_TUPLE_SIZE as usize - 7))
+            .insert_tuple(meta.clone(), get_varchar_tuple(MAX_TUPLE_SIZE as usize - 8))
             .is_none());
     }
 
@@ -337,4 +573,130 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn update_tuple_in_place() {
+        let mut page = TablePage::empty();
+        let meta = TupleMeta {
+            ts: 0,
+            is_deleted: false,
+        };
+        let slot = page.insert_tuple(meta.clone(), get_varchar_tuple(10)).unwrap();
+        let _ = page.insert_tuple(meta.clone(), get_simple_tuple()).unwrap();
+        let rid = RID::new(0, slot);
+
+        // larger tuple doesn't fit in the old slot
+        assert!(page
+            .update_tuple_in_place(meta.clone(), get_varchar_tuple(11), &rid)
+            .is_err());
+
+        let new_meta = TupleMeta {
+            ts: 1,
+            is_deleted: false,
+        };
+        let free_space = page.free_space();
+        assert_eq!(
+            page.update_tuple_in_place(new_meta.clone(), get_varchar_tuple(5), &rid),
+            Ok(rid.clone())
+        );
+        assert_eq!(page.free_space(), free_space);
+
+        let deserialized = TablePage::deserialize(&page.serialize());
+        assert_eq!(page, deserialized);
+
+        let (page_meta, page_tuple) = deserialized.get_tuple(&rid).unwrap();
+        assert_eq!(page_meta.clone(), new_meta);
+        assert_eq!(page_tuple.clone(), get_varchar_tuple(5));
+        let (_, page_tuple) = deserialized.get_tuple(&RID::new(0, slot + 1)).unwrap();
+        assert_eq!(page_tuple.clone(), get_simple_tuple());
+    }
+
+    #[test]
+    fn lazy_decoding() {
+        let mut page = TablePage::empty();
+        page.next_page = 12;
+        let meta = TupleMeta {
+            ts: 3,
+            is_deleted: true,
+        };
+        page.insert_tuple(meta.clone(), get_simple_tuple()).unwrap();
+        page.insert_tuple(meta.clone(), get_varchar_tuple(10)).unwrap();
+        let data = page.serialize();
+        let page_ref = TablePageRef::new(&data);
+
+        assert_eq!(page_ref.next_page(), 12);
+        assert_eq!(page_ref.num_tuples(), 2);
+        assert_eq!(page_ref.free_space(), page.free_space());
+        assert_eq!(page_ref.get_tuple(&RID::new(0, 0)), Some((meta.clone(), get_simple_tuple())));
+        assert_eq!(page_ref.get_tuple(&RID::new(0, 1)), Some((meta, get_varchar_tuple(10))));
+        assert_eq!(page_ref.get_tuple(&RID::new(0, 2)), None);
+    }
+
+    #[test]
+    fn mut_view_matches_owned_page() {
+        let meta = TupleMeta {
+            ts: 0,
+            is_deleted: false,
+        };
+        let mut page = TablePage::empty();
+        let mut data = vec![0; DB_PAGE_SIZE as usize];
+        let mut page_mut = TablePageMut::init(&mut data);
+
+        // insert until full
+        loop {
+            let slot = page.insert_tuple(meta.clone(), get_varchar_tuple(100));
+            assert_eq!(slot, page_mut.insert_tuple(meta.clone(), &get_varchar_tuple(100)));
+            if slot.is_none() {
+                break;
+            }
+        }
+
+        let rid = RID::new(0, 3);
+        let deleted = TupleMeta {
+            ts: 1,
+            is_deleted: true,
+        };
+        assert_eq!(
+            page.update_tuple_meta(deleted.clone(), &rid),
+            page_mut.update_tuple_meta(deleted.clone(), &rid)
+        );
+        assert_eq!(
+            page.update_tuple_in_place(meta.clone(), get_varchar_tuple(50), &RID::new(0, 5)),
+            page_mut.update_tuple_in_place(meta.clone(), &get_varchar_tuple(50), &RID::new(0, 5))
+        );
+        assert!(page_mut
+            .update_tuple_in_place(meta, &get_varchar_tuple(101), &rid)
+            .is_err());
+        page.next_page = 7;
+        page_mut.set_next_page(7);
+
+        assert_eq!(page_mut.as_page_ref().num_deleted_tuples(), 1);
+        assert_eq!(page_mut.as_page_ref().free_space(), page.free_space());
+        assert_eq!(page.serialize(), data);
+        assert_eq!(TablePage::deserialize(&data), page);
+    }
+
+    #[test]
+    fn free_space() {
+        let mut page = TablePage::empty();
+        let meta = TupleMeta {
+            ts: 0,
+            is_deleted: false,
+        };
+        assert_eq!(page.free_space() as usize, DB_PAGE_SIZE as usize - 8);
+
+        let tuple = get_simple_tuple();
+        let before = page.free_space() as usize;
+        page.insert_tuple(meta.clone(), tuple.clone()).unwrap();
+        assert_eq!(
+            page.free_space() as usize,
+            before - TablePage::required_space(&tuple)
+        );
+
+        // a tuple fits as long as its required space is not larger than the free space
+        let big = get_varchar_tuple(page.free_space() as usize - TablePage::required_space(&get_varchar_tuple(0)));
+        assert_eq!(TablePage::required_space(&big), page.free_space() as usize);
+        assert!(page.insert_tuple(meta, big).is_some());
+        assert_eq!(page.free_space(), 0);
+    }
 }
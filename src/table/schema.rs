@@ -1,3 +1,13 @@
+use crate::parser::{
+    ast::general::{ColumnConstraint, ColumnDef},
+    token::data_type::DataType,
+};
+
+use super::{numeric::MAX_NUMERIC_PRECISION, value::ColumnValue};
+
+/// Scale used for SQL `DECIMAL` / `NUMERIC` columns declared without a precision and scale.
+pub const DEFAULT_NUMERIC_SCALE: u8 = 6;
+
 #[derive(Clone)]
 pub struct Schema {
     columns: Vec<Column>,
@@ -27,6 +37,45 @@ impl Schema {
         }
     }
 
+    /// Creates a new schema from the column definitions of a `CREATE TABLE` statement. Only the `NOT NULL` (implied by
+    /// `PRIMARY KEY`) and `DEFAULT` constraints are part of the schema, the others are stored in the catalog.
+    ///
+    /// # Errors
+    /// Will return `Err` if a decimal has more than `MAX_NUMERIC_PRECISION` digits or if a default value doesn't match
+    /// the type of its column.
+    pub fn from_column_defs(defs: &[ColumnDef]) -> Result<Self, String> {
+        let mut columns = vec![];
+        for def in defs {
+            let col_type = ColumnType::from_data_type(&def.data_type);
+            if let ColumnType::Numeric(precision, _) = col_type {
+                if precision > MAX_NUMERIC_PRECISION {
+                    return Err(format!(
+                        "Precision of column {} can be at most {}",
+                        def.name, MAX_NUMERIC_PRECISION
+                    ));
+                }
+            }
+            let mut column = Column::new_named(def.name.clone(), col_type.clone());
+
+            for constraint in def.constraints.iter() {
+                column = match constraint {
+                    ColumnConstraint::NotNull | ColumnConstraint::PrimaryKey => column.not_null(),
+                    ColumnConstraint::Default(value) => column.with_default(
+                        ColumnValue::from_literal(value, &col_type)
+                            .map_err(|e| format!("Invalid default for column {}: {}", def.name, e))?,
+                    ),
+                    ColumnConstraint::Unique
+                    | ColumnConstraint::Check(_)
+                    | ColumnConstraint::References(_) => column,
+                };
+            }
+
+            columns.push(column);
+        }
+
+        Ok(Self::new(columns))
+    }
+
     /// Less flexible (but more convenient) constructor for creating a schema with columns of the same type.
     pub fn with_types(types: Vec<ColumnType>) -> Self {
         let columns = types
@@ -48,11 +97,16 @@ impl Schema {
         self.columns.get(col_index).map(|c| c.col_type.size())
     }
 
-    /// Returns the length of the tuple.
+    /// Returns the length of the values of the tuple, without the null bitmap.
     pub fn get_tuple_len(&self) -> usize {
         self.tuple_length
     }
 
+    /// Returns the length of the null bitmap that follows the values of the tuple (1 bit per column).
+    pub fn get_null_bitmap_len(&self) -> usize {
+        self.columns.len().div_ceil(8)
+    }
+
     pub fn get_cols_count(&self) -> usize {
         self.columns.len()
     }
@@ -61,6 +115,15 @@ impl Schema {
         self.columns[index].col_type.clone()
     }
 
+    pub fn get_column(&self, index: usize) -> &Column {
+        &self.columns[index]
+    }
+
+    /// Returns the index of the column named `name`, `None` if there is no such column.
+    pub fn get_col_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c.name == name)
+    }
+
     pub fn to_string(&self) -> String {
         format!(
             "{{ {} }}",
@@ -77,28 +140,59 @@ impl Schema {
 pub struct Column {
     name: String,
     col_type: ColumnType,
+    /// `false` if the column has a `NOT NULL` constraint
+    nullable: bool,
+    /// Value that replaces nulls inserted in the column
+    default: Option<ColumnValue>,
 }
 
 impl Column {
     /// Create a new column with an empty name and an integer type. In case you want more control, use the other constructor.
     pub fn new(typ: ColumnType) -> Self {
-        Self {
-            name: "".to_string(),
-            col_type: typ,
-        }
+        Self::new_named("".to_string(), typ)
     }
 
     pub fn new_named(name: String, typ: ColumnType) -> Self {
         Self {
             name,
             col_type: typ,
+            nullable: true,
+            default: None,
         }
     }
 
+    /// Returns the column with a `NOT NULL` constraint.
+    pub fn not_null(mut self) -> Self {
+        self.nullable = false;
+        self
+    }
+
+    /// Returns the column with a `DEFAULT` constraint.
+    ///
+    /// # Panics
+    /// Will panic if `value` is not of the type of the column.
+    pub fn with_default(mut self, value: ColumnValue) -> Self {
+        assert!(value.is_of_type(self.col_type.clone()), "Default value doesn't match column type");
+        self.default = Some(value);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub fn col_type(&self) -> &ColumnType {
         &self.col_type
     }
 
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    pub fn default(&self) -> Option<&ColumnValue> {
+        self.default.as_ref()
+    }
+
     /// Returns the size (in bytes) of the column's data.
     fn size(&self) -> usize {
         self.col_type.size()
@@ -115,6 +209,20 @@ pub enum ColumnType {
     Decimal,
     Timestamp,
     Varchar(usize),
+    Float,
+    Double,
+    /// Days since `1970-01-01`
+    Date,
+    /// Microseconds since midnight
+    Time,
+    /// Fixed length string, padded with spaces
+    Char(usize),
+    /// Fixed length byte string, padded with zeros
+    Binary(usize),
+    /// Exact fixed-point number with `precision` total digits, `scale` of which are after the point
+    Numeric(u8, u8),
+    /// Months, days and microseconds
+    Interval,
 }
 
 impl ColumnType {
@@ -128,6 +236,36 @@ impl ColumnType {
             ColumnType::Decimal => 8,
             ColumnType::Timestamp => 8,
             ColumnType::Varchar(length) => *length + 4,
+            ColumnType::Float => 4,
+            ColumnType::Double => 8,
+            ColumnType::Date => 4,
+            ColumnType::Time => 8,
+            ColumnType::Char(length) => *length,
+            ColumnType::Binary(length) => *length + 4,
+            ColumnType::Numeric(_, _) => 16,
+            ColumnType::Interval => 16,
+        }
+    }
+
+    /// Returns the column type that is used for storing values of the SQL `data_type`. Since the parser doesn't support
+    /// lengths yet, varchar, char and binary are of set size (255). Decimals without a precision are `NUMERIC(38, 6)`.
+    pub fn from_data_type(data_type: &DataType) -> Self {
+        match data_type {
+            DataType::Integer => ColumnType::Integer,
+            DataType::BigInt => ColumnType::BigInt,
+            DataType::Float => ColumnType::Float,
+            DataType::Double => ColumnType::Double,
+            DataType::Decimal(Some((precision, scale))) => ColumnType::Numeric(*precision, *scale),
+            DataType::Decimal(None) => {
+                ColumnType::Numeric(MAX_NUMERIC_PRECISION, DEFAULT_NUMERIC_SCALE)
+            }
+            DataType::Varchar => ColumnType::Varchar(255),
+            DataType::Char => ColumnType::Char(255),
+            DataType::Boolean => ColumnType::Boolean,
+            DataType::Date => ColumnType::Date,
+            DataType::Time => ColumnType::Time,
+            DataType::Timestamp => ColumnType::Timestamp,
+            DataType::Binary => ColumnType::Binary(255),
         }
     }
 
@@ -141,12 +279,22 @@ impl ColumnType {
             ColumnType::Decimal => "DECIMAL".to_string(),
             ColumnType::Timestamp => "TIMESTAMP".to_string(),
             ColumnType::Varchar(length) => format!("VARCHAR({})", length),
+            ColumnType::Float => "FLOAT".to_string(),
+            ColumnType::Double => "DOUBLE".to_string(),
+            ColumnType::Date => "DATE".to_string(),
+            ColumnType::Time => "TIME".to_string(),
+            ColumnType::Char(length) => format!("CHAR({})", length),
+            ColumnType::Binary(length) => format!("BINARY({})", length),
+            ColumnType::Numeric(precision, scale) => format!("NUMERIC({}, {})", precision, scale),
+            ColumnType::Interval => "INTERVAL".to_string(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::parser::{ast::general::ColumnDef, token::data_type::DataType};
+
     use super::{Column, ColumnType, Schema};
 
     #[test]
@@ -190,4 +338,61 @@ mod tests {
     fn schema_constructor_no_cols() {
         let _ = Schema::new(vec![]);
     }
+
+    #[test]
+    fn schema_from_column_defs() {
+        let defs = vec![
+            ColumnDef {
+                name: "f".to_string(),
+                data_type: DataType::Float,
+                constraints: vec![],
+            },
+            ColumnDef {
+                name: "d".to_string(),
+                data_type: DataType::Date,
+                constraints: vec![],
+            },
+            ColumnDef {
+                name: "c".to_string(),
+                data_type: DataType::Char,
+                constraints: vec![],
+            },
+            ColumnDef {
+                name: "t".to_string(),
+                data_type: DataType::Time,
+                constraints: vec![],
+            },
+            ColumnDef {
+                name: "n".to_string(),
+                data_type: DataType::Decimal(None),
+                constraints: vec![],
+            },
+            ColumnDef {
+                name: "p".to_string(),
+                data_type: DataType::Decimal(Some((10, 2))),
+                constraints: vec![],
+            },
+        ];
+        let schema = Schema::from_column_defs(&defs).unwrap();
+
+        assert_eq!(schema.get_col_type(0), ColumnType::Float);
+        assert_eq!(schema.get_col_type(1), ColumnType::Date);
+        assert_eq!(schema.get_col_type(2), ColumnType::Char(255));
+        assert_eq!(schema.get_col_type(3), ColumnType::Time);
+        assert_eq!(schema.get_col_type(4), ColumnType::Numeric(38, 6));
+        assert_eq!(schema.get_col_type(5), ColumnType::Numeric(10, 2));
+        assert_eq!(schema.offsets, vec![0, 4, 8, 263, 271, 287]);
+        assert_eq!(schema.tuple_length, 303);
+        assert_eq!(
+            schema.to_string(),
+            "{ f:FLOAT, d:DATE, c:CHAR(255), t:TIME, n:NUMERIC(38, 6), p:NUMERIC(10, 2) }"
+        );
+
+        let defs = vec![ColumnDef {
+            name: "n".to_string(),
+            data_type: DataType::Decimal(Some((39, 0))),
+            constraints: vec![],
+        }];
+        assert!(Schema::from_column_defs(&defs).is_err());
+    }
 }
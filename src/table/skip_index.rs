@@ -0,0 +1,287 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Mutex,
+};
+
+use crate::disk::disk_manager::PageID;
+
+use super::{
+    schema::{ColumnType, Schema},
+    tuple::Tuple,
+    value::ColumnValue,
+};
+
+/// Number of bits of the bloom filters of the page summaries.
+const BLOOM_FILTER_BITS: usize = 256;
+/// Number of bits set in the bloom filters for each value.
+const BLOOM_FILTER_HASHES: u64 = 3;
+
+/// Comparison of a column with a constant, used to find the pages of a heap that can have matching tuples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComparisonType {
+    EQ,
+    GT,
+    GE,
+    LT,
+    LE,
+}
+
+/// Condition `column <typ> value` that the tuples returned by a scan must satisfy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnPredicate {
+    pub col_index: usize,
+    pub typ: ComparisonType,
+    pub value: ColumnValue,
+}
+
+/// Small bloom filter of the values of a column in a page. It can have false positives, but no false negatives.
+#[derive(Debug, Clone, Default)]
+struct BloomFilter {
+    bits: [u64; BLOOM_FILTER_BITS / 64],
+}
+
+impl BloomFilter {
+    fn insert(&mut self, bytes: &[u8]) {
+        for bit in Self::bits_of(bytes) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Returns `false` if the value with the given bytes was never inserted in the filter.
+    fn may_contain(&self, bytes: &[u8]) -> bool {
+        Self::bits_of(bytes).all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Returns the bits of a value, computed with double hashing.
+    fn bits_of(bytes: &[u8]) -> impl Iterator<Item = usize> {
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        let hash = hasher.finish();
+        let (h1, h2) = (hash & u32::MAX as u64, hash >> 32);
+
+        (0..BLOOM_FILTER_HASHES)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % BLOOM_FILTER_BITS as u64) as usize)
+    }
+
+    /// Returns the bytes under which `value` is kept in the filter, or `None` if equal values can have different bytes
+    /// (e.g. `0.0` and `-0.0`), in which case the filter can't be used.
+    fn value_bytes(value: &ColumnValue) -> Option<Vec<u8>> {
+        match value.typ() {
+            ColumnType::Decimal
+            | ColumnType::Float
+            | ColumnType::Double
+            | ColumnType::Numeric(_, _) => None,
+            _ => Some(value.serialize()),
+        }
+    }
+}
+
+/// Summary of the non-null values of a column in a page.
+#[derive(Debug, Clone, Default)]
+struct ColumnSummary {
+    min: Option<ColumnValue>,
+    max: Option<ColumnValue>,
+    bloom: BloomFilter,
+}
+
+impl ColumnSummary {
+    fn add(&mut self, value: ColumnValue) {
+        if value.is_null() {
+            return;
+        }
+
+        if let Some(bytes) = BloomFilter::value_bytes(&value) {
+            self.bloom.insert(&bytes);
+        }
+        if self
+            .min
+            .as_ref()
+            .is_none_or(|min| value.compare(min) == Ok(Ordering::Less))
+        {
+            self.min = Some(value.clone());
+        }
+        if self
+            .max
+            .as_ref()
+            .is_none_or(|max| value.compare(max) == Ok(Ordering::Greater))
+        {
+            self.max = Some(value);
+        }
+    }
+
+    /// Returns `false` if no value of the column in the page can satisfy `predicate`. Values that can't be compared with
+    /// the ones of the column (e.g. of another type) may match any page.
+    fn may_match(&self, predicate: &ColumnPredicate) -> bool {
+        // comparisons with null are never true
+        let (Some(min), Some(max)) = (&self.min, &self.max) else {
+            return false;
+        };
+        let (Ok(min_ordering), Ok(max_ordering)) =
+            (min.compare(&predicate.value), max.compare(&predicate.value))
+        else {
+            return true;
+        };
+
+        match predicate.typ {
+            ComparisonType::EQ => {
+                min_ordering != Ordering::Greater
+                    && max_ordering != Ordering::Less
+                    && BloomFilter::value_bytes(&predicate.value)
+                        .is_none_or(|bytes| self.bloom.may_contain(&bytes))
+            }
+            ComparisonType::GT => max_ordering == Ordering::Greater,
+            ComparisonType::GE => max_ordering != Ordering::Less,
+            ComparisonType::LT => min_ordering == Ordering::Less,
+            ComparisonType::LE => min_ordering != Ordering::Greater,
+        }
+    }
+}
+
+/// Summaries of the values of some columns in each page of a table heap (their minimum, maximum and a bloom filter), so
+/// scans can skip the pages that can't have tuples matching their filters.
+///
+/// Summaries only grow: the values of deleted or replaced tuples stay in them (they are still correct, but can't skip as
+/// many pages) until they are rebuilt with the live tuples of the heap, when the table is repaired.
+pub struct SkipIndex {
+    schema: Schema,
+    /// Columns of the table that are summarized
+    columns: Vec<usize>,
+    /// Summaries of the summarized columns for every page with tuples
+    pages: Mutex<HashMap<PageID, Vec<ColumnSummary>>>,
+}
+
+impl SkipIndex {
+    /// Creates an empty skip index on the `columns` of tuples with `schema`.
+    pub fn new(schema: Schema, columns: Vec<usize>) -> Self {
+        Self {
+            schema,
+            columns,
+            pages: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn columns(&self) -> &[usize] {
+        &self.columns
+    }
+
+    /// Adds the values of `tuple`, stored in the page with `page_id`, to the summaries of the page.
+    pub fn add(&self, page_id: PageID, tuple: &Tuple) {
+        let mut pages = self.pages.lock().unwrap();
+        let summaries = pages
+            .entry(page_id)
+            .or_insert_with(|| vec![ColumnSummary::default(); self.columns.len()]);
+
+        for (summary, &col_index) in summaries.iter_mut().zip(self.columns.iter()) {
+            summary.add(tuple.get_value(&self.schema, col_index));
+        }
+    }
+
+    /// Returns `false` if no tuple of the page with `page_id` can satisfy all the `predicates`. Predicates on columns
+    /// that aren't summarized are ignored.
+    pub fn may_match(&self, page_id: PageID, predicates: &[ColumnPredicate]) -> bool {
+        let pages = self.pages.lock().unwrap();
+        // pages without summaries don't have tuples
+        let Some(summaries) = pages.get(&page_id) else {
+            return false;
+        };
+
+        predicates.iter().all(|predicate| {
+            match self
+                .columns
+                .iter()
+                .position(|&col_index| col_index == predicate.col_index)
+            {
+                Some(i) => summaries[i].may_match(predicate),
+                None => true,
+            }
+        })
+    }
+
+    /// Removes the summaries of all the pages.
+    pub fn clear(&self) {
+        self.pages.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::table::{
+        schema::{ColumnType, Schema},
+        tuple::Tuple,
+        value::{ColumnValue, DecimalValue, IntegerValue},
+    };
+
+    use super::{ColumnPredicate, ComparisonType, SkipIndex};
+
+    fn predicate(col_index: usize, typ: ComparisonType, value: ColumnValue) -> ColumnPredicate {
+        ColumnPredicate {
+            col_index,
+            typ,
+            value,
+        }
+    }
+
+    fn int(value: i32) -> ColumnValue {
+        ColumnValue::Integer(IntegerValue { value })
+    }
+
+    fn decimal(value: f64) -> ColumnValue {
+        ColumnValue::Decimal(DecimalValue { value })
+    }
+
+    #[test]
+    fn may_match() {
+        let schema = Schema::with_types(vec![
+            ColumnType::Integer,
+            ColumnType::Decimal,
+            ColumnType::Integer,
+        ]);
+        let skip_index = SkipIndex::new(schema.clone(), vec![0, 1]);
+        for i in [10, 20, 30] {
+            skip_index.add(
+                1,
+                &Tuple::new(vec![int(i), decimal(i as f64 / 10.0), int(i)], &schema),
+            );
+        }
+        skip_index.add(
+            2,
+            &Tuple::new(
+                vec![ColumnValue::Null(ColumnType::Integer), decimal(5.0), int(5)],
+                &schema,
+            ),
+        );
+
+        // min/max
+        assert!(skip_index.may_match(1, &[predicate(0, ComparisonType::GE, int(30))]));
+        assert!(!skip_index.may_match(1, &[predicate(0, ComparisonType::GT, int(30))]));
+        assert!(skip_index.may_match(1, &[predicate(0, ComparisonType::LE, int(10))]));
+        assert!(!skip_index.may_match(1, &[predicate(0, ComparisonType::LT, int(10))]));
+        assert!(!skip_index.may_match(1, &[predicate(0, ComparisonType::EQ, int(40))]));
+        // bloom filter
+        assert!(skip_index.may_match(1, &[predicate(0, ComparisonType::EQ, int(20))]));
+        assert!(!skip_index.may_match(1, &[predicate(0, ComparisonType::EQ, int(25))]));
+        // decimals don't use the bloom filter
+        assert!(skip_index.may_match(1, &[predicate(1, ComparisonType::EQ, decimal(2.5))]));
+        assert!(!skip_index.may_match(1, &[predicate(1, ComparisonType::EQ, decimal(3.5))]));
+        // all the predicates must be satisfiable
+        assert!(!skip_index.may_match(
+            1,
+            &[
+                predicate(0, ComparisonType::GE, int(20)),
+                predicate(1, ComparisonType::LT, decimal(1.0))
+            ]
+        ));
+
+        // nulls never match, columns that aren't summarized and values of other types always do
+        assert!(!skip_index.may_match(2, &[predicate(0, ComparisonType::GE, int(0))]));
+        assert!(skip_index.may_match(2, &[predicate(2, ComparisonType::EQ, int(100))]));
+        assert!(skip_index.may_match(1, &[predicate(0, ComparisonType::EQ, decimal(100.0))]));
+        // pages without tuples
+        assert!(!skip_index.may_match(3, &[]));
+
+        skip_index.clear();
+        assert!(!skip_index.may_match(1, &[]));
+    }
+}
@@ -1,9 +1,14 @@
+use std::cmp::Ordering;
+
 use crate::{
     disk::disk_manager::PageID,
     index::serial::{Deserialize, Serialize},
 };
 
-use super::{schema::Schema, value::ColumnValue};
+use super::{
+    schema::{ColumnType, Schema},
+    value::{ColumnValue, VarcharValue},
+};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Tuple {
@@ -18,19 +23,27 @@ impl Tuple {
 
     /// Creates a new tuple from the given values as long as they match the given schema.
     ///
+    /// Structure: `| values (schema tuple length) | null bitmap (1 bit per column) |`
+    ///
     /// # Panics
     /// Will panic if the values don't match the `schema`.
     pub fn new(values: Vec<ColumnValue>, schema: &Schema) -> Self {
         assert_eq!(values.len(), schema.get_cols_count()); // values don't match schema
 
         let mut data = vec![];
+        let mut null_bitmap = vec![0u8; schema.get_null_bitmap_len()];
         for (i, value) in values.iter().enumerate() {
             if !value.is_of_type(schema.get_col_type(i)) {
                 panic!("Schema doesn't match values");
             }
 
+            if value.is_null() {
+                null_bitmap[i / 8] |= 1 << (i % 8);
+            }
+
             data.append(&mut value.serialize());
         }
+        data.append(&mut null_bitmap);
 
         Self { data }
     }
@@ -50,6 +63,14 @@ impl Tuple {
     }
 
     pub fn get_value(&self, schema: &Schema, col_index: usize) -> ColumnValue {
+        let null_bitmap = &self.data[schema.get_tuple_len()..];
+        if null_bitmap.get(col_index / 8).expect("Column index out of schema bounds")
+            & (1 << (col_index % 8))
+            != 0
+        {
+            return ColumnValue::Null(schema.get_col_type(col_index));
+        }
+
         let offset = schema
             .get_offset(col_index)
             .expect("Column index out of schema bounds");
@@ -63,10 +84,101 @@ impl Tuple {
         )
     }
 
+    /// Compares two tuples of the same `schema` column by column. `NULL` values are ordered before every other value
+    /// (and are equal to each other), so the result is a total order that can be used for sorting index keys.
+    ///
+    /// # Panics
+    /// Will panic if the tuples don't match the `schema`.
+    pub fn compare(&self, other: &Tuple, schema: &Schema) -> Ordering {
+        for i in 0..schema.get_cols_count() {
+            let left = self.get_value(schema, i);
+            let right = other.get_value(schema, i);
+
+            let ordering = match (left.is_null(), right.is_null()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                (false, false) => left
+                    .compare(&right)
+                    .expect("Tuples don't match the schema"),
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        Ordering::Equal
+    }
+
     pub fn size(&self) -> usize {
         self.data.len() + 4
     }
 
+    /// Serializes the tuple without the padding of `VARCHAR` values, so that the result is only as long as the actual
+    /// data. Two tuples of the same `schema` are equal if and only if their compact serializations are equal.
+    ///
+    /// Structure: `| null bitmap (1 bit per column) | values |`, where `NULL` values are skipped and `VARCHAR` values
+    /// are stored as `| len (4) | content (len) |`.
+    pub fn serialize_compact(&self, schema: &Schema) -> Vec<u8> {
+        let null_bitmap = &self.data[schema.get_tuple_len()..];
+        let mut data = null_bitmap.to_vec();
+
+        for i in 0..schema.get_cols_count() {
+            match self.get_value(schema, i) {
+                ColumnValue::Null(_) => {}
+                ColumnValue::Varchar(value) => {
+                    data.extend_from_slice(&(value.value.len() as u32).to_be_bytes());
+                    data.extend_from_slice(value.value.as_bytes());
+                }
+                value => data.append(&mut value.serialize()),
+            }
+        }
+
+        data
+    }
+
+    /// Creates a tuple of `schema` from its compact serialization (see `serialize_compact`).
+    pub fn deserialize_compact(data: &[u8], schema: &Schema) -> Self {
+        let null_bitmap_len = schema.get_null_bitmap_len();
+        let null_bitmap = &data[..null_bitmap_len];
+
+        let mut offset = null_bitmap_len;
+        let mut values = vec![];
+        for i in 0..schema.get_cols_count() {
+            let col_type = schema.get_col_type(i);
+            if null_bitmap[i / 8] & (1 << (i % 8)) != 0 {
+                values.push(ColumnValue::Null(col_type));
+                continue;
+            }
+
+            let value = match col_type {
+                ColumnType::Varchar(length) => {
+                    let len = u32::from_be_bytes([
+                        data[offset],
+                        data[offset + 1],
+                        data[offset + 2],
+                        data[offset + 3],
+                    ]) as usize;
+                    let value = str::from_utf8(&data[offset + 4..offset + 4 + len])
+                        .expect("Invalid string payload in compact tuple")
+                        .to_string();
+                    offset += 4 + len;
+
+                    ColumnValue::Varchar(VarcharValue { value, length })
+                }
+                col_type => {
+                    let len = col_type.size();
+                    offset += len;
+
+                    ColumnValue::deserialize(&data[offset - len..offset], col_type)
+                }
+            };
+            values.push(value);
+        }
+
+        Self::new(values, schema)
+    }
+
     pub fn to_string(&self, schema: &Schema) -> String {
         let mut result = String::from("{ ");
         for i in 0..schema.get_cols_count() {
@@ -159,6 +271,8 @@ impl Deserialize for RID {
 
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
+
     use crate::{
         index::serial::{Deserialize, Serialize},
         table::{
@@ -168,6 +282,7 @@ mod tests {
                 BigIntValue, BooleanValue, ColumnValue, TimestampValue, TinyIntValue, VarcharValue,
             },
         },
+        test_utils::{bool_value, int_value},
     };
 
     #[test]
@@ -249,6 +364,30 @@ mod tests {
         assert_eq!(tuple.get_value(&schema, 4), values[4]);
     }
 
+    #[test]
+    fn tuple_null_values() {
+        let schema = Schema::new(vec![
+            Column::new_named("tiny".to_string(), ColumnType::TinyInt),
+            Column::new_named("varchar".to_string(), ColumnType::Varchar(255)),
+            Column::new_named("bool".to_string(), ColumnType::Boolean),
+        ]);
+        let values: Vec<ColumnValue> = vec![
+            ColumnValue::Null(ColumnType::TinyInt),
+            ColumnValue::Varchar(VarcharValue {
+                value: "test".to_string(),
+                length: 255,
+            }),
+            ColumnValue::Null(ColumnType::Boolean),
+        ];
+
+        let tuple = Tuple::deserialize(&Tuple::new(values.clone(), &schema).serialize());
+        // values + null bitmap + length
+        assert_eq!(tuple.size(), schema.get_tuple_len() + 1 + 4);
+        assert_eq!(tuple.get_value(&schema, 0), values[0]);
+        assert_eq!(tuple.get_value(&schema, 1), values[1]);
+        assert_eq!(tuple.get_value(&schema, 2), values[2]);
+    }
+
     #[test]
     #[should_panic]
     fn tuple_create_wrong_schema() {
@@ -334,4 +473,67 @@ mod tests {
         assert_eq!(projected.get_value(&new_schema, 1), expected_values[1]);
         assert_eq!(projected.get_value(&new_schema, 2), expected_values[2]);
     }
+
+    #[test]
+    fn tuple_compare() {
+        let schema = Schema::with_types(vec![ColumnType::Integer, ColumnType::Boolean]);
+        let tuple = |a: ColumnValue, b: ColumnValue| Tuple::new(vec![a, b], &schema);
+
+        let small = tuple(int_value(1), bool_value(true));
+        let large = tuple(int_value(2), bool_value(false));
+        let null = tuple(ColumnValue::Null(ColumnType::Integer), bool_value(true));
+
+        assert_eq!(small.compare(&large, &schema), Ordering::Less);
+        assert_eq!(large.compare(&small, &schema), Ordering::Greater);
+        assert_eq!(
+            small.compare(&tuple(int_value(1), bool_value(false)), &schema),
+            Ordering::Greater
+        );
+        assert_eq!(small.compare(&small.clone(), &schema), Ordering::Equal);
+
+        // nulls come first
+        assert_eq!(null.compare(&small, &schema), Ordering::Less);
+        assert_eq!(null.compare(&null.clone(), &schema), Ordering::Equal);
+    }
+
+    #[test]
+    fn tuple_compact_serialization() {
+        let schema = Schema::new(vec![
+            Column::new_named("tiny".to_string(), ColumnType::TinyInt),
+            Column::new_named("varchar".to_string(), ColumnType::Varchar(255)),
+            Column::new_named("bool".to_string(), ColumnType::Boolean),
+        ]);
+        let varchar = |value: &str| {
+            ColumnValue::Varchar(VarcharValue {
+                value: value.to_string(),
+                length: 255,
+            })
+        };
+
+        let tuple = Tuple::new(
+            vec![
+                ColumnValue::TinyInt(TinyIntValue { value: 8 }),
+                varchar("test"),
+                ColumnValue::Boolean(BooleanValue { value: true }),
+            ],
+            &schema,
+        );
+        let compact = tuple.serialize_compact(&schema);
+        // null bitmap + tiny + varchar length + varchar content + bool
+        assert_eq!(compact.len(), 1 + 1 + 4 + 4 + 1);
+        assert_eq!(Tuple::deserialize_compact(&compact, &schema), tuple);
+
+        // null values are skipped
+        let null_tuple = Tuple::new(
+            vec![
+                ColumnValue::Null(ColumnType::TinyInt),
+                varchar(""),
+                ColumnValue::Null(ColumnType::Boolean),
+            ],
+            &schema,
+        );
+        let compact = null_tuple.serialize_compact(&schema);
+        assert_eq!(compact.len(), 1 + 4);
+        assert_eq!(Tuple::deserialize_compact(&compact, &schema), null_tuple);
+    }
 }
@@ -1,7 +1,13 @@
 use core::str;
 use std::cmp::Ordering;
 
-use super::schema::ColumnType;
+use crate::parser::token::value::Value;
+
+use super::{
+    datetime::{format_date, format_time, format_timestamp, parse_date, parse_time, parse_timestamp, Interval},
+    numeric::{Numeric, RoundingMode},
+    schema::ColumnType,
+};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum ColumnValue {
@@ -13,6 +19,16 @@ pub enum ColumnValue {
     Decimal(DecimalValue),
     Timestamp(TimestampValue),
     Varchar(VarcharValue),
+    Float(FloatValue),
+    Double(DoubleValue),
+    Date(DateValue),
+    Time(TimeValue),
+    Char(CharValue),
+    Binary(BinaryValue),
+    Numeric(NumericValue),
+    Interval(IntervalValue),
+    /// Missing value of a column with the given type
+    Null(ColumnType),
 }
 
 impl ColumnValue {
@@ -31,6 +47,16 @@ impl ColumnValue {
 
                 ColumnValue::Varchar(varchar_val)
             }
+            ColumnType::Float => ColumnValue::Float(FloatValue::deserialize(data)),
+            ColumnType::Double => ColumnValue::Double(DoubleValue::deserialize(data)),
+            ColumnType::Date => ColumnValue::Date(DateValue::deserialize(data)),
+            ColumnType::Time => ColumnValue::Time(TimeValue::deserialize(data)),
+            ColumnType::Char(len) => ColumnValue::Char(CharValue::deserialize(data, len)),
+            ColumnType::Binary(len) => ColumnValue::Binary(BinaryValue::deserialize(data, len)),
+            ColumnType::Numeric(precision, scale) => {
+                ColumnValue::Numeric(NumericValue::deserialize(data, precision, scale))
+            }
+            ColumnType::Interval => ColumnValue::Interval(IntervalValue::deserialize(data)),
         }
     }
 
@@ -44,6 +70,16 @@ impl ColumnValue {
             ColumnValue::Decimal(decimal_value) => decimal_value.serialize(),
             ColumnValue::Timestamp(timestamp_value) => timestamp_value.serialize(),
             ColumnValue::Varchar(varchar_value) => varchar_value.serialize(),
+            ColumnValue::Float(float_value) => float_value.serialize(),
+            ColumnValue::Double(double_value) => double_value.serialize(),
+            ColumnValue::Date(date_value) => date_value.serialize(),
+            ColumnValue::Time(time_value) => time_value.serialize(),
+            ColumnValue::Char(char_value) => char_value.serialize(),
+            ColumnValue::Binary(binary_value) => binary_value.serialize(),
+            ColumnValue::Numeric(numeric_value) => numeric_value.serialize(),
+            ColumnValue::Interval(interval_value) => interval_value.serialize(),
+            // nulls are marked in the null bitmap of the tuple, the data is just a placeholder
+            ColumnValue::Null(typ) => vec![0; typ.size()],
         }
     }
 
@@ -57,6 +93,15 @@ impl ColumnValue {
             ColumnValue::Decimal(decimal_value) => decimal_value.is_of_type(typ),
             ColumnValue::Timestamp(timestamp_value) => timestamp_value.is_of_type(typ),
             ColumnValue::Varchar(varchar_value) => varchar_value.is_of_type(typ),
+            ColumnValue::Float(float_value) => float_value.is_of_type(typ),
+            ColumnValue::Double(double_value) => double_value.is_of_type(typ),
+            ColumnValue::Date(date_value) => date_value.is_of_type(typ),
+            ColumnValue::Time(time_value) => time_value.is_of_type(typ),
+            ColumnValue::Char(char_value) => char_value.is_of_type(typ),
+            ColumnValue::Binary(binary_value) => binary_value.is_of_type(typ),
+            ColumnValue::Numeric(numeric_value) => numeric_value.is_of_type(typ),
+            ColumnValue::Interval(interval_value) => interval_value.is_of_type(typ),
+            ColumnValue::Null(null_type) => *null_type == typ,
         }
     }
 
@@ -70,6 +115,17 @@ impl ColumnValue {
             ColumnValue::Decimal(_) => ColumnType::Decimal,
             ColumnValue::Timestamp(_) => ColumnType::Timestamp,
             ColumnValue::Varchar(varchar_value) => ColumnType::Varchar(varchar_value.length),
+            ColumnValue::Float(_) => ColumnType::Float,
+            ColumnValue::Double(_) => ColumnType::Double,
+            ColumnValue::Date(_) => ColumnType::Date,
+            ColumnValue::Time(_) => ColumnType::Time,
+            ColumnValue::Char(char_value) => ColumnType::Char(char_value.length),
+            ColumnValue::Binary(binary_value) => ColumnType::Binary(binary_value.length),
+            ColumnValue::Numeric(numeric_value) => {
+                ColumnType::Numeric(numeric_value.precision, numeric_value.value.scale)
+            }
+            ColumnValue::Interval(_) => ColumnType::Interval,
+            ColumnValue::Null(typ) => typ.clone(),
         }
     }
 
@@ -81,15 +137,31 @@ impl ColumnValue {
             ColumnValue::Integer(integer_value) => integer_value.value.to_string(),
             ColumnValue::BigInt(big_int_value) => big_int_value.value.to_string(),
             ColumnValue::Decimal(decimal_value) => decimal_value.value.to_string(),
-            ColumnValue::Timestamp(timestamp_value) => timestamp_value.value.to_string(),
+            ColumnValue::Timestamp(timestamp_value) => format_timestamp(timestamp_value.value),
             ColumnValue::Varchar(varchar_value) => varchar_value.value.clone(),
+            ColumnValue::Float(float_value) => float_value.value.to_string(),
+            ColumnValue::Double(double_value) => double_value.value.to_string(),
+            ColumnValue::Date(date_value) => format_date(date_value.value),
+            ColumnValue::Time(time_value) => format_time(time_value.value),
+            ColumnValue::Char(char_value) => char_value.value.clone(),
+            ColumnValue::Binary(binary_value) => format!(
+                "0x{}",
+                binary_value
+                    .value
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect::<String>()
+            ),
+            ColumnValue::Numeric(numeric_value) => numeric_value.value.to_string(),
+            ColumnValue::Interval(interval_value) => interval_value.value.to_string(),
+            ColumnValue::Null(_) => "NULL".to_string(),
         }
     }
 
     /// Casts value to decimal `ColumnValue`. Works for all the numeric types.
     ///
     /// # Errors
-    /// Will return `Err` if called on boolean or varchar.
+    /// Will return `Err` if called on a non-numeric type (boolean, date, time or strings).
     pub fn to_decimal(&self) -> Result<ColumnValue, ()> {
         match self {
             ColumnValue::TinyInt(tiny_int_value) => Ok(ColumnValue::Decimal(DecimalValue {
@@ -108,11 +180,183 @@ impl ColumnValue {
             ColumnValue::Timestamp(timestamp_value) => Ok(ColumnValue::Decimal(DecimalValue {
                 value: timestamp_value.value as f64,
             })),
+            ColumnValue::Float(float_value) => Ok(ColumnValue::Decimal(DecimalValue {
+                value: float_value.value as f64,
+            })),
+            ColumnValue::Double(double_value) => Ok(ColumnValue::Decimal(DecimalValue {
+                value: double_value.value,
+            })),
+            ColumnValue::Numeric(numeric_value) => Ok(ColumnValue::Decimal(DecimalValue {
+                value: numeric_value.value.to_f64(),
+            })),
             _ => Err(()),
         }
     }
 
-    /// Compare two values. Returns `Ok(Ordering)` if the values are of the same type and `Err(())` otherwise.
+    /// Returns the exact fixed-point representation of the value. Works for the integer types and numerics.
+    ///
+    /// # Errors
+    /// Will return `Err` if called on any other type, including the floating point ones, which can't be represented
+    /// exactly.
+    pub fn to_numeric(&self) -> Result<Numeric, ()> {
+        match self {
+            ColumnValue::TinyInt(tiny_int_value) => Ok(Numeric::from_int(tiny_int_value.value as i128)),
+            ColumnValue::SmallInt(small_int_value) => {
+                Ok(Numeric::from_int(small_int_value.value as i128))
+            }
+            ColumnValue::Integer(integer_value) => Ok(Numeric::from_int(integer_value.value as i128)),
+            ColumnValue::BigInt(big_int_value) => Ok(Numeric::from_int(big_int_value.value as i128)),
+            ColumnValue::Numeric(numeric_value) => Ok(numeric_value.value),
+            _ => Err(()),
+        }
+    }
+
+    /// Converts the value to an equal value of type `typ` (e.g. an `INTEGER` to a `BIGINT`).
+    ///
+    /// # Errors
+    /// Will return `Err` if the value can't be represented exactly as `typ` (e.g. it is out of range or it would be
+    /// rounded).
+    pub fn cast(&self, typ: &ColumnType) -> Result<ColumnValue, ()> {
+        if self.is_of_type(typ.clone()) {
+            return Ok(self.clone());
+        }
+
+        let literal = match self {
+            ColumnValue::TinyInt(value) => Value::Integer(value.value as i64),
+            ColumnValue::SmallInt(value) => Value::Integer(value.value as i64),
+            ColumnValue::Integer(value) => Value::Integer(value.value as i64),
+            ColumnValue::BigInt(value) => Value::Integer(value.value),
+            ColumnValue::Decimal(value) => Value::Float(value.value.to_string()),
+            ColumnValue::Float(value) => Value::Float(value.value.to_string()),
+            ColumnValue::Double(value) => Value::Float(value.value.to_string()),
+            ColumnValue::Numeric(value) => Value::String(value.value.to_string()),
+            ColumnValue::Varchar(value) => Value::String(value.value.clone()),
+            ColumnValue::Char(value) => Value::String(value.value.clone()),
+            ColumnValue::Null(_) => Value::Null,
+            _ => return Err(()),
+        };
+        let value = ColumnValue::from_literal(&literal, typ).map_err(|_| ())?;
+
+        // numbers can be rounded, e.g. to the scale of a numeric or to a float
+        match (self.to_decimal(), value.to_decimal()) {
+            (Ok(original), Ok(cast)) if original != cast => Err(()),
+            _ => Ok(value),
+        }
+    }
+
+    /// Converts the SQL literal `value` into a value of type `typ`.
+    ///
+    /// # Errors
+    /// Will return `Err` with a description of the problem if the literal can't be represented as `typ` (e.g. it is out of
+    /// range, too long or not a valid date).
+    pub fn from_literal(value: &Value, typ: &ColumnType) -> Result<ColumnValue, String> {
+        let invalid = || format!("Can't use {:?} as a value of type {}", value, typ.to_string());
+
+        let column_value = match (value, typ) {
+            (Value::Null, _) => ColumnValue::Null(typ.clone()),
+            (Value::Boolean(value), ColumnType::Boolean) => {
+                ColumnValue::Boolean(BooleanValue { value: *value })
+            }
+            (Value::Integer(value), ColumnType::TinyInt) => ColumnValue::TinyInt(TinyIntValue {
+                value: i8::try_from(*value).map_err(|_| invalid())?,
+            }),
+            (Value::Integer(value), ColumnType::SmallInt) => ColumnValue::SmallInt(SmallIntValue {
+                value: i16::try_from(*value).map_err(|_| invalid())?,
+            }),
+            (Value::Integer(value), ColumnType::Integer) => ColumnValue::Integer(IntegerValue {
+                value: i32::try_from(*value).map_err(|_| invalid())?,
+            }),
+            (Value::Integer(value), ColumnType::BigInt) => {
+                ColumnValue::BigInt(BigIntValue { value: *value })
+            }
+            (Value::Integer(value), ColumnType::Decimal) => ColumnValue::Decimal(DecimalValue {
+                value: *value as f64,
+            }),
+            (Value::Float(value), ColumnType::Decimal) => ColumnValue::Decimal(DecimalValue {
+                value: value.parse().map_err(|_| invalid())?,
+            }),
+            (Value::Integer(value), ColumnType::Float) => ColumnValue::Float(FloatValue {
+                value: *value as f32,
+            }),
+            (Value::Float(value), ColumnType::Float) => ColumnValue::Float(FloatValue {
+                value: value.parse().map_err(|_| invalid())?,
+            }),
+            (Value::Integer(value), ColumnType::Double) => ColumnValue::Double(DoubleValue {
+                value: *value as f64,
+            }),
+            (Value::Float(value), ColumnType::Double) => ColumnValue::Double(DoubleValue {
+                value: value.parse().map_err(|_| invalid())?,
+            }),
+            (Value::Integer(_) | Value::Float(_) | Value::String(_), ColumnType::Numeric(precision, scale)) => {
+                let raw = match value {
+                    Value::Integer(value) => value.to_string(),
+                    Value::Float(value) | Value::String(value) => value.clone(),
+                    _ => unreachable!(),
+                };
+                let value = Numeric::parse(&raw)
+                    .and_then(|n| n.rescale(*scale, RoundingMode::HalfUp))
+                    .and_then(|n| n.check_precision(*precision))
+                    .map_err(|_| invalid())?;
+
+                ColumnValue::Numeric(NumericValue {
+                    value,
+                    precision: *precision,
+                })
+            }
+            (Value::String(value), ColumnType::Varchar(length)) if value.len() <= *length => {
+                ColumnValue::Varchar(VarcharValue {
+                    value: value.clone(),
+                    length: *length,
+                })
+            }
+            (Value::String(value), ColumnType::Char(length)) if value.len() <= *length => {
+                ColumnValue::Char(CharValue {
+                    value: value.clone(),
+                    length: *length,
+                })
+            }
+            // bytes are written as hexadecimal digits after `0x`, the same way they are displayed
+            (Value::String(value), ColumnType::Binary(length)) => {
+                let digits = value.strip_prefix("0x").ok_or_else(invalid)?;
+                if !digits.bytes().all(|b| b.is_ascii_hexdigit())
+                    || digits.len() % 2 != 0
+                    || digits.len() / 2 > *length
+                {
+                    return Err(invalid());
+                }
+
+                ColumnValue::Binary(BinaryValue {
+                    value: (0..digits.len())
+                        .step_by(2)
+                        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).unwrap())
+                        .collect(),
+                    length: *length,
+                })
+            }
+            (Value::String(value), ColumnType::Date) => ColumnValue::Date(DateValue {
+                value: parse_date(value).map_err(|_| invalid())?,
+            }),
+            (Value::String(value), ColumnType::Time) => ColumnValue::Time(TimeValue {
+                value: parse_time(value).map_err(|_| invalid())?,
+            }),
+            (Value::String(value), ColumnType::Timestamp) => ColumnValue::Timestamp(TimestampValue {
+                value: parse_timestamp(value).map_err(|_| invalid())?,
+            }),
+            (Value::String(value), ColumnType::Interval) => ColumnValue::Interval(IntervalValue {
+                value: Interval::parse(value).map_err(|_| invalid())?,
+            }),
+            _ => return Err(invalid()),
+        };
+
+        Ok(column_value)
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, ColumnValue::Null(_))
+    }
+
+    /// Compare two values. Returns `Ok(Ordering)` if the values are of the same type and `Err(())` otherwise (nulls
+    /// can't be compared).
     pub fn compare(&self, other: &ColumnValue) -> Result<Ordering, ()> {
         if !other.is_of_type(self.typ()) {
             return Err(());
@@ -144,6 +388,31 @@ impl ColumnValue {
             (ColumnValue::Varchar(left), ColumnValue::Varchar(right)) => {
                 Ok(left.value.cmp(&right.value))
             }
+            (ColumnValue::Float(left), ColumnValue::Float(right)) => {
+                Ok(left.value.total_cmp(&right.value))
+            }
+            (ColumnValue::Double(left), ColumnValue::Double(right)) => {
+                Ok(left.value.total_cmp(&right.value))
+            }
+            (ColumnValue::Date(left), ColumnValue::Date(right)) => {
+                Ok(left.value.cmp(&right.value))
+            }
+            (ColumnValue::Time(left), ColumnValue::Time(right)) => {
+                Ok(left.value.cmp(&right.value))
+            }
+            (ColumnValue::Char(left), ColumnValue::Char(right)) => {
+                Ok(left.value.cmp(&right.value))
+            }
+            (ColumnValue::Binary(left), ColumnValue::Binary(right)) => {
+                Ok(left.value.cmp(&right.value))
+            }
+            (ColumnValue::Numeric(left), ColumnValue::Numeric(right)) => {
+                Ok(left.value.compare(&right.value))
+            }
+            (ColumnValue::Interval(left), ColumnValue::Interval(right)) => {
+                let left = (left.value.months, left.value.days, left.value.micros);
+                Ok(left.cmp(&(right.value.months, right.value.days, right.value.micros)))
+            }
             _ => Err(()),
         }
     }
@@ -335,7 +604,8 @@ impl DecimalValue {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct TimestampValue {
-    pub value: u64,
+    /// Microseconds since `1970-01-01T00:00:00`, negative before
+    pub value: i64,
 }
 
 impl TimestampValue {
@@ -360,7 +630,7 @@ impl TimestampValue {
         assert_eq!(data.len(), ColumnType::Timestamp.size());
 
         Self {
-            value: u64::from_be_bytes([
+            value: i64::from_be_bytes([
                 data[0], data[1], data[2], data[3], data[4], data[5], data[6], data[7],
             ]),
         }
@@ -414,11 +684,246 @@ impl VarcharValue {
     }
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct FloatValue {
+    pub value: f32,
+}
+
+impl FloatValue {
+    fn serialize(&self) -> Vec<u8> {
+        self.value.to_be_bytes().to_vec()
+    }
+
+    fn deserialize(data: &[u8]) -> Self {
+        assert_eq!(data.len(), ColumnType::Float.size());
+
+        Self {
+            value: f32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+        }
+    }
+
+    fn is_of_type(&self, typ: ColumnType) -> bool {
+        typ == ColumnType::Float
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DoubleValue {
+    pub value: f64,
+}
+
+impl DoubleValue {
+    fn serialize(&self) -> Vec<u8> {
+        self.value.to_be_bytes().to_vec()
+    }
+
+    fn deserialize(data: &[u8]) -> Self {
+        assert_eq!(data.len(), ColumnType::Double.size());
+
+        Self {
+            value: f64::from_be_bytes([
+                data[0], data[1], data[2], data[3], data[4], data[5], data[6], data[7],
+            ]),
+        }
+    }
+
+    fn is_of_type(&self, typ: ColumnType) -> bool {
+        typ == ColumnType::Double
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DateValue {
+    /// Days since `1970-01-01`
+    pub value: i32,
+}
+
+impl DateValue {
+    fn serialize(&self) -> Vec<u8> {
+        self.value.to_be_bytes().to_vec()
+    }
+
+    fn deserialize(data: &[u8]) -> Self {
+        assert_eq!(data.len(), ColumnType::Date.size());
+
+        Self {
+            value: i32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+        }
+    }
+
+    fn is_of_type(&self, typ: ColumnType) -> bool {
+        typ == ColumnType::Date
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TimeValue {
+    /// Microseconds since midnight
+    pub value: u64,
+}
+
+impl TimeValue {
+    fn serialize(&self) -> Vec<u8> {
+        self.value.to_be_bytes().to_vec()
+    }
+
+    fn deserialize(data: &[u8]) -> Self {
+        assert_eq!(data.len(), ColumnType::Time.size());
+
+        Self {
+            value: u64::from_be_bytes([
+                data[0], data[1], data[2], data[3], data[4], data[5], data[6], data[7],
+            ]),
+        }
+    }
+
+    fn is_of_type(&self, typ: ColumnType) -> bool {
+        typ == ColumnType::Time
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CharValue {
+    /// Value without the trailing padding
+    pub value: String,
+    pub length: usize,
+}
+
+impl CharValue {
+    /// Structure: `| content (length) |`, padded with spaces
+    fn serialize(&self) -> Vec<u8> {
+        let mut content: Vec<_> = self.value.bytes().collect();
+        content.resize(self.length, b' ');
+
+        content
+    }
+
+    fn deserialize(data: &[u8], length: usize) -> Self {
+        assert_eq!(data.len(), ColumnType::Char(length).size());
+        let value = str::from_utf8(data)
+            .expect("Invalid string payload, potentially wrong cast")
+            .trim_end_matches(' ')
+            .to_string();
+
+        Self { value, length }
+    }
+
+    fn is_of_type(&self, typ: ColumnType) -> bool {
+        typ == ColumnType::Char(self.length)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BinaryValue {
+    /// Value without the trailing padding
+    pub value: Vec<u8>,
+    pub length: usize,
+}
+
+impl BinaryValue {
+    /// Structure: `| len (4) | content (length) |`, where `len` is the length of the value and the content is padded
+    /// with zeros. The length is stored since the value can end with zeros itself.
+    fn serialize(&self) -> Vec<u8> {
+        let mut bytes = (self.value.len() as u32).to_be_bytes().to_vec();
+
+        let mut content = self.value.clone();
+        content.resize(self.length, 0);
+        bytes.append(&mut content);
+
+        bytes
+    }
+
+    fn deserialize(data: &[u8], length: usize) -> Self {
+        assert_eq!(data.len(), ColumnType::Binary(length).size());
+        let len = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+
+        Self {
+            value: data[4..4 + len].to_vec(),
+            length,
+        }
+    }
+
+    fn is_of_type(&self, typ: ColumnType) -> bool {
+        typ == ColumnType::Binary(self.length)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct NumericValue {
+    pub value: Numeric,
+    pub precision: u8,
+}
+
+impl NumericValue {
+    /// Structure: `| unscaled value (16) |`, the scale is part of the column type
+    fn serialize(&self) -> Vec<u8> {
+        self.value.value.to_be_bytes().to_vec()
+    }
+
+    fn deserialize(data: &[u8], precision: u8, scale: u8) -> Self {
+        assert_eq!(data.len(), ColumnType::Numeric(precision, scale).size());
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(data);
+
+        Self {
+            value: Numeric::new(i128::from_be_bytes(bytes), scale),
+            precision,
+        }
+    }
+
+    fn is_of_type(&self, typ: ColumnType) -> bool {
+        typ == ColumnType::Numeric(self.precision, self.value.scale)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct IntervalValue {
+    pub value: Interval,
+}
+
+impl IntervalValue {
+    /// Structure: `| months (4) | days (4) | microseconds (8) |`
+    fn serialize(&self) -> Vec<u8> {
+        let mut bytes = self.value.months.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&self.value.days.to_be_bytes());
+        bytes.extend_from_slice(&self.value.micros.to_be_bytes());
+
+        bytes
+    }
+
+    fn deserialize(data: &[u8]) -> Self {
+        assert_eq!(data.len(), ColumnType::Interval.size());
+
+        Self {
+            value: Interval::new(
+                i32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+                i32::from_be_bytes([data[4], data[5], data[6], data[7]]),
+                i64::from_be_bytes([
+                    data[8], data[9], data[10], data[11], data[12], data[13], data[14], data[15],
+                ]),
+            ),
+        }
+    }
+
+    fn is_of_type(&self, typ: ColumnType) -> bool {
+        typ == ColumnType::Interval
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::table::value::{BigIntValue, DecimalValue, IntegerValue, TimestampValue};
-
-    use super::{BooleanValue, SmallIntValue, TinyIntValue, VarcharValue};
+    use crate::table::{
+        datetime::{days_from_civil, parse_timestamp, Interval},
+        numeric::Numeric,
+        value::{
+            BigIntValue, DecimalValue, IntegerValue, IntervalValue, NumericValue, TimestampValue,
+        },
+    };
+
+    use super::{
+        BinaryValue, BooleanValue, CharValue, ColumnValue, DateValue, DoubleValue, FloatValue,
+        SmallIntValue, TimeValue, TinyIntValue, VarcharValue,
+    };
 
     #[test]
     fn boolean_value_serialization_consistency() {
@@ -480,13 +985,13 @@ mod tests {
 
     #[test]
     fn timestamp_value_serialization_consistency() {
-        let value = TimestampValue {
-            value: 17446744912301425290,
-        };
-        let serialized = value.serialize();
-        let deserialized = TimestampValue::deserialize(&serialized);
+        for value in [7446744912301425290, -7446744912301425290] {
+            let value = TimestampValue { value };
+            let serialized = value.serialize();
+            let deserialized = TimestampValue::deserialize(&serialized);
 
-        assert_eq!(value, deserialized);
+            assert_eq!(value, deserialized);
+        }
     }
 
     #[test]
@@ -518,4 +1023,290 @@ mod tests {
 
         assert_eq!(value, deserialized);
     }
+
+    #[test]
+    fn float_value_serialization_consistency() {
+        let value = FloatValue { value: -12.625 };
+        let serialized = value.serialize();
+        let deserialized = FloatValue::deserialize(&serialized);
+
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn double_value_serialization_consistency() {
+        let value = DoubleValue {
+            value: 234534563.890423,
+        };
+        let serialized = value.serialize();
+        let deserialized = DoubleValue::deserialize(&serialized);
+
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn date_value_serialization_consistency() {
+        let value = DateValue { value: -4021 };
+        let serialized = value.serialize();
+        let deserialized = DateValue::deserialize(&serialized);
+
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn time_value_serialization_consistency() {
+        let value = TimeValue {
+            value: 86_399_999_999,
+        };
+        let serialized = value.serialize();
+        let deserialized = TimeValue::deserialize(&serialized);
+
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn char_value_serialization_consistency() {
+        let value = CharValue {
+            value: "abc".to_string(),
+            length: 10,
+        };
+        let serialized = value.serialize();
+        assert_eq!(serialized, b"abc       ".to_vec());
+        let deserialized = CharValue::deserialize(&serialized, 10);
+
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn binary_value_serialization_consistency() {
+        let value = BinaryValue {
+            value: vec![0xde, 0xad, 0xbe, 0xef],
+            length: 4,
+        };
+        let serialized = value.serialize();
+        let deserialized = BinaryValue::deserialize(&serialized, 4);
+
+        assert_eq!(value, deserialized);
+
+        // shorter than the column, ending with a zero
+        let value = BinaryValue {
+            value: vec![0xab, 0x00],
+            length: 6,
+        };
+        let serialized = value.serialize();
+        assert_eq!(serialized, vec![0, 0, 0, 2, 0xab, 0, 0, 0, 0, 0]);
+        let deserialized = BinaryValue::deserialize(&serialized, 6);
+
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn new_types_to_string_and_compare() {
+        let date = ColumnValue::Date(DateValue {
+            value: days_from_civil(2024, 2, 29),
+        });
+        let later_date = ColumnValue::Date(DateValue {
+            value: days_from_civil(2024, 3, 1),
+        });
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert_eq!(date.compare(&later_date), Ok(std::cmp::Ordering::Less));
+
+        let time = ColumnValue::Time(TimeValue {
+            value: 3_723_000_000,
+        });
+        assert_eq!(time.to_string(), "01:02:03");
+
+        let binary = ColumnValue::Binary(BinaryValue {
+            value: vec![0x0a, 0xff],
+            length: 2,
+        });
+        assert_eq!(binary.to_string(), "0x0aff");
+
+        let float = ColumnValue::Float(FloatValue { value: 1.5 });
+        assert_eq!(float.to_string(), "1.5");
+        assert_eq!(float.to_decimal(), Ok(ColumnValue::Decimal(DecimalValue { value: 1.5 })));
+        assert!(float.compare(&ColumnValue::Double(DoubleValue { value: 1.5 })).is_err());
+        let nan = ColumnValue::Double(DoubleValue { value: f64::NAN });
+        let double = ColumnValue::Double(DoubleValue { value: 1.5 });
+        assert_eq!(nan.compare(&double), Ok(std::cmp::Ordering::Greater));
+        assert_eq!(nan.compare(&nan), Ok(std::cmp::Ordering::Equal));
+        let nan = ColumnValue::Float(FloatValue { value: f32::NAN });
+        assert_eq!(float.compare(&nan), Ok(std::cmp::Ordering::Less));
+
+        let char_a = ColumnValue::Char(CharValue {
+            value: "a".to_string(),
+            length: 3,
+        });
+        let char_b = ColumnValue::Char(CharValue {
+            value: "b".to_string(),
+            length: 3,
+        });
+        assert_eq!(char_a.compare(&char_b), Ok(std::cmp::Ordering::Less));
+    }
+
+    #[test]
+    fn numeric_value_serialization_consistency() {
+        let value = NumericValue {
+            value: Numeric::parse("-12345.678").unwrap(),
+            precision: 10,
+        };
+        let serialized = value.serialize();
+        let deserialized = NumericValue::deserialize(&serialized, 10, 3);
+
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn numeric_value_to_string_and_compare() {
+        let small = ColumnValue::Numeric(NumericValue {
+            value: Numeric::parse("0.30").unwrap(),
+            precision: 5,
+        });
+        let large = ColumnValue::Numeric(NumericValue {
+            value: Numeric::parse("12.05").unwrap(),
+            precision: 5,
+        });
+        assert_eq!(small.to_string(), "0.30");
+        assert_eq!(small.compare(&large), Ok(std::cmp::Ordering::Less));
+        assert_eq!(
+            ColumnValue::Integer(IntegerValue { value: -3 }).to_numeric(),
+            Ok(Numeric::new(-3, 0))
+        );
+        assert_eq!(large.to_decimal(), Ok(ColumnValue::Decimal(DecimalValue { value: 12.05 })));
+    }
+
+    #[test]
+    fn interval_value_serialization_consistency() {
+        let value = IntervalValue {
+            value: Interval::new(-14, 3, 7_200_000_001),
+        };
+        let serialized = value.serialize();
+        let deserialized = IntervalValue::deserialize(&serialized);
+
+        assert_eq!(value, deserialized);
+    }
+
+    #[test]
+    fn timestamp_and_interval_to_string() {
+        let timestamp = ColumnValue::Timestamp(TimestampValue {
+            value: parse_timestamp("2024-02-29T13:05:09").unwrap(),
+        });
+        assert_eq!(timestamp.to_string(), "2024-02-29T13:05:09");
+
+        let interval = ColumnValue::Interval(IntervalValue {
+            value: Interval::days(2).add(Interval::hours(3)),
+        });
+        assert_eq!(interval.to_string(), "P2DT3H");
+    }
+
+    #[test]
+    fn value_from_literal() {
+        use crate::{parser::token::value::Value, table::schema::ColumnType};
+
+        assert_eq!(
+            ColumnValue::from_literal(&Value::Integer(7), &ColumnType::SmallInt),
+            Ok(ColumnValue::SmallInt(SmallIntValue { value: 7 }))
+        );
+        assert_eq!(
+            ColumnValue::from_literal(&Value::Null, &ColumnType::Integer),
+            Ok(ColumnValue::Null(ColumnType::Integer))
+        );
+        assert_eq!(
+            ColumnValue::from_literal(
+                &Value::Float("1.255".to_string()),
+                &ColumnType::Numeric(5, 2)
+            ),
+            Ok(ColumnValue::Numeric(NumericValue {
+                value: Numeric::new(126, 2),
+                precision: 5
+            }))
+        );
+        // more digits than an f64 can hold
+        assert_eq!(
+            ColumnValue::from_literal(
+                &Value::Float("12345678901234567890.123456789".to_string()),
+                &ColumnType::Numeric(38, 9)
+            ),
+            Ok(ColumnValue::Numeric(NumericValue {
+                value: Numeric::new(12345678901234567890123456789, 9),
+                precision: 38
+            }))
+        );
+        assert_eq!(
+            ColumnValue::from_literal(
+                &Value::String("2024-02-29T13:05:09".to_string()),
+                &ColumnType::Timestamp
+            ),
+            Ok(ColumnValue::Timestamp(TimestampValue {
+                value: parse_timestamp("2024-02-29T13:05:09").unwrap()
+            }))
+        );
+
+        let binary = ColumnValue::from_literal(
+            &Value::String("0x0aff00".to_string()),
+            &ColumnType::Binary(4),
+        );
+        assert_eq!(
+            binary,
+            Ok(ColumnValue::Binary(BinaryValue {
+                value: vec![0x0a, 0xff, 0x00],
+                length: 4
+            }))
+        );
+        let binary = binary.unwrap();
+        assert_eq!(
+            ColumnValue::deserialize(&binary.serialize(), ColumnType::Binary(4)),
+            binary
+        );
+        assert_eq!(
+            ColumnValue::from_literal(&Value::String(binary.to_string()), &ColumnType::Binary(4)),
+            Ok(binary)
+        );
+
+        assert!(ColumnValue::from_literal(&Value::Integer(300), &ColumnType::TinyInt).is_err());
+        for raw in ["0x0aff0000ff", "0aff", "0x0af", "0x+f", "0xzz"] {
+            assert!(ColumnValue::from_literal(
+                &Value::String(raw.to_string()),
+                &ColumnType::Binary(4)
+            )
+            .is_err());
+        }
+        assert!(
+            ColumnValue::from_literal(&Value::String("abc".to_string()), &ColumnType::Char(2))
+                .is_err()
+        );
+        assert!(ColumnValue::from_literal(&Value::Boolean(true), &ColumnType::Integer).is_err());
+    }
+
+    #[test]
+    fn cast_value() {
+        use crate::table::schema::ColumnType;
+
+        let int = ColumnValue::Integer(IntegerValue { value: 300 });
+        assert_eq!(
+            int.cast(&ColumnType::BigInt),
+            Ok(ColumnValue::BigInt(BigIntValue { value: 300 }))
+        );
+        assert_eq!(
+            int.cast(&ColumnType::Numeric(5, 1)),
+            Ok(ColumnValue::Numeric(NumericValue {
+                value: Numeric::new(3000, 1),
+                precision: 5
+            }))
+        );
+        assert_eq!(
+            ColumnValue::Null(ColumnType::Integer).cast(&ColumnType::SmallInt),
+            Ok(ColumnValue::Null(ColumnType::SmallInt))
+        );
+
+        // out of range or rounded
+        assert!(int.cast(&ColumnType::TinyInt).is_err());
+        assert!(ColumnValue::Decimal(DecimalValue { value: 1.255 })
+            .cast(&ColumnType::Numeric(5, 2))
+            .is_err());
+        assert!(ColumnValue::Decimal(DecimalValue { value: 1.5 })
+            .cast(&ColumnType::Integer)
+            .is_err());
+    }
+
 }
@@ -26,7 +26,7 @@ pub fn const_decimal(value: f64) -> Expression {
     })
 }
 
-pub fn const_timestamp(value: u64) -> Expression {
+pub fn const_timestamp(value: i64) -> Expression {
     Expression::Constant(ConstantExpression {
         value: ColumnValue::Timestamp(TimestampValue { value }),
     })
@@ -49,6 +49,15 @@ pub fn column_with(col_index: usize, typ: ColumnType) -> Expression {
     })
 }
 
+/// Column of the right side of a join.
+pub fn right_column_with(col_index: usize, typ: ColumnType) -> Expression {
+    Expression::ColumnValue(ColumnValueExpression {
+        join_side: JoinSide::Right,
+        col_index,
+        return_type: Column::new(typ),
+    })
+}
+
 pub fn int_value(value: i32) -> ColumnValue {
     ColumnValue::Integer(IntegerValue { value })
 }